@@ -12,7 +12,7 @@ use anchor_lang::{
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use arrayref::array_ref;
-use metaplex_token_metadata::state::Metadata;
+use mpl_token_metadata::{state::Metadata, utils::resolve_royalty};
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::{instruction::initialize_account2, state::Account as SplAccount};
 use std::{convert::TryInto, slice::Iter};
@@ -322,7 +322,14 @@ pub fn pay_creator_fees<'a>(
     is_native: bool,
 ) -> Result<u64> {
     let metadata = Metadata::from_account_info(metadata_info)?;
-    let fees = metadata.data.seller_fee_basis_points;
+    // Items inheriting the royalty of their collection pass its royalty record ahead of the creators.
+    let collection_royalty_info = if metadata.inherits_collection_royalty() {
+        Some(next_account_info(remaining_accounts)?)
+    } else {
+        None
+    };
+    let royalty = resolve_royalty(&metadata, collection_royalty_info)?;
+    let fees = royalty.seller_fee_basis_points;
     let total_fee = (fees as u128)
         .checked_mul(size as u128)
         .ok_or(ErrorCode::NumericalOverflow)?
@@ -332,7 +339,7 @@ pub fn pay_creator_fees<'a>(
     let remaining_size = size
         .checked_sub(total_fee)
         .ok_or(ErrorCode::NumericalOverflow)?;
-    match metadata.data.creators {
+    match royalty.creators {
         Some(creators) => {
             for creator in creators {
                 let pct = creator.share as u128;
@@ -2,17 +2,29 @@
 pub mod utils;
 
 use anchor_lang::{prelude::*, AccountDeserialize, InstructionData, ToAccountMetas};
-use mpl_testing_utils::{solana::airdrop, utils::Metadata};
+use mpl_testing_utils::{
+    solana::airdrop,
+    utils::{MasterEditionV2, Metadata},
+};
 use solana_program_test::*;
 use solana_sdk::signer::Signer;
 
 use std::assert_eq;
 
-use solana_program::{instruction::Instruction, system_program, sysvar};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    system_program, sysvar,
+};
 use solana_program::instruction::InstructionError;
 
 use solana_program::program_pack::Pack;
 
+use mpl_token_metadata::{
+    instruction::{set_collection_royalty, update_metadata_accounts_v2, verify_collection},
+    pda::find_collection_royalty_account,
+    state::{Collection, Creator, DataV2},
+};
+
 use mpl_auction_house::{
     pda::{find_escrow_payment_address, find_program_as_signer_address, find_trade_state_address},
     receipt::{BidReceipt, ListingReceipt, PurchaseReceipt},
@@ -169,6 +181,240 @@ async fn execute_sale_success() {
     assert_eq!(seller_before.lamports < seller_after.lamports, true);
     assert_eq!(buyer_token_after.amount, 1);
 }
+#[tokio::test]
+async fn execute_sale_inherited_collection_royalty_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    // Payer Wallet
+    let (ah, ahkey, authority) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+    // Collection paying 5% to a single creator
+    let test_collection = Metadata::new();
+    test_collection
+        .create_v2(
+            &mut context,
+            "Collection".to_string(),
+            "COL".to_string(),
+            "uri".to_string(),
+            None,
+            0,
+            true,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let collection_master_edition = MasterEditionV2::new(&test_collection);
+    collection_master_edition
+        .create_v3(&mut context, Some(0))
+        .await
+        .unwrap();
+    let artist = Keypair::new();
+    let (collection_royalty_record, _) =
+        find_collection_royalty_account(&test_collection.mint.pubkey());
+    let set_royalty_tx = Transaction::new_signed_with_payer(
+        &[set_collection_royalty(
+            mpl_token_metadata::id(),
+            collection_royalty_record,
+            payer.pubkey(),
+            payer.pubkey(),
+            test_collection.pubkey,
+            collection_master_edition.pubkey,
+            500,
+            Some(vec![Creator {
+                address: artist.pubkey(),
+                verified: false,
+                share: 100,
+            }]),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(set_royalty_tx)
+        .await
+        .unwrap();
+
+    // Item without creators of its own, in the verified collection
+    let test_metadata = Metadata::new();
+    airdrop(&mut context, &test_metadata.token.pubkey(), 10_000_000_000)
+        .await
+        .unwrap();
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+    let collection_tx = Transaction::new_signed_with_payer(
+        &[
+            update_metadata_accounts_v2(
+                mpl_token_metadata::id(),
+                test_metadata.pubkey,
+                payer.pubkey(),
+                None,
+                Some(DataV2 {
+                    name: "Test".to_string(),
+                    symbol: "TST".to_string(),
+                    uri: "uri".to_string(),
+                    seller_fee_basis_points: 10,
+                    creators: None,
+                    collection: Some(Collection {
+                        verified: false,
+                        key: test_collection.mint.pubkey(),
+                    }),
+                    uses: None,
+                }),
+                None,
+                None,
+            ),
+            verify_collection(
+                mpl_token_metadata::id(),
+                test_metadata.pubkey,
+                payer.pubkey(),
+                payer.pubkey(),
+                test_collection.mint.pubkey(),
+                test_collection.pubkey,
+                collection_master_edition.pubkey,
+                None,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(collection_tx)
+        .await
+        .unwrap();
+
+    let ((sell_acc, _), sell_tx) = sell(&mut context, &ahkey, &ah, &test_metadata, 100_000_000);
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10_000_000_000)
+        .await
+        .unwrap();
+    let ((bid_acc, _), buy_tx) = buy(
+        &mut context,
+        &ahkey,
+        &ah,
+        &test_metadata,
+        &test_metadata.token.pubkey(),
+        &buyer,
+        100_000_000,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+    let buyer_token_account =
+        get_associated_token_address(&buyer.pubkey(), &test_metadata.mint.pubkey());
+
+    let mut accounts = mpl_auction_house::accounts::ExecuteSale {
+        buyer: buyer.pubkey(),
+        seller: test_metadata.token.pubkey(),
+        auction_house: ahkey,
+        metadata: test_metadata.pubkey,
+        token_account: sell_acc.token_account,
+        authority: ah.authority,
+        seller_trade_state: sell_acc.seller_trade_state,
+        buyer_trade_state: bid_acc.buyer_trade_state,
+        token_program: spl_token::id(),
+        free_trade_state: sell_acc.free_seller_trade_state,
+        seller_payment_receipt_account: test_metadata.token.pubkey(),
+        buyer_receipt_token_account: buyer_token_account,
+        escrow_payment_account: bid_acc.escrow_payment_account,
+        token_mint: test_metadata.mint.pubkey(),
+        auction_house_fee_account: ah.auction_house_fee_account,
+        auction_house_treasury: ah.auction_house_treasury,
+        treasury_mint: ah.treasury_mint,
+        program_as_signer: sell_acc.program_as_signer,
+        system_program: system_program::id(),
+        ata_program: spl_associated_token_account::id(),
+        rent: sysvar::rent::id(),
+    }
+    .to_account_metas(None);
+    // Royalty record of the collection goes ahead of the creators it pays
+    accounts.push(AccountMeta::new_readonly(collection_royalty_record, false));
+    accounts.push(AccountMeta::new(artist.pubkey(), false));
+    let (_, free_sts_bump) = find_trade_state_address(
+        &test_metadata.token.pubkey(),
+        &ahkey,
+        &sell_acc.token_account,
+        &ah.treasury_mint,
+        &test_metadata.mint.pubkey(),
+        0,
+        1,
+    );
+    let (_, escrow_bump) = find_escrow_payment_address(&ahkey, &buyer.pubkey());
+    let (_, pas_bump) = find_program_as_signer_address();
+
+    let instruction = Instruction {
+        program_id: mpl_auction_house::id(),
+        data: mpl_auction_house::instruction::ExecuteSale {
+            escrow_payment_bump: escrow_bump,
+            _free_trade_state_bump: free_sts_bump,
+            program_as_signer_bump: pas_bump,
+            token_size: 1,
+            buyer_price: 100_000_000,
+        }
+        .data(),
+        accounts,
+    };
+    airdrop(&mut context, &ah.auction_house_fee_account, 10_000_000_000)
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    let seller_before = context
+        .banks_client
+        .get_account(test_metadata.token.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let seller_after = context
+        .banks_client
+        .get_account(test_metadata.token.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let artist_after = context
+        .banks_client
+        .get_account(artist.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    // Royalty follows the record of the collection, not the 0.1% of the item
+    let royalty: u64 = 100_000_000 * 500 / 10000;
+    let fee_minus: u64 =
+        100_000_000 - royalty - ((ah.seller_fee_basis_points as u64 * 100_000_000) / 10000);
+    assert_eq!(artist_after.lamports, royalty);
+    assert_eq!(seller_before.lamports + fee_minus, seller_after.lamports);
+}
+
 #[tokio::test]
 async fn execute_sale_existing_token_account_success() {
     let mut context = auction_house_program_test().start_with_context().await;
@@ -1,2 +1,6 @@
 export * from './CandyMachine';
 export * from './CollectionPDA';
+export * from './MintCounterPDA';
+export * from './MintSettingsPDA';
+export * from './PendingAuthorityPDA';
+export * from './PhaseMintCounterPDA';
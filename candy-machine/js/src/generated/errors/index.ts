@@ -643,6 +643,281 @@ createErrorFromNameLookup.set(
   () => new MismatchedCollectionMintError(),
 );
 
+/**
+ * MetadataAccountMustBeEmpty: 'The metadata account has data in it, and this must be empty to mint a new NFT'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class MetadataAccountMustBeEmptyError extends Error {
+  readonly code: number = 0x178e;
+  readonly name: string = 'MetadataAccountMustBeEmpty';
+  constructor() {
+    super('The metadata account has data in it, and this must be empty to mint a new NFT');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, MetadataAccountMustBeEmptyError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x178e, () => new MetadataAccountMustBeEmptyError());
+createErrorFromNameLookup.set(
+  'MetadataAccountMustBeEmpty',
+  () => new MetadataAccountMustBeEmptyError(),
+);
+
+/**
+ * AuthorityChangedSinceNomination: 'Candy machine authority has changed since the nomination was made'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class AuthorityChangedSinceNominationError extends Error {
+  readonly code: number = 0x178f;
+  readonly name: string = 'AuthorityChangedSinceNomination';
+  constructor() {
+    super('Candy machine authority has changed since the nomination was made');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, AuthorityChangedSinceNominationError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x178f, () => new AuthorityChangedSinceNominationError());
+createErrorFromNameLookup.set(
+  'AuthorityChangedSinceNomination',
+  () => new AuthorityChangedSinceNominationError(),
+);
+
+/**
+ * TooManyPhases: 'Can only provide up to 5 phases to candy machine'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class TooManyPhasesError extends Error {
+  readonly code: number = 0x1790;
+  readonly name: string = 'TooManyPhases';
+  constructor() {
+    super('Can only provide up to 5 phases to candy machine');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, TooManyPhasesError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1790, () => new TooManyPhasesError());
+createErrorFromNameLookup.set('TooManyPhases', () => new TooManyPhasesError());
+
+/**
+ * InvalidPhases: 'Phases must be sorted by start date, end after they start and not overlap'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidPhasesError extends Error {
+  readonly code: number = 0x1791;
+  readonly name: string = 'InvalidPhases';
+  constructor() {
+    super('Phases must be sorted by start date, end after they start and not overlap');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidPhasesError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1791, () => new InvalidPhasesError());
+createErrorFromNameLookup.set('InvalidPhases', () => new InvalidPhasesError());
+
+/**
+ * PhaseMintCounterMissing: 'Missing phase mint counter when required'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class PhaseMintCounterMissingError extends Error {
+  readonly code: number = 0x1792;
+  readonly name: string = 'PhaseMintCounterMissing';
+  constructor() {
+    super('Missing phase mint counter when required');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, PhaseMintCounterMissingError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1792, () => new PhaseMintCounterMissingError());
+createErrorFromNameLookup.set('PhaseMintCounterMissing', () => new PhaseMintCounterMissingError());
+
+/**
+ * MismatchedPhaseMintCounterPDA: 'Phase mint counter PDA address is invalid'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class MismatchedPhaseMintCounterPDAError extends Error {
+  readonly code: number = 0x1793;
+  readonly name: string = 'MismatchedPhaseMintCounterPDA';
+  constructor() {
+    super('Phase mint counter PDA address is invalid');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, MismatchedPhaseMintCounterPDAError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1793, () => new MismatchedPhaseMintCounterPDAError());
+createErrorFromNameLookup.set(
+  'MismatchedPhaseMintCounterPDA',
+  () => new MismatchedPhaseMintCounterPDAError(),
+);
+
+/**
+ * PhaseMintLimitReached: 'Wallet reached the mint limit of the phase'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class PhaseMintLimitReachedError extends Error {
+  readonly code: number = 0x1794;
+  readonly name: string = 'PhaseMintLimitReached';
+  constructor() {
+    super('Wallet reached the mint limit of the phase');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, PhaseMintLimitReachedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1794, () => new PhaseMintLimitReachedError());
+createErrorFromNameLookup.set('PhaseMintLimitReached', () => new PhaseMintLimitReachedError());
+
+/**
+ * MintCounterMissing: 'Missing mint counter when required'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class MintCounterMissingError extends Error {
+  readonly code: number = 0x1795;
+  readonly name: string = 'MintCounterMissing';
+  constructor() {
+    super('Missing mint counter when required');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, MintCounterMissingError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1795, () => new MintCounterMissingError());
+createErrorFromNameLookup.set('MintCounterMissing', () => new MintCounterMissingError());
+
+/**
+ * MismatchedMintCounterPDA: 'Mint counter PDA address is invalid'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class MismatchedMintCounterPDAError extends Error {
+  readonly code: number = 0x1796;
+  readonly name: string = 'MismatchedMintCounterPDA';
+  constructor() {
+    super('Mint counter PDA address is invalid');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, MismatchedMintCounterPDAError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1796, () => new MismatchedMintCounterPDAError());
+createErrorFromNameLookup.set(
+  'MismatchedMintCounterPDA',
+  () => new MismatchedMintCounterPDAError(),
+);
+
+/**
+ * MintLimitReached: 'Wallet reached the mint limit of the candy machine'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class MintLimitReachedError extends Error {
+  readonly code: number = 0x1797;
+  readonly name: string = 'MintLimitReached';
+  constructor() {
+    super('Wallet reached the mint limit of the candy machine');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, MintLimitReachedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1797, () => new MintLimitReachedError());
+createErrorFromNameLookup.set('MintLimitReached', () => new MintLimitReachedError());
+
+/**
+ * InvalidCreatorShares: 'Creator shares must add up to 100 to split mint payment'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidCreatorSharesError extends Error {
+  readonly code: number = 0x1798;
+  readonly name: string = 'InvalidCreatorShares';
+  constructor() {
+    super('Creator shares must add up to 100 to split mint payment');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidCreatorSharesError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1798, () => new InvalidCreatorSharesError());
+createErrorFromNameLookup.set('InvalidCreatorShares', () => new InvalidCreatorSharesError());
+
+/**
+ * BurnPaymentRequiresTokenMint: 'Burn payment mode requires a token mint'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class BurnPaymentRequiresTokenMintError extends Error {
+  readonly code: number = 0x1799;
+  readonly name: string = 'BurnPaymentRequiresTokenMint';
+  constructor() {
+    super('Burn payment mode requires a token mint');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, BurnPaymentRequiresTokenMintError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1799, () => new BurnPaymentRequiresTokenMintError());
+createErrorFromNameLookup.set(
+  'BurnPaymentRequiresTokenMint',
+  () => new BurnPaymentRequiresTokenMintError(),
+);
+
+/**
+ * PaymentAccountMissing: 'Missing payment account when required'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class PaymentAccountMissingError extends Error {
+  readonly code: number = 0x179a;
+  readonly name: string = 'PaymentAccountMissing';
+  constructor() {
+    super('Missing payment account when required');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, PaymentAccountMissingError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x179a, () => new PaymentAccountMissingError());
+createErrorFromNameLookup.set('PaymentAccountMissing', () => new PaymentAccountMissingError());
+
 /**
  * Attempts to resolve a custom program error from the provided error code.
  * @category Errors
@@ -1,9 +1,13 @@
+export * from './acceptAuthority';
 export * from './addConfigLines';
+export * from './cancelAuthorityNomination';
 export * from './initializeCandyMachine';
 export * from './mintNft';
+export * from './nominateAuthority';
 export * from './removeCollection';
 export * from './setCollection';
 export * from './setCollectionDuringMint';
+export * from './setMintSettings';
 export * from './updateAuthority';
 export * from './updateCandyMachine';
 export * from './withdrawFunds';
@@ -41,6 +41,19 @@ export enum ErrorCode {
   IncorrectCollectionAuthority,
   MismatchedCollectionPDA,
   MismatchedCollectionMint,
+  MetadataAccountMustBeEmpty,
+  AuthorityChangedSinceNomination,
+  TooManyPhases,
+  InvalidPhases,
+  PhaseMintCounterMissing,
+  MismatchedPhaseMintCounterPDA,
+  PhaseMintLimitReached,
+  MintCounterMissing,
+  MismatchedMintCounterPDA,
+  MintLimitReached,
+  InvalidCreatorShares,
+  BurnPaymentRequiresTokenMint,
+  PaymentAccountMissing,
 }
 
 /**
@@ -6,5 +6,7 @@ export * from './EndSettings';
 export * from './ErrorCode';
 export * from './GatekeeperConfig';
 export * from './HiddenSettings';
+export * from './MintPhase';
+export * from './PaymentMode';
 export * from './WhitelistMintMode';
 export * from './WhitelistMintSettings';
@@ -2,6 +2,7 @@ pub mod utils;
 
 use crate::utils::{
     assert_initialized, assert_is_ata, assert_keys_equal, assert_owned_by, assert_valid_go_live,
+    assert_valid_payment_mode, assert_valid_phases, get_active_phase, get_creator_split,
     punish_bots, spl_token_burn, spl_token_transfer, TokenBurnParams, TokenTransferParams,
 };
 use anchor_lang::{
@@ -131,7 +132,57 @@ pub mod candy_machine {
             return Err(ErrorCode::IncorrectSlotHashesPubkey.into());
         }
 
-        let mut price = candy_machine.data.price;
+        // The mint settings PDA is passed as the first remaining account so clients that never
+        // set mint settings keep working without it.
+        let mut remaining_accounts_counter: usize = 0;
+        let (mint_settings_key, _) = Pubkey::find_program_address(
+            &[b"mint_settings".as_ref(), candy_machine.key().as_ref()],
+            &candy_machine::id(),
+        );
+        let mint_settings = match ctx.remaining_accounts.first() {
+            Some(mint_settings_pda) if mint_settings_pda.key() == mint_settings_key => {
+                remaining_accounts_counter += 1;
+                if mint_settings_pda.data_is_empty() {
+                    MintSettingsPDA::default()
+                } else {
+                    assert_owned_by(mint_settings_pda, &candy_machine::id())?;
+                    let data_ref: &[u8] = &mint_settings_pda.try_borrow_data()?;
+                    MintSettingsPDA::try_deserialize(&mut &*data_ref)?
+                }
+            }
+            _ => MintSettingsPDA::default(),
+        };
+
+        // The active phase replaces price, whitelist, gatekeeper and go live date of the machine,
+        // outside of phases only the authority can mint.
+        let phase = get_active_phase(&mint_settings.phases, clock.unix_timestamp);
+        if !mint_settings.phases.is_empty()
+            && phase.is_none()
+            && ctx.accounts.payer.key() != candy_machine.authority
+        {
+            punish_bots(
+                ErrorCode::CandyMachineNotLive,
+                payer.to_account_info(),
+                ctx.accounts.candy_machine.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                BOT_FEE,
+            )?;
+            return Ok(());
+        }
+        let (mut price, gatekeeper, whitelist_mint_settings, go_live_date) = match &phase {
+            Some(p) => (
+                p.price,
+                p.gatekeeper.clone(),
+                p.whitelist_mint_settings.clone(),
+                Some(p.start),
+            ),
+            None => (
+                candy_machine.data.price,
+                candy_machine.data.gatekeeper.clone(),
+                candy_machine.data.whitelist_mint_settings.clone(),
+                candy_machine.data.go_live_date,
+            ),
+        };
         if let Some(es) = &candy_machine.data.end_settings {
             match es.end_setting_type {
                 EndSettingType::Date => {
@@ -166,8 +217,7 @@ pub mod candy_machine {
             }
         }
 
-        let mut remaining_accounts_counter: usize = 0;
-        if let Some(gatekeeper) = &candy_machine.data.gatekeeper {
+        if let Some(gatekeeper) = &gatekeeper {
             if ctx.remaining_accounts.len() <= remaining_accounts_counter {
                 punish_bots(
                     ErrorCode::GatewayTokenMissing,
@@ -218,10 +268,10 @@ pub mod candy_machine {
             }
             // verifies that the gatway token was not created before the candy
             // machine go_live_date (avoids pre-solving the captcha)
-            match candy_machine.data.go_live_date {
+            match go_live_date {
                 Some(val) => {
                     if (expire_time - EXPIRE_OFFSET) < val {
-                        if let Some(ws) = &candy_machine.data.whitelist_mint_settings {
+                        if let Some(ws) = &whitelist_mint_settings {
                             // when dealing with whitelist, the expire_time can be
                             // before the go_live_date only if presale enabled
                             if !ws.presale {
@@ -244,7 +294,7 @@ pub mod candy_machine {
             }
         }
 
-        if let Some(ws) = &candy_machine.data.whitelist_mint_settings {
+        if let Some(ws) = &whitelist_mint_settings {
             let whitelist_token_account = &ctx.remaining_accounts[remaining_accounts_counter];
             remaining_accounts_counter += 1;
             // If the user has not actually made this account,
@@ -254,7 +304,7 @@ pub mod candy_machine {
             match assert_is_ata(whitelist_token_account, &payer.key(), &ws.mint) {
                 Ok(wta) => {
                     if wta.amount > 0 {
-                        match candy_machine.data.go_live_date {
+                        match go_live_date {
                             None => {
                                 if ctx.accounts.payer.key() != candy_machine.authority
                                     && !ws.presale
@@ -332,7 +382,8 @@ pub mod candy_machine {
                             )?;
                             return Ok(());
                         }
-                        let go_live = assert_valid_go_live(payer, clock, candy_machine);
+                        let go_live =
+                            assert_valid_go_live(payer, clock, candy_machine, go_live_date);
                         if go_live.is_err() {
                             punish_bots(
                                 ErrorCode::CandyMachineNotLive,
@@ -365,7 +416,7 @@ pub mod candy_machine {
                     if ws.mode == WhitelistMintMode::BurnEveryTime {
                         remaining_accounts_counter += 2;
                     }
-                    let go_live = assert_valid_go_live(payer, clock, candy_machine);
+                    let go_live = assert_valid_go_live(payer, clock, candy_machine, go_live_date);
                     if go_live.is_err() {
                         punish_bots(
                             ErrorCode::CandyMachineNotLive,
@@ -380,7 +431,7 @@ pub mod candy_machine {
             }
         } else {
             // no whitelist means normal datecheck
-            let go_live = assert_valid_go_live(payer, clock, candy_machine);
+            let go_live = assert_valid_go_live(payer, clock, candy_machine, go_live_date);
             if go_live.is_err() {
                 punish_bots(
                     ErrorCode::CandyMachineNotLive,
@@ -404,6 +455,153 @@ pub mod candy_machine {
             return Ok(());
         }
 
+        if let Some(mint_limit) = mint_settings.mint_limit {
+            if ctx.remaining_accounts.len() <= remaining_accounts_counter {
+                punish_bots(
+                    ErrorCode::MintCounterMissing,
+                    payer.to_account_info(),
+                    ctx.accounts.candy_machine.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    BOT_FEE,
+                )?;
+                return Ok(());
+            }
+            let mint_counter = &ctx.remaining_accounts[remaining_accounts_counter];
+            remaining_accounts_counter += 1;
+
+            let cm_key = candy_machine.key();
+            let seeds = [
+                b"mint_counter".as_ref(),
+                cm_key.as_ref(),
+                payer.key.as_ref(),
+            ];
+            let (mint_counter_key, bump) =
+                Pubkey::find_program_address(&seeds, &candy_machine::id());
+            if mint_counter.key() != mint_counter_key {
+                return Err(ErrorCode::MismatchedMintCounterPDA.into());
+            }
+
+            let count = if mint_counter.data_is_empty() {
+                create_or_allocate_account_raw(
+                    crate::id(),
+                    mint_counter,
+                    &ctx.accounts.rent.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    &payer.to_account_info(),
+                    MINT_COUNTER_PDA_SIZE,
+                    &[
+                        b"mint_counter".as_ref(),
+                        cm_key.as_ref(),
+                        payer.key.as_ref(),
+                        &[bump],
+                    ],
+                )?;
+                0
+            } else {
+                assert_owned_by(mint_counter, &candy_machine::id())?;
+                let data_ref: &[u8] = &mint_counter.try_borrow_data()?;
+                MintCounterPDA::try_deserialize(&mut &*data_ref)?.count
+            };
+
+            if count >= mint_limit {
+                punish_bots(
+                    ErrorCode::MintLimitReached,
+                    payer.to_account_info(),
+                    ctx.accounts.candy_machine.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    BOT_FEE,
+                )?;
+                return Ok(());
+            }
+
+            let mut data_ref: &mut [u8] = &mut mint_counter.try_borrow_mut_data()?;
+            let mint_counter_object = MintCounterPDA {
+                candy_machine: cm_key,
+                wallet: payer.key(),
+                count: count
+                    .checked_add(1)
+                    .ok_or(ErrorCode::NumericalOverflowError)?,
+            };
+            mint_counter_object.try_serialize(&mut data_ref)?;
+        }
+
+        if let Some(phase) = &phase {
+            if let Some(mint_limit) = phase.mint_limit {
+                if ctx.remaining_accounts.len() <= remaining_accounts_counter {
+                    punish_bots(
+                        ErrorCode::PhaseMintCounterMissing,
+                        payer.to_account_info(),
+                        ctx.accounts.candy_machine.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                        BOT_FEE,
+                    )?;
+                    return Ok(());
+                }
+                let phase_mint_counter = &ctx.remaining_accounts[remaining_accounts_counter];
+                remaining_accounts_counter += 1;
+
+                let cm_key = candy_machine.key();
+                // Keyed on the phase start, so reordering phases doesn't share counters
+                let phase_seed = phase.start.to_le_bytes();
+                let seeds = [
+                    b"phase_mint_counter".as_ref(),
+                    cm_key.as_ref(),
+                    phase_seed.as_ref(),
+                    payer.key.as_ref(),
+                ];
+                let (phase_mint_counter_key, bump) =
+                    Pubkey::find_program_address(&seeds, &candy_machine::id());
+                if phase_mint_counter.key() != phase_mint_counter_key {
+                    return Err(ErrorCode::MismatchedPhaseMintCounterPDA.into());
+                }
+
+                let count = if phase_mint_counter.data_is_empty() {
+                    create_or_allocate_account_raw(
+                        crate::id(),
+                        phase_mint_counter,
+                        &ctx.accounts.rent.to_account_info(),
+                        &ctx.accounts.system_program.to_account_info(),
+                        &payer.to_account_info(),
+                        PHASE_MINT_COUNTER_PDA_SIZE,
+                        &[
+                            b"phase_mint_counter".as_ref(),
+                            cm_key.as_ref(),
+                            phase_seed.as_ref(),
+                            payer.key.as_ref(),
+                            &[bump],
+                        ],
+                    )?;
+                    0
+                } else {
+                    assert_owned_by(phase_mint_counter, &candy_machine::id())?;
+                    let data_ref: &[u8] = &phase_mint_counter.try_borrow_data()?;
+                    PhaseMintCounterPDA::try_deserialize(&mut &*data_ref)?.count
+                };
+
+                if count >= mint_limit {
+                    punish_bots(
+                        ErrorCode::PhaseMintLimitReached,
+                        payer.to_account_info(),
+                        ctx.accounts.candy_machine.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                        BOT_FEE,
+                    )?;
+                    return Ok(());
+                }
+
+                let mut data_ref: &mut [u8] = &mut phase_mint_counter.try_borrow_mut_data()?;
+                let phase_mint_counter_object = PhaseMintCounterPDA {
+                    candy_machine: cm_key,
+                    wallet: payer.key(),
+                    phase_start: phase.start,
+                    count: count
+                        .checked_add(1)
+                        .ok_or(ErrorCode::NumericalOverflowError)?,
+                };
+                phase_mint_counter_object.try_serialize(&mut data_ref)?;
+            }
+        }
+
         if let Some(mint) = candy_machine.token_mint {
             let token_account_info = &ctx.remaining_accounts[remaining_accounts_counter];
             remaining_accounts_counter += 1;
@@ -415,27 +613,110 @@ pub mod candy_machine {
                 return Err(ErrorCode::NotEnoughTokens.into());
             }
 
-            spl_token_transfer(TokenTransferParams {
-                source: token_account_info.clone(),
-                destination: wallet.to_account_info(),
-                authority: transfer_authority_info.clone(),
-                authority_signer_seeds: &[],
-                token_program: token_program.to_account_info(),
-                amount: price,
-            })?;
+            match mint_settings.payment_mode {
+                PaymentMode::Wallet => {
+                    spl_token_transfer(TokenTransferParams {
+                        source: token_account_info.clone(),
+                        destination: wallet.to_account_info(),
+                        authority: transfer_authority_info.clone(),
+                        authority_signer_seeds: &[],
+                        token_program: token_program.to_account_info(),
+                        amount: price,
+                    })?;
+                }
+                PaymentMode::CreatorSplit => {
+                    for (creator, amount) in get_creator_split(&candy_machine.data.creators, price)?
+                    {
+                        if ctx.remaining_accounts.len() <= remaining_accounts_counter {
+                            return Err(ErrorCode::PaymentAccountMissing.into());
+                        }
+                        let creator_token_account =
+                            &ctx.remaining_accounts[remaining_accounts_counter];
+                        remaining_accounts_counter += 1;
+                        assert_is_ata(creator_token_account, &creator, &mint)?;
+                        if amount == 0 {
+                            continue;
+                        }
+
+                        spl_token_transfer(TokenTransferParams {
+                            source: token_account_info.clone(),
+                            destination: creator_token_account.clone(),
+                            authority: transfer_authority_info.clone(),
+                            authority_signer_seeds: &[],
+                            token_program: token_program.to_account_info(),
+                            amount,
+                        })?;
+                    }
+                }
+                PaymentMode::Burn => {
+                    if ctx.remaining_accounts.len() <= remaining_accounts_counter {
+                        return Err(ErrorCode::PaymentAccountMissing.into());
+                    }
+                    let token_mint_info = &ctx.remaining_accounts[remaining_accounts_counter];
+                    remaining_accounts_counter += 1;
+                    assert_keys_equal(token_mint_info.key(), mint)?;
+
+                    spl_token_burn(TokenBurnParams {
+                        mint: token_mint_info.clone(),
+                        source: token_account_info.clone(),
+                        amount: price,
+                        authority: transfer_authority_info.clone(),
+                        authority_signer_seeds: None,
+                        token_program: token_program.to_account_info(),
+                    })?;
+                }
+            }
         } else {
             if ctx.accounts.payer.lamports() < price {
                 return Err(ErrorCode::NotEnoughSOL.into());
             }
 
-            invoke(
-                &system_instruction::transfer(&ctx.accounts.payer.key(), &wallet.key(), price),
-                &[
-                    ctx.accounts.payer.to_account_info(),
-                    wallet.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-            )?;
+            match mint_settings.payment_mode {
+                PaymentMode::Wallet => {
+                    invoke(
+                        &system_instruction::transfer(
+                            &ctx.accounts.payer.key(),
+                            &wallet.key(),
+                            price,
+                        ),
+                        &[
+                            ctx.accounts.payer.to_account_info(),
+                            wallet.to_account_info(),
+                            ctx.accounts.system_program.to_account_info(),
+                        ],
+                    )?;
+                }
+                PaymentMode::CreatorSplit => {
+                    for (creator, amount) in get_creator_split(&candy_machine.data.creators, price)?
+                    {
+                        if ctx.remaining_accounts.len() <= remaining_accounts_counter {
+                            return Err(ErrorCode::PaymentAccountMissing.into());
+                        }
+                        let creator_info = &ctx.remaining_accounts[remaining_accounts_counter];
+                        remaining_accounts_counter += 1;
+                        assert_keys_equal(creator_info.key(), creator)?;
+                        if amount == 0 {
+                            continue;
+                        }
+
+                        invoke(
+                            &system_instruction::transfer(
+                                &ctx.accounts.payer.key(),
+                                &creator_info.key(),
+                                amount,
+                            ),
+                            &[
+                                ctx.accounts.payer.to_account_info(),
+                                creator_info.clone(),
+                                ctx.accounts.system_program.to_account_info(),
+                            ],
+                        )?;
+                    }
+                }
+                PaymentMode::Burn => {
+                    return Err(ErrorCode::BurnPaymentRequiresTokenMint.into());
+                }
+            }
         }
 
         let data = recent_slothashes.data.borrow();
@@ -954,6 +1235,48 @@ pub mod candy_machine {
         Ok(())
     }
 
+    pub fn set_mint_settings(
+        ctx: Context<SetMintSettings>,
+        phases: Vec<MintPhase>,
+        mint_limit: Option<u64>,
+        payment_mode: PaymentMode,
+    ) -> ProgramResult {
+        let candy_machine = &ctx.accounts.candy_machine;
+
+        assert_valid_phases(&phases)?;
+        assert_valid_payment_mode(
+            &payment_mode,
+            &candy_machine.data.creators,
+            candy_machine.token_mint,
+        )?;
+
+        if ctx.accounts.mint_settings_pda.data_is_empty() {
+            create_or_allocate_account_raw(
+                crate::id(),
+                &ctx.accounts.mint_settings_pda.to_account_info(),
+                &ctx.accounts.rent.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                MINT_SETTINGS_PDA_SIZE,
+                &[
+                    b"mint_settings".as_ref(),
+                    &candy_machine.key().as_ref(),
+                    &[*ctx.bumps.get("mint_settings_pda").unwrap()],
+                ],
+            )?;
+        }
+        // Setting again replaces the previous settings.
+        let mut data_ref: &mut [u8] = &mut ctx.accounts.mint_settings_pda.try_borrow_mut_data()?;
+        let mint_settings_object = MintSettingsPDA {
+            candy_machine: candy_machine.key(),
+            phases,
+            mint_limit,
+            payment_mode,
+        };
+        mint_settings_object.try_serialize(&mut data_ref)?;
+        Ok(())
+    }
+
     pub fn update_authority(
         ctx: Context<UpdateCandyMachine>,
         new_authority: Option<Pubkey>,
@@ -967,6 +1290,53 @@ pub mod candy_machine {
         Ok(())
     }
 
+    pub fn nominate_authority(
+        ctx: Context<NominateAuthority>,
+        new_authority: Pubkey,
+    ) -> ProgramResult {
+        let candy_machine = &ctx.accounts.candy_machine;
+        if ctx.accounts.pending_authority_pda.data_is_empty() {
+            create_or_allocate_account_raw(
+                crate::id(),
+                &ctx.accounts.pending_authority_pda.to_account_info(),
+                &ctx.accounts.rent.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                PENDING_AUTHORITY_PDA_SIZE,
+                &[
+                    b"pending_authority".as_ref(),
+                    &candy_machine.key().as_ref(),
+                    &[*ctx.bumps.get("pending_authority_pda").unwrap()],
+                ],
+            )?;
+        }
+        // Nominating again replaces the previous nominee.
+        let mut data_ref: &mut [u8] =
+            &mut ctx.accounts.pending_authority_pda.try_borrow_mut_data()?;
+        let mut pending_authority_object: PendingAuthorityPDA =
+            AnchorDeserialize::deserialize(&mut &*data_ref)?;
+        pending_authority_object.authority = candy_machine.authority;
+        pending_authority_object.new_authority = new_authority;
+        pending_authority_object.candy_machine = candy_machine.key();
+        pending_authority_object.try_serialize(&mut data_ref)?;
+        msg!("Nominated {} as candy machine authority", new_authority);
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> ProgramResult {
+        let candy_machine = &mut ctx.accounts.candy_machine;
+        // The authority may have been changed directly after the nomination was made.
+        if ctx.accounts.pending_authority_pda.authority != candy_machine.authority {
+            return Err(ErrorCode::AuthorityChangedSinceNomination.into());
+        }
+        candy_machine.authority = ctx.accounts.new_authority.key();
+        Ok(())
+    }
+
+    pub fn cancel_authority_nomination(_ctx: Context<CancelAuthorityNomination>) -> ProgramResult {
+        Ok(())
+    }
+
     pub fn withdraw_funds<'info>(ctx: Context<WithdrawFunds<'info>>) -> ProgramResult {
         let authority = &ctx.accounts.authority;
         let pay = &ctx.accounts.candy_machine.to_account_info();
@@ -1106,6 +1476,60 @@ pub struct RemoveCollection<'info> {
     token_metadata_program: UncheckedAccount<'info>,
 }
 
+/// Set the mint settings PDA for the candy machine
+#[derive(Accounts)]
+pub struct SetMintSettings<'info> {
+    #[account(has_one = authority)]
+    candy_machine: Account<'info, CandyMachine>,
+    authority: Signer<'info>,
+    /// CHECK: account constraints checked in account trait
+    #[account(mut, seeds = [b"mint_settings".as_ref(), candy_machine.to_account_info().key.as_ref()], bump)]
+    mint_settings_pda: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Nominate a new authority for the candy machine.
+#[derive(Accounts)]
+pub struct NominateAuthority<'info> {
+    #[account(has_one = authority)]
+    candy_machine: Account<'info, CandyMachine>,
+    authority: Signer<'info>,
+    /// CHECK: account constraints checked in account trait
+    #[account(mut, seeds = [b"pending_authority".as_ref(), candy_machine.to_account_info().key.as_ref()], bump)]
+    pending_authority_pda: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Accept a pending authority nomination for the candy machine.
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut)]
+    candy_machine: Account<'info, CandyMachine>,
+    new_authority: Signer<'info>,
+    /// CHECK: rent of the closed pending authority PDA is returned to the current authority
+    #[account(mut, address = candy_machine.authority)]
+    authority: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"pending_authority".as_ref(), candy_machine.to_account_info().key.as_ref()], bump, has_one = new_authority, has_one = candy_machine, close = authority)]
+    pending_authority_pda: Account<'info, PendingAuthorityPDA>,
+}
+
+/// Cancel a pending authority nomination for the candy machine.
+#[derive(Accounts)]
+pub struct CancelAuthorityNomination<'info> {
+    #[account(has_one = authority)]
+    candy_machine: Account<'info, CandyMachine>,
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, seeds = [b"pending_authority".as_ref(), candy_machine.to_account_info().key.as_ref()], bump, close = authority)]
+    pending_authority_pda: Account<'info, PendingAuthorityPDA>,
+}
+
 /// Add multiple config lines to the candy machine.
 #[derive(Accounts)]
 pub struct AddConfigLines<'info> {
@@ -1167,19 +1591,29 @@ pub struct MintNFT<'info> {
     /// CHECK: account constraints checked in account trait
     #[account(address = sysvar::instructions::id())]
     instruction_sysvar_account: UncheckedAccount<'info>,
-    // > Only needed if candy machine has a gatekeeper
+    // > Only needed if mint settings are set, checked against the derived address
+    // mint_settings_pda
+    // > Only needed if candy machine or the active phase has a gatekeeper
     // gateway_token
     // > Only needed if candy machine has a gatekeeper and it has expire_on_use set to true:
     // gateway program
     // network_expire_feature
-    // > Only needed if candy machine has whitelist_mint_settings
+    // > Only needed if candy machine or the active phase has whitelist_mint_settings
     // whitelist_token_account
     // > Only needed if candy machine has whitelist_mint_settings and mode is BurnEveryTime
     // whitelist_token_mint
     // whitelist_burn_authority
+    // > Only needed if mint settings have mint_limit
+    // mint_counter
+    // > Only needed if the active phase has mint_limit
+    // phase_mint_counter
     // > Only needed if candy machine has token mint
     // token_account_info
     // transfer_authority_info
+    // > Only needed if mint settings payment_mode is CreatorSplit, one per creator in creators order
+    // creator wallet, or its associated token account if candy machine has token mint
+    // > Only needed if mint settings payment_mode is Burn
+    // token_mint
 }
 
 /// Update the candy machine state.
@@ -1220,6 +1654,60 @@ pub struct CollectionPDA {
     pub candy_machine: Pubkey,
 }
 
+const PENDING_AUTHORITY_PDA_SIZE: usize = 8 + 96;
+
+/// Pending authority PDA account
+#[account]
+#[derive(Default, Debug)]
+pub struct PendingAuthorityPDA {
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub candy_machine: Pubkey,
+}
+
+const MINT_COUNTER_PDA_SIZE: usize = 8 + 32 + 32 + 8;
+
+/// Mint counter PDA account, NFTs minted by a wallet
+#[account]
+#[derive(Default, Debug)]
+pub struct MintCounterPDA {
+    pub candy_machine: Pubkey,
+    pub wallet: Pubkey,
+    pub count: u64,
+}
+
+const PHASE_MINT_COUNTER_PDA_SIZE: usize = 8 + 32 + 32 + 8 + 8;
+
+/// Phase mint counter PDA account, NFTs minted by a wallet during a phase
+#[account]
+#[derive(Default, Debug)]
+pub struct PhaseMintCounterPDA {
+    pub candy_machine: Pubkey,
+    pub wallet: Pubkey,
+    pub phase_start: i64,
+    pub count: u64,
+}
+
+const MINT_SETTINGS_PDA_SIZE: usize = 8 + // key
+    32 + // candy machine
+    4 + MAX_PHASES * MINT_PHASE_SIZE + // phases
+    9 + // mint limit
+    1 // payment mode
+;
+
+/// Mint settings PDA account, kept out of the candy machine account so its layout doesn't change
+#[account]
+#[derive(Default)]
+pub struct MintSettingsPDA {
+    pub candy_machine: Pubkey,
+    /// Minting phases sorted by start date, the active one replaces `price`, `go_live_date`,
+    /// `whitelist_mint_settings` and `gatekeeper`
+    pub phases: Vec<MintPhase>,
+    /// Max NFTs minted by one wallet, counted in a mint counter PDA
+    pub mint_limit: Option<u64>,
+    pub payment_mode: PaymentMode,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct WhitelistMintSettings {
     pub mode: WhitelistMintMode,
@@ -1259,6 +1747,45 @@ pub struct CandyMachineData {
     pub gatekeeper: Option<GatekeeperConfig>,
 }
 
+/// Where the mint price goes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum PaymentMode {
+    /// Price is paid to `wallet`
+    Wallet,
+    /// Price is split between `creators` by share, in SOL or `token_mint`
+    CreatorSplit,
+    /// Price is burned from the payer's `token_mint` account
+    Burn,
+}
+
+impl Default for PaymentMode {
+    fn default() -> Self {
+        PaymentMode::Wallet
+    }
+}
+
+/// Minting phase, active from `start` until `end` or until a later phase starts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintPhase {
+    pub start: i64,
+    pub end: Option<i64>,
+    pub price: u64,
+    pub whitelist_mint_settings: Option<WhitelistMintSettings>,
+    pub gatekeeper: Option<GatekeeperConfig>,
+    /// Max NFTs minted by one wallet during the phase
+    pub mint_limit: Option<u64>,
+}
+
+pub const MAX_PHASES: usize = 5;
+
+pub const MINT_PHASE_SIZE: usize = 8 + // start
+    9 + // end
+    8 + // price
+    1 + 1 + 32 + 1 + 9 + // whitelist mint settings
+    1 + 32 + 1 + // gatekeeper
+    9 // mint limit
+;
+
 /// Configurations options for the gatekeeper.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct GatekeeperConfig {
@@ -1551,4 +2078,28 @@ pub enum ErrorCode {
     MismatchedCollectionMint,
     #[msg("The metadata account has data in it, and this must be empty to mint a new NFT")]
     MetadataAccountMustBeEmpty,
+    #[msg("Candy machine authority has changed since the nomination was made")]
+    AuthorityChangedSinceNomination,
+    #[msg("Can only provide up to 5 phases to candy machine")]
+    TooManyPhases,
+    #[msg("Phases must be sorted by start date, end after they start and not overlap")]
+    InvalidPhases,
+    #[msg("Missing phase mint counter when required")]
+    PhaseMintCounterMissing,
+    #[msg("Phase mint counter PDA address is invalid")]
+    MismatchedPhaseMintCounterPDA,
+    #[msg("Wallet reached the mint limit of the phase")]
+    PhaseMintLimitReached,
+    #[msg("Missing mint counter when required")]
+    MintCounterMissing,
+    #[msg("Mint counter PDA address is invalid")]
+    MismatchedMintCounterPDA,
+    #[msg("Wallet reached the mint limit of the candy machine")]
+    MintLimitReached,
+    #[msg("Creator shares must add up to 100 to split mint payment")]
+    InvalidCreatorShares,
+    #[msg("Burn payment mode requires a token mint")]
+    BurnPaymentRequiresTokenMint,
+    #[msg("Missing payment account when required")]
+    PaymentAccountMissing,
 }
@@ -3,7 +3,7 @@ use solana_program::program::invoke;
 use solana_program::system_instruction;
 
 use {
-    crate::{CandyMachine, ErrorCode},
+    crate::{CandyMachine, Creator, ErrorCode, MintPhase, PaymentMode, MAX_PHASES},
     anchor_lang::{
         prelude::{Account, AccountInfo, Clock, ProgramError, ProgramResult, Pubkey},
         solana_program::{
@@ -30,8 +30,9 @@ pub fn assert_valid_go_live<'info>(
     payer: &Signer<'info>,
     clock: &Sysvar<Clock>,
     candy_machine: &Account<'info, CandyMachine>,
+    go_live_date: Option<i64>,
 ) -> ProgramResult {
-    match candy_machine.data.go_live_date {
+    match go_live_date {
         None => {
             if *payer.key != candy_machine.authority {
                 return Err(ErrorCode::CandyMachineNotLive.into());
@@ -47,6 +48,82 @@ pub fn assert_valid_go_live<'info>(
     Ok(())
 }
 
+pub fn assert_valid_phases(phases: &[MintPhase]) -> ProgramResult {
+    if phases.len() > MAX_PHASES {
+        return Err(ErrorCode::TooManyPhases.into());
+    }
+
+    let mut previous: Option<&MintPhase> = None;
+    for phase in phases {
+        if previous.map_or(false, |previous| {
+            phase.start <= previous.start || previous.end.map_or(false, |end| phase.start < end)
+        }) || phase.end.map_or(false, |end| end <= phase.start)
+        {
+            return Err(ErrorCode::InvalidPhases.into());
+        }
+        previous = Some(phase);
+    }
+
+    Ok(())
+}
+
+pub fn assert_valid_payment_mode(
+    payment_mode: &PaymentMode,
+    creators: &[Creator],
+    token_mint: Option<Pubkey>,
+) -> ProgramResult {
+    match payment_mode {
+        PaymentMode::Wallet => {}
+        PaymentMode::CreatorSplit => {
+            let total_share: u64 = creators.iter().map(|c| c.share as u64).sum();
+            if total_share != 100 {
+                return Err(ErrorCode::InvalidCreatorShares.into());
+            }
+        }
+        PaymentMode::Burn => {
+            if token_mint.is_none() {
+                return Err(ErrorCode::BurnPaymentRequiresTokenMint.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Split `price` between creators by share, the last creator gets the rounding remainder.
+pub fn get_creator_split(
+    creators: &[Creator],
+    price: u64,
+) -> Result<Vec<(Pubkey, u64)>, ProgramError> {
+    let mut split = Vec::with_capacity(creators.len());
+    let mut remaining = price;
+    for (index, creator) in creators.iter().enumerate() {
+        let amount = if index == creators.len() - 1 {
+            remaining
+        } else {
+            (price as u128)
+                .checked_mul(creator.share as u128)
+                .and_then(|v| v.checked_div(100))
+                .ok_or(ErrorCode::NumericalOverflowError)? as u64
+        };
+        remaining = remaining
+            .checked_sub(amount)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+        split.push((creator.address, amount));
+    }
+
+    Ok(split)
+}
+
+/// Latest started phase which hasn't ended yet.
+pub fn get_active_phase(phases: &[MintPhase], now: i64) -> Option<MintPhase> {
+    phases
+        .iter()
+        .rev()
+        .find(|phase| phase.start <= now && phase.end.map_or(true, |end| now < end))
+        .cloned()
+}
+
 pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
     if account.owner != owner {
         Err(ErrorCode::IncorrectOwner.into())
@@ -194,3 +271,192 @@ pub fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> ProgramResult {
     );
     result.map_err(|_| ErrorCode::TokenBurnFailed.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        utils::{
+            assert_valid_payment_mode, assert_valid_phases, get_active_phase, get_creator_split,
+        },
+        Creator, ErrorCode, MintPhase, PaymentMode,
+    };
+    use anchor_lang::prelude::{ProgramError, Pubkey};
+
+    fn phase(start: i64, end: Option<i64>) -> MintPhase {
+        MintPhase {
+            start,
+            end,
+            price: start as u64,
+            whitelist_mint_settings: None,
+            gatekeeper: None,
+            mint_limit: None,
+        }
+    }
+
+    fn invalid_phases() -> Result<(), ProgramError> {
+        Err(ErrorCode::InvalidPhases.into())
+    }
+
+    fn creator(address: Pubkey, share: u8) -> Creator {
+        Creator {
+            address,
+            verified: false,
+            share,
+        }
+    }
+
+    #[test]
+    fn get_active_phase_boundaries() {
+        let phases = vec![
+            phase(100, Some(200)),
+            phase(200, None),
+            phase(300, Some(400)),
+        ];
+
+        assert!(get_active_phase(&phases, 99).is_none());
+        assert_eq!(get_active_phase(&phases, 100).unwrap().start, 100);
+        assert_eq!(get_active_phase(&phases, 199).unwrap().start, 100);
+        // `end` is exclusive, the next phase starts at the same time
+        assert_eq!(get_active_phase(&phases, 200).unwrap().start, 200);
+        // Phase without `end` lasts until a later phase starts
+        assert_eq!(get_active_phase(&phases, 299).unwrap().start, 200);
+        assert_eq!(get_active_phase(&phases, 300).unwrap().start, 300);
+        assert!(get_active_phase(&phases, 400).is_none());
+        assert!(get_active_phase(&[], 100).is_none());
+    }
+
+    #[test]
+    fn get_active_phase_gap() {
+        let phases = vec![phase(100, Some(150)), phase(200, None)];
+
+        assert!(get_active_phase(&phases, 150).is_none());
+        assert!(get_active_phase(&phases, 199).is_none());
+        assert_eq!(get_active_phase(&phases, 200).unwrap().start, 200);
+    }
+
+    #[test]
+    fn assert_valid_phases_success() {
+        assert!(assert_valid_phases(&[]).is_ok());
+        assert!(assert_valid_phases(&[
+            phase(100, Some(200)),
+            phase(200, None),
+            phase(300, Some(400))
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn assert_valid_phases_unsorted() {
+        assert_eq!(
+            assert_valid_phases(&[phase(200, None), phase(100, None)]),
+            invalid_phases()
+        );
+        assert_eq!(
+            assert_valid_phases(&[phase(100, None), phase(100, None)]),
+            invalid_phases()
+        );
+    }
+
+    #[test]
+    fn assert_valid_phases_overlapping() {
+        assert_eq!(
+            assert_valid_phases(&[phase(100, Some(201)), phase(200, None)]),
+            invalid_phases()
+        );
+    }
+
+    #[test]
+    fn assert_valid_phases_end_before_start() {
+        assert_eq!(
+            assert_valid_phases(&[phase(100, Some(100))]),
+            invalid_phases()
+        );
+    }
+
+    #[test]
+    fn assert_valid_phases_too_many() {
+        let phases: Vec<MintPhase> = (0..6).map(|i| phase(i * 100, None)).collect();
+
+        assert_eq!(
+            assert_valid_phases(&phases),
+            Err(ErrorCode::TooManyPhases.into())
+        );
+    }
+
+    #[test]
+    fn get_creator_split_by_share() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let creators = vec![creator(first, 30), creator(second, 70)];
+
+        assert_eq!(
+            get_creator_split(&creators, 1_000).unwrap(),
+            vec![(first, 300), (second, 700)]
+        );
+    }
+
+    #[test]
+    fn get_creator_split_remainder_to_last() {
+        let (first, second, third) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let creators = vec![creator(first, 33), creator(second, 33), creator(third, 34)];
+
+        // 33% of 10 rounds down to 3, the last creator gets the remaining 4
+        assert_eq!(
+            get_creator_split(&creators, 10).unwrap(),
+            vec![(first, 3), (second, 3), (third, 4)]
+        );
+    }
+
+    #[test]
+    fn get_creator_split_zero_amounts() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let creators = vec![creator(first, 1), creator(second, 99)];
+
+        // Zero amounts are kept so payment accounts stay in creators order, mint_nft skips them
+        assert_eq!(
+            get_creator_split(&creators, 10).unwrap(),
+            vec![(first, 0), (second, 10)]
+        );
+        assert_eq!(
+            get_creator_split(&creators, 0).unwrap(),
+            vec![(first, 0), (second, 0)]
+        );
+    }
+
+    #[test]
+    fn assert_valid_payment_mode_wallet() {
+        assert!(assert_valid_payment_mode(&PaymentMode::Wallet, &[], None).is_ok());
+    }
+
+    #[test]
+    fn assert_valid_payment_mode_creator_split() {
+        let creators = vec![
+            creator(Pubkey::new_unique(), 40),
+            creator(Pubkey::new_unique(), 60),
+        ];
+
+        assert!(assert_valid_payment_mode(&PaymentMode::CreatorSplit, &creators, None).is_ok());
+        assert_eq!(
+            assert_valid_payment_mode(&PaymentMode::CreatorSplit, &creators[..1], None),
+            Err(ErrorCode::InvalidCreatorShares.into())
+        );
+        assert_eq!(
+            assert_valid_payment_mode(&PaymentMode::CreatorSplit, &[], None),
+            Err(ErrorCode::InvalidCreatorShares.into())
+        );
+    }
+
+    #[test]
+    fn assert_valid_payment_mode_burn() {
+        assert!(
+            assert_valid_payment_mode(&PaymentMode::Burn, &[], Some(Pubkey::new_unique())).is_ok()
+        );
+        assert_eq!(
+            assert_valid_payment_mode(&PaymentMode::Burn, &[], None),
+            Err(ErrorCode::BurnPaymentRequiresTokenMint.into())
+        );
+    }
+}
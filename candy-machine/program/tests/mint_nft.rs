@@ -0,0 +1,568 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod mint_nft {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::{airdrop, error_code},
+            setup_functions::{
+                find_mint_counter_address, find_phase_mint_counter_address, mint_nft,
+                mint_nft_without_mint_settings, set_mint_settings, setup_authority,
+                setup_candy_machine,
+            },
+        },
+    };
+    use anchor_lang::AccountDeserialize;
+    use mpl_candy_machine::{
+        CandyMachine, ErrorCode, MintCounterPDA, MintPhase, PaymentMode, PhaseMintCounterPDA,
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{AccountMeta, InstructionError},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        transaction::TransactionError,
+        transport::TransportError,
+    };
+
+    fn phase(start: i64, end: Option<i64>, mint_limit: Option<u64>) -> MintPhase {
+        MintPhase {
+            start,
+            end,
+            price: 1_000_000,
+            whitelist_mint_settings: None,
+            gatekeeper: None,
+            mint_limit,
+        }
+    }
+
+    async fn now(context: &mut ProgramTestContext) -> i64 {
+        context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+    }
+
+    async fn items_redeemed(context: &mut ProgramTestContext, candy_machine: &Pubkey) -> u64 {
+        let candy_machine_acc = context
+            .banks_client
+            .get_account(*candy_machine)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        CandyMachine::try_deserialize(&mut candy_machine_acc.data.as_ref())
+            .unwrap()
+            .items_redeemed
+    }
+
+    #[tokio::test]
+    async fn success_authority_outside_phases() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        let now = now(&mut context).await;
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![phase(now + 100_000, None, None)],
+            None,
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &authority,
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            items_redeemed(&mut context, &candy_machine.pubkey()).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn success_without_mint_settings_pda() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        mint_nft_without_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &authority,
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            items_redeemed(&mut context, &candy_machine.pubkey()).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_not_live_outside_phases() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        let now = now(&mut context).await;
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![
+                phase(now - 200_000, Some(now - 100_000), None),
+                phase(now + 100_000, None, None),
+            ],
+            None,
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        // Bots are charged the bot fee instead of failing
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            items_redeemed(&mut context, &candy_machine.pubkey()).await,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn success_phase_mint_counter() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        let now = now(&mut context).await;
+        let phase_start = now - 100_000;
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![phase(phase_start, None, Some(2))],
+            None,
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        let (phase_mint_counter, _) =
+            find_phase_mint_counter_address(&candy_machine.pubkey(), phase_start, &minter.pubkey());
+
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(phase_mint_counter, false)],
+        )
+        .await
+        .unwrap();
+
+        let phase_mint_counter_acc = context
+            .banks_client
+            .get_account(phase_mint_counter)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+        let phase_mint_counter_data =
+            PhaseMintCounterPDA::try_deserialize(&mut phase_mint_counter_acc.data.as_ref())
+                .unwrap();
+
+        assert_eq!(
+            phase_mint_counter_data.candy_machine,
+            candy_machine.pubkey()
+        );
+        assert_eq!(phase_mint_counter_data.wallet, minter.pubkey());
+        assert_eq!(phase_mint_counter_data.phase_start, phase_start);
+        assert_eq!(phase_mint_counter_data.count, 1);
+        assert_eq!(
+            items_redeemed(&mut context, &candy_machine.pubkey()).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_phase_mint_limit_reached() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        let now = now(&mut context).await;
+        let phase_start = now - 100_000;
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![phase(phase_start, None, Some(1))],
+            None,
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        let (phase_mint_counter, _) =
+            find_phase_mint_counter_address(&candy_machine.pubkey(), phase_start, &minter.pubkey());
+
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(phase_mint_counter, false)],
+        )
+        .await
+        .unwrap();
+
+        // Bots are charged the bot fee instead of failing
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(phase_mint_counter, false)],
+        )
+        .await
+        .unwrap();
+
+        let phase_mint_counter_acc = context
+            .banks_client
+            .get_account(phase_mint_counter)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+        let phase_mint_counter_data =
+            PhaseMintCounterPDA::try_deserialize(&mut phase_mint_counter_acc.data.as_ref())
+                .unwrap();
+
+        assert_eq!(phase_mint_counter_data.count, 1);
+        assert_eq!(
+            items_redeemed(&mut context, &candy_machine.pubkey()).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_mismatched_phase_mint_counter() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        let now = now(&mut context).await;
+        let phase_start = now - 100_000;
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![phase(phase_start, None, Some(1))],
+            None,
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        // Counter of another phase
+        let (phase_mint_counter, _) = find_phase_mint_counter_address(
+            &candy_machine.pubkey(),
+            phase_start + 1,
+            &minter.pubkey(),
+        );
+
+        let err = mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(phase_mint_counter, false)],
+        )
+        .await
+        .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    error_code(ErrorCode::MismatchedPhaseMintCounterPDA)
+                );
+            }
+            _ => panic!("Wrong error occurs"),
+        }
+    }
+
+    #[tokio::test]
+    async fn success_mint_counter() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![],
+            Some(2),
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        let (mint_counter, _) =
+            find_mint_counter_address(&candy_machine.pubkey(), &minter.pubkey());
+
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(mint_counter, false)],
+        )
+        .await
+        .unwrap();
+
+        let mint_counter_acc = context
+            .banks_client
+            .get_account(mint_counter)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+        let mint_counter_data =
+            MintCounterPDA::try_deserialize(&mut mint_counter_acc.data.as_ref()).unwrap();
+
+        assert_eq!(mint_counter_data.candy_machine, candy_machine.pubkey());
+        assert_eq!(mint_counter_data.wallet, minter.pubkey());
+        assert_eq!(mint_counter_data.count, 1);
+        assert_eq!(
+            items_redeemed(&mut context, &candy_machine.pubkey()).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_mint_limit_reached() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![],
+            Some(1),
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        let (mint_counter, _) =
+            find_mint_counter_address(&candy_machine.pubkey(), &minter.pubkey());
+
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(mint_counter, false)],
+        )
+        .await
+        .unwrap();
+
+        // Bots are charged the bot fee instead of failing
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(mint_counter, false)],
+        )
+        .await
+        .unwrap();
+
+        let mint_counter_acc = context
+            .banks_client
+            .get_account(mint_counter)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+        let mint_counter_data =
+            MintCounterPDA::try_deserialize(&mut mint_counter_acc.data.as_ref()).unwrap();
+
+        assert_eq!(mint_counter_data.count, 1);
+        assert_eq!(
+            items_redeemed(&mut context, &candy_machine.pubkey()).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_mismatched_mint_counter() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![],
+            Some(1),
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        // Counter of another wallet
+        let (mint_counter, _) =
+            find_mint_counter_address(&candy_machine.pubkey(), &authority.pubkey());
+
+        let err = mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(mint_counter, false)],
+        )
+        .await
+        .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(err_code, error_code(ErrorCode::MismatchedMintCounterPDA));
+            }
+            _ => panic!("Wrong error occurs"),
+        }
+    }
+
+    #[tokio::test]
+    async fn success_creator_split() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![],
+            None,
+            PaymentMode::CreatorSplit,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        // The only creator gets the whole price
+        mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![AccountMeta::new(authority.pubkey(), false)],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            items_redeemed(&mut context, &candy_machine.pubkey()).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_creator_split_payment_account_missing() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![],
+            None,
+            PaymentMode::CreatorSplit,
+        )
+        .await
+        .unwrap();
+
+        let minter = Keypair::new();
+        airdrop(&mut context, &minter.pubkey(), 10_000_000_000).await;
+
+        let err = mint_nft(
+            &mut context,
+            &candy_machine,
+            &authority.pubkey(),
+            &minter,
+            vec![],
+        )
+        .await
+        .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(err_code, error_code(ErrorCode::PaymentAccountMissing));
+            }
+            _ => panic!("Wrong error occurs"),
+        }
+    }
+}
@@ -0,0 +1,179 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod set_mint_settings {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::error_code,
+            setup_functions::{
+                find_mint_settings_address, set_mint_settings, setup_authority, setup_candy_machine,
+            },
+        },
+    };
+    use anchor_lang::AccountDeserialize;
+    use mpl_candy_machine::{ErrorCode, MintPhase, MintSettingsPDA, PaymentMode};
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::InstructionError, signer::Signer, transaction::TransactionError,
+        transport::TransportError,
+    };
+
+    fn phase(start: i64, end: Option<i64>) -> MintPhase {
+        MintPhase {
+            start,
+            end,
+            price: 1_000_000,
+            whitelist_mint_settings: None,
+            gatekeeper: None,
+            mint_limit: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![phase(100, Some(200)), phase(200, None)],
+            Some(3),
+            PaymentMode::CreatorSplit,
+        )
+        .await
+        .unwrap();
+
+        let (mint_settings_pda, _) = find_mint_settings_address(&candy_machine.pubkey());
+        let mint_settings_acc = context
+            .banks_client
+            .get_account(mint_settings_pda)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+        let mint_settings =
+            MintSettingsPDA::try_deserialize(&mut mint_settings_acc.data.as_ref()).unwrap();
+
+        assert_eq!(mint_settings.candy_machine, candy_machine.pubkey());
+        assert_eq!(mint_settings.phases.len(), 2);
+        assert_eq!(mint_settings.phases[0].start, 100);
+        assert_eq!(mint_settings.phases[1].start, 200);
+        assert_eq!(mint_settings.mint_limit, Some(3));
+        assert!(mint_settings.payment_mode == PaymentMode::CreatorSplit);
+
+        // Setting again replaces all of the settings
+        set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![],
+            None,
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap();
+
+        let mint_settings_acc = context
+            .banks_client
+            .get_account(mint_settings_pda)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+        let mint_settings =
+            MintSettingsPDA::try_deserialize(&mut mint_settings_acc.data.as_ref()).unwrap();
+
+        assert!(mint_settings.phases.is_empty());
+        assert_eq!(mint_settings.mint_limit, None);
+        assert!(mint_settings.payment_mode == PaymentMode::Wallet);
+    }
+
+    #[tokio::test]
+    async fn fail_unsorted_phases() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        let err = set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![phase(200, None), phase(100, Some(150))],
+            None,
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(err_code, error_code(ErrorCode::InvalidPhases));
+            }
+            _ => panic!("Wrong error occurs"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_overlapping_phases() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        let err = set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![phase(100, Some(300)), phase(200, None)],
+            None,
+            PaymentMode::Wallet,
+        )
+        .await
+        .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(err_code, error_code(ErrorCode::InvalidPhases));
+            }
+            _ => panic!("Wrong error occurs"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_burn_without_token_mint() {
+        setup_context!(context, mpl_candy_machine, mpl_token_metadata);
+        let authority = setup_authority(&mut context).await;
+        let candy_machine = setup_candy_machine(&mut context, &authority, 10).await;
+
+        let err = set_mint_settings(
+            &mut context,
+            &candy_machine,
+            &authority,
+            vec![],
+            None,
+            PaymentMode::Burn,
+        )
+        .await
+        .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    error_code(ErrorCode::BurnPaymentRequiresTokenMint)
+                );
+            }
+            _ => panic!("Wrong error occurs"),
+        }
+    }
+}
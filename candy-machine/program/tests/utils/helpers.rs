@@ -0,0 +1,80 @@
+#![allow(unused)]
+
+use anchor_lang::prelude::ProgramError;
+use mpl_candy_machine::ErrorCode;
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+
+/// Custom program error code of `ErrorCode`
+pub fn error_code(error: ErrorCode) -> u32 {
+    match ProgramError::from(error) {
+        ProgramError::Custom(code) => code,
+        _ => unreachable!(),
+    }
+}
+
+pub async fn airdrop(context: &mut ProgramTestContext, receiver: &Pubkey, amount: u64) {
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            receiver,
+            amount,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+/// Create a mint with `owner` as authority and mint one token to the `owner` associated token account,
+/// like clients do before `mint_nft`
+pub async fn create_nft_mint(context: &mut ProgramTestContext, mint: &Keypair, owner: &Keypair) {
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &owner.pubkey(),
+                Some(&owner.pubkey()),
+                0,
+            )
+            .unwrap(),
+            spl_associated_token_account::create_associated_token_account(
+                &context.payer.pubkey(),
+                &owner.pubkey(),
+                &mint.pubkey(),
+            ),
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &spl_associated_token_account::get_associated_token_address(
+                    &owner.pubkey(),
+                    &mint.pubkey(),
+                ),
+                &owner.pubkey(),
+                &[],
+                1,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, mint, owner],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
@@ -0,0 +1,303 @@
+#![allow(unused)]
+
+use super::helpers::{airdrop, create_nft_mint};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_candy_machine::{
+    accounts as mpl_candy_machine_accounts, instruction as mpl_candy_machine_instruction,
+    CandyMachineData, ConfigLine, Creator, MintPhase, PaymentMode, CONFIG_ARRAY_START,
+    CONFIG_LINE_SIZE,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program, sysvar,
+    transaction::Transaction,
+    transport::TransportError,
+};
+
+/// Setup Program Test Context
+#[macro_export]
+macro_rules! setup_context {
+    ( $context:ident, $( $program_name:ident ),+ ) => {
+        let mut program_test = ProgramTest::default();
+        $(
+            program_test.add_program(stringify!($program_name), $program_name::id(), None);
+        )+
+        let mut $context = program_test.start_with_context().await;
+    };
+}
+
+pub fn find_mint_settings_address(candy_machine: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"mint_settings".as_ref(), candy_machine.as_ref()],
+        &mpl_candy_machine::id(),
+    )
+}
+
+pub fn find_mint_counter_address(candy_machine: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"mint_counter".as_ref(),
+            candy_machine.as_ref(),
+            wallet.as_ref(),
+        ],
+        &mpl_candy_machine::id(),
+    )
+}
+
+pub fn find_phase_mint_counter_address(
+    candy_machine: &Pubkey,
+    phase_start: i64,
+    wallet: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"phase_mint_counter".as_ref(),
+            candy_machine.as_ref(),
+            &phase_start.to_le_bytes(),
+            wallet.as_ref(),
+        ],
+        &mpl_candy_machine::id(),
+    )
+}
+
+/// Setup funded candy machine authority, also used as the candy machine wallet
+pub async fn setup_authority(context: &mut ProgramTestContext) -> Keypair {
+    let authority = Keypair::new();
+    airdrop(context, &authority.pubkey(), 10_000_000_000).await;
+
+    authority
+}
+
+/// Setup candy machine live for everyone outside of phases, with every config line added
+pub async fn setup_candy_machine(
+    context: &mut ProgramTestContext,
+    authority: &Keypair,
+    items_available: u64,
+) -> Keypair {
+    let candy_machine = Keypair::new();
+
+    let space = CONFIG_ARRAY_START
+        + 4
+        + (items_available as usize) * CONFIG_LINE_SIZE
+        + 8
+        + 2 * ((items_available / 8 + 1) as usize);
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let accounts = mpl_candy_machine_accounts::InitializeCandyMachine {
+        candy_machine: candy_machine.pubkey(),
+        wallet: authority.pubkey(),
+        authority: authority.pubkey(),
+        payer: context.payer.pubkey(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine_instruction::InitializeCandyMachine {
+        data: CandyMachineData {
+            uuid: "ABCDEF".to_string(),
+            price: 1_000_000,
+            symbol: "CANDY".to_string(),
+            seller_fee_basis_points: 500,
+            is_mutable: true,
+            retain_authority: true,
+            go_live_date: Some(0),
+            creators: vec![Creator {
+                address: authority.pubkey(),
+                verified: false,
+                share: 100,
+            }],
+            items_available,
+            ..CandyMachineData::default()
+        },
+    }
+    .data();
+
+    let initialize_instruction = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+
+    let accounts = mpl_candy_machine_accounts::AddConfigLines {
+        candy_machine: candy_machine.pubkey(),
+        authority: authority.pubkey(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine_instruction::AddConfigLines {
+        index: 0,
+        config_lines: (0..items_available)
+            .map(|index| ConfigLine {
+                name: format!("Candy #{}", index),
+                uri: format!("https://example.com/{}.json", index),
+            })
+            .collect(),
+    }
+    .data();
+
+    let add_config_lines_instruction = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &candy_machine.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &mpl_candy_machine::id(),
+            ),
+            initialize_instruction,
+            add_config_lines_instruction,
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &candy_machine, authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    candy_machine
+}
+
+pub async fn set_mint_settings(
+    context: &mut ProgramTestContext,
+    candy_machine: &Keypair,
+    authority: &Keypair,
+    phases: Vec<MintPhase>,
+    mint_limit: Option<u64>,
+    payment_mode: PaymentMode,
+) -> Result<(), TransportError> {
+    let (mint_settings_pda, _) = find_mint_settings_address(&candy_machine.pubkey());
+
+    let accounts = mpl_candy_machine_accounts::SetMintSettings {
+        candy_machine: candy_machine.pubkey(),
+        authority: authority.pubkey(),
+        mint_settings_pda,
+        payer: context.payer.pubkey(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine_instruction::SetMintSettings {
+        phases,
+        mint_limit,
+        payment_mode,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Mint NFT from a new mint paid by `minter`, `remaining_accounts` follow the `MintNFT` order
+/// after the mint settings PDA, which is passed first
+pub async fn mint_nft(
+    context: &mut ProgramTestContext,
+    candy_machine: &Keypair,
+    wallet: &Pubkey,
+    minter: &Keypair,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Result<Keypair, TransportError> {
+    let (mint_settings_pda, _) = find_mint_settings_address(&candy_machine.pubkey());
+    let mut accounts = vec![AccountMeta::new_readonly(mint_settings_pda, false)];
+    accounts.extend(remaining_accounts);
+
+    mint_nft_without_mint_settings(context, candy_machine, wallet, minter, accounts).await
+}
+
+/// Mint NFT without passing the mint settings PDA, as clients built before mint settings do
+pub async fn mint_nft_without_mint_settings(
+    context: &mut ProgramTestContext,
+    candy_machine: &Keypair,
+    wallet: &Pubkey,
+    minter: &Keypair,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Result<Keypair, TransportError> {
+    let mint = Keypair::new();
+    create_nft_mint(context, &mint, minter).await;
+
+    let (candy_machine_creator, creator_bump) = Pubkey::find_program_address(
+        &[b"candy_machine".as_ref(), candy_machine.pubkey().as_ref()],
+        &mpl_candy_machine::id(),
+    );
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let mut accounts = mpl_candy_machine_accounts::MintNFT {
+        candy_machine: candy_machine.pubkey(),
+        candy_machine_creator,
+        payer: minter.pubkey(),
+        wallet: *wallet,
+        metadata,
+        mint: mint.pubkey(),
+        mint_authority: minter.pubkey(),
+        update_authority: minter.pubkey(),
+        master_edition,
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        clock: sysvar::clock::id(),
+        recent_blockhashes: sysvar::slot_hashes::id(),
+        instruction_sysvar_account: sysvar::instructions::id(),
+    }
+    .to_account_metas(None);
+    accounts.extend(remaining_accounts);
+
+    let data = mpl_candy_machine_instruction::MintNft { creator_bump }.data();
+
+    let instruction = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+
+    // Minter pays, so it is writable for the price and bot fee transfers
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&minter.pubkey()),
+        &[minter],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(mint)
+}
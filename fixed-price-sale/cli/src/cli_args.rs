@@ -1,6 +1,6 @@
 //! Module define CLI structure.
 
-use clap::{Parser, Subcommand};
+use clap::{ArgEnum, Parser, Subcommand};
 use std::env;
 
 /// CLI arguments.
@@ -44,6 +44,28 @@ pub enum Commands {
         #[clap(short, value_name = "STRING")]
         account: String,
     },
+    /// Report sales of a market from its `TradeHistory` accounts and export its buyers.
+    MarketReport {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, arg_enum, default_value = "csv")]
+        format: ReportFormat,
+
+        /// File to export buyers to, printed if not provided.
+        #[clap(long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Create store, selling resource, market and primary metadata creators from a config file.
+    /// Accounts which already exist are skipped.
+    Launch {
+        #[clap(long, value_name = "FILE")]
+        config: String,
+
+        /// Simulate transactions and print their costs instead of sending them.
+        #[clap(long)]
+        dry_run: bool,
+    },
     /// Perform `CreateStore` instruction of `mpl_fixed_price_sale` program.
     CreateStore {
         #[clap(long, value_name = "FILE")]
@@ -54,6 +76,13 @@ pub enum Commands {
 
         #[clap(long, value_name = "STRING")]
         description: String,
+
+        /// Wallet receiving platform fee on every withdrawal.
+        #[clap(long, value_name = "PUBKEY", requires = "platform_fee_basis_points")]
+        platform_fee_recipient: Option<String>,
+
+        #[clap(long, value_name = "U16", requires = "platform_fee_recipient")]
+        platform_fee_basis_points: Option<u16>,
     },
     /// Perform `Buy` instruction of `mpl_fixed_price_sale` program.
     Buy {
@@ -65,6 +94,71 @@ pub enum Commands {
 
         #[clap(long, value_name = "FILE")]
         user_wallet_keypair: Option<String>,
+
+        #[clap(long, value_name = "FILE")]
+        allowlist: Option<String>,
+
+        /// One of `Market::payment_mints` to pay with instead of `Market::treasury_mint`.
+        #[clap(long, value_name = "PUBKEY")]
+        payment_mint: Option<String>,
+
+        /// Create `PurchaseReceipt` account, rent is paid by the user wallet.
+        #[clap(long)]
+        purchase_receipt: bool,
+    },
+    /// Perform `BuyMany` instruction of `mpl_fixed_price_sale` program.
+    BuyMany {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "PUBKEY")]
+        user_token_account: String,
+
+        #[clap(long, value_name = "FILE")]
+        user_wallet_keypair: Option<String>,
+
+        #[clap(long)]
+        quantity: u64,
+
+        #[clap(long, value_name = "FILE")]
+        allowlist: Option<String>,
+
+        /// One of `Market::payment_mints` to pay with instead of `Market::treasury_mint`.
+        #[clap(long, value_name = "PUBKEY")]
+        payment_mint: Option<String>,
+
+        /// Create `PurchaseReceipt` account, rent is paid by the user wallet.
+        #[clap(long)]
+        purchase_receipt: bool,
+    },
+    /// Perform `BuyToken` instruction of `mpl_fixed_price_sale` program.
+    BuyToken {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "PUBKEY")]
+        user_token_account: String,
+
+        #[clap(long, value_name = "FILE")]
+        user_wallet_keypair: Option<String>,
+
+        /// Token account of the resource mint owned by the user wallet.
+        #[clap(long, value_name = "PUBKEY")]
+        destination: String,
+
+        #[clap(long, default_value = "1")]
+        quantity: u64,
+
+        #[clap(long, value_name = "FILE")]
+        allowlist: Option<String>,
+
+        /// One of `Market::payment_mints` to pay with instead of `Market::treasury_mint`.
+        #[clap(long, value_name = "PUBKEY")]
+        payment_mint: Option<String>,
+
+        /// Create `PurchaseReceipt` account, rent is paid by the user wallet.
+        #[clap(long)]
+        purchase_receipt: bool,
     },
     /// Perform `InitSellingResource` instruction of `mpl_fixed_price_sale` program.
     InitSellingResource {
@@ -86,6 +180,30 @@ pub enum Commands {
         #[clap(long, value_name = "U64")]
         max_supply: Option<u64>,
     },
+    /// Perform `InitTokenSellingResource` instruction of `mpl_fixed_price_sale` program.
+    InitTokenSellingResource {
+        #[clap(long, value_name = "PUBKEY")]
+        store: String,
+
+        #[clap(long, value_name = "FILE")]
+        admin_keypair: Option<String>,
+
+        #[clap(long, value_name = "PUBKEY")]
+        selling_resource_owner: Option<String>,
+
+        #[clap(long, value_name = "PUBKEY")]
+        resource_mint: String,
+
+        #[clap(long, value_name = "PUBKEY")]
+        resource_token: String,
+
+        /// Amount of tokens sold as one piece, in UI amount.
+        #[clap(long, value_name = "F64")]
+        piece_size: f64,
+
+        #[clap(long, value_name = "U64")]
+        pieces: u64,
+    },
     /// Perform `CreateMarket` instruction of `mpl_fixed_price_sale` program.
     CreateMarket {
         #[clap(long, value_name = "FILE")]
@@ -120,6 +238,51 @@ pub enum Commands {
 
         #[clap(long, value_name = "FILE")]
         gating_config: Option<String>,
+
+        #[clap(long, value_name = "FILE")]
+        price_schedule: Option<String>,
+
+        #[clap(long, value_name = "FILE")]
+        allowlist: Option<String>,
+
+        #[clap(long, value_name = "U64")]
+        allocation: Option<u64>,
+    },
+    /// Perform `AddPaymentMint` instruction of `mpl_fixed_price_sale` program.
+    AddPaymentMint {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "FILE")]
+        owner: Option<String>,
+
+        #[clap(long, value_name = "PUBKEY")]
+        mint: String,
+
+        /// Fixed price of a piece in `mint` tokens.
+        #[clap(long, value_name = "F64", required_unless_present = "price_feed")]
+        price: Option<f64>,
+
+        /// Pyth price feed of `mint`, piece price is converted from its quote currency on every buy.
+        #[clap(
+            long,
+            value_name = "PUBKEY",
+            conflicts_with = "price",
+            requires = "quote_price"
+        )]
+        price_feed: Option<String>,
+
+        /// Price of a piece in the quote currency of `price_feed`.
+        #[clap(long, value_name = "F64")]
+        quote_price: Option<f64>,
+
+        /// Max age of the feed price in seconds.
+        #[clap(long, value_name = "U64", default_value = "60")]
+        max_staleness: u64,
+
+        /// Max feed confidence interval relative to the price.
+        #[clap(long, value_name = "U16", default_value = "100")]
+        max_confidence_basis_points: u16,
     },
     /// Perform `CloseMarket` instruction of `mpl_fixed_price_sale` program.
     CloseMarket {
@@ -129,6 +292,131 @@ pub enum Commands {
         #[clap(long, value_name = "FILE")]
         owner: Option<String>,
     },
+    /// Perform `CancelMarket` instruction of `mpl_fixed_price_sale` program.
+    CancelMarket {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "FILE")]
+        owner: Option<String>,
+    },
+    /// Perform `ClosePurchaseReceipt` instruction of `mpl_fixed_price_sale` program.
+    ClosePurchaseReceipt {
+        #[clap(long, value_name = "PUBKEY")]
+        purchase_receipt: String,
+
+        #[clap(long, value_name = "FILE")]
+        buyer_keypair: Option<String>,
+    },
+    /// Perform `MigrateMarket` instruction of `mpl_fixed_price_sale` program.
+    MigrateMarket {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+    },
+    /// Perform `MigrateSellingResource` instruction of `mpl_fixed_price_sale` program.
+    MigrateSellingResource {
+        #[clap(long, value_name = "PUBKEY")]
+        selling_resource: String,
+    },
+    /// Perform `MigrateStore` instruction of `mpl_fixed_price_sale` program.
+    MigrateStore {
+        #[clap(long, value_name = "PUBKEY")]
+        store: String,
+    },
+    /// Perform `MigrateTradeHistory` instruction of `mpl_fixed_price_sale` program.
+    MigrateTradeHistory {
+        #[clap(long, value_name = "PUBKEY")]
+        trade_history: String,
+    },
+    /// Perform `SetPlatformFee` instruction of `mpl_fixed_price_sale` program.
+    SetPlatformFee {
+        #[clap(long, value_name = "PUBKEY")]
+        store: String,
+
+        #[clap(long, value_name = "FILE")]
+        admin_keypair: Option<String>,
+
+        /// Wallet receiving platform fee on every withdrawal, store stops charging fee if not set.
+        #[clap(long, value_name = "PUBKEY", requires = "platform_fee_basis_points")]
+        platform_fee_recipient: Option<String>,
+
+        #[clap(long, value_name = "U16", requires = "platform_fee_recipient")]
+        platform_fee_basis_points: Option<u16>,
+    },
+    /// Perform `InitRaffle` instruction of `mpl_fixed_price_sale` program.
+    InitRaffle {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "FILE")]
+        owner: Option<String>,
+
+        /// `.json` array of 32 bytes revealed once entries are closed, generated if file doesn't exist.
+        #[clap(long, value_name = "FILE")]
+        seed: String,
+
+        /// Editions given away, one per winner.
+        #[clap(long, value_name = "U64")]
+        winners: u64,
+    },
+    /// Perform `EnterRaffle` instruction of `mpl_fixed_price_sale` program.
+    EnterRaffle {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "PUBKEY")]
+        user_token_account: String,
+
+        #[clap(long, value_name = "FILE")]
+        user_wallet_keypair: Option<String>,
+    },
+    /// Perform `CloseRaffleEntries` instruction of `mpl_fixed_price_sale` program.
+    CloseRaffleEntries {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+    },
+    /// Perform `RevealRaffleSeed` instruction of `mpl_fixed_price_sale` program.
+    RevealRaffleSeed {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "FILE")]
+        owner: Option<String>,
+
+        /// Seed file passed to `InitRaffle`.
+        #[clap(long, value_name = "FILE")]
+        seed: String,
+    },
+    /// Perform `DrawRaffle` instructions of `mpl_fixed_price_sale` program until every entry is drawn.
+    DrawRaffle {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+    },
+    /// Perform `ExpireRaffle` instruction of `mpl_fixed_price_sale` program.
+    ExpireRaffle {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+    },
+    /// Perform `ClaimRafflePrize` instruction of `mpl_fixed_price_sale` program.
+    ClaimRafflePrize {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "FILE")]
+        user_wallet_keypair: Option<String>,
+    },
+    /// Perform `ClaimRaffleRefund` instruction of `mpl_fixed_price_sale` program.
+    ClaimRaffleRefund {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "FILE")]
+        user_wallet_keypair: Option<String>,
+
+        /// User wallet for native `SOL` market or token account otherwise, user wallet by default.
+        #[clap(long, value_name = "PUBKEY")]
+        destination: Option<String>,
+    },
     /// Perform `SuspendMarket` instruction of `mpl_fixed_price_sale` program.
     SuspendMarket {
         #[clap(long, value_name = "PUBKEY")]
@@ -158,6 +446,27 @@ pub enum Commands {
         #[clap(long, value_name = "PUBKEY")]
         claim_token: String,
     },
+    /// Perform `ReleaseMarket` instruction of `mpl_fixed_price_sale` program.
+    ReleaseMarket {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+    },
+    /// Perform `ClaimRefund` instruction of `mpl_fixed_price_sale` program.
+    ClaimRefund {
+        #[clap(long, value_name = "PUBKEY")]
+        market: String,
+
+        #[clap(long, value_name = "FILE")]
+        user_wallet_keypair: Option<String>,
+
+        /// User wallet for native `SOL` market or token account otherwise, user wallet by default.
+        #[clap(long, value_name = "PUBKEY")]
+        destination: Option<String>,
+
+        /// Token account of a returned edition, can be repeated.
+        #[clap(long, value_name = "PUBKEY", required = true)]
+        edition_token_account: Vec<String>,
+    },
     /// Perform `SavePrimaryMetadataCreators` instruction of `mpl_fixed_price_sale` program.
     SavePrimaryMetadataCreators {
         #[clap(long, value_name = "FILE")]
@@ -191,5 +500,18 @@ pub enum Commands {
 
         #[clap(long, value_name = "U64")]
         new_pieces_in_one_wallet: Option<u64>,
+
+        #[clap(long, value_name = "FILE", conflicts_with = "remove_price_schedule")]
+        new_price_schedule: Option<String>,
+
+        #[clap(long)]
+        remove_price_schedule: bool,
     },
 }
+
+/// Export formats of `MarketReport` command.
+#[derive(ArgEnum, Clone, Debug)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
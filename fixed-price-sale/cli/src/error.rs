@@ -21,6 +21,15 @@ pub enum Error {
 
     #[error("Solana program error.")]
     SolanaProgramError(ProgramError),
+
+    #[error("Config parse error.")]
+    ConfigParseError(toml::de::Error),
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Error {
+        Error::ConfigParseError(e)
+    }
 }
 
 impl From<ProgramError> for Error {
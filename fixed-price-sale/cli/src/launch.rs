@@ -0,0 +1,416 @@
+//! Module provide handler for `Launch` command.
+
+use crate::{error, processor, utils};
+use chrono::prelude::*;
+use mpl_fixed_price_sale::state::{
+    Market, PlatformFee, PrimaryMetadataCreators, SellingResource, Store,
+};
+use serde::Deserialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    native_token::lamports_to_sol,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signer::{
+        keypair::{read_keypair_file, write_keypair_file, Keypair},
+        Signer,
+    },
+    transaction::Transaction,
+};
+use std::{fs, path::Path, str::FromStr};
+
+/// Sale described by a `.toml` config file.
+#[derive(Debug, Deserialize)]
+pub struct LaunchConfig {
+    /// Directory holding keypairs of created accounts, `<config>-keypairs` by default.
+    pub keypairs_dir: Option<String>,
+    pub store: StoreConfig,
+    pub primary_metadata_creators: Option<PrimaryMetadataCreatorsConfig>,
+    pub selling_resource: SellingResourceConfig,
+    pub market: MarketConfig,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StoreConfig {
+    /// Path to store admin keypair file, payer by default.
+    pub admin_keypair: Option<String>,
+    pub name: String,
+    pub description: String,
+    pub platform_fee: Option<PlatformFeeConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlatformFeeConfig {
+    pub recipient: String,
+    pub basis_points: u16,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PrimaryMetadataCreatorsConfig {
+    /// Path to metadata update authority keypair file, payer by default.
+    pub admin_keypair: Option<String>,
+    pub creators: Vec<CreatorConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatorConfig {
+    pub address: String,
+    pub share: u8,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SellingResourceConfig {
+    /// Path to selling resource owner keypair file, payer by default.
+    pub owner_keypair: Option<String>,
+    pub resource_mint: String,
+    pub resource_token: String,
+    pub max_supply: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarketConfig {
+    /// Treasury mint, native `SOL` by default.
+    pub mint: Option<String>,
+    pub name: String,
+    pub description: String,
+    pub mutable: bool,
+    pub price: f64,
+    pub pieces_in_one_wallet: Option<u64>,
+    pub start_date: Option<u64>,
+    pub end_date: Option<u64>,
+    pub gating_config: Option<GatingConfigConfig>,
+    pub price_schedule: Option<utils::PriceScheduleConfig>,
+    /// Path to allowlist `.json` file.
+    pub allowlist: Option<String>,
+    /// Pieces reserved for the market, other markets of the selling resource can't sell them.
+    pub allocation: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GatingConfigConfig {
+    pub collection: String,
+    pub expire_on_use: bool,
+    pub gating_time: Option<u64>,
+}
+
+/// Transaction creating one account of the sale.
+struct LaunchStep {
+    name: &'static str,
+    tx: Transaction,
+    ui_info: Box<dyn processor::UiTransactionInfo>,
+    /// Rent of accounts created by the transaction.
+    rent: u64,
+    /// Account created by an earlier step, so transaction can't be simulated before it.
+    created_earlier: Option<Pubkey>,
+}
+
+/// Read keypair `name` from `keypairs_dir`, or generate and save it on first launch.
+fn load_or_create_keypair(keypairs_dir: &Path, name: &str) -> Result<Keypair, error::Error> {
+    let path = keypairs_dir.join(format!("{}.json", name));
+
+    if path.exists() {
+        Ok(read_keypair_file(&path)?)
+    } else {
+        let keypair = Keypair::new();
+        write_keypair_file(&keypair, &path)?;
+        Ok(keypair)
+    }
+}
+
+fn keypair_or_payer(keypair: &Option<String>, payer: &Keypair) -> Result<Keypair, error::Error> {
+    Ok(if let Some(keypair) = keypair {
+        read_keypair_file(keypair)?
+    } else {
+        utils::clone_keypair(payer)
+    })
+}
+
+/// Create every account of the sale in `config` which doesn't exist yet.
+/// With `dry_run` transactions are simulated instead of sent.
+pub fn launch(
+    client: &RpcClient,
+    payer: &Keypair,
+    config: &str,
+    dry_run: bool,
+) -> Result<(), error::Error> {
+    let config_path = Path::new(config);
+    let launch_config: LaunchConfig = toml::from_str(&fs::read_to_string(config_path)?)?;
+
+    let keypairs_dir = if let Some(keypairs_dir) = &launch_config.keypairs_dir {
+        Path::new(keypairs_dir).to_path_buf()
+    } else {
+        let stem = config_path.file_stem().unwrap().to_string_lossy();
+        config_path.with_file_name(format!("{}-keypairs", stem))
+    };
+    fs::create_dir_all(&keypairs_dir)?;
+
+    let store = load_or_create_keypair(&keypairs_dir, "store")?;
+    let selling_resource = load_or_create_keypair(&keypairs_dir, "selling_resource")?;
+    let vault = load_or_create_keypair(&keypairs_dir, "vault")?;
+    let market = load_or_create_keypair(&keypairs_dir, "market")?;
+    let treasury_holder = load_or_create_keypair(&keypairs_dir, "treasury_holder")?;
+
+    let admin = keypair_or_payer(&launch_config.store.admin_keypair, payer)?;
+    let selling_resource_owner =
+        keypair_or_payer(&launch_config.selling_resource.owner_keypair, payer)?;
+
+    let resource_mint = Pubkey::from_str(&launch_config.selling_resource.resource_mint)?;
+    let token_account_rent =
+        client.get_minimum_balance_for_rent_exemption(spl_token::state::Account::LEN)?;
+
+    let mut steps = Vec::new();
+
+    let store_exists = utils::account_exists(client, &store.pubkey())?;
+    if store_exists {
+        println!("Launch::store - {} exists, skipped", store.pubkey());
+    } else {
+        let platform_fee = if let Some(platform_fee) = &launch_config.store.platform_fee {
+            Some(PlatformFee {
+                recipient: Pubkey::from_str(&platform_fee.recipient)?,
+                basis_points: platform_fee.basis_points,
+            })
+        } else {
+            None
+        };
+
+        let (tx, ui_info) = processor::create_store(
+            client,
+            payer,
+            &admin,
+            &store,
+            &launch_config.store.name,
+            &launch_config.store.description,
+            platform_fee,
+        )?;
+
+        steps.push(LaunchStep {
+            name: "CreateStore",
+            tx,
+            ui_info,
+            rent: client.get_minimum_balance_for_rent_exemption(Store::LEN)?,
+            created_earlier: None,
+        });
+    }
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource_mint.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+    let (primary_metadata_creators, _) =
+        mpl_fixed_price_sale::utils::find_primary_metadata_creators(&metadata);
+    let metadata_state: mpl_token_metadata::state::Metadata =
+        processor::get_account_state_legacy(client, &metadata)?;
+
+    if metadata_state.primary_sale_happened {
+        println!("Launch::primary_metadata_creators - primary sale happened, skipped");
+    } else if utils::account_exists(client, &primary_metadata_creators)? {
+        println!(
+            "Launch::primary_metadata_creators - {} exists, skipped",
+            primary_metadata_creators
+        );
+    } else {
+        let (metadata_admin, creators) =
+            if let Some(creators_config) = &launch_config.primary_metadata_creators {
+                let creators = creators_config
+                    .creators
+                    .iter()
+                    .map(|creator| -> Result<_, error::Error> {
+                        Ok(mpl_token_metadata::state::Creator {
+                            address: Pubkey::from_str(&creator.address)?,
+                            verified: false,
+                            share: creator.share,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                (
+                    keypair_or_payer(&creators_config.admin_keypair, payer)?,
+                    creators,
+                )
+            } else {
+                (
+                    utils::clone_keypair(payer),
+                    metadata_state.data.creators.unwrap_or(vec![
+                        mpl_token_metadata::state::Creator {
+                            address: payer.pubkey(),
+                            verified: false,
+                            share: 100,
+                        },
+                    ]),
+                )
+            };
+
+        let (tx, ui_info) = processor::save_primary_metadata_creators(
+            client,
+            payer,
+            &metadata_admin,
+            &metadata,
+            &creators,
+        )?;
+
+        steps.push(LaunchStep {
+            name: "SavePrimaryMetadataCreators",
+            tx,
+            ui_info,
+            rent: client.get_minimum_balance_for_rent_exemption(PrimaryMetadataCreators::LEN)?,
+            created_earlier: None,
+        });
+    }
+
+    let selling_resource_exists = utils::account_exists(client, &selling_resource.pubkey())?;
+    if selling_resource_exists {
+        println!(
+            "Launch::selling_resource - {} exists, skipped",
+            selling_resource.pubkey()
+        );
+    } else {
+        let (tx, ui_info) = processor::init_selling_resource(
+            client,
+            payer,
+            &store.pubkey(),
+            &admin,
+            &selling_resource_owner.pubkey(),
+            &resource_mint,
+            &Pubkey::from_str(&launch_config.selling_resource.resource_token)?,
+            &selling_resource,
+            &vault,
+            launch_config.selling_resource.max_supply,
+        )?;
+
+        steps.push(LaunchStep {
+            name: "InitSellingResource",
+            tx,
+            ui_info,
+            rent: client.get_minimum_balance_for_rent_exemption(SellingResource::LEN)?
+                + token_account_rent,
+            created_earlier: if store_exists {
+                None
+            } else {
+                Some(store.pubkey())
+            },
+        });
+    }
+
+    if utils::account_exists(client, &market.pubkey())? {
+        println!("Launch::market - {} exists, skipped", market.pubkey());
+    } else {
+        let market_config = &launch_config.market;
+
+        let mint = if let Some(mint) = &market_config.mint {
+            Pubkey::from_str(mint)?
+        } else {
+            spl_token::native_mint::id()
+        };
+
+        let start_date = if let Some(start_date) = market_config.start_date {
+            start_date
+        } else {
+            Utc::now().timestamp() as u64
+        };
+
+        let gating_config = if let Some(gating_config) = &market_config.gating_config {
+            Some(mpl_fixed_price_sale::state::GatingConfig {
+                collection: Pubkey::from_str(&gating_config.collection)?,
+                expire_on_use: gating_config.expire_on_use,
+                gating_time: gating_config.gating_time,
+            })
+        } else {
+            None
+        };
+
+        let allowlist = if let Some(allowlist) = &market_config.allowlist {
+            Some(utils::read_allowlist(allowlist)?.to_allowlist_config()?)
+        } else {
+            None
+        };
+
+        let decimals = utils::get_mint(client, &mint)?.decimals;
+
+        let price_schedule = if let Some(price_schedule) = &market_config.price_schedule {
+            Some(price_schedule.to_price_schedule(decimals)?)
+        } else {
+            None
+        };
+
+        let (tx, ui_info) = processor::create_market(
+            client,
+            payer,
+            &selling_resource_owner,
+            &selling_resource.pubkey(),
+            &store.pubkey(),
+            &market,
+            &treasury_holder,
+            &mint,
+            &market_config.name,
+            &market_config.description,
+            market_config.mutable,
+            spl_token::ui_amount_to_amount(market_config.price, decimals),
+            market_config.pieces_in_one_wallet,
+            start_date,
+            market_config.end_date,
+            gating_config,
+            price_schedule,
+            allowlist,
+            market_config.allocation,
+        )?;
+
+        steps.push(LaunchStep {
+            name: "CreateMarket",
+            tx,
+            ui_info,
+            rent: client.get_minimum_balance_for_rent_exemption(Market::LEN)? + token_account_rent,
+            created_earlier: if selling_resource_exists {
+                None
+            } else {
+                Some(selling_resource.pubkey())
+            },
+        });
+    }
+
+    let mut total_cost = 0;
+
+    for step in steps {
+        println!();
+        step.ui_info.print();
+
+        if !dry_run {
+            client.send_and_confirm_transaction(&step.tx)?;
+            continue;
+        }
+
+        let fee = client.get_fee_for_message(&step.tx.message)?;
+        total_cost += step.rent + fee;
+
+        println!("{}::rent - {} SOL", step.name, lamports_to_sol(step.rent));
+        println!("{}::fee - {} SOL", step.name, lamports_to_sol(fee));
+
+        if let Some(account) = step.created_earlier {
+            println!(
+                "{}::simulation - skipped, {} is created by an earlier step",
+                step.name, account
+            );
+            continue;
+        }
+
+        let simulation = client.simulate_transaction(&step.tx)?.value;
+        if let Some(err) = simulation.err {
+            println!("{}::simulation - failed, {}", step.name, err);
+            for log in simulation.logs.unwrap_or_default() {
+                println!("    {}", log);
+            }
+        } else {
+            println!("{}::simulation - succeeded", step.name);
+        }
+    }
+
+    if dry_run {
+        println!();
+        println!("Launch::total_cost - {} SOL", lamports_to_sol(total_cost));
+    }
+
+    Ok(())
+}
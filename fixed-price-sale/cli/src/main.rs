@@ -1,6 +1,8 @@
 mod cli_args;
 mod error;
+mod launch;
 mod processor;
+mod report;
 mod utils;
 
 use chrono::prelude::*;
@@ -9,7 +11,10 @@ use cli_args::{CliArgs, Commands};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     pubkey::Pubkey,
-    signer::{keypair::read_keypair_file, Signer},
+    signer::{
+        keypair::{read_keypair_file, Keypair},
+        Signer,
+    },
     transaction::Transaction,
 };
 use std::{fs::File, io::BufReader, str::FromStr};
@@ -47,6 +52,12 @@ fn main() -> Result<(), error::Error> {
                     }
                 );
                 println!("SellingResource::state - {:?}", selling_resource.state);
+                println!("SellingResource::kind - {:?}", selling_resource.kind);
+                println!(
+                    "SellingResource::allocated - {}",
+                    selling_resource.allocated
+                );
+                println!("SellingResource::markets - {}", selling_resource.markets);
 
                 None
             }
@@ -59,6 +70,14 @@ fn main() -> Result<(), error::Error> {
                 println!("Store::admin - {}", store.admin);
                 println!("Store::name - {}", store.name);
                 println!("Store::description - {}", store.description);
+                println!(
+                    "Store::platform_fee - {}",
+                    if let Some(x) = store.platform_fee {
+                        format!("{:?}", x)
+                    } else {
+                        String::from("<none>")
+                    }
+                );
 
                 None
             }
@@ -101,6 +120,30 @@ fn main() -> Result<(), error::Error> {
                     }
                 );
                 println!("Market::state - {:?}", market.state);
+                println!(
+                    "Market::price_schedule - {}",
+                    if let Some(x) = market.price_schedule {
+                        format!("{:?}", x)
+                    } else {
+                        String::from("<none>")
+                    }
+                );
+                for payment_mint in market.payment_mints {
+                    println!("Market::payment_mint - {:?}", payment_mint);
+                }
+                if let Some(raffle) = market.raffle {
+                    println!("Market::raffle - {:?}", raffle);
+                }
+                println!(
+                    "Market::allocation - {}",
+                    if let Some(x) = market.allocation {
+                        x.to_string()
+                    } else {
+                        String::from("<unallocated>")
+                    }
+                );
+                println!("Market::sold - {}", market.sold);
+                println!("Market::released - {}", market.released);
 
                 None
             }
@@ -115,6 +158,51 @@ fn main() -> Result<(), error::Error> {
                     "TradeHistory::already_bought - {}",
                     trade_history.already_bought
                 );
+                println!("TradeHistory::currency - {}", trade_history.currency);
+
+                None
+            }
+            Commands::Launch { config, dry_run } => {
+                launch::launch(&client, &payer_wallet, &config, dry_run)?;
+
+                None
+            }
+            Commands::MarketReport {
+                market,
+                format,
+                output,
+            } => {
+                let market_address = Pubkey::from_str(&market)?;
+                let market = processor::get_account_state::<mpl_fixed_price_sale::state::Market>(
+                    &client,
+                    &market_address,
+                )?;
+                let selling_resource = processor::get_account_state::<
+                    mpl_fixed_price_sale::state::SellingResource,
+                >(&client, &market.selling_resource)?;
+
+                let decimals = utils::get_mint(&client, &market.treasury_mint)?.decimals;
+                let trade_histories =
+                    processor::get_trade_histories(&client, &market_address, &market)?;
+
+                let report = report::MarketReport::new(
+                    &market_address,
+                    &market,
+                    &selling_resource,
+                    decimals,
+                    trade_histories,
+                );
+
+                report.print();
+                println!();
+
+                let exported = report.export(&format);
+                if let Some(output) = output {
+                    std::fs::write(&output, exported)?;
+                    println!("Buyers exported to {}", output);
+                } else {
+                    print!("{}", exported);
+                }
 
                 None
             }
@@ -122,6 +210,8 @@ fn main() -> Result<(), error::Error> {
                 admin_keypair,
                 name,
                 description,
+                platform_fee_recipient,
+                platform_fee_basis_points,
             } => {
                 let admin_keypair = if let Some(keypair) = admin_keypair {
                     read_keypair_file(keypair)?
@@ -129,12 +219,25 @@ fn main() -> Result<(), error::Error> {
                     utils::clone_keypair(&payer_wallet)
                 };
 
+                let platform_fee = if let (Some(recipient), Some(basis_points)) =
+                    (platform_fee_recipient, platform_fee_basis_points)
+                {
+                    Some(mpl_fixed_price_sale::state::PlatformFee {
+                        recipient: Pubkey::from_str(&recipient)?,
+                        basis_points,
+                    })
+                } else {
+                    None
+                };
+
                 let (tx, ui_info) = processor::create_store(
                     &client,
                     &payer_wallet,
                     &admin_keypair,
+                    &Keypair::new(),
                     &name,
                     &description,
+                    platform_fee,
                 )?;
 
                 Some(vec![(tx, ui_info)])
@@ -167,11 +270,53 @@ fn main() -> Result<(), error::Error> {
                     &selling_resource_owner,
                     &Pubkey::from_str(&resource_mint)?,
                     &Pubkey::from_str(&resource_token)?,
+                    &Keypair::new(),
+                    &Keypair::new(),
                     max_supply,
                 )?;
 
                 Some(vec![(tx, ui_info)])
             }
+            Commands::InitTokenSellingResource {
+                store,
+                admin_keypair,
+                selling_resource_owner,
+                resource_mint,
+                resource_token,
+                piece_size,
+                pieces,
+            } => {
+                let admin_keypair = if let Some(keypair) = admin_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let selling_resource_owner = if let Some(owner) = selling_resource_owner {
+                    Pubkey::from_str(&owner)?
+                } else {
+                    payer_wallet.pubkey()
+                };
+
+                let resource_mint = Pubkey::from_str(&resource_mint)?;
+                let decimals = utils::get_mint(&client, &resource_mint)?.decimals;
+
+                let (tx, ui_info) = processor::init_token_selling_resource(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&store)?,
+                    &admin_keypair,
+                    &selling_resource_owner,
+                    &resource_mint,
+                    &Pubkey::from_str(&resource_token)?,
+                    &Keypair::new(),
+                    &Keypair::new(),
+                    spl_token::ui_amount_to_amount(piece_size, decimals),
+                    pieces,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
             Commands::CreateMarket {
                 selling_resource_owner_keypair,
                 selling_resource,
@@ -184,6 +329,9 @@ fn main() -> Result<(), error::Error> {
                 start_date,
                 end_date,
                 gating_config,
+                price_schedule,
+                allowlist,
+                allocation,
             } => {
                 let selling_resource_owner = if let Some(owner) = selling_resource_owner_keypair {
                     read_keypair_file(&owner)?
@@ -274,11 +422,26 @@ fn main() -> Result<(), error::Error> {
                         None
                     };
 
+                let price_schedule = if let Some(price_schedule) = price_schedule {
+                    Some(utils::read_price_schedule(&price_schedule, decimals)?)
+                } else {
+                    None
+                };
+
+                let allowlist = if let Some(allowlist) = allowlist {
+                    Some(utils::read_allowlist(&allowlist)?.to_allowlist_config()?)
+                } else {
+                    None
+                };
+
                 let (tx, ui_info) = processor::create_market(
                     &client,
                     &payer_wallet,
                     &selling_resource_owner,
                     &selling_resource,
+                    &selling_resource_state.store,
+                    &Keypair::new(),
+                    &Keypair::new(),
                     &mint,
                     &name,
                     &description,
@@ -288,12 +451,70 @@ fn main() -> Result<(), error::Error> {
                     start_date,
                     end_date,
                     gating_config,
+                    price_schedule,
+                    allowlist,
+                    allocation,
                 )?;
 
                 bundle.push((tx, ui_info));
 
                 Some(bundle)
             }
+            Commands::AddPaymentMint {
+                market,
+                owner,
+                mint,
+                price,
+                price_feed,
+                quote_price,
+                max_staleness,
+                max_confidence_basis_points,
+            } => {
+                let owner = if let Some(owner) = owner {
+                    read_keypair_file(&owner)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let market = Pubkey::from_str(&market)?;
+                let mint = Pubkey::from_str(&mint)?;
+
+                let market_state = processor::get_account_state::<
+                    mpl_fixed_price_sale::state::Market,
+                >(&client, &market)?;
+
+                let price = if let Some(price_feed) = price_feed {
+                    let price_feed = Pubkey::from_str(&price_feed)?;
+                    let exponent = utils::get_price_feed_exponent(&client, &price_feed)?;
+
+                    mpl_fixed_price_sale::state::PaymentPrice::Oracle {
+                        price_feed,
+                        quote_price: (quote_price.unwrap() * 10f64.powi(-exponent)).round() as u64,
+                        max_staleness,
+                        max_confidence_basis_points,
+                        exponent,
+                    }
+                } else {
+                    let decimals = utils::get_mint(&client, &mint)?.decimals;
+
+                    mpl_fixed_price_sale::state::PaymentPrice::Fixed {
+                        price: spl_token::ui_amount_to_amount(price.unwrap(), decimals),
+                    }
+                };
+
+                let (tx, ui_info) = processor::add_payment_mint(
+                    &client,
+                    &payer_wallet,
+                    &owner,
+                    &market,
+                    &market_state.selling_resource,
+                    &Keypair::new(),
+                    &mint,
+                    price,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
             Commands::ChangeMarket {
                 market,
                 owner,
@@ -302,6 +523,8 @@ fn main() -> Result<(), error::Error> {
                 mutable,
                 new_price,
                 new_pieces_in_one_wallet,
+                new_price_schedule,
+                remove_price_schedule,
             } => {
                 let owner = if let Some(owner) = owner {
                     read_keypair_file(&owner)?
@@ -309,15 +532,34 @@ fn main() -> Result<(), error::Error> {
                     utils::clone_keypair(&payer_wallet)
                 };
 
+                let market = Pubkey::from_str(&market)?;
+
+                let new_price_schedule = if let Some(new_price_schedule) = new_price_schedule {
+                    let market_state = processor::get_account_state::<
+                        mpl_fixed_price_sale::state::Market,
+                    >(&client, &market)?;
+                    let decimals = utils::get_mint(&client, &market_state.treasury_mint)?.decimals;
+
+                    Some(Some(utils::read_price_schedule(
+                        &new_price_schedule,
+                        decimals,
+                    )?))
+                } else if remove_price_schedule {
+                    Some(None)
+                } else {
+                    None
+                };
+
                 let (tx, ui_info) = processor::change_market(
                     &client,
                     &owner,
-                    &Pubkey::from_str(&market)?,
+                    &market,
                     new_name,
                     new_description,
                     mutable,
                     new_price,
                     new_pieces_in_one_wallet,
+                    new_price_schedule,
                 )?;
 
                 Some(vec![(tx, ui_info)])
@@ -340,6 +582,46 @@ fn main() -> Result<(), error::Error> {
 
                 Some(vec![(tx, ui_info)])
             }
+            Commands::ReleaseMarket { market } => {
+                let (tx, ui_info) =
+                    processor::release_market(&client, &payer_wallet, &Pubkey::from_str(&market)?)?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::ClaimRefund {
+                market,
+                user_wallet_keypair,
+                destination,
+                edition_token_account,
+            } => {
+                let user_wallet = if let Some(keypair) = user_wallet_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let destination = if let Some(destination) = destination {
+                    Pubkey::from_str(&destination)?
+                } else {
+                    user_wallet.pubkey()
+                };
+
+                let edition_token_accounts = edition_token_account
+                    .iter()
+                    .map(|account| Pubkey::from_str(account))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let (tx, ui_info) = processor::claim_refund(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&market)?,
+                    &user_wallet,
+                    &destination,
+                    &edition_token_accounts,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
             Commands::SavePrimaryMetadataCreators {
                 admin,
                 metadata,
@@ -402,6 +684,234 @@ fn main() -> Result<(), error::Error> {
 
                 Some(vec![(tx, ui_info)])
             }
+            Commands::CancelMarket { market, owner } => {
+                let owner = if let Some(owner) = owner {
+                    read_keypair_file(&owner)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let (tx, ui_info) =
+                    processor::cancel_market(&client, &owner, &Pubkey::from_str(&market)?)?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::ClosePurchaseReceipt {
+                purchase_receipt,
+                buyer_keypair,
+            } => {
+                let buyer = if let Some(keypair) = buyer_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let (tx, ui_info) = processor::close_purchase_receipt(
+                    &client,
+                    &buyer,
+                    &Pubkey::from_str(&purchase_receipt)?,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::MigrateMarket { market } => {
+                let (tx, ui_info) =
+                    processor::migrate_market(&client, &payer_wallet, &Pubkey::from_str(&market)?)?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::MigrateSellingResource { selling_resource } => {
+                let (tx, ui_info) = processor::migrate_selling_resource(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&selling_resource)?,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::MigrateStore { store } => {
+                let (tx, ui_info) =
+                    processor::migrate_store(&client, &payer_wallet, &Pubkey::from_str(&store)?)?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::SetPlatformFee {
+                store,
+                admin_keypair,
+                platform_fee_recipient,
+                platform_fee_basis_points,
+            } => {
+                let admin_keypair = if let Some(keypair) = admin_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let platform_fee = if let (Some(recipient), Some(basis_points)) =
+                    (platform_fee_recipient, platform_fee_basis_points)
+                {
+                    Some(mpl_fixed_price_sale::state::PlatformFee {
+                        recipient: Pubkey::from_str(&recipient)?,
+                        basis_points,
+                    })
+                } else {
+                    None
+                };
+
+                let (tx, ui_info) = processor::set_platform_fee(
+                    &client,
+                    &payer_wallet,
+                    &admin_keypair,
+                    &Pubkey::from_str(&store)?,
+                    platform_fee,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::MigrateTradeHistory { trade_history } => {
+                let (tx, ui_info) = processor::migrate_trade_history(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&trade_history)?,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::InitRaffle {
+                market,
+                owner,
+                seed,
+                winners,
+            } => {
+                let owner = if let Some(owner) = owner {
+                    read_keypair_file(&owner)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let seed = utils::read_or_create_raffle_seed(&seed)?;
+
+                let (tx, ui_info) = processor::init_raffle(
+                    &client,
+                    &payer_wallet,
+                    &owner,
+                    &Pubkey::from_str(&market)?,
+                    &seed,
+                    winners,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::EnterRaffle {
+                market,
+                user_token_account,
+                user_wallet_keypair,
+            } => {
+                let user_wallet = if let Some(keypair) = user_wallet_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let (tx, ui_info) = processor::enter_raffle(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&market)?,
+                    &Pubkey::from_str(&user_token_account)?,
+                    &user_wallet,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::CloseRaffleEntries { market } => {
+                let (tx, ui_info) = processor::close_raffle_entries(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&market)?,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::RevealRaffleSeed {
+                market,
+                owner,
+                seed,
+            } => {
+                let owner = if let Some(owner) = owner {
+                    read_keypair_file(&owner)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let seed = utils::read_raffle_seed(&seed)?;
+
+                let (tx, ui_info) = processor::reveal_raffle_seed(
+                    &client,
+                    &payer_wallet,
+                    &owner,
+                    &Pubkey::from_str(&market)?,
+                    &seed,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::DrawRaffle { market } => Some(processor::draw_raffle(
+                &client,
+                &payer_wallet,
+                &Pubkey::from_str(&market)?,
+            )?),
+            Commands::ExpireRaffle { market } => {
+                let (tx, ui_info) =
+                    processor::expire_raffle(&client, &payer_wallet, &Pubkey::from_str(&market)?)?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::ClaimRafflePrize {
+                market,
+                user_wallet_keypair,
+            } => {
+                let user_wallet = if let Some(keypair) = user_wallet_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let (tx, ui_info) = processor::claim_raffle_prize(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&market)?,
+                    &user_wallet,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::ClaimRaffleRefund {
+                market,
+                user_wallet_keypair,
+                destination,
+            } => {
+                let user_wallet = if let Some(keypair) = user_wallet_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let destination = if let Some(destination) = destination {
+                    Pubkey::from_str(&destination)?
+                } else {
+                    user_wallet.pubkey()
+                };
+
+                let (tx, ui_info) = processor::claim_raffle_refund(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&market)?,
+                    &user_wallet,
+                    &destination,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
             Commands::ResumeMarket { market, owner } => {
                 let owner = if let Some(owner) = owner {
                     read_keypair_file(&owner)?
@@ -430,6 +940,9 @@ fn main() -> Result<(), error::Error> {
                 market,
                 user_token_account,
                 user_wallet_keypair,
+                allowlist,
+                payment_mint,
+                purchase_receipt,
             } => {
                 let user_wallet = if let Some(keypair) = user_wallet_keypair {
                     read_keypair_file(keypair)?
@@ -437,12 +950,114 @@ fn main() -> Result<(), error::Error> {
                     utils::clone_keypair(&payer_wallet)
                 };
 
+                let allowlist_proof = if let Some(allowlist) = allowlist {
+                    let allowlist = utils::read_allowlist(&allowlist)?;
+                    Some(allowlist.to_allowlist_proof(&user_wallet.pubkey())?)
+                } else {
+                    None
+                };
+
+                let payment_mint = if let Some(payment_mint) = payment_mint {
+                    Some(Pubkey::from_str(&payment_mint)?)
+                } else {
+                    None
+                };
+
                 let (tx, ui_info) = processor::buy(
                     &client,
                     &payer_wallet,
                     &Pubkey::from_str(&market)?,
                     &Pubkey::from_str(&user_token_account)?,
                     &user_wallet,
+                    allowlist_proof,
+                    payment_mint.as_ref(),
+                    purchase_receipt,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::BuyToken {
+                market,
+                user_token_account,
+                user_wallet_keypair,
+                destination,
+                quantity,
+                allowlist,
+                payment_mint,
+                purchase_receipt,
+            } => {
+                let user_wallet = if let Some(keypair) = user_wallet_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let allowlist_proof = if let Some(allowlist) = allowlist {
+                    let allowlist = utils::read_allowlist(&allowlist)?;
+                    Some(allowlist.to_allowlist_proof(&user_wallet.pubkey())?)
+                } else {
+                    None
+                };
+
+                let payment_mint = if let Some(payment_mint) = payment_mint {
+                    Some(Pubkey::from_str(&payment_mint)?)
+                } else {
+                    None
+                };
+
+                let (tx, ui_info) = processor::buy_token(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&market)?,
+                    &Pubkey::from_str(&user_token_account)?,
+                    &user_wallet,
+                    &Pubkey::from_str(&destination)?,
+                    quantity,
+                    allowlist_proof,
+                    payment_mint.as_ref(),
+                    purchase_receipt,
+                )?;
+
+                Some(vec![(tx, ui_info)])
+            }
+            Commands::BuyMany {
+                market,
+                user_token_account,
+                user_wallet_keypair,
+                quantity,
+                allowlist,
+                payment_mint,
+                purchase_receipt,
+            } => {
+                let user_wallet = if let Some(keypair) = user_wallet_keypair {
+                    read_keypair_file(keypair)?
+                } else {
+                    utils::clone_keypair(&payer_wallet)
+                };
+
+                let allowlist_proof = if let Some(allowlist) = allowlist {
+                    let allowlist = utils::read_allowlist(&allowlist)?;
+                    Some(allowlist.to_allowlist_proof(&user_wallet.pubkey())?)
+                } else {
+                    None
+                };
+
+                let payment_mint = if let Some(payment_mint) = payment_mint {
+                    Some(Pubkey::from_str(&payment_mint)?)
+                } else {
+                    None
+                };
+
+                let (tx, ui_info) = processor::buy_many(
+                    &client,
+                    &payer_wallet,
+                    &Pubkey::from_str(&market)?,
+                    &Pubkey::from_str(&user_token_account)?,
+                    &user_wallet,
+                    quantity,
+                    allowlist_proof,
+                    payment_mint.as_ref(),
+                    purchase_receipt,
                 )?;
 
                 Some(vec![(tx, ui_info)])
@@ -0,0 +1,85 @@
+//! Module provide handler for `AddPaymentMint` command.
+
+use super::UiTransactionInfo;
+use crate::{error, utils};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::{state::PaymentPrice, utils::find_treasury_owner_address};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    transaction::Transaction,
+};
+
+/// Additional `AddPaymentMint` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct AddPaymentMintUiInfo {
+    mint: Pubkey,
+    treasury_owner: Pubkey,
+    treasury_holder: Pubkey,
+}
+
+impl UiTransactionInfo for AddPaymentMintUiInfo {
+    fn print(&self) {
+        println!("AddPaymentMint::mint - {}", self.mint);
+        println!("AddPaymentMint::treasury_owner - {}", self.treasury_owner);
+        println!("AddPaymentMint::treasury_holder - {}", self.treasury_holder);
+    }
+}
+
+pub fn add_payment_mint(
+    client: &RpcClient,
+    payer: &Keypair,
+    owner: &Keypair,
+    market: &Pubkey,
+    selling_resource: &Pubkey,
+    treasury_holder: &Keypair,
+    mint: &Pubkey,
+    price: PaymentPrice,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let (treasury_owner, treasury_owner_bump) = find_treasury_owner_address(mint, selling_resource);
+
+    let mut instructions = utils::create_token_account_instructions(
+        client,
+        &payer.pubkey(),
+        &treasury_holder.pubkey(),
+        mint,
+        &treasury_owner,
+    )?;
+
+    let accounts = mpl_fixed_price_sale::accounts::AddPaymentMint {
+        market: *market,
+        owner: owner.pubkey(),
+        mint: *mint,
+        treasury_holder: treasury_holder.pubkey(),
+        treasury_owner,
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::AddPaymentMint {
+        _treasury_owner_bump: treasury_owner_bump,
+        price,
+    }
+    .data();
+
+    instructions.push(Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    });
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer, owner, treasury_holder],
+            recent_blockhash,
+        ),
+        Box::new(AddPaymentMintUiInfo {
+            mint: *mint,
+            treasury_owner,
+            treasury_holder: treasury_holder.pubkey(),
+        }),
+    ))
+}
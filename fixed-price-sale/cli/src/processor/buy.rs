@@ -18,6 +18,7 @@ use solana_sdk::{
 /// Additional `Buy` instruction info, that need to be displayed in TUI.
 #[derive(Debug)]
 pub struct BuyUiInfo {
+    purchase_receipt: Option<Pubkey>,
     owner: Pubkey,
     trade_history: Pubkey,
     edition_marker: Pubkey,
@@ -28,6 +29,9 @@ pub struct BuyUiInfo {
 
 impl UiTransactionInfo for BuyUiInfo {
     fn print(&self) {
+        if let Some(purchase_receipt) = self.purchase_receipt {
+            println!("Buy::purchase_receipt - {}", purchase_receipt);
+        }
         println!("Buy::owner - {}", self.owner);
         println!("Buy::trade_history - {}", self.trade_history);
         println!("Buy::edition_marker - {}", self.edition_marker);
@@ -43,6 +47,9 @@ pub fn buy(
     market: &Pubkey,
     user_token_account: &Pubkey,
     user_wallet: &Keypair,
+    allowlist_proof: Option<mpl_fixed_price_sale::state::AllowlistProof>,
+    payment_mint: Option<&Pubkey>,
+    purchase_receipt: bool,
 ) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
     let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
     let selling_resource_state = get_account_state::<mpl_fixed_price_sale::state::SellingResource>(
@@ -74,6 +81,16 @@ pub fn buy(
     let (owner, vault_owner_bump) = find_vault_owner_address(&resource_mint, &store);
     let (trade_history, trade_history_bump) =
         find_trade_history_address(&user_wallet.pubkey(), market);
+    let (treasury_holder, payment_accounts) = utils::payment_accounts(&market_state, payment_mint)?;
+    let purchase_receipt = if purchase_receipt {
+        Some(utils::purchase_receipt_account(
+            client,
+            market,
+            &user_wallet.pubkey(),
+        )?)
+    } else {
+        None
+    };
 
     // Should be created
     let (master_edition, _) = Pubkey::find_program_address(
@@ -126,13 +143,13 @@ pub fn buy(
         &mpl_token_metadata::id(),
     );
 
-    let accounts = mpl_fixed_price_sale::accounts::Buy {
+    let mut accounts = mpl_fixed_price_sale::accounts::Buy {
         market: *market,
         selling_resource: market_state.selling_resource,
         user_token_account: *user_token_account,
         user_wallet: user_wallet.pubkey(),
         trade_history,
-        treasury_holder: market_state.treasury_holder,
+        treasury_holder,
         new_metadata,
         new_edition,
         master_edition,
@@ -149,10 +166,15 @@ pub fn buy(
         system_program: system_program::id(),
     }
     .to_account_metas(None);
+    if let Some(purchase_receipt) = &purchase_receipt {
+        accounts.push(purchase_receipt.clone());
+    }
+    accounts.extend(payment_accounts);
 
     let data = mpl_fixed_price_sale::instruction::Buy {
         _trade_history_bump: trade_history_bump,
         vault_owner_bump,
+        allowlist_proof,
     }
     .data();
 
@@ -172,6 +194,7 @@ pub fn buy(
             recent_blockhash,
         ),
         Box::new(BuyUiInfo {
+            purchase_receipt: purchase_receipt.map(|purchase_receipt| purchase_receipt.pubkey),
             owner,
             edition_marker,
             new_edition,
@@ -0,0 +1,212 @@
+//! Module provide handler for `BuyMany` command.
+
+use super::{get_account_state, get_account_state_legacy, UiTransactionInfo};
+use crate::{error, utils};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::utils::{find_trade_history_address, find_vault_owner_address};
+use mpl_token_metadata::state::{MasterEditionV2, EDITION, EDITION_MARKER_BIT_SIZE, PREFIX};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signer,
+    signer::keypair::Keypair,
+    system_program,
+    sysvar::{clock, rent},
+    transaction::Transaction,
+};
+
+/// Additional `BuyMany` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct BuyManyUiInfo {
+    purchase_receipt: Option<Pubkey>,
+    owner: Pubkey,
+    trade_history: Pubkey,
+    new_mints: Vec<Pubkey>,
+}
+
+impl UiTransactionInfo for BuyManyUiInfo {
+    fn print(&self) {
+        if let Some(purchase_receipt) = self.purchase_receipt {
+            println!("BuyMany::purchase_receipt - {}", purchase_receipt);
+        }
+        println!("BuyMany::owner - {}", self.owner);
+        println!("BuyMany::trade_history - {}", self.trade_history);
+        for new_mint in &self.new_mints {
+            println!("BuyMany::new_mint - {}", new_mint);
+        }
+    }
+}
+
+pub fn buy_many(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+    user_token_account: &Pubkey,
+    user_wallet: &Keypair,
+    quantity: u64,
+    allowlist_proof: Option<mpl_fixed_price_sale::state::AllowlistProof>,
+    payment_mint: Option<&Pubkey>,
+    purchase_receipt: bool,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+    let selling_resource_state = get_account_state::<mpl_fixed_price_sale::state::SellingResource>(
+        client,
+        &market_state.selling_resource,
+    )?;
+    let store = market_state.store;
+    let resource_mint = selling_resource_state.resource;
+
+    let (owner, vault_owner_bump) = find_vault_owner_address(&resource_mint, &store);
+    let (trade_history, trade_history_bump) =
+        find_trade_history_address(&user_wallet.pubkey(), market);
+    let (treasury_holder, payment_accounts) = utils::payment_accounts(&market_state, payment_mint)?;
+    let purchase_receipt = if purchase_receipt {
+        Some(utils::purchase_receipt_account(
+            client,
+            market,
+            &user_wallet.pubkey(),
+        )?)
+    } else {
+        None
+    };
+
+    // Should be created
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource_mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    // Should be created
+    let (master_edition_metadata, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource_mint.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let master_edition_supply =
+        get_account_state_legacy::<MasterEditionV2>(client, &master_edition)?.supply;
+
+    let mut accounts = mpl_fixed_price_sale::accounts::BuyMany {
+        market: *market,
+        selling_resource: market_state.selling_resource,
+        user_token_account: *user_token_account,
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder,
+        master_edition,
+        vault: selling_resource_state.vault,
+        owner,
+        master_edition_metadata,
+        clock: clock::id(),
+        rent: rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+    if let Some(purchase_receipt) = &purchase_receipt {
+        accounts.push(purchase_receipt.clone());
+    }
+    accounts.extend(payment_accounts);
+
+    let mut new_mints = Vec::new();
+    for i in 1..=quantity {
+        let new_mint = Keypair::new();
+        utils::create_mint(client, payer, &new_mint, 0)?;
+
+        let new_mint_token_account = Keypair::new();
+        utils::create_token_account(
+            client,
+            payer,
+            &new_mint_token_account,
+            &new_mint.pubkey(),
+            &user_wallet.pubkey(),
+        )?;
+        utils::mint_to(
+            client,
+            payer,
+            &new_mint.pubkey(),
+            &new_mint_token_account.pubkey(),
+            1,
+        )?;
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint.pubkey().as_ref(),
+                EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let edition = master_edition_supply + i;
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                resource_mint.as_ref(),
+                EDITION.as_bytes(),
+                (edition / EDITION_MARKER_BIT_SIZE).to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        accounts.push(AccountMeta::new(new_mint.pubkey(), false));
+        accounts.push(AccountMeta::new(new_metadata, false));
+        accounts.push(AccountMeta::new(new_edition, false));
+        accounts.push(AccountMeta::new(edition_marker, false));
+        accounts.push(AccountMeta::new(new_mint_token_account.pubkey(), false));
+
+        new_mints.push(new_mint.pubkey());
+    }
+
+    let data = mpl_fixed_price_sale::instruction::BuyMany {
+        _trade_history_bump: trade_history_bump,
+        vault_owner_bump,
+        quantity,
+        allowlist_proof,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, user_wallet],
+            recent_blockhash,
+        ),
+        Box::new(BuyManyUiInfo {
+            purchase_receipt: purchase_receipt.map(|purchase_receipt| purchase_receipt.pubkey),
+            owner,
+            trade_history,
+            new_mints,
+        }),
+    ))
+}
@@ -0,0 +1,116 @@
+//! Module provide handler for `BuyToken` command.
+
+use super::{get_account_state, UiTransactionInfo};
+use crate::{error, utils};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::utils::{find_trade_history_address, find_vault_owner_address};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    system_program, sysvar::clock, transaction::Transaction,
+};
+
+/// Additional `BuyToken` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct BuyTokenUiInfo {
+    purchase_receipt: Option<Pubkey>,
+    trade_history: Pubkey,
+    destination: Pubkey,
+    quantity: u64,
+}
+
+impl UiTransactionInfo for BuyTokenUiInfo {
+    fn print(&self) {
+        if let Some(purchase_receipt) = self.purchase_receipt {
+            println!("BuyToken::purchase_receipt - {}", purchase_receipt);
+        }
+        println!("BuyToken::trade_history - {}", self.trade_history);
+        println!("BuyToken::destination - {}", self.destination);
+        println!("BuyToken::quantity - {}", self.quantity);
+    }
+}
+
+pub fn buy_token(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+    user_token_account: &Pubkey,
+    user_wallet: &Keypair,
+    destination: &Pubkey,
+    quantity: u64,
+    allowlist_proof: Option<mpl_fixed_price_sale::state::AllowlistProof>,
+    payment_mint: Option<&Pubkey>,
+    purchase_receipt: bool,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+    let selling_resource_state = get_account_state::<mpl_fixed_price_sale::state::SellingResource>(
+        client,
+        &market_state.selling_resource,
+    )?;
+
+    let (owner, vault_owner_bump) =
+        find_vault_owner_address(&selling_resource_state.resource, &market_state.store);
+    let (trade_history, trade_history_bump) =
+        find_trade_history_address(&user_wallet.pubkey(), market);
+    let (treasury_holder, payment_accounts) = utils::payment_accounts(&market_state, payment_mint)?;
+    let purchase_receipt = if purchase_receipt {
+        Some(utils::purchase_receipt_account(
+            client,
+            market,
+            &user_wallet.pubkey(),
+        )?)
+    } else {
+        None
+    };
+
+    let mut accounts = mpl_fixed_price_sale::accounts::BuyToken {
+        market: *market,
+        selling_resource: market_state.selling_resource,
+        user_token_account: *user_token_account,
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder,
+        vault: selling_resource_state.vault,
+        owner,
+        destination: *destination,
+        clock: clock::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+    if let Some(purchase_receipt) = &purchase_receipt {
+        accounts.push(purchase_receipt.clone());
+    }
+    accounts.extend(payment_accounts);
+
+    let data = mpl_fixed_price_sale::instruction::BuyToken {
+        _trade_history_bump: trade_history_bump,
+        vault_owner_bump,
+        quantity,
+        allowlist_proof,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, user_wallet],
+            recent_blockhash,
+        ),
+        Box::new(BuyTokenUiInfo {
+            purchase_receipt: purchase_receipt.map(|purchase_receipt| purchase_receipt.pubkey),
+            trade_history,
+            destination: *destination,
+            quantity,
+        }),
+    ))
+}
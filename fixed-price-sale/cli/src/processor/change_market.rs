@@ -30,6 +30,7 @@ pub fn change_market(
     mutable: Option<bool>,
     new_price: Option<u64>,
     new_pieces_in_one_wallet: Option<u64>,
+    new_price_schedule: Option<Option<mpl_fixed_price_sale::state::PriceSchedule>>,
 ) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
     let accounts = mpl_fixed_price_sale::accounts::ChangeMarket {
         market: *market,
@@ -44,6 +45,7 @@ pub fn change_market(
         mutable,
         new_price,
         new_pieces_in_one_wallet,
+        new_price_schedule,
     }
     .data();
 
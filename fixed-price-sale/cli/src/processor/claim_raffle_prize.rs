@@ -0,0 +1,183 @@
+//! Module provide handler for `ClaimRafflePrize` command.
+
+use super::{get_account_state, UiTransactionInfo};
+use crate::{error, utils};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::utils::{find_trade_history_address, find_vault_owner_address};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signer,
+    signer::keypair::Keypair,
+    system_program,
+    sysvar::{clock, rent},
+    transaction::Transaction,
+};
+
+/// Additional `ClaimRafflePrize` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct ClaimRafflePrizeUiInfo {
+    raffle_ticket: Pubkey,
+    trade_history: Pubkey,
+    edition_marker: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    new_mint: Pubkey,
+}
+
+impl UiTransactionInfo for ClaimRafflePrizeUiInfo {
+    fn print(&self) {
+        println!("ClaimRafflePrize::raffle_ticket - {}", self.raffle_ticket);
+        println!("ClaimRafflePrize::trade_history - {}", self.trade_history);
+        println!("ClaimRafflePrize::edition_marker - {}", self.edition_marker);
+        println!("ClaimRafflePrize::new_metadata - {}", self.new_metadata);
+        println!("ClaimRafflePrize::new_edition - {}", self.new_edition);
+        println!("ClaimRafflePrize::new_mint - {}", self.new_mint);
+    }
+}
+
+pub fn claim_raffle_prize(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+    user_wallet: &Keypair,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+    let selling_resource_state = get_account_state::<mpl_fixed_price_sale::state::SellingResource>(
+        client,
+        &market_state.selling_resource,
+    )?;
+    let store = market_state.store;
+    let resource_mint = selling_resource_state.resource;
+
+    let (raffle_ticket, _) = utils::find_raffle_ticket(client, market, &user_wallet.pubkey())?;
+
+    let new_mint = Keypair::new();
+    utils::create_mint(client, payer, &new_mint, 0)?;
+
+    let new_mint_token_account = Keypair::new();
+    utils::create_token_account(
+        client,
+        payer,
+        &new_mint_token_account,
+        &new_mint.pubkey(),
+        &user_wallet.pubkey(),
+    )?;
+    utils::mint_to(
+        client,
+        payer,
+        &new_mint.pubkey(),
+        &new_mint_token_account.pubkey(),
+        1,
+    )?;
+
+    let (owner, vault_owner_bump) = find_vault_owner_address(&resource_mint, &store);
+    let (trade_history, trade_history_bump) =
+        find_trade_history_address(&user_wallet.pubkey(), market);
+
+    // Should be created
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource_mint.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource_mint.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource_state.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    // Should be created
+    let (master_edition_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource_mint.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let accounts = mpl_fixed_price_sale::accounts::ClaimRafflePrize {
+        market: *market,
+        selling_resource: market_state.selling_resource,
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        raffle_ticket,
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint.pubkey(),
+        edition_marker,
+        vault: selling_resource_state.vault,
+        owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        master_edition_metadata,
+        clock: clock::id(),
+        rent: rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::ClaimRafflePrize {
+        _trade_history_bump: trade_history_bump,
+        vault_owner_bump,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, user_wallet],
+            recent_blockhash,
+        ),
+        Box::new(ClaimRafflePrizeUiInfo {
+            raffle_ticket,
+            trade_history,
+            edition_marker,
+            new_metadata,
+            new_edition,
+            new_mint: new_mint.pubkey(),
+        }),
+    ))
+}
@@ -0,0 +1,86 @@
+//! Module provide handler for `ClaimRaffleRefund` command.
+
+use super::{get_account_state, UiTransactionInfo};
+use crate::{error, utils};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::utils::{find_trade_history_address, find_treasury_owner_address};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    system_program, transaction::Transaction,
+};
+
+/// Additional `ClaimRaffleRefund` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct ClaimRaffleRefundUiInfo {
+    raffle_ticket: Pubkey,
+    destination: Pubkey,
+    refund: u64,
+}
+
+impl UiTransactionInfo for ClaimRaffleRefundUiInfo {
+    fn print(&self) {
+        println!("ClaimRaffleRefund::raffle_ticket - {}", self.raffle_ticket);
+        println!("ClaimRaffleRefund::destination - {}", self.destination);
+        println!("ClaimRaffleRefund::refund - {}", self.refund);
+    }
+}
+
+pub fn claim_raffle_refund(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+    user_wallet: &Keypair,
+    destination: &Pubkey,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+
+    let (raffle_ticket, raffle_ticket_state) =
+        utils::find_raffle_ticket(client, market, &user_wallet.pubkey())?;
+
+    let (trade_history, trade_history_bump) =
+        find_trade_history_address(&user_wallet.pubkey(), market);
+    let (owner, treasury_owner_bump) =
+        find_treasury_owner_address(&market_state.treasury_mint, &market_state.selling_resource);
+
+    let accounts = mpl_fixed_price_sale::accounts::ClaimRaffleRefund {
+        market: *market,
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        raffle_ticket,
+        treasury_holder: market_state.treasury_holder,
+        owner,
+        destination: *destination,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::ClaimRaffleRefund {
+        _trade_history_bump: trade_history_bump,
+        treasury_owner_bump,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, user_wallet],
+            recent_blockhash,
+        ),
+        Box::new(ClaimRaffleRefundUiInfo {
+            raffle_ticket,
+            destination: *destination,
+            refund: raffle_ticket_state.deposit,
+        }),
+    ))
+}
@@ -0,0 +1,121 @@
+//! Module provide handler for `ClaimRefund` command.
+
+use super::{get_account_state, UiTransactionInfo};
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::utils::{find_trade_history_address, find_treasury_owner_address};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Signer,
+    signer::keypair::Keypair,
+    system_program,
+    transaction::Transaction,
+};
+
+/// Additional `ClaimRefund` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct ClaimRefundUiInfo {
+    trade_history: Pubkey,
+    destination: Pubkey,
+    returned_editions: usize,
+}
+
+impl UiTransactionInfo for ClaimRefundUiInfo {
+    fn print(&self) {
+        println!("ClaimRefund::trade_history - {}", self.trade_history);
+        println!("ClaimRefund::destination - {}", self.destination);
+        println!(
+            "ClaimRefund::returned_editions - {}",
+            self.returned_editions
+        );
+    }
+}
+
+pub fn claim_refund(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+    user_wallet: &Keypair,
+    destination: &Pubkey,
+    edition_token_accounts: &[Pubkey],
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+
+    let (trade_history, trade_history_bump) =
+        find_trade_history_address(&user_wallet.pubkey(), market);
+    let trade_history_state =
+        get_account_state::<mpl_fixed_price_sale::state::TradeHistory>(client, &trade_history)?;
+
+    // Refund is paid in the currency wallet bought with
+    let treasury_mint = trade_history_state.currency;
+    let (treasury_holder, _) = market_state.treasury(&treasury_mint).ok_or_else(|| {
+        error::Error::DynamicError(format!("Mint {} is not accepted by market", treasury_mint))
+    })?;
+    let (owner, treasury_owner_bump) =
+        find_treasury_owner_address(&treasury_mint, &market_state.selling_resource);
+
+    let mut accounts = mpl_fixed_price_sale::accounts::ClaimRefund {
+        market: *market,
+        selling_resource: market_state.selling_resource,
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder,
+        treasury_mint,
+        owner,
+        destination: *destination,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    // Returned editions are burned, three accounts per edition
+    for edition_token_account in edition_token_accounts {
+        let token_account =
+            spl_token::state::Account::unpack(&client.get_account_data(edition_token_account)?)?;
+
+        let (edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                token_account.mint.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        accounts.push(AccountMeta::new(*edition_token_account, false));
+        accounts.push(AccountMeta::new(token_account.mint, false));
+        accounts.push(AccountMeta::new_readonly(edition, false));
+    }
+
+    let data = mpl_fixed_price_sale::instruction::ClaimRefund {
+        _trade_history_bump: trade_history_bump,
+        treasury_owner_bump,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, user_wallet],
+            recent_blockhash,
+        ),
+        Box::new(ClaimRefundUiInfo {
+            trade_history,
+            destination: *destination,
+            returned_editions: edition_token_accounts.len(),
+        }),
+    ))
+}
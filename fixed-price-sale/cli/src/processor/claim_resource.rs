@@ -5,8 +5,13 @@ use crate::error;
 use anchor_lang::{InstructionData, ToAccountMetas};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
-    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
-    system_program, sysvar::clock, transaction::Transaction,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signer,
+    signer::keypair::Keypair,
+    system_program,
+    sysvar::clock,
+    transaction::Transaction,
 };
 
 /// Additional `ClaimResource` instruction info, that need to be displayed in TUI.
@@ -51,7 +56,7 @@ pub fn claim_resource(
         &selling_resource_state.store,
     );
 
-    let accounts = mpl_fixed_price_sale::accounts::ClaimResource {
+    let mut accounts = mpl_fixed_price_sale::accounts::ClaimResource {
         market: *market,
         selling_resource: market_state.selling_resource,
         treasury_holder: market_state.treasury_holder,
@@ -67,6 +72,14 @@ pub fn claim_resource(
     }
     .to_account_metas(None);
 
+    // Treasuries of `Market::payment_mints` are checked to be empty too
+    for payment_mint in &market_state.payment_mints {
+        accounts.push(AccountMeta::new_readonly(
+            payment_mint.treasury_holder,
+            false,
+        ));
+    }
+
     let data = mpl_fixed_price_sale::instruction::ClaimResource { vault_owner_bump }.data();
 
     let instruction = Instruction {
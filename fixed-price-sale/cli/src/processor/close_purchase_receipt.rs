@@ -0,0 +1,59 @@
+//! Module provide handler for `ClosePurchaseReceipt` command.
+
+use super::UiTransactionInfo;
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    transaction::Transaction,
+};
+
+/// Additional `ClosePurchaseReceipt` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct ClosePurchaseReceiptUiInfo {
+    purchase_receipt: Pubkey,
+}
+
+impl UiTransactionInfo for ClosePurchaseReceiptUiInfo {
+    fn print(&self) {
+        println!(
+            "ClosePurchaseReceipt::purchase_receipt - {}",
+            self.purchase_receipt
+        );
+    }
+}
+
+pub fn close_purchase_receipt(
+    client: &RpcClient,
+    buyer: &Keypair,
+    purchase_receipt: &Pubkey,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let accounts = mpl_fixed_price_sale::accounts::ClosePurchaseReceipt {
+        purchase_receipt: *purchase_receipt,
+        buyer: buyer.pubkey(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::ClosePurchaseReceipt {}.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            recent_blockhash,
+        ),
+        Box::new(ClosePurchaseReceiptUiInfo {
+            purchase_receipt: *purchase_receipt,
+        }),
+    ))
+}
@@ -0,0 +1,54 @@
+//! Module provide handler for `CloseRaffleEntries` command.
+
+use super::UiTransactionInfo;
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    sysvar::clock, transaction::Transaction,
+};
+
+/// Additional `CloseRaffleEntries` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct CloseRaffleEntriesUiInfo {
+    market: Pubkey,
+}
+
+impl UiTransactionInfo for CloseRaffleEntriesUiInfo {
+    fn print(&self) {
+        println!("CloseRaffleEntries::market - {}", self.market);
+    }
+}
+
+pub fn close_raffle_entries(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let accounts = mpl_fixed_price_sale::accounts::CloseRaffleEntries {
+        market: *market,
+        clock: clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::CloseRaffleEntries {}.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        ),
+        Box::new(CloseRaffleEntriesUiInfo { market: *market }),
+    ))
+}
@@ -1,6 +1,6 @@
 //! Module provide handler for `CreateMarket` command.
 
-use super::{get_account_state, UiTransactionInfo};
+use super::UiTransactionInfo;
 use crate::{error, utils};
 use anchor_lang::{InstructionData, ToAccountMetas};
 use mpl_fixed_price_sale::utils::find_treasury_owner_address;
@@ -31,6 +31,9 @@ pub fn create_market(
     payer: &Keypair,
     selling_resource_owner: &Keypair,
     selling_resource: &Pubkey,
+    store: &Pubkey,
+    market: &Keypair,
+    treasury_holder: &Keypair,
     mint: &Pubkey,
     name: &String,
     description: &String,
@@ -40,22 +43,24 @@ pub fn create_market(
     start_date: u64,
     end_date: Option<u64>,
     gating_config: Option<mpl_fixed_price_sale::state::GatingConfig>,
+    price_schedule: Option<mpl_fixed_price_sale::state::PriceSchedule>,
+    allowlist: Option<mpl_fixed_price_sale::state::AllowlistConfig>,
+    allocation: Option<u64>,
 ) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
     let (treasury_owner, treasury_owner_bump) =
         find_treasury_owner_address(&mint, selling_resource);
 
-    let treasury_holder = Keypair::new();
-    utils::create_token_account(client, payer, &treasury_holder, &mint, &treasury_owner)?;
-
-    let selling_resource_state = get_account_state::<mpl_fixed_price_sale::state::SellingResource>(
+    let mut instructions = utils::create_token_account_instructions(
         client,
-        selling_resource,
+        &payer.pubkey(),
+        &treasury_holder.pubkey(),
+        mint,
+        &treasury_owner,
     )?;
 
-    let market = Keypair::new();
     let accounts = mpl_fixed_price_sale::accounts::CreateMarket {
         market: market.pubkey(),
-        store: selling_resource_state.store,
+        store: *store,
         selling_resource_owner: selling_resource_owner.pubkey(),
         selling_resource: *selling_resource,
         mint: *mint,
@@ -75,22 +80,25 @@ pub fn create_market(
         start_date,
         end_date,
         gating_config,
+        price_schedule,
+        allowlist,
+        allocation,
     }
     .data();
 
-    let instruction = Instruction {
+    instructions.push(Instruction {
         program_id: mpl_fixed_price_sale::id(),
         data,
         accounts,
-    };
+    });
 
     let recent_blockhash = client.get_latest_blockhash()?;
 
     Ok((
         Transaction::new_signed_with_payer(
-            &[instruction],
+            &instructions,
             Some(&payer.pubkey()),
-            &[payer, &market, selling_resource_owner],
+            &[payer, market, selling_resource_owner, treasury_holder],
             recent_blockhash,
         ),
         Box::new(CreateMarketUiInfo {
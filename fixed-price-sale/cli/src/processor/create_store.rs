@@ -3,6 +3,7 @@
 use super::UiTransactionInfo;
 use crate::error;
 use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::state::PlatformFee;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
@@ -25,11 +26,11 @@ pub fn create_store(
     client: &RpcClient,
     payer: &Keypair,
     admin_wallet: &Keypair,
+    store: &Keypair,
     name: &String,
     description: &String,
+    platform_fee: Option<PlatformFee>,
 ) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
-    let store = Keypair::new();
-
     let accounts = mpl_fixed_price_sale::accounts::CreateStore {
         admin: admin_wallet.pubkey(),
         store: store.pubkey(),
@@ -40,6 +41,7 @@ pub fn create_store(
     let data = mpl_fixed_price_sale::instruction::CreateStore {
         name: name.to_owned(),
         description: description.to_owned(),
+        platform_fee,
     }
     .data();
 
@@ -55,7 +57,7 @@ pub fn create_store(
         Transaction::new_signed_with_payer(
             &[instruction],
             Some(&payer.pubkey()),
-            &[payer, admin_wallet, &store],
+            &[payer, admin_wallet, store],
             recent_blockhash,
         ),
         Box::new(CreateStoreUiInfo {
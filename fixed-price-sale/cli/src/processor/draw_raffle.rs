@@ -0,0 +1,84 @@
+//! Module provide handler for `DrawRaffle` command.
+
+use super::{get_account_state, UiTransactionInfo};
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::utils::find_raffle_ticket_address;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signer,
+    signer::keypair::Keypair,
+    transaction::Transaction,
+};
+
+/// Raffle tickets drawn in one transaction, keeps it under the size and compute limits.
+const TICKETS_PER_DRAW: u64 = 20;
+
+/// Additional `DrawRaffle` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct DrawRaffleUiInfo {
+    first_index: u64,
+    tickets: u64,
+}
+
+impl UiTransactionInfo for DrawRaffleUiInfo {
+    fn print(&self) {
+        println!("DrawRaffle::first_index - {}", self.first_index);
+        println!("DrawRaffle::tickets - {}", self.tickets);
+    }
+}
+
+pub fn draw_raffle(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+) -> Result<Vec<(Transaction, Box<dyn UiTransactionInfo>)>, error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+    let raffle = market_state
+        .raffle
+        .ok_or_else(|| error::Error::DynamicError(format!("Market {} is not a raffle", market)))?;
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    let mut bundle: Vec<(Transaction, Box<dyn UiTransactionInfo>)> = Vec::new();
+
+    // Chunks continue from `Raffle::drawn`, so they are sent in order
+    let mut first_index = raffle.drawn;
+    while first_index < raffle.entries {
+        let tickets = TICKETS_PER_DRAW.min(raffle.entries - first_index);
+
+        let mut accounts =
+            mpl_fixed_price_sale::accounts::DrawRaffle { market: *market }.to_account_metas(None);
+        for index in first_index..first_index + tickets {
+            let (raffle_ticket, _) = find_raffle_ticket_address(market, index);
+            accounts.push(AccountMeta::new(raffle_ticket, false));
+        }
+
+        let data = mpl_fixed_price_sale::instruction::DrawRaffle {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        bundle.push((
+            Transaction::new_signed_with_payer(
+                &[instruction],
+                Some(&payer.pubkey()),
+                &[payer],
+                recent_blockhash,
+            ),
+            Box::new(DrawRaffleUiInfo {
+                first_index,
+                tickets,
+            }),
+        ));
+
+        first_index += tickets;
+    }
+
+    Ok(bundle)
+}
@@ -0,0 +1,85 @@
+//! Module provide handler for `EnterRaffle` command.
+
+use super::{get_account_state, UiTransactionInfo};
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::utils::{find_raffle_ticket_address, find_trade_history_address};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    system_program, sysvar::clock, transaction::Transaction,
+};
+
+/// Additional `EnterRaffle` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct EnterRaffleUiInfo {
+    trade_history: Pubkey,
+    raffle_ticket: Pubkey,
+    index: u64,
+}
+
+impl UiTransactionInfo for EnterRaffleUiInfo {
+    fn print(&self) {
+        println!("EnterRaffle::trade_history - {}", self.trade_history);
+        println!("EnterRaffle::raffle_ticket - {}", self.raffle_ticket);
+        println!("EnterRaffle::index - {}", self.index);
+    }
+}
+
+pub fn enter_raffle(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+    user_token_account: &Pubkey,
+    user_wallet: &Keypair,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+    let raffle = market_state
+        .raffle
+        .ok_or_else(|| error::Error::DynamicError(format!("Market {} is not a raffle", market)))?;
+
+    let (trade_history, trade_history_bump) =
+        find_trade_history_address(&user_wallet.pubkey(), market);
+    // Ticket is indexed by entry order, entering concurrently with others may need a retry
+    let (raffle_ticket, _) = find_raffle_ticket_address(market, raffle.entries);
+
+    let accounts = mpl_fixed_price_sale::accounts::EnterRaffle {
+        market: *market,
+        user_token_account: *user_token_account,
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: market_state.treasury_holder,
+        raffle_ticket,
+        clock: clock::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::EnterRaffle {
+        _trade_history_bump: trade_history_bump,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, user_wallet],
+            recent_blockhash,
+        ),
+        Box::new(EnterRaffleUiInfo {
+            trade_history,
+            raffle_ticket,
+            index: raffle.entries,
+        }),
+    ))
+}
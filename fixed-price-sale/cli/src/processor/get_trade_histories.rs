@@ -0,0 +1,62 @@
+//! Module provide handler for query commands.
+
+use crate::error;
+use anchor_lang::Discriminator;
+use mpl_fixed_price_sale::state::{Market, TradeHistory};
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+};
+use solana_sdk::{borsh::try_from_slice_unchecked, pubkey::Pubkey};
+
+/// Return every `TradeHistory` account of `market` with its address.
+///
+/// Accounts created by older program versions are shorter, fields they miss are filled from `market`.
+pub fn get_trade_histories(
+    client: &RpcClient,
+    market_address: &Pubkey,
+    market: &Market,
+) -> Result<Vec<(Pubkey, TradeHistory)>, error::Error> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            // First 8-bytes filled with sha256 hash by anchor
+            RpcFilterType::Memcmp(Memcmp {
+                offset: 0,
+                bytes: MemcmpEncodedBytes::Bytes(TradeHistory::discriminator().to_vec()),
+                encoding: None,
+            }),
+            RpcFilterType::Memcmp(Memcmp {
+                offset: 8,
+                bytes: MemcmpEncodedBytes::Base58(market_address.to_string()),
+                encoding: None,
+            }),
+        ]),
+        account_config: RpcAccountInfoConfig::default(),
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    let mut trade_histories = Vec::new();
+
+    for (address, account) in
+        client.get_program_accounts_with_config(&mpl_fixed_price_sale::id(), config)?
+    {
+        let mut data = account.data[8..].to_vec();
+        if data.len() < TradeHistory::LEN - 8 {
+            data.resize(TradeHistory::LEN - 8, 0);
+        }
+        let mut trade_history: TradeHistory = try_from_slice_unchecked(&data)?;
+
+        // Only the fixed `Market::price` in `Market::treasury_mint` was accepted back then
+        if account.data.len() <= TradeHistory::LEN_WITHOUT_FUNDS_SPENT {
+            trade_history.funds_spent = trade_history.already_bought.saturating_mul(market.price);
+        }
+        if account.data.len() <= TradeHistory::LEN_WITHOUT_CURRENCY {
+            trade_history.currency = market.treasury_mint;
+        }
+
+        trade_histories.push((address, trade_history));
+    }
+
+    Ok(trade_histories)
+}
@@ -0,0 +1,71 @@
+//! Module provide handler for `InitRaffle` command.
+
+use super::{get_account_state, UiTransactionInfo};
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, keccak, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    transaction::Transaction,
+};
+
+/// Additional `InitRaffle` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct InitRaffleUiInfo {
+    seed_commitment: keccak::Hash,
+    winners: u64,
+}
+
+impl UiTransactionInfo for InitRaffleUiInfo {
+    fn print(&self) {
+        println!("InitRaffle::seed_commitment - {}", self.seed_commitment);
+        println!("InitRaffle::winners - {}", self.winners);
+    }
+}
+
+pub fn init_raffle(
+    client: &RpcClient,
+    payer: &Keypair,
+    owner: &Keypair,
+    market: &Pubkey,
+    seed: &[u8; 32],
+    winners: u64,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+
+    let seed_commitment = keccak::hash(seed);
+
+    let accounts = mpl_fixed_price_sale::accounts::InitRaffle {
+        market: *market,
+        owner: owner.pubkey(),
+        selling_resource: market_state.selling_resource,
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::InitRaffle {
+        seed_commitment: seed_commitment.to_bytes(),
+        winners,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, owner],
+            recent_blockhash,
+        ),
+        Box::new(InitRaffleUiInfo {
+            seed_commitment,
+            winners,
+        }),
+    ))
+}
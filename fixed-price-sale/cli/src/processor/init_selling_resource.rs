@@ -37,10 +37,11 @@ pub fn init_selling_resource(
     selling_resource_owner: &Pubkey,
     resource_mint: &Pubkey,
     resource_token: &Pubkey,
+    selling_resource: &Keypair,
+    vault: &Keypair,
     max_supply: Option<u64>,
 ) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
     let (vault_owner, vault_owner_bump) = find_vault_owner_address(resource_mint, store);
-    let selling_resource = Keypair::new();
 
     // Should be created
     let (master_edition, master_edition_bump) = Pubkey::find_program_address(
@@ -62,8 +63,13 @@ pub fn init_selling_resource(
         &mpl_token_metadata::id(),
     );
 
-    let vault_keypair = Keypair::new();
-    utils::create_token_account(client, payer, &vault_keypair, &resource_mint, &vault_owner)?;
+    let mut instructions = utils::create_token_account_instructions(
+        client,
+        &payer.pubkey(),
+        &vault.pubkey(),
+        resource_mint,
+        &vault_owner,
+    )?;
 
     let accounts = mpl_fixed_price_sale::accounts::InitSellingResource {
         store: *store,
@@ -73,7 +79,7 @@ pub fn init_selling_resource(
         resource_mint: *resource_mint,
         master_edition,
         metadata,
-        vault: vault_keypair.pubkey(),
+        vault: vault.pubkey(),
         owner: vault_owner,
         resource_token: *resource_token,
         rent: rent::id(),
@@ -89,24 +95,24 @@ pub fn init_selling_resource(
     }
     .data();
 
-    let instruction = Instruction {
+    instructions.push(Instruction {
         program_id: mpl_fixed_price_sale::id(),
         data,
         accounts,
-    };
+    });
 
     let recent_blockhash = client.get_latest_blockhash()?;
 
     Ok((
         Transaction::new_signed_with_payer(
-            &[instruction],
+            &instructions,
             Some(&payer.pubkey()),
-            &[payer, admin_keypair, &selling_resource],
+            &[payer, admin_keypair, selling_resource, vault],
             recent_blockhash,
         ),
         Box::new(InitSellingResourceUiInfo {
             selling_resource: selling_resource.pubkey(),
-            vault: vault_keypair.pubkey(),
+            vault: vault.pubkey(),
             vault_owner,
         }),
     ))
@@ -0,0 +1,111 @@
+//! Module provide handler for `InitTokenSellingResource` command.
+
+use super::UiTransactionInfo;
+use crate::{error, utils};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::utils::find_vault_owner_address;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    system_program, sysvar::rent, transaction::Transaction,
+};
+
+/// Additional `InitTokenSellingResource` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct InitTokenSellingResourceUiInfo {
+    selling_resource: Pubkey,
+    vault: Pubkey,
+    vault_owner: Pubkey,
+}
+
+impl UiTransactionInfo for InitTokenSellingResourceUiInfo {
+    fn print(&self) {
+        println!(
+            "InitTokenSellingResource::selling_resource - {}",
+            self.selling_resource
+        );
+        println!("InitTokenSellingResource::vault - {}", self.vault);
+        println!(
+            "InitTokenSellingResource::vault_owner - {}",
+            self.vault_owner
+        );
+    }
+}
+
+pub fn init_token_selling_resource(
+    client: &RpcClient,
+    payer: &Keypair,
+    store: &Pubkey,
+    admin_keypair: &Keypair,
+    selling_resource_owner: &Pubkey,
+    resource_mint: &Pubkey,
+    resource_token: &Pubkey,
+    selling_resource: &Keypair,
+    vault: &Keypair,
+    piece_size: u64,
+    pieces: u64,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let (vault_owner, vault_owner_bump) = find_vault_owner_address(resource_mint, store);
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource_mint.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let mut instructions = utils::create_token_account_instructions(
+        client,
+        &payer.pubkey(),
+        &vault.pubkey(),
+        resource_mint,
+        &vault_owner,
+    )?;
+
+    let accounts = mpl_fixed_price_sale::accounts::InitTokenSellingResource {
+        store: *store,
+        admin: admin_keypair.pubkey(),
+        selling_resource: selling_resource.pubkey(),
+        selling_resource_owner: *selling_resource_owner,
+        resource_mint: *resource_mint,
+        metadata,
+        vault: vault.pubkey(),
+        owner: vault_owner,
+        resource_token: *resource_token,
+        rent: rent::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::InitTokenSellingResource {
+        vault_owner_bump,
+        piece_size,
+        pieces,
+    }
+    .data();
+
+    instructions.push(Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    });
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer, admin_keypair, selling_resource, vault],
+            recent_blockhash,
+        ),
+        Box::new(InitTokenSellingResourceUiInfo {
+            selling_resource: selling_resource.pubkey(),
+            vault: vault.pubkey(),
+            vault_owner,
+        }),
+    ))
+}
@@ -0,0 +1,55 @@
+//! Module provide handler for `MigrateMarket` command.
+
+use super::UiTransactionInfo;
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    system_program, transaction::Transaction,
+};
+
+/// Additional `MigrateMarket` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct MigrateMarketUiInfo {
+    market: Pubkey,
+}
+
+impl UiTransactionInfo for MigrateMarketUiInfo {
+    fn print(&self) {
+        println!("MigrateMarket::market - {}", self.market);
+    }
+}
+
+pub fn migrate_market(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let accounts = mpl_fixed_price_sale::accounts::MigrateMarket {
+        market: *market,
+        payer: payer.pubkey(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::MigrateMarket {}.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        ),
+        Box::new(MigrateMarketUiInfo { market: *market }),
+    ))
+}
@@ -0,0 +1,60 @@
+//! Module provide handler for `MigrateSellingResource` command.
+
+use super::UiTransactionInfo;
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    system_program, transaction::Transaction,
+};
+
+/// Additional `MigrateSellingResource` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct MigrateSellingResourceUiInfo {
+    selling_resource: Pubkey,
+}
+
+impl UiTransactionInfo for MigrateSellingResourceUiInfo {
+    fn print(&self) {
+        println!(
+            "MigrateSellingResource::selling_resource - {}",
+            self.selling_resource
+        );
+    }
+}
+
+pub fn migrate_selling_resource(
+    client: &RpcClient,
+    payer: &Keypair,
+    selling_resource: &Pubkey,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let accounts = mpl_fixed_price_sale::accounts::MigrateSellingResource {
+        selling_resource: *selling_resource,
+        payer: payer.pubkey(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::MigrateSellingResource {}.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        ),
+        Box::new(MigrateSellingResourceUiInfo {
+            selling_resource: *selling_resource,
+        }),
+    ))
+}
@@ -0,0 +1,55 @@
+//! Module provide handler for `MigrateStore` command.
+
+use super::UiTransactionInfo;
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    system_program, transaction::Transaction,
+};
+
+/// Additional `MigrateStore` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct MigrateStoreUiInfo {
+    store: Pubkey,
+}
+
+impl UiTransactionInfo for MigrateStoreUiInfo {
+    fn print(&self) {
+        println!("MigrateStore::store - {}", self.store);
+    }
+}
+
+pub fn migrate_store(
+    client: &RpcClient,
+    payer: &Keypair,
+    store: &Pubkey,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let accounts = mpl_fixed_price_sale::accounts::MigrateStore {
+        store: *store,
+        payer: payer.pubkey(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::MigrateStore {}.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        ),
+        Box::new(MigrateStoreUiInfo { store: *store }),
+    ))
+}
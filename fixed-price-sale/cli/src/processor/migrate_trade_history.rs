@@ -0,0 +1,68 @@
+//! Module provide handler for `MigrateTradeHistory` command.
+
+use super::UiTransactionInfo;
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    system_program, transaction::Transaction,
+};
+
+/// Additional `MigrateTradeHistory` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct MigrateTradeHistoryUiInfo {
+    trade_history: Pubkey,
+    market: Pubkey,
+}
+
+impl UiTransactionInfo for MigrateTradeHistoryUiInfo {
+    fn print(&self) {
+        println!(
+            "MigrateTradeHistory::trade_history - {}",
+            self.trade_history
+        );
+        println!("MigrateTradeHistory::market - {}", self.market);
+    }
+}
+
+pub fn migrate_trade_history(
+    client: &RpcClient,
+    payer: &Keypair,
+    trade_history: &Pubkey,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    // Legacy account can't be deserialized, `market` is the first field after anchor discriminator
+    let trade_history_data = client.get_account_data(trade_history)?;
+    let market = Pubkey::new(&trade_history_data[8..40]);
+
+    let accounts = mpl_fixed_price_sale::accounts::MigrateTradeHistory {
+        trade_history: *trade_history,
+        market,
+        payer: payer.pubkey(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::MigrateTradeHistory {}.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        ),
+        Box::new(MigrateTradeHistoryUiInfo {
+            trade_history: *trade_history,
+            market,
+        }),
+    ))
+}
@@ -1,29 +1,73 @@
 //! Module provide instructions builder for `mpl_fixed_price_sale` program.
 
+mod add_payment_mint;
 mod buy;
+mod buy_many;
+mod buy_token;
+mod cancel_market;
 mod change_market;
+mod claim_raffle_prize;
+mod claim_raffle_refund;
+mod claim_refund;
 mod claim_resource;
 mod close_market;
+mod close_purchase_receipt;
+mod close_raffle_entries;
 mod create_market;
 mod create_store;
+mod draw_raffle;
+mod enter_raffle;
+mod expire_raffle;
 mod get_account_state;
 mod get_account_state_legacy;
+mod get_trade_histories;
+mod init_raffle;
 mod init_selling_resource;
+mod init_token_selling_resource;
+mod migrate_market;
+mod migrate_selling_resource;
+mod migrate_store;
+mod migrate_trade_history;
+mod release_market;
 mod resume_market;
+mod reveal_raffle_seed;
 mod save_primary_metadata_creators;
+mod set_platform_fee;
 mod suspend_market;
 mod withdraw;
+pub use add_payment_mint::*;
 pub use buy::*;
+pub use buy_many::*;
+pub use buy_token::*;
+pub use cancel_market::*;
 pub use change_market::*;
+pub use claim_raffle_prize::*;
+pub use claim_raffle_refund::*;
+pub use claim_refund::*;
 pub use claim_resource::*;
 pub use close_market::*;
+pub use close_purchase_receipt::*;
+pub use close_raffle_entries::*;
 pub use create_market::*;
 pub use create_store::*;
+pub use draw_raffle::*;
+pub use enter_raffle::*;
+pub use expire_raffle::*;
 pub use get_account_state::*;
 pub use get_account_state_legacy::*;
+pub use get_trade_histories::*;
+pub use init_raffle::*;
 pub use init_selling_resource::*;
+pub use init_token_selling_resource::*;
+pub use migrate_market::*;
+pub use migrate_selling_resource::*;
+pub use migrate_store::*;
+pub use migrate_trade_history::*;
+pub use release_market::*;
 pub use resume_market::*;
+pub use reveal_raffle_seed::*;
 pub use save_primary_metadata_creators::*;
+pub use set_platform_fee::*;
 pub use suspend_market::*;
 pub use withdraw::*;
 
@@ -0,0 +1,65 @@
+//! Module provide handler for `ReleaseMarket` command.
+
+use super::{get_account_state, UiTransactionInfo};
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    sysvar::clock, transaction::Transaction,
+};
+
+/// Additional `ReleaseMarket` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct ReleaseMarketUiInfo {
+    market: Pubkey,
+    selling_resource: Pubkey,
+}
+
+impl UiTransactionInfo for ReleaseMarketUiInfo {
+    fn print(&self) {
+        println!("ReleaseMarket::market - {}", self.market);
+        println!(
+            "ReleaseMarket::selling_resource - {}",
+            self.selling_resource
+        );
+    }
+}
+
+pub fn release_market(
+    client: &RpcClient,
+    payer: &Keypair,
+    market: &Pubkey,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let market_state = get_account_state::<mpl_fixed_price_sale::state::Market>(client, market)?;
+
+    let accounts = mpl_fixed_price_sale::accounts::ReleaseMarket {
+        market: *market,
+        selling_resource: market_state.selling_resource,
+        clock: clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::ReleaseMarket {}.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        ),
+        Box::new(ReleaseMarketUiInfo {
+            market: *market,
+            selling_resource: market_state.selling_resource,
+        }),
+    ))
+}
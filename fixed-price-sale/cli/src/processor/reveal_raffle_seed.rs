@@ -0,0 +1,62 @@
+//! Module provide handler for `RevealRaffleSeed` command.
+
+use super::UiTransactionInfo;
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signer,
+    signer::keypair::Keypair,
+    sysvar::{clock, slot_hashes},
+    transaction::Transaction,
+};
+
+/// Additional `RevealRaffleSeed` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct RevealRaffleSeedUiInfo {
+    market: Pubkey,
+}
+
+impl UiTransactionInfo for RevealRaffleSeedUiInfo {
+    fn print(&self) {
+        println!("RevealRaffleSeed::market - {}", self.market);
+    }
+}
+
+pub fn reveal_raffle_seed(
+    client: &RpcClient,
+    payer: &Keypair,
+    owner: &Keypair,
+    market: &Pubkey,
+    seed: &[u8; 32],
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let accounts = mpl_fixed_price_sale::accounts::RevealRaffleSeed {
+        market: *market,
+        owner: owner.pubkey(),
+        slot_hashes: slot_hashes::id(),
+        clock: clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::RevealRaffleSeed { seed: *seed }.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, owner],
+            recent_blockhash,
+        ),
+        Box::new(RevealRaffleSeedUiInfo { market: *market }),
+    ))
+}
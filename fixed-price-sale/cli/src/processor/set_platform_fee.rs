@@ -0,0 +1,57 @@
+//! Module provide handler for `SetPlatformFee` command.
+
+use super::UiTransactionInfo;
+use crate::error;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_fixed_price_sale::state::PlatformFee;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+    transaction::Transaction,
+};
+
+/// Additional `SetPlatformFee` instruction info, that need to be displayed in TUI.
+#[derive(Debug)]
+pub struct SetPlatformFeeUiInfo {
+    store: Pubkey,
+}
+
+impl UiTransactionInfo for SetPlatformFeeUiInfo {
+    fn print(&self) {
+        println!("SetPlatformFee::store - {}", self.store);
+    }
+}
+
+pub fn set_platform_fee(
+    client: &RpcClient,
+    payer: &Keypair,
+    admin_wallet: &Keypair,
+    store: &Pubkey,
+    platform_fee: Option<PlatformFee>,
+) -> Result<(Transaction, Box<dyn UiTransactionInfo>), error::Error> {
+    let accounts = mpl_fixed_price_sale::accounts::SetPlatformFee {
+        store: *store,
+        admin: admin_wallet.pubkey(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale::instruction::SetPlatformFee { platform_fee }.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    Ok((
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, admin_wallet],
+            recent_blockhash,
+        ),
+        Box::new(SetPlatformFeeUiInfo { store: *store }),
+    ))
+}
@@ -17,14 +17,17 @@ use solana_sdk::{
 /// Additional `Withdraw` instruction info, that need to be displayed in TUI.
 #[derive(Debug)]
 pub struct WithdrawUiInfo {
+    treasury_mint: Pubkey,
     primary_metadata_creators: Pubkey,
     primary_royalties_holder: Pubkey,
     payout_ticket: Pubkey,
     destination: Pubkey,
+    platform_fee_destination: Pubkey,
 }
 
 impl UiTransactionInfo for WithdrawUiInfo {
     fn print(&self) {
+        println!("Withdraw::treasury_mint - {}", self.treasury_mint);
         println!(
             "Withdraw::primary_metadata_creators - {}",
             self.primary_metadata_creators
@@ -35,6 +38,10 @@ impl UiTransactionInfo for WithdrawUiInfo {
         );
         println!("Withdraw::payout_ticket - {}", self.payout_ticket);
         println!("Withdraw::destination - {}", self.destination);
+        println!(
+            "Withdraw::platform_fee_destination - {}",
+            self.platform_fee_destination
+        );
     }
 }
 
@@ -49,12 +56,8 @@ pub fn withdraw(
         &market_state.selling_resource,
     )?;
     let resource_mint = selling_resource_state.resource;
-
-    let (treasury_owner, treasury_owner_bump) =
-        mpl_fixed_price_sale::utils::find_treasury_owner_address(
-            &market_state.treasury_mint,
-            &market_state.selling_resource,
-        );
+    let store_state =
+        get_account_state::<mpl_fixed_price_sale::state::Store>(client, &market_state.store)?;
 
     // Should be created
     let (master_edition_metadata, _) = Pubkey::find_program_address(
@@ -73,69 +76,104 @@ pub fn withdraw(
         mpl_fixed_price_sale::state::PrimaryMetadataCreators,
     >(client, &primary_metadata_creators)?;
 
+    // Primary treasury and every `Market::payment_mints` treasury with collected funds
+    let mut treasuries = vec![(market_state.treasury_mint, market_state.treasury_holder)];
+    for payment_mint in &market_state.payment_mints {
+        if payment_mint.funds_collected > 0 {
+            treasuries.push((payment_mint.mint, payment_mint.treasury_holder));
+        }
+    }
+
     let mut data_bundle: Vec<(Transaction, Box<dyn UiTransactionInfo>)> = Vec::new();
-    for c in primary_metadata_creators_state.creators {
-        let primary_royalties_holder = c.address;
+    for (treasury_mint, treasury_holder) in treasuries {
+        let (treasury_owner, treasury_owner_bump) =
+            mpl_fixed_price_sale::utils::find_treasury_owner_address(
+                &treasury_mint,
+                &market_state.selling_resource,
+            );
 
-        let destination = spl_associated_token_account::get_associated_token_address(
-            &primary_royalties_holder,
-            &market_state.treasury_mint,
-        );
+        for c in &primary_metadata_creators_state.creators {
+            let primary_royalties_holder = c.address;
 
-        let (payout_ticket, payout_ticket_bump) =
-            mpl_fixed_price_sale::utils::find_payout_ticket_address(
-                &market,
+            let destination = spl_associated_token_account::get_associated_token_address(
                 &primary_royalties_holder,
+                &treasury_mint,
             );
 
-        let mut accounts = mpl_fixed_price_sale::accounts::Withdraw {
-            market: *market,
-            selling_resource: market_state.selling_resource,
-            treasury_holder: market_state.treasury_holder,
-            metadata: master_edition_metadata,
-            treasury_mint: market_state.treasury_mint,
-            owner: treasury_owner,
-            funder: primary_royalties_holder,
-            payer: payer.pubkey(),
-            payout_ticket,
-            destination,
-            clock: clock::id(),
-            rent: rent::id(),
-            associated_token_program: spl_associated_token_account::id(),
-            token_program: spl_token::id(),
-            system_program: system_program::id(),
-        }
-        .to_account_metas(None);
-        accounts.push(AccountMeta::new(primary_metadata_creators, false));
-
-        let data = mpl_fixed_price_sale::instruction::Withdraw {
-            treasury_owner_bump,
-            payout_ticket_bump,
-        }
-        .data();
-
-        let instruction = Instruction {
-            program_id: mpl_fixed_price_sale::id(),
-            data,
-            accounts,
-        };
-
-        let recent_blockhash = client.get_latest_blockhash()?;
-
-        data_bundle.push((
-            Transaction::new_signed_with_payer(
-                &[instruction],
-                Some(&payer.pubkey()),
-                &[payer],
-                recent_blockhash,
-            ),
-            Box::new(WithdrawUiInfo {
-                primary_metadata_creators,
-                destination,
-                primary_royalties_holder,
+            // Same as `destination` if store doesn't charge platform fee, program ignores it then
+            let platform_fee_destination = if let Some(platform_fee) = &store_state.platform_fee {
+                if treasury_mint == system_program::id() {
+                    platform_fee.recipient
+                } else {
+                    spl_associated_token_account::get_associated_token_address(
+                        &platform_fee.recipient,
+                        &treasury_mint,
+                    )
+                }
+            } else {
+                destination
+            };
+
+            let (payout_ticket, payout_ticket_bump) =
+                mpl_fixed_price_sale::utils::find_payout_ticket_address(
+                    &market,
+                    &primary_royalties_holder,
+                    &treasury_mint,
+                );
+
+            let mut accounts = mpl_fixed_price_sale::accounts::Withdraw {
+                market: *market,
+                store: market_state.store,
+                selling_resource: market_state.selling_resource,
+                treasury_holder,
+                metadata: master_edition_metadata,
+                treasury_mint,
+                owner: treasury_owner,
+                funder: primary_royalties_holder,
+                payer: payer.pubkey(),
                 payout_ticket,
-            }),
-        ));
+                destination,
+                platform_fee_destination,
+                clock: clock::id(),
+                rent: rent::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                token_program: spl_token::id(),
+                system_program: system_program::id(),
+            }
+            .to_account_metas(None);
+            accounts.push(AccountMeta::new(primary_metadata_creators, false));
+
+            let data = mpl_fixed_price_sale::instruction::Withdraw {
+                treasury_owner_bump,
+                payout_ticket_bump,
+            }
+            .data();
+
+            let instruction = Instruction {
+                program_id: mpl_fixed_price_sale::id(),
+                data,
+                accounts,
+            };
+
+            let recent_blockhash = client.get_latest_blockhash()?;
+
+            data_bundle.push((
+                Transaction::new_signed_with_payer(
+                    &[instruction],
+                    Some(&payer.pubkey()),
+                    &[payer],
+                    recent_blockhash,
+                ),
+                Box::new(WithdrawUiInfo {
+                    treasury_mint,
+                    primary_metadata_creators,
+                    destination,
+                    platform_fee_destination,
+                    primary_royalties_holder,
+                    payout_ticket,
+                }),
+            ));
+        }
     }
 
     Ok(data_bundle)
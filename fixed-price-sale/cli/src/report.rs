@@ -0,0 +1,187 @@
+//! Module provide sales report of a market.
+
+use crate::cli_args::ReportFormat;
+use mpl_fixed_price_sale::state::{Market, SellingResource, TradeHistory};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+/// Wallet which bought at least one piece on a market.
+#[derive(Debug)]
+pub struct Buyer {
+    pub wallet: Pubkey,
+    pub trade_history: Pubkey,
+    pub pieces_bought: u64,
+    /// mint wallet paid in
+    pub currency: Pubkey,
+    pub funds_spent: f64,
+}
+
+/// Funds collected in one currency of a market.
+#[derive(Debug)]
+pub struct CurrencyTotal {
+    pub mint: Pubkey,
+    pub funds_collected: f64,
+}
+
+/// Totals and buyers of a market.
+#[derive(Debug)]
+pub struct MarketReport {
+    pub market: Pubkey,
+    pub treasury_mint: Pubkey,
+    /// `treasury_mint` first, then every payment mint
+    pub totals: Vec<CurrencyTotal>,
+    pub unique_wallets: usize,
+    pub pieces_sold: u64,
+    pub supply: u64,
+    pub max_supply: Option<u64>,
+    pub buyers: Vec<Buyer>,
+}
+
+impl MarketReport {
+    pub fn new(
+        market_address: &Pubkey,
+        market: &Market,
+        selling_resource: &SellingResource,
+        decimals: u8,
+        trade_histories: Vec<(Pubkey, TradeHistory)>,
+    ) -> Self {
+        // `funds_collected` is kept by the program, price may change after some sales
+        let currencies: Vec<(Pubkey, u8, u64)> =
+            std::iter::once((market.treasury_mint, decimals, market.funds_collected))
+                .chain(market.payment_mints.iter().map(|payment_mint| {
+                    (
+                        payment_mint.mint,
+                        payment_mint.decimals,
+                        payment_mint.funds_collected,
+                    )
+                }))
+                .collect();
+        let decimals_of = |mint: &Pubkey| {
+            currencies
+                .iter()
+                .find(|(currency, _, _)| currency == mint)
+                .map_or(decimals, |(_, decimals, _)| *decimals)
+        };
+
+        let mut buyers: Vec<Buyer> = trade_histories
+            .into_iter()
+            .filter(|(_, trade_history)| trade_history.already_bought > 0)
+            .map(|(address, trade_history)| Buyer {
+                wallet: trade_history.wallet,
+                trade_history: address,
+                pieces_bought: trade_history.already_bought,
+                currency: trade_history.currency,
+                funds_spent: spl_token::amount_to_ui_amount(
+                    trade_history.funds_spent,
+                    decimals_of(&trade_history.currency),
+                ),
+            })
+            .collect();
+        buyers.sort_by(|a, b| b.pieces_bought.cmp(&a.pieces_bought));
+
+        Self {
+            market: *market_address,
+            treasury_mint: market.treasury_mint,
+            totals: currencies
+                .iter()
+                .map(|(mint, decimals, funds_collected)| CurrencyTotal {
+                    mint: *mint,
+                    funds_collected: spl_token::amount_to_ui_amount(*funds_collected, *decimals),
+                })
+                .collect(),
+            unique_wallets: buyers.len(),
+            pieces_sold: buyers.iter().map(|buyer| buyer.pieces_bought).sum(),
+            supply: selling_resource.supply,
+            max_supply: selling_resource.max_supply,
+            buyers,
+        }
+    }
+
+    /// Print totals in TUI.
+    pub fn print(&self) {
+        println!("MarketReport::market - {}", self.market);
+        println!("MarketReport::treasury_mint - {}", self.treasury_mint);
+        for total in &self.totals {
+            println!(
+                "MarketReport::funds_collected - {} {}",
+                total.funds_collected, total.mint
+            );
+        }
+        println!("MarketReport::unique_wallets - {}", self.unique_wallets);
+        println!("MarketReport::pieces_sold - {}", self.pieces_sold);
+        println!("MarketReport::supply - {}", self.supply);
+        println!(
+            "MarketReport::max_supply - {}",
+            if let Some(x) = self.max_supply {
+                x.to_string()
+            } else {
+                String::from("<unlimited>")
+            }
+        );
+    }
+
+    /// Buyer list in `format`.
+    pub fn export(&self, format: &ReportFormat) -> String {
+        match format {
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Json => self.to_json(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("wallet,trade_history,pieces_bought,currency,funds_spent\n");
+
+        for buyer in &self.buyers {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                buyer.wallet,
+                buyer.trade_history,
+                buyer.pieces_bought,
+                buyer.currency,
+                buyer.funds_spent
+            ));
+        }
+
+        csv
+    }
+
+    fn to_json(&self) -> String {
+        let buyers: Vec<serde_json::Value> = self
+            .buyers
+            .iter()
+            .map(|buyer| {
+                json!({
+                    "wallet": buyer.wallet.to_string(),
+                    "trade_history": buyer.trade_history.to_string(),
+                    "pieces_bought": buyer.pieces_bought,
+                    "currency": buyer.currency.to_string(),
+                    "funds_spent": buyer.funds_spent,
+                })
+            })
+            .collect();
+
+        let totals: Vec<serde_json::Value> = self
+            .totals
+            .iter()
+            .map(|total| {
+                json!({
+                    "mint": total.mint.to_string(),
+                    "funds_collected": total.funds_collected,
+                })
+            })
+            .collect();
+
+        let report = json!({
+            "market": self.market.to_string(),
+            "treasury_mint": self.treasury_mint.to_string(),
+            "totals": totals,
+            "unique_wallets": self.unique_wallets,
+            "pieces_sold": self.pieces_sold,
+            "supply": self.supply,
+            "max_supply": self.max_supply,
+            "buyers": buyers,
+        });
+
+        serde_json::to_string_pretty(&report).unwrap()
+    }
+}
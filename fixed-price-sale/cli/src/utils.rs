@@ -3,8 +3,26 @@
 #![allow(unused)]
 
 use crate::error;
-use solana_client::rpc_client::RpcClient;
+use anchor_lang::Discriminator;
+use mpl_fixed_price_sale::{
+    state::{
+        AllowlistConfig, AllowlistProof, Market, PaymentPrice, PriceSchedule, PriceScheduleKind,
+        PriceTier, RaffleTicket, TradeHistory,
+    },
+    utils::{
+        allowlist_leaf, allowlist_node, find_purchase_receipt_address, find_trade_history_address,
+        PRICE_FEED_EXPONENT_OFFSET, PRICE_FEED_LEN,
+    },
+};
+use serde::Deserialize;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+};
 use solana_sdk::{
+    borsh::try_from_slice_unchecked,
+    instruction::{AccountMeta, Instruction},
     program_pack::Pack,
     pubkey::Pubkey,
     signer::{keypair::Keypair, Signer},
@@ -12,6 +30,48 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use spl_token::state::Mint;
+use std::{fs::File, io::BufReader, path::Path, str::FromStr};
+
+/// `PriceSchedule` as written in `.json` and `.toml` files, prices in UI amount.
+#[derive(Debug, Deserialize)]
+pub struct PriceScheduleConfig {
+    /// `by_supply` or `by_time`
+    pub kind: String,
+    pub tiers: Vec<PriceTierConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PriceTierConfig {
+    pub until: u64,
+    pub price: f64,
+}
+
+impl PriceScheduleConfig {
+    /// Convert to `PriceSchedule` with prices in `decimals` of the treasury mint.
+    pub fn to_price_schedule(&self, decimals: u8) -> Result<PriceSchedule, error::Error> {
+        let kind = match self.kind.as_str() {
+            "by_supply" => PriceScheduleKind::BySupply,
+            "by_time" => PriceScheduleKind::ByTime,
+            kind => {
+                return Err(error::Error::DynamicError(format!(
+                    "Unknown price schedule kind {}",
+                    kind
+                )))
+            }
+        };
+
+        let tiers = self
+            .tiers
+            .iter()
+            .map(|tier| PriceTier {
+                until: tier.until,
+                price: spl_token::ui_amount_to_amount(tier.price, decimals),
+            })
+            .collect();
+
+        Ok(PriceSchedule { kind, tiers })
+    }
+}
 
 /// Return `Mint` account state from `spl_token` program.
 pub fn get_mint(client: &RpcClient, mint: &Pubkey) -> Result<Mint, error::Error> {
@@ -19,6 +79,28 @@ pub fn get_mint(client: &RpcClient, mint: &Pubkey) -> Result<Mint, error::Error>
     Ok(Mint::unpack(&data)?)
 }
 
+/// Return instructions to create token `Account` from `spl_token` program.
+pub fn create_token_account_instructions(
+    client: &RpcClient,
+    payer: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Vec<Instruction>, error::Error> {
+    let lamports = client.get_minimum_balance_for_rent_exemption(spl_token::state::Account::LEN)?;
+
+    Ok(vec![
+        system_instruction::create_account(
+            payer,
+            account,
+            lamports,
+            spl_token::state::Account::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account(&spl_token::id(), account, mint, owner)?,
+    ])
+}
+
 /// Create token `Account` from `spl_token` program.
 pub fn create_token_account(
     client: &RpcClient,
@@ -28,25 +110,15 @@ pub fn create_token_account(
     owner: &Pubkey,
 ) -> Result<(), error::Error> {
     let recent_blockhash = client.get_latest_blockhash()?;
-    let lamports = client.get_minimum_balance_for_rent_exemption(spl_token::state::Account::LEN)?;
 
     let tx = Transaction::new_signed_with_payer(
-        &[
-            system_instruction::create_account(
-                &payer.pubkey(),
-                &account.pubkey(),
-                lamports,
-                spl_token::state::Account::LEN as u64,
-                &spl_token::id(),
-            ),
-            spl_token::instruction::initialize_account(
-                &spl_token::id(),
-                &account.pubkey(),
-                mint,
-                owner,
-            )
-            .unwrap(),
-        ],
+        &create_token_account_instructions(
+            client,
+            &payer.pubkey(),
+            &account.pubkey(),
+            mint,
+            owner,
+        )?,
         Some(&payer.pubkey()),
         &[payer, &account],
         recent_blockhash,
@@ -95,6 +167,25 @@ pub fn create_mint(
     Ok(())
 }
 
+/// Return exponent of Pyth price feed, prices of the feed are scaled by it.
+pub fn get_price_feed_exponent(
+    client: &RpcClient,
+    price_feed: &Pubkey,
+) -> Result<i32, error::Error> {
+    let data = client.get_account_data(price_feed)?;
+    if data.len() < PRICE_FEED_LEN {
+        return Err(error::Error::DynamicError(format!(
+            "Account {} is not a price feed",
+            price_feed
+        )));
+    }
+
+    let mut exponent = [0u8; 4];
+    exponent.copy_from_slice(&data[PRICE_FEED_EXPONENT_OFFSET..PRICE_FEED_EXPONENT_OFFSET + 4]);
+
+    Ok(i32::from_le_bytes(exponent))
+}
+
 /// Check if `account` is empty.
 pub fn is_account_empty(client: &RpcClient, account: &Pubkey) -> Result<bool, error::Error> {
     let account = client.get_account(account)?;
@@ -102,6 +193,15 @@ pub fn is_account_empty(client: &RpcClient, account: &Pubkey) -> Result<bool, er
     Ok(account.data.is_empty())
 }
 
+/// Check if `account` exists.
+pub fn account_exists(client: &RpcClient, account: &Pubkey) -> Result<bool, error::Error> {
+    let account = client
+        .get_account_with_commitment(account, client.commitment())?
+        .value;
+
+    Ok(account.is_some())
+}
+
 /// Mint new tokens.
 pub fn mint_to(
     client: &RpcClient,
@@ -132,6 +232,237 @@ pub fn mint_to(
     Ok(())
 }
 
+/// Read `PriceSchedule` from `.json` file.
+pub fn read_price_schedule(path: &str, decimals: u8) -> Result<PriceSchedule, error::Error> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let config: PriceScheduleConfig = serde_json::from_reader(reader).unwrap();
+
+    config.to_price_schedule(decimals)
+}
+
+/// Allowlist `.json` file, each wallet with the total pieces it may buy.
+#[derive(Debug, Deserialize)]
+pub struct AllowlistFile {
+    pub gating_time: Option<u64>,
+    pub wallets: Vec<AllowlistEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AllowlistEntry {
+    pub wallet: String,
+    pub allocation: u64,
+}
+
+impl AllowlistFile {
+    /// Return merkle tree layers, from leaves to root.
+    /// Last node of a layer with odd length is moved to the next layer as is.
+    fn layers(&self) -> Result<Vec<Vec<[u8; 32]>>, error::Error> {
+        if self.wallets.is_empty() {
+            return Err(error::Error::DynamicError(String::from(
+                "Allowlist has no wallets",
+            )));
+        }
+
+        let leaves = self
+            .wallets
+            .iter()
+            .map(|entry| {
+                Ok(allowlist_leaf(
+                    &Pubkey::from_str(&entry.wallet)?,
+                    entry.allocation,
+                ))
+            })
+            .collect::<Result<Vec<_>, error::Error>>()?;
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| {
+                    if pair.len() == 2 {
+                        allowlist_node(&pair[0], &pair[1])
+                    } else {
+                        pair[0]
+                    }
+                })
+                .collect();
+
+            layers.push(next);
+        }
+
+        Ok(layers)
+    }
+
+    pub fn to_allowlist_config(&self) -> Result<AllowlistConfig, error::Error> {
+        Ok(AllowlistConfig {
+            merkle_root: self.layers()?.last().unwrap()[0],
+            gating_time: self.gating_time,
+        })
+    }
+
+    /// Return allocation of `wallet` with its merkle proof.
+    pub fn to_allowlist_proof(&self, wallet: &Pubkey) -> Result<AllowlistProof, error::Error> {
+        let wallet = wallet.to_string();
+        let (mut index, entry) = self
+            .wallets
+            .iter()
+            .enumerate()
+            .find(|(_, entry)| entry.wallet == wallet)
+            .ok_or_else(|| {
+                error::Error::DynamicError(format!("Wallet {} is not in allowlist", wallet))
+            })?;
+
+        let layers = self.layers()?;
+        let mut proof = Vec::new();
+        for layer in &layers[..layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+
+            index /= 2;
+        }
+
+        Ok(AllowlistProof {
+            allocation: entry.allocation,
+            proof,
+        })
+    }
+}
+
+/// Read `AllowlistFile` from `.json` file.
+pub fn read_allowlist(path: &str) -> Result<AllowlistFile, error::Error> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+
+    Ok(serde_json::from_reader(reader).unwrap())
+}
+
+/// Return treasury holder to pay into and leading remaining accounts of buy instructions.
+/// `Market::treasury_mint` is used if `payment_mint` is `None`,
+/// price feed is passed first for `PaymentPrice::Oracle`.
+pub fn payment_accounts(
+    market: &Market,
+    payment_mint: Option<&Pubkey>,
+) -> Result<(Pubkey, Vec<AccountMeta>), error::Error> {
+    let payment_mint = match payment_mint {
+        Some(payment_mint) if *payment_mint != market.treasury_mint => payment_mint,
+        _ => return Ok((market.treasury_holder, Vec::new())),
+    };
+
+    let payment_mint = market
+        .payment_mints
+        .iter()
+        .find(|accepted| accepted.mint == *payment_mint)
+        .ok_or_else(|| {
+            error::Error::DynamicError(format!("Mint {} is not accepted by market", payment_mint))
+        })?;
+
+    let remaining_accounts = match payment_mint.price {
+        PaymentPrice::Fixed { .. } => Vec::new(),
+        PaymentPrice::Oracle { price_feed, .. } => {
+            vec![AccountMeta::new_readonly(price_feed, false)]
+        }
+    };
+
+    Ok((payment_mint.treasury_holder, remaining_accounts))
+}
+
+/// Return `PurchaseReceipt` of the next purchase of `buyer`, passed first to buy instructions.
+pub fn purchase_receipt_account(
+    client: &RpcClient,
+    market: &Pubkey,
+    buyer: &Pubkey,
+) -> Result<AccountMeta, error::Error> {
+    let (trade_history, _) = find_trade_history_address(buyer, market);
+
+    let purchase_index = if account_exists(client, &trade_history)? {
+        crate::processor::get_account_state::<TradeHistory>(client, &trade_history)?.already_bought
+    } else {
+        0
+    };
+
+    let (purchase_receipt, _) = find_purchase_receipt_address(market, buyer, purchase_index);
+
+    Ok(AccountMeta::new(purchase_receipt, false))
+}
+
+/// Read raffle seed, `.json` array of 32 bytes, new random seed is written if file doesn't exist.
+pub fn read_or_create_raffle_seed(path: &str) -> Result<[u8; 32], error::Error> {
+    if !Path::new(path).exists() {
+        // Secret key of a fresh keypair is a good enough source of randomness
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&Keypair::new().to_bytes()[..32]);
+
+        let file = File::create(path)?;
+        serde_json::to_writer(file, &seed.to_vec()).unwrap();
+
+        return Ok(seed);
+    }
+
+    read_raffle_seed(path)
+}
+
+/// Read raffle seed, `.json` array of 32 bytes.
+pub fn read_raffle_seed(path: &str) -> Result<[u8; 32], error::Error> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let seed: Vec<u8> = serde_json::from_reader(reader).unwrap();
+
+    if seed.len() != 32 {
+        return Err(error::Error::DynamicError(format!(
+            "Raffle seed in {} should be 32 bytes long",
+            path
+        )));
+    }
+
+    let mut raffle_seed = [0u8; 32];
+    raffle_seed.copy_from_slice(&seed);
+
+    Ok(raffle_seed)
+}
+
+/// Return `RaffleTicket` of `wallet` entry into `market` with its address.
+pub fn find_raffle_ticket(
+    client: &RpcClient,
+    market: &Pubkey,
+    wallet: &Pubkey,
+) -> Result<(Pubkey, RaffleTicket), error::Error> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize(RaffleTicket::LEN as u64),
+            RpcFilterType::Memcmp(Memcmp {
+                offset: 8,
+                bytes: MemcmpEncodedBytes::Base58(market.to_string()),
+                encoding: None,
+            }),
+            RpcFilterType::Memcmp(Memcmp {
+                offset: 40,
+                bytes: MemcmpEncodedBytes::Base58(wallet.to_string()),
+                encoding: None,
+            }),
+        ]),
+        account_config: RpcAccountInfoConfig::default(),
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    for (address, account) in
+        client.get_program_accounts_with_config(&mpl_fixed_price_sale::id(), config)?
+    {
+        // First 8-bytes filled with sha256 hash by anchor
+        if account.data[..8] == RaffleTicket::discriminator() {
+            return Ok((address, try_from_slice_unchecked(&account.data[8..])?));
+        }
+    }
+
+    Err(error::Error::DynamicError(format!(
+        "Wallet {} didn't enter raffle of market {}",
+        wallet, market
+    )))
+}
+
 /// Return `Clone`'d `Keypair`.
 pub fn clone_keypair(keypair: &Keypair) -> Keypair {
     Keypair::from_bytes(&keypair.to_bytes()).unwrap()
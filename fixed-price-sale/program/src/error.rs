@@ -133,4 +133,162 @@ pub enum ErrorCode {
     // 6042
     #[msg("Wrong collection mint key")]
     WrongCollectionMintKey,
+    // 6043
+    #[msg("Collection royalty record is not provided")]
+    CollectionRoyaltyRecordNotProvided,
+    // 6044
+    #[msg("Price schedule is empty or has more tiers than allowed")]
+    PriceScheduleInvalidLength,
+    // 6045
+    #[msg("Price schedule tiers should be sorted and have non-zero prices")]
+    PriceScheduleInvalidTier,
+    // 6046
+    #[msg("Allowlist proof is missing")]
+    AllowlistProofMissing,
+    // 6047
+    #[msg("Allowlist proof is invalid")]
+    AllowlistProofInvalid,
+    // 6048
+    #[msg("User reach allowlist allocation")]
+    AllowlistAllocationReached,
+    // 6049
+    #[msg("Market is cancelled")]
+    MarketIsCancelled,
+    // 6050
+    #[msg("Market is not cancelled")]
+    MarketIsNotCancelled,
+    // 6051
+    #[msg("Nothing to refund, editions should be passed in remaining accounts")]
+    NothingToRefund,
+    // 6052
+    #[msg("Edition is not printed from the selling resource to the wallet in the market")]
+    WrongRefundEdition,
+    // 6053
+    #[msg("Invalid refund destination")]
+    InvalidRefundDestination,
+    // 6054
+    #[msg("Buy quantity should be greater than zero")]
+    BuyQuantityIsZero,
+    // 6055
+    #[msg("Accounts of new editions are missing")]
+    NewEditionAccountsMissing,
+    // 6056
+    #[msg("Selling resource kind doesn't support this instruction")]
+    WrongSellingResourceKind,
+    // 6057
+    #[msg("Piece size and amount of pieces should be greater than zero")]
+    PieceSizeIsZero,
+    // 6058
+    #[msg("Platform fee basis points should be less or equal to 10000")]
+    PlatformFeeIsTooBig,
+    // 6059
+    #[msg("Invalid platform fee destination")]
+    InvalidPlatformFeeDestination,
+    // 6060
+    #[msg("Market accepts max amount of payment mints")]
+    PaymentMintsLimitReached,
+    // 6061
+    #[msg("Mint is already accepted by market")]
+    PaymentMintIsAlreadyAccepted,
+    // 6062
+    #[msg("Mint is not accepted by market")]
+    PaymentMintIsNotAccepted,
+    // 6063
+    #[msg("Oracle quote price should be positive and confidence no more than 10000 bps")]
+    InvalidPaymentPrice,
+    // 6064
+    #[msg("Price feed account is missing")]
+    PriceFeedMissing,
+    // 6065
+    #[msg("Price feed account is not a trading price")]
+    PriceFeedInvalid,
+    // 6066
+    #[msg("Price feed is stale")]
+    PriceFeedIsStale,
+    // 6067
+    #[msg("Price feed confidence interval is too wide")]
+    PriceFeedConfidenceIsTooWide,
+    // 6068
+    #[msg("Wallet already paid in another currency")]
+    CurrencyMismatch,
+    // 6069
+    #[msg("Treasury holders of market payment mints are missing")]
+    PaymentMintTreasuryMissing,
+    // 6070
+    #[msg("Market is a raffle, pieces can't be bought")]
+    MarketIsRaffle,
+    // 6071
+    #[msg("Market is not a raffle")]
+    MarketIsNotRaffle,
+    // 6072
+    #[msg("Raffle should have winners, fixed non-zero price and market should have end date")]
+    InvalidRaffleConfig,
+    // 6073
+    #[msg("Wallet already entered raffle")]
+    RaffleEntryExists,
+    // 6074
+    #[msg("Raffle entries are not closed yet")]
+    RaffleEntriesAreOpen,
+    // 6075
+    #[msg("Raffle seed is already revealed")]
+    RaffleSeedIsRevealed,
+    // 6076
+    #[msg("Raffle seed doesn't match commitment")]
+    RaffleSeedMismatch,
+    // 6077
+    #[msg("Raffle is not drawn yet")]
+    RaffleIsNotDrawn,
+    // 6078
+    #[msg("Raffle ticket is invalid or out of draw order")]
+    RaffleTicketInvalid,
+    // 6079
+    #[msg("Raffle ticket can't be claimed")]
+    RaffleTicketIsNotClaimable,
+    // 6080
+    #[msg("Raffle prizes are not claimed yet")]
+    RafflePrizesAreNotClaimed,
+
+    // 6081
+    #[msg("Market allocation is greater than supply available to allocate")]
+    AllocationIsGtThanAvailable,
+
+    // 6082
+    #[msg("Market allocation is sold out")]
+    AllocationIsSoldOut,
+
+    // 6083
+    #[msg("Market is already released")]
+    MarketIsReleased,
+
+    // 6084
+    #[msg("Selling resource is used by other markets")]
+    SellingResourceIsInUse,
+
+    // 6085
+    #[msg("Account is already migrated to the current layout")]
+    AccountIsMigrated,
+
+    // 6086
+    #[msg("Price feed exponent doesn't match the one of oracle quote price")]
+    PriceFeedExponentMismatch,
+
+    // 6087
+    #[msg("Raffle entries are already closed")]
+    RaffleEntriesAreClosed,
+
+    // 6088
+    #[msg("Raffle reveal slot hash is not available yet")]
+    RaffleRevealIsEarly,
+
+    // 6089
+    #[msg("Raffle reveal period is expired")]
+    RaffleRevealIsExpired,
+
+    // 6090
+    #[msg("Raffle reveal period is not expired yet")]
+    RaffleRevealIsNotExpired,
+
+    // 6091
+    #[msg("Price schedule is in treasury mint, market can't accept payment mints with it")]
+    PriceScheduleWithPaymentMints,
 }
@@ -0,0 +1,49 @@
+//! Events emitted for indexers, so sales can be tracked without diffing accounts.
+
+use crate::state::MarketState;
+use anchor_lang::prelude::*;
+
+/// Emitted for every bought piece, per edition for `BuyMany`.
+#[event]
+pub struct PurchaseEvent {
+    pub market: Pubkey,
+    pub buyer: Pubkey,
+    /// new edition mint, `SellingResource::resource` for `BuyToken`
+    pub mint: Pubkey,
+    pub quantity: u64,
+    /// total amount paid in `currency`
+    pub price: u64,
+    pub currency: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when funds of a currency are paid out to a funder.
+#[event]
+pub struct WithdrawEvent {
+    pub market: Pubkey,
+    pub funder: Pubkey,
+    pub currency: Pubkey,
+    /// amount transferred to the funder
+    pub amount: u64,
+    /// amount transferred to `PlatformFee::recipient`
+    pub platform_fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when unsold resource is claimed back from the vault.
+#[event]
+pub struct ClaimResourceEvent {
+    pub market: Pubkey,
+    pub resource: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted on every `Market::state` change.
+#[event]
+pub struct MarketStateEvent {
+    pub market: Pubkey,
+    pub state: MarketState,
+    pub timestamp: i64,
+}
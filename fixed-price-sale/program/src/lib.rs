@@ -1,11 +1,16 @@
 pub mod error;
+pub mod events;
 pub mod processor;
 pub mod state;
 pub mod utils;
 
 use crate::{
     error::ErrorCode,
-    state::{GatingConfig, Market, PrimaryMetadataCreators, SellingResource, Store, TradeHistory},
+    state::{
+        AllowlistConfig, AllowlistProof, GatingConfig, Market, PaymentPrice, PlatformFee,
+        PriceSchedule, PrimaryMetadataCreators, PurchaseReceipt, RaffleTicket, SellingResource,
+        Store, TradeHistory,
+    },
     utils::*,
 };
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize, System};
@@ -30,22 +35,66 @@ pub mod fixed_price_sale {
             .process(master_edition_bump, vault_owner_bump, max_supply)
     }
 
+    pub fn init_token_selling_resource<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitTokenSellingResource<'info>>,
+        vault_owner_bump: u8,
+        piece_size: u64,
+        pieces: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(vault_owner_bump, piece_size, pieces)
+    }
+
     pub fn create_store<'info>(
         ctx: Context<'_, '_, '_, 'info, CreateStore<'info>>,
         name: String,
         description: String,
+        platform_fee: Option<PlatformFee>,
     ) -> Result<()> {
-        ctx.accounts.process(name, description)
+        ctx.accounts.process(name, description, platform_fee)
     }
 
     pub fn buy<'info>(
         ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
         _trade_history_bump: u8,
         vault_owner_bump: u8,
+        allowlist_proof: Option<AllowlistProof>,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            _trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn buy_many<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyMany<'info>>,
+        _trade_history_bump: u8,
+        vault_owner_bump: u8,
+        quantity: u64,
+        allowlist_proof: Option<AllowlistProof>,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            _trade_history_bump,
+            vault_owner_bump,
+            quantity,
+            allowlist_proof,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn buy_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyToken<'info>>,
+        _trade_history_bump: u8,
+        vault_owner_bump: u8,
+        quantity: u64,
+        allowlist_proof: Option<AllowlistProof>,
     ) -> Result<()> {
         ctx.accounts.process(
             _trade_history_bump,
             vault_owner_bump,
+            quantity,
+            allowlist_proof,
             ctx.remaining_accounts,
         )
     }
@@ -67,6 +116,7 @@ pub mod fixed_price_sale {
         mutable: Option<bool>,
         new_price: Option<u64>,
         new_pieces_in_one_wallet: Option<u64>,
+        new_price_schedule: Option<Option<PriceSchedule>>,
     ) -> Result<()> {
         ctx.accounts.process(
             new_name,
@@ -74,6 +124,7 @@ pub mod fixed_price_sale {
             mutable,
             new_price,
             new_pieces_in_one_wallet,
+            new_price_schedule,
         )
     }
 
@@ -106,6 +157,9 @@ pub mod fixed_price_sale {
         start_date: u64,
         end_date: Option<u64>,
         gating_config: Option<GatingConfig>,
+        price_schedule: Option<PriceSchedule>,
+        allowlist: Option<AllowlistConfig>,
+        allocation: Option<u64>,
     ) -> Result<()> {
         ctx.accounts.process(
             _treasury_owner_bump,
@@ -117,15 +171,112 @@ pub mod fixed_price_sale {
             start_date,
             end_date,
             gating_config,
+            price_schedule,
+            allowlist,
+            allocation,
             ctx.remaining_accounts,
         )
     }
 
+    pub fn add_payment_mint<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddPaymentMint<'info>>,
+        _treasury_owner_bump: u8,
+        price: PaymentPrice,
+    ) -> Result<()> {
+        ctx.accounts.process(_treasury_owner_bump, price)
+    }
+
     pub fn claim_resource<'info>(
         ctx: Context<'_, '_, '_, 'info, ClaimResource<'info>>,
         vault_owner_bump: u8,
     ) -> Result<()> {
-        ctx.accounts.process(vault_owner_bump)
+        ctx.accounts
+            .process(vault_owner_bump, ctx.remaining_accounts)
+    }
+
+    pub fn release_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReleaseMarket<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn cancel_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelMarket<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn claim_refund<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRefund<'info>>,
+        _trade_history_bump: u8,
+        treasury_owner_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            _trade_history_bump,
+            treasury_owner_bump,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn close_purchase_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClosePurchaseReceipt<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn init_raffle<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitRaffle<'info>>,
+        seed_commitment: [u8; 32],
+        winners: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(seed_commitment, winners)
+    }
+
+    pub fn enter_raffle<'info>(
+        ctx: Context<'_, '_, '_, 'info, EnterRaffle<'info>>,
+        _trade_history_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(_trade_history_bump)
+    }
+
+    pub fn close_raffle_entries<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseRaffleEntries<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn reveal_raffle_seed<'info>(
+        ctx: Context<'_, '_, '_, 'info, RevealRaffleSeed<'info>>,
+        seed: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.process(seed)
+    }
+
+    pub fn draw_raffle<'info>(ctx: Context<'_, '_, '_, 'info, DrawRaffle<'info>>) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn expire_raffle<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExpireRaffle<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn claim_raffle_prize<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRafflePrize<'info>>,
+        _trade_history_bump: u8,
+        vault_owner_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(_trade_history_bump, vault_owner_bump)
+    }
+
+    pub fn claim_raffle_refund<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRaffleRefund<'info>>,
+        _trade_history_bump: u8,
+        treasury_owner_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(_trade_history_bump, treasury_owner_bump)
     }
 
     pub fn save_primary_metadata_creators<'info>(
@@ -136,10 +287,41 @@ pub mod fixed_price_sale {
         ctx.accounts
             .process(primary_metadata_creators_bump, creators)
     }
+
+    pub fn migrate_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateMarket<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn migrate_selling_resource<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateSellingResource<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn migrate_store<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateStore<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn migrate_trade_history<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateTradeHistory<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn set_platform_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetPlatformFee<'info>>,
+        platform_fee: Option<PlatformFee>,
+    ) -> Result<()> {
+        ctx.accounts.process(platform_fee)
+    }
 }
 
 #[derive(Accounts)]
-#[instruction(name: String, description: String)]
+#[instruction(name: String, description: String, platform_fee: Option<PlatformFee>)]
 pub struct CreateStore<'info> {
     #[account(mut)]
     admin: Signer<'info>,
@@ -174,8 +356,34 @@ pub struct InitSellingResource<'info> {
     system_program: Program<'info, System>,
 }
 
+// Sells `pieces * piece_size` tokens of a single `resource_mint`, it needs token metadata whose
+// creators are paid on `Withdraw`
+#[derive(Accounts)]
+#[instruction(vault_owner_bump: u8, piece_size: u64, pieces: u64)]
+pub struct InitTokenSellingResource<'info> {
+    #[account(has_one=admin)]
+    store: Box<Account<'info, Store>>,
+    #[account(mut)]
+    admin: Signer<'info>,
+    #[account(init, payer=admin, space=SellingResource::LEN)]
+    selling_resource: Box<Account<'info, SellingResource>>,
+    selling_resource_owner: UncheckedAccount<'info>,
+    resource_mint: Box<Account<'info, Mint>>,
+    #[account(owner=mpl_token_metadata::id())]
+    metadata: UncheckedAccount<'info>,
+    #[account(mut, has_one=owner, constraint = vault.mint == resource_mint.key())]
+    vault: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), resource_mint.key().as_ref(), store.key().as_ref()], bump=vault_owner_bump)]
+    owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    resource_token: UncheckedAccount<'info>,
+    rent: Sysvar<'info, Rent>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
-#[instruction(treasury_owner_bump: u8, name: String, description: String, mutable: bool, price: u64, pieces_in_one_wallet: Option<u64>, start_date: u64, end_date: Option<u64>, gating_config: Option<GatingConfig>)]
+#[instruction(treasury_owner_bump: u8, name: String, description: String, mutable: bool, price: u64, pieces_in_one_wallet: Option<u64>, start_date: u64, end_date: Option<u64>, gating_config: Option<GatingConfig>, price_schedule: Option<PriceSchedule>, allowlist: Option<AllowlistConfig>, allocation: Option<u64>)]
 pub struct CreateMarket<'info> {
     #[account(init, space=Market::LEN, payer=selling_resource_owner)]
     market: Box<Account<'info, Market>>,
@@ -195,9 +403,22 @@ pub struct CreateMarket<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(trade_history:u8, vault_owner_bump: u8)]
+#[instruction(treasury_owner_bump: u8, price: PaymentPrice)]
+pub struct AddPaymentMint<'info> {
+    #[account(mut, has_one=owner)]
+    market: Box<Account<'info, Market>>,
+    owner: Signer<'info>,
+    mint: UncheckedAccount<'info>,
+    treasury_holder: UncheckedAccount<'info>,
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), mint.key().as_ref(), market.selling_resource.as_ref()], bump=treasury_owner_bump)]
+    treasury_owner: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(trade_history:u8, vault_owner_bump: u8, allowlist_proof: Option<AllowlistProof>)]
 pub struct Buy<'info> {
-    #[account(mut, has_one=treasury_holder)]
+    // `treasury_holder` of `Market::treasury_mint` or one of `Market::payment_mints`
+    #[account(mut, has_one=selling_resource)]
     market: Box<Account<'info, Market>>,
     #[account(mut)]
     selling_resource: Box<Account<'info, SellingResource>>,
@@ -235,6 +456,92 @@ pub struct Buy<'info> {
     token_metadata_program: UncheckedAccount<'info>,
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
+    // if receipt is wanted, empty `PurchaseReceipt` of `find_purchase_receipt_address` should be passed first
+    // purchase_receipt: UncheckedAccount<'info>
+    // if paid in oracle priced `PaymentMint` its price feed should be passed next
+    // price_feed: UncheckedAccount<'info>
+    // if gatekeeper set for the collection these accounts also should be passed
+    // IMPORTANT: accounts should be passed strictly in this order
+    // user_collection_token_account: Account<'info, TokenAccount>
+    // token_account_mint: Account<'info, Mint>
+    // metadata_account: UncheckedAccount<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(trade_history_bump: u8, vault_owner_bump: u8, quantity: u64, allowlist_proof: Option<AllowlistProof>)]
+pub struct BuyMany<'info> {
+    // `treasury_holder` of `Market::treasury_mint` or one of `Market::payment_mints`
+    #[account(mut, has_one=selling_resource)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut)]
+    selling_resource: Box<Account<'info, SellingResource>>,
+    #[account(mut)]
+    user_token_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    #[account(init_if_needed, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump, payer=user_wallet)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut)]
+    treasury_holder: UncheckedAccount<'info>,
+    #[account(mut, owner=mpl_token_metadata::id())]
+    master_edition: UncheckedAccount<'info>,
+    #[account(mut, has_one=owner)]
+    vault: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=vault_owner_bump)]
+    owner: UncheckedAccount<'info>,
+    #[account(mut, owner=mpl_token_metadata::id())]
+    master_edition_metadata: UncheckedAccount<'info>,
+    clock: Sysvar<'info, Clock>,
+    rent: Sysvar<'info, Rent>,
+    token_metadata_program: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    // if receipt is wanted, empty `PurchaseReceipt` of `find_purchase_receipt_address` should be passed first
+    // purchase_receipt: UncheckedAccount<'info>
+    // if paid in oracle priced `PaymentMint` its price feed should be passed next
+    // price_feed: UncheckedAccount<'info>
+    // if gatekeeper set for the collection these accounts also should be passed
+    // user_collection_token_account: Account<'info, TokenAccount>
+    // token_account_mint: Account<'info, Mint>
+    // metadata_account: UncheckedAccount<'info>
+    // then these accounts should be passed for every new edition, `new_token_account` owned by `user_wallet`
+    // IMPORTANT: accounts should be passed strictly in this order
+    // new_mint: Account<'info, Mint>
+    // new_metadata: UncheckedAccount<'info>
+    // new_edition: UncheckedAccount<'info>
+    // edition_marker: UncheckedAccount<'info>
+    // new_token_account: Account<'info, TokenAccount>
+}
+
+#[derive(Accounts)]
+#[instruction(trade_history_bump: u8, vault_owner_bump: u8, quantity: u64, allowlist_proof: Option<AllowlistProof>)]
+pub struct BuyToken<'info> {
+    // `treasury_holder` of `Market::treasury_mint` or one of `Market::payment_mints`
+    #[account(mut, has_one=selling_resource)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut, has_one=vault)]
+    selling_resource: Box<Account<'info, SellingResource>>,
+    #[account(mut)]
+    user_token_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    #[account(init_if_needed, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump, payer=user_wallet)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut)]
+    treasury_holder: UncheckedAccount<'info>,
+    #[account(mut, has_one=owner)]
+    vault: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=vault_owner_bump)]
+    owner: UncheckedAccount<'info>,
+    #[account(mut, constraint = destination.owner == user_wallet.key())]
+    destination: Box<Account<'info, TokenAccount>>,
+    clock: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    // if receipt is wanted, empty `PurchaseReceipt` of `find_purchase_receipt_address` should be passed first
+    // purchase_receipt: UncheckedAccount<'info>
+    // if paid in oracle priced `PaymentMint` its price feed should be passed next
+    // price_feed: UncheckedAccount<'info>
     // if gatekeeper set for the collection these accounts also should be passed
     // IMPORTANT: accounts should be passed strictly in this order
     // user_collection_token_account: Account<'info, TokenAccount>
@@ -245,36 +552,44 @@ pub struct Buy<'info> {
 #[derive(Accounts)]
 #[instruction(treasury_owner_bump: u8, payout_ticket_bump: u8)]
 pub struct Withdraw<'info> {
-    #[account(has_one=treasury_holder, has_one=selling_resource, has_one=treasury_mint)]
+    // `treasury_mint` is `Market::treasury_mint` or one of `Market::payment_mints`
+    #[account(has_one=store, has_one=selling_resource)]
     market: Box<Account<'info, Market>>,
+    store: Box<Account<'info, Store>>,
     selling_resource: Box<Account<'info, SellingResource>>,
     #[account(owner=mpl_token_metadata::id())]
     metadata: UncheckedAccount<'info>,
     #[account(mut)]
     treasury_holder: UncheckedAccount<'info>,
     treasury_mint: UncheckedAccount<'info>,
-    #[account(seeds=[HOLDER_PREFIX.as_bytes(), market.treasury_mint.as_ref(), market.selling_resource.as_ref()], bump=treasury_owner_bump)]
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), treasury_mint.key().as_ref(), market.selling_resource.as_ref()], bump=treasury_owner_bump)]
     owner: UncheckedAccount<'info>,
     #[account(mut)]
     destination: UncheckedAccount<'info>,
+    // ignored if `Store` doesn't charge platform fee
+    #[account(mut)]
+    platform_fee_destination: UncheckedAccount<'info>,
     funder: UncheckedAccount<'info>,
     payer: Signer<'info>,
-    #[account(mut, seeds=[PAYOUT_TICKET_PREFIX.as_bytes(), market.key().as_ref(), funder.key().as_ref()], bump=payout_ticket_bump)]
+    #[account(mut, seeds=[PAYOUT_TICKET_PREFIX.as_bytes(), market.key().as_ref(), funder.key().as_ref(), treasury_mint.key().as_ref()], bump=payout_ticket_bump)]
     payout_ticket: UncheckedAccount<'info>,
     rent: Sysvar<'info, Rent>,
     clock: Sysvar<'info, Clock>,
     token_program: Program<'info, Token>,
     associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
+    // remaining_accounts[0] depends on the sale type
+    // primary sale: `PrimaryMetadataCreators` PDA of `metadata`
+    // secondary sale of item inheriting collection royalty: `CollectionRoyaltyRecord` of its collection
 }
 
 #[derive(Accounts)]
 #[instruction(vault_owner_bump: u8)]
 pub struct ClaimResource<'info> {
-    #[account(has_one=selling_resource, has_one=treasury_holder)]
+    #[account(mut, has_one=selling_resource, has_one=treasury_holder)]
     market: Account<'info, Market>,
     treasury_holder: UncheckedAccount<'info>,
-    #[account(has_one=vault, constraint = selling_resource.owner == selling_resource_owner.key())]
+    #[account(mut, has_one=vault, constraint = selling_resource.owner == selling_resource_owner.key())]
     selling_resource: Account<'info, SellingResource>,
     selling_resource_owner: Signer<'info>,
     #[account(mut, has_one=owner)]
@@ -289,6 +604,18 @@ pub struct ClaimResource<'info> {
     token_program: Program<'info, Token>,
     token_metadata_program: UncheckedAccount<'info>,
     system_program: Program<'info, System>,
+    // treasury holder of every `Market::payment_mints` should be passed in the same order
+    // payment_treasury_holder: UncheckedAccount<'info>
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ReleaseMarket<'info> {
+    #[account(mut, has_one=selling_resource)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut)]
+    selling_resource: Box<Account<'info, SellingResource>>,
+    clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
@@ -319,7 +646,7 @@ pub struct ResumeMarket<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(new_name: Option<String>, new_description: Option<String>, mutable: Option<bool>, new_price: Option<u64>, new_pieces_in_one_wallet: Option<u64>)]
+#[instruction(new_name: Option<String>, new_description: Option<String>, mutable: Option<bool>, new_price: Option<u64>, new_pieces_in_one_wallet: Option<u64>, new_price_schedule: Option<Option<PriceSchedule>>)]
 pub struct ChangeMarket<'info> {
     #[account(mut, has_one=owner)]
     market: Account<'info, Market>,
@@ -327,6 +654,180 @@ pub struct ChangeMarket<'info> {
     clock: Sysvar<'info, Clock>,
 }
 
+#[derive(Accounts)]
+#[instruction()]
+pub struct CancelMarket<'info> {
+    #[account(mut, has_one=owner)]
+    market: Account<'info, Market>,
+    owner: Signer<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(trade_history_bump: u8, treasury_owner_bump: u8)]
+pub struct ClaimRefund<'info> {
+    // `treasury_mint` is `TradeHistory::currency`
+    #[account(mut, has_one=selling_resource)]
+    market: Box<Account<'info, Market>>,
+    selling_resource: Box<Account<'info, SellingResource>>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    #[account(mut, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump=trade_history_bump)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut)]
+    treasury_holder: UncheckedAccount<'info>,
+    treasury_mint: UncheckedAccount<'info>,
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), treasury_mint.key().as_ref(), market.selling_resource.as_ref()], bump=treasury_owner_bump)]
+    owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    destination: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    // returned editions should be passed, three accounts per edition
+    // IMPORTANT: accounts should be passed strictly in this order
+    // token_account: Account<'info, TokenAccount>
+    // mint: Account<'info, Mint>
+    // edition: UncheckedAccount<'info>
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ClosePurchaseReceipt<'info> {
+    #[account(mut, has_one=buyer, close=buyer)]
+    purchase_receipt: Account<'info, PurchaseReceipt>,
+    #[account(mut)]
+    buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(seed_commitment: [u8; 32], winners: u64)]
+pub struct InitRaffle<'info> {
+    #[account(mut, has_one=owner, has_one=selling_resource)]
+    market: Box<Account<'info, Market>>,
+    owner: Signer<'info>,
+    selling_resource: Box<Account<'info, SellingResource>>,
+}
+
+#[derive(Accounts)]
+#[instruction(trade_history_bump: u8)]
+pub struct EnterRaffle<'info> {
+    #[account(mut, has_one=treasury_holder)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut)]
+    user_token_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    #[account(init_if_needed, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump, payer=user_wallet)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut)]
+    treasury_holder: UncheckedAccount<'info>,
+    // Will be created, `find_raffle_ticket_address` indexed by `Raffle::entries`
+    #[account(mut)]
+    raffle_ticket: UncheckedAccount<'info>,
+    clock: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct CloseRaffleEntries<'info> {
+    #[account(mut)]
+    market: Box<Account<'info, Market>>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct RevealRaffleSeed<'info> {
+    #[account(mut, has_one=owner)]
+    market: Box<Account<'info, Market>>,
+    owner: Signer<'info>,
+    #[account(address=anchor_lang::solana_program::sysvar::slot_hashes::id())]
+    slot_hashes: UncheckedAccount<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct DrawRaffle<'info> {
+    #[account(mut)]
+    market: Box<Account<'info, Market>>,
+    // tickets continuing from `Raffle::drawn` should be passed, any number per transaction
+    // IMPORTANT: accounts should be passed strictly in `RaffleTicket::index` order
+    // raffle_ticket: Account<'info, RaffleTicket>
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ExpireRaffle<'info> {
+    #[account(mut)]
+    market: Box<Account<'info, Market>>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(trade_history_bump: u8, vault_owner_bump: u8)]
+pub struct ClaimRafflePrize<'info> {
+    #[account(mut, has_one=selling_resource)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut)]
+    selling_resource: Box<Account<'info, SellingResource>>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    #[account(mut, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump=trade_history_bump)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut, has_one=market, constraint = raffle_ticket.wallet == user_wallet.key())]
+    raffle_ticket: Box<Account<'info, RaffleTicket>>,
+    // Will be created by `mpl_token_metadata`
+    #[account(mut)]
+    new_metadata: UncheckedAccount<'info>,
+    // Will be created by `mpl_token_metadata`
+    #[account(mut)]
+    new_edition: UncheckedAccount<'info>,
+    #[account(mut, owner=mpl_token_metadata::id())]
+    master_edition: UncheckedAccount<'info>,
+    #[account(mut)]
+    new_mint: Box<Account<'info, Mint>>,
+    // Will be created by `mpl_token_metadata`
+    #[account(mut)]
+    edition_marker: UncheckedAccount<'info>,
+    #[account(mut, has_one=owner)]
+    vault: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=vault_owner_bump)]
+    owner: UncheckedAccount<'info>,
+    #[account(mut, constraint = new_token_account.owner == user_wallet.key())]
+    new_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, owner=mpl_token_metadata::id())]
+    master_edition_metadata: UncheckedAccount<'info>,
+    clock: Sysvar<'info, Clock>,
+    rent: Sysvar<'info, Rent>,
+    token_metadata_program: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(trade_history_bump: u8, treasury_owner_bump: u8)]
+pub struct ClaimRaffleRefund<'info> {
+    #[account(mut, has_one=treasury_holder)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    #[account(mut, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump=trade_history_bump)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut, has_one=market, constraint = raffle_ticket.wallet == user_wallet.key())]
+    raffle_ticket: Box<Account<'info, RaffleTicket>>,
+    #[account(mut)]
+    treasury_holder: UncheckedAccount<'info>,
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), market.treasury_mint.as_ref(), market.selling_resource.as_ref()], bump=treasury_owner_bump)]
+    owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    destination: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(primary_metadata_creators: u8, creators: Vec<mpl_token_metadata::state::Creator>)]
 pub struct SavePrimaryMetadataCreators<'info> {
@@ -338,3 +839,58 @@ pub struct SavePrimaryMetadataCreators<'info> {
     primary_metadata_creators: Box<Account<'info, PrimaryMetadataCreators>>,
     system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct MigrateMarket<'info> {
+    // `Market` created by an older program version can't be deserialized until it's migrated
+    #[account(mut, owner=id())]
+    market: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct MigrateSellingResource<'info> {
+    // `SellingResource` created by an older program version can't be deserialized until it's migrated
+    #[account(mut, owner=id())]
+    selling_resource: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct MigrateStore<'info> {
+    // `Store` created by an older program version can't be deserialized until it's migrated
+    #[account(mut, owner=id())]
+    store: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct MigrateTradeHistory<'info> {
+    // `TradeHistory` created by an older program version can't be deserialized until it's migrated
+    #[account(mut, owner=id())]
+    trade_history: UncheckedAccount<'info>,
+    // should be migrated first
+    market: Box<Account<'info, Market>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(platform_fee: Option<PlatformFee>)]
+pub struct SetPlatformFee<'info> {
+    // stores migrated by `MigrateStore` have no platform fee until their admin sets it
+    #[account(mut, has_one=admin)]
+    store: Box<Account<'info, Store>>,
+    admin: Signer<'info>,
+}
@@ -0,0 +1,88 @@
+use crate::{
+    error::ErrorCode,
+    state::{MarketState, PaymentMint, PaymentPrice},
+    utils::*,
+    AddPaymentMint,
+};
+use anchor_lang::{prelude::*, solana_program::program_pack::Pack, System};
+use anchor_spl::token::accessor;
+
+impl<'info> AddPaymentMint<'info> {
+    pub fn process(&mut self, _treasury_owner_bump: u8, price: PaymentPrice) -> Result<()> {
+        let market = &mut self.market;
+        let mint = self.mint.to_account_info();
+        let treasury_holder = self.treasury_holder.to_account_info();
+        let treasury_owner = &self.treasury_owner;
+
+        // Check, that nobody paid yet, funds of every buyer are in one currency
+        if market.state != MarketState::Created {
+            return Err(ErrorCode::MarketInInvalidState.into());
+        }
+
+        // Every raffle entry deposits the same `price` in `treasury_mint`
+        if market.raffle.is_some() {
+            return Err(ErrorCode::MarketIsRaffle.into());
+        }
+
+        // Payment mint prices are not scaled by `Market::price_schedule` tiers
+        if market.price_schedule.is_some() {
+            return Err(ErrorCode::PriceScheduleWithPaymentMints.into());
+        }
+
+        if market.payment_mints.len() >= MAX_PAYMENT_MINTS_LEN {
+            return Err(ErrorCode::PaymentMintsLimitReached.into());
+        }
+
+        if market.treasury(mint.key).is_some() {
+            return Err(ErrorCode::PaymentMintIsAlreadyAccepted.into());
+        }
+
+        if let PaymentPrice::Oracle {
+            quote_price,
+            max_confidence_basis_points,
+            ..
+        } = price
+        {
+            if quote_price == 0 || max_confidence_basis_points > 10000 {
+                return Err(ErrorCode::InvalidPaymentPrice.into());
+            }
+        }
+
+        let is_native = mint.key() == System::id();
+
+        let decimals = if !is_native {
+            if mint.owner != &anchor_spl::token::ID
+                || treasury_holder.owner != &anchor_spl::token::ID
+            {
+                return Err(ProgramError::IllegalOwner.into());
+            }
+
+            if accessor::mint(&treasury_holder)? != *mint.key {
+                return Err(ProgramError::InvalidAccountData.into());
+            }
+
+            if accessor::authority(&treasury_holder)? != treasury_owner.key() {
+                return Err(ProgramError::InvalidAccountData.into());
+            }
+
+            spl_token::state::Mint::unpack(&mint.data.borrow())?.decimals
+        } else {
+            // for native SOL we use PDA as a treasury holder, like `CreateMarket`
+            if treasury_holder.key != treasury_owner.key {
+                return Err(ProgramError::InvalidAccountData.into());
+            }
+
+            NATIVE_DECIMALS
+        };
+
+        market.payment_mints.push(PaymentMint {
+            mint: mint.key(),
+            treasury_holder: treasury_holder.key(),
+            decimals,
+            price,
+            funds_collected: 0,
+        });
+
+        Ok(())
+    }
+}
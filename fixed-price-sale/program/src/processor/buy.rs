@@ -1,6 +1,11 @@
 use crate::{
     error::ErrorCode,
-    state::{GatingConfig, MarketState, SellingResourceState},
+    events::{MarketStateEvent, PurchaseEvent},
+    id,
+    state::{
+        AllowlistConfig, AllowlistProof, GatingConfig, Market, MarketState, PaymentPrice,
+        PurchaseReceipt, SellingResourceKind, SellingResourceState, TradeHistory,
+    },
     utils::*,
     Buy,
 };
@@ -17,6 +22,7 @@ impl<'info> Buy<'info> {
         &mut self,
         _trade_history_bump: u8,
         vault_owner_bump: u8,
+        allowlist_proof: Option<AllowlistProof>,
         remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         let market = &mut self.market;
@@ -39,6 +45,16 @@ impl<'info> Buy<'info> {
         let token_program = &self.token_program;
         let system_program = &self.system_program;
 
+        // Check, that `Market` sells pieces, raffle entries are deposits
+        if market.raffle.is_some() {
+            return Err(ErrorCode::MarketIsRaffle.into());
+        }
+
+        // Check, that `SellingResource` prints editions, `BuyToken` sells other kinds
+        if selling_resource.kind != SellingResourceKind::MasterEdition {
+            return Err(ErrorCode::WrongSellingResourceKind.into());
+        }
+
         let metadata_mint = selling_resource.resource.clone();
         // do supply +1 to increase master edition supply
         let edition = get_supply_off_master_edition(&master_edition.to_account_info())?
@@ -50,6 +66,11 @@ impl<'info> Buy<'info> {
             return Err(ErrorCode::MarketIsSuspended.into());
         }
 
+        // Check, that `Market` is not in `Cancelled` state
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
         // Check, that `Market` is started
         if market.start_date > clock.unix_timestamp as u64 {
             return Err(ErrorCode::MarketIsNotStarted.into());
@@ -64,6 +85,21 @@ impl<'info> Buy<'info> {
             return Err(ErrorCode::MarketIsEnded.into());
         }
 
+        let purchase_index = trade_history.already_bought;
+        let (purchase_receipt, remaining_accounts) = Self::take_purchase_receipt(
+            &market.key(),
+            &user_wallet.key(),
+            purchase_index,
+            remaining_accounts,
+        );
+
+        let (currency, payment_mint_price, remaining_accounts) = Self::resolve_currency(
+            &market,
+            &treasury_holder.key(),
+            remaining_accounts,
+            clock.unix_timestamp,
+        )?;
+
         if trade_history.market != market.key() {
             trade_history.market = market.key();
         }
@@ -72,6 +108,8 @@ impl<'info> Buy<'info> {
             trade_history.wallet = user_wallet.key();
         }
 
+        Self::verify_currency(trade_history, &currency)?;
+
         // Check, that user not reach buy limit
         if let Some(pieces_in_one_wallet) = market.pieces_in_one_wallet {
             if trade_history.already_bought == pieces_in_one_wallet {
@@ -79,19 +117,38 @@ impl<'info> Buy<'info> {
             }
         }
 
+        Self::verify_allowlist(
+            &market.allowlist,
+            &allowlist_proof,
+            &user_wallet.key(),
+            trade_history.already_bought,
+            1,
+            clock.unix_timestamp as u64,
+        )?;
+
         if market.state != MarketState::Active {
             market.state = MarketState::Active;
+
+            emit!(MarketStateEvent {
+                market: market.key(),
+                state: MarketState::Active,
+                timestamp: clock.unix_timestamp,
+            });
         }
 
         Self::verify_gating_token(
             &market.gatekeeper,
             &user_wallet,
             remaining_accounts,
+            1,
             clock.unix_timestamp as u64,
         )?;
 
         // Buy new edition
-        let is_native = market.treasury_mint == System::id();
+        let is_native = currency == System::id();
+        let price = payment_mint_price.unwrap_or_else(|| {
+            market.current_price(selling_resource.supply, clock.unix_timestamp as u64)
+        });
 
         if !is_native {
             let cpi_program = token_program.to_account_info();
@@ -101,7 +158,7 @@ impl<'info> Buy<'info> {
                 authority: user_wallet.to_account_info(),
             };
             let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            token::transfer(cpi_ctx, market.price)?;
+            token::transfer(cpi_ctx, price)?;
         } else {
             if user_token_account.key() != user_wallet.key() {
                 return Err(ErrorCode::UserWalletMustMatchUserTokenAccount.into());
@@ -112,7 +169,7 @@ impl<'info> Buy<'info> {
                 &system_instruction::transfer(
                     &user_token_account.key(),
                     &treasury_holder.key(),
-                    market.price,
+                    price,
                 ),
                 &[
                     user_token_account.to_account_info(),
@@ -121,9 +178,11 @@ impl<'info> Buy<'info> {
             )?;
         }
 
-        market.funds_collected = market
-            .funds_collected
-            .checked_add(market.price)
+        let funds_collected = market
+            .funds_collected_mut(&currency)
+            .ok_or(ErrorCode::PaymentMintIsNotAccepted)?;
+        *funds_collected = funds_collected
+            .checked_add(price)
             .ok_or(ErrorCode::MathOverflow)?;
 
         mpl_mint_new_edition_from_master_edition_via_token(
@@ -162,6 +221,22 @@ impl<'info> Buy<'info> {
             .checked_add(1)
             .ok_or(ErrorCode::MathOverflow)?;
 
+        // Record edition, so only it could be burned for refund
+        trade_history.editions.push(edition);
+        grow_account(
+            &trade_history.to_account_info(),
+            trade_history.space(),
+            &user_wallet.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+
+        trade_history.funds_spent = trade_history
+            .funds_spent
+            .checked_add(price)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        take_market_allocation(market, selling_resource, 1)?;
+
         selling_resource.supply = selling_resource
             .supply
             .checked_add(1)
@@ -174,16 +249,197 @@ impl<'info> Buy<'info> {
             } else if selling_resource.supply == max_supply {
                 selling_resource.state = SellingResourceState::Exhausted;
                 market.state = MarketState::Ended;
+
+                emit!(MarketStateEvent {
+                    market: market.key(),
+                    state: MarketState::Ended,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        // Check, that `Market::allocation` is not sold out
+        if market.allocation == Some(market.sold) && market.state != MarketState::Ended {
+            market.state = MarketState::Ended;
+
+            emit!(MarketStateEvent {
+                market: market.key(),
+                state: MarketState::Ended,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        if let Some((purchase_receipt, purchase_receipt_bump)) = purchase_receipt {
+            Self::create_purchase_receipt(
+                purchase_receipt,
+                purchase_receipt_bump,
+                &user_wallet.to_account_info(),
+                purchase_index,
+                PurchaseReceipt {
+                    market: market.key(),
+                    buyer: user_wallet.key(),
+                    mint: new_mint.key(),
+                    quantity: 1,
+                    price,
+                    currency,
+                    created_at: clock.unix_timestamp,
+                },
+            )?;
+        }
+
+        emit!(PurchaseEvent {
+            market: market.key(),
+            buyer: user_wallet.key(),
+            mint: new_mint.key(),
+            quantity: 1,
+            price,
+            currency,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// `PurchaseReceipt` is taken off the head of `remaining_accounts` if buyer passed it.
+    pub(crate) fn take_purchase_receipt<'a>(
+        market: &Pubkey,
+        buyer: &Pubkey,
+        purchase_index: u64,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> (
+        Option<(&'a AccountInfo<'info>, u8)>,
+        &'a [AccountInfo<'info>],
+    ) {
+        if let Some((purchase_receipt, rest)) = remaining_accounts.split_first() {
+            let (purchase_receipt_key, purchase_receipt_bump) =
+                find_purchase_receipt_address(market, buyer, purchase_index);
+            if purchase_receipt.key() == purchase_receipt_key {
+                return (Some((purchase_receipt, purchase_receipt_bump)), rest);
+            }
+        }
+
+        (None, remaining_accounts)
+    }
+
+    /// Create `PurchaseReceipt` account, rent is paid by the buyer.
+    pub(crate) fn create_purchase_receipt(
+        purchase_receipt: &AccountInfo<'info>,
+        purchase_receipt_bump: u8,
+        buyer: &AccountInfo<'info>,
+        purchase_index: u64,
+        receipt: PurchaseReceipt,
+    ) -> Result<()> {
+        sys_create_account(
+            buyer,
+            purchase_receipt,
+            Rent::get()?.minimum_balance(PurchaseReceipt::LEN),
+            PurchaseReceipt::LEN,
+            &id(),
+            &[
+                PURCHASE_RECEIPT_PREFIX.as_bytes(),
+                receipt.market.as_ref(),
+                receipt.buyer.as_ref(),
+                &purchase_index.to_le_bytes(),
+                &[purchase_receipt_bump],
+            ],
+        )?;
+
+        let mut purchase_receipt_data = purchase_receipt.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut purchase_receipt_data;
+        receipt.try_serialize(&mut writer)?;
+
+        Ok(())
+    }
+
+    /// Currency paid to `treasury_holder`, with price of a piece if it's one of `Market::payment_mints`.
+    /// Price feed of oracle priced payment mint is taken off the head of `remaining_accounts`.
+    pub(crate) fn resolve_currency<'a>(
+        market: &Market,
+        treasury_holder: &Pubkey,
+        remaining_accounts: &'a [AccountInfo<'info>],
+        current_time: i64,
+    ) -> Result<(Pubkey, Option<u64>, &'a [AccountInfo<'info>])> {
+        if *treasury_holder == market.treasury_holder {
+            return Ok((market.treasury_mint, None, remaining_accounts));
+        }
+
+        let payment_mint = market
+            .payment_mints
+            .iter()
+            .find(|payment_mint| payment_mint.treasury_holder == *treasury_holder)
+            .ok_or(ErrorCode::PaymentMintIsNotAccepted)?;
+
+        let (price_feed, remaining_accounts) = match payment_mint.price {
+            PaymentPrice::Oracle { .. } => {
+                let (price_feed, remaining_accounts) = remaining_accounts
+                    .split_first()
+                    .ok_or(ErrorCode::PriceFeedMissing)?;
+                (Some(price_feed), remaining_accounts)
+            }
+            PaymentPrice::Fixed { .. } => (None, remaining_accounts),
+        };
+
+        let price = calculate_payment_mint_price(payment_mint, price_feed, current_time)?;
+
+        Ok((payment_mint.mint, Some(price), remaining_accounts))
+    }
+
+    /// Wallet pays in one currency, so it can be refunded from one treasury.
+    pub(crate) fn verify_currency(
+        trade_history: &mut TradeHistory,
+        currency: &Pubkey,
+    ) -> Result<()> {
+        if trade_history.already_bought == 0 {
+            trade_history.currency = *currency;
+        } else if trade_history.currency != *currency {
+            return Err(ErrorCode::CurrencyMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn verify_allowlist(
+        allowlist: &Option<AllowlistConfig>,
+        allowlist_proof: &Option<AllowlistProof>,
+        user_wallet: &Pubkey,
+        already_bought: u64,
+        quantity: u64,
+        current_time: u64,
+    ) -> Result<()> {
+        if let Some(allowlist) = allowlist {
+            if let Some(gating_time) = allowlist.gating_time {
+                if current_time > gating_time {
+                    return Ok(());
+                }
+            }
+
+            let allowlist_proof = allowlist_proof
+                .as_ref()
+                .ok_or(ErrorCode::AllowlistProofMissing)?;
+
+            let leaf = allowlist_leaf(user_wallet, allowlist_proof.allocation);
+            if !verify_allowlist_proof(&allowlist_proof.proof, &allowlist.merkle_root, leaf) {
+                return Err(ErrorCode::AllowlistProofInvalid.into());
+            }
+
+            // Check, that user not reach allocation, `TradeHistory` counts every piece bought
+            let will_be_bought = already_bought
+                .checked_add(quantity)
+                .ok_or(ErrorCode::MathOverflow)?;
+            if will_be_bought > allowlist_proof.allocation {
+                return Err(ErrorCode::AllowlistAllocationReached.into());
             }
         }
 
         Ok(())
     }
 
-    fn verify_gating_token(
+    /// `amount` of gating tokens is burned if `expire_on_use` is set, one per piece bought.
+    pub(crate) fn verify_gating_token(
         gate: &Option<GatingConfig>,
         user_wallet: &AccountInfo<'info>,
         remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
         current_time: u64,
     ) -> Result<()> {
         if let Some(gatekeeper) = gate {
@@ -246,7 +502,7 @@ impl<'info> Buy<'info> {
                         &token_acc_mint.key(),
                         &user_wallet.key(),
                         &[&user_wallet.key()],
-                        1,
+                        amount,
                     )?,
                     &[
                         user_token_acc.clone(),
@@ -0,0 +1,285 @@
+use crate::{
+    error::ErrorCode,
+    events::{MarketStateEvent, PurchaseEvent},
+    state::{
+        AllowlistProof, MarketState, PurchaseReceipt, SellingResourceKind, SellingResourceState,
+    },
+    utils::*,
+    Buy, BuyToken,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::{
+    solana_program::{program::invoke, system_instruction},
+    System,
+};
+use anchor_spl::token;
+
+impl<'info> BuyToken<'info> {
+    pub fn process(
+        &mut self,
+        _trade_history_bump: u8,
+        vault_owner_bump: u8,
+        quantity: u64,
+        allowlist_proof: Option<AllowlistProof>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let market = &mut self.market;
+        let selling_resource = &mut self.selling_resource;
+        let user_token_account = Box::new(&self.user_token_account);
+        let user_wallet = &mut self.user_wallet;
+        let trade_history = &mut self.trade_history;
+        let treasury_holder = Box::new(&self.treasury_holder);
+        let vault = &self.vault;
+        let owner = &self.owner;
+        let destination = &self.destination;
+        let clock = &self.clock;
+        let token_program = &self.token_program;
+
+        let piece_size = match selling_resource.kind {
+            SellingResourceKind::Token { piece_size } => piece_size,
+            SellingResourceKind::MasterEdition => {
+                return Err(ErrorCode::WrongSellingResourceKind.into())
+            }
+        };
+
+        if quantity == 0 {
+            return Err(ErrorCode::BuyQuantityIsZero.into());
+        }
+
+        // Check, that `Market` sells pieces, raffle entries are deposits
+        if market.raffle.is_some() {
+            return Err(ErrorCode::MarketIsRaffle.into());
+        }
+
+        // Check, that `Market` is not in `Suspended` state
+        if market.state == MarketState::Suspended {
+            return Err(ErrorCode::MarketIsSuspended.into());
+        }
+
+        // Check, that `Market` is not in `Cancelled` state
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
+        // Check, that `Market` is started
+        if market.start_date > clock.unix_timestamp as u64 {
+            return Err(ErrorCode::MarketIsNotStarted.into());
+        }
+
+        // Check, that `Market` is ended
+        if let Some(end_date) = market.end_date {
+            if clock.unix_timestamp as u64 > end_date {
+                return Err(ErrorCode::MarketIsEnded.into());
+            }
+        } else if market.state == MarketState::Ended {
+            return Err(ErrorCode::MarketIsEnded.into());
+        }
+
+        let purchase_index = trade_history.already_bought;
+        let (purchase_receipt, remaining_accounts) = Buy::take_purchase_receipt(
+            &market.key(),
+            &user_wallet.key(),
+            purchase_index,
+            remaining_accounts,
+        );
+
+        let (currency, payment_mint_price, remaining_accounts) = Buy::resolve_currency(
+            &market,
+            &treasury_holder.key(),
+            remaining_accounts,
+            clock.unix_timestamp,
+        )?;
+
+        if trade_history.market != market.key() {
+            trade_history.market = market.key();
+        }
+
+        if trade_history.wallet != user_wallet.key() {
+            trade_history.wallet = user_wallet.key();
+        }
+
+        Buy::verify_currency(trade_history, &currency)?;
+
+        // Check, that user not reach buy limit
+        let will_be_bought = trade_history
+            .already_bought
+            .checked_add(quantity)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if let Some(pieces_in_one_wallet) = market.pieces_in_one_wallet {
+            if will_be_bought > pieces_in_one_wallet {
+                return Err(ErrorCode::UserReachBuyLimit.into());
+            }
+        }
+
+        Buy::verify_allowlist(
+            &market.allowlist,
+            &allowlist_proof,
+            &user_wallet.key(),
+            trade_history.already_bought,
+            quantity,
+            clock.unix_timestamp as u64,
+        )?;
+
+        if market.state != MarketState::Active {
+            market.state = MarketState::Active;
+
+            emit!(MarketStateEvent {
+                market: market.key(),
+                state: MarketState::Active,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        Buy::verify_gating_token(
+            &market.gatekeeper,
+            &user_wallet,
+            remaining_accounts,
+            quantity,
+            clock.unix_timestamp as u64,
+        )?;
+
+        // Price could change between pieces with `PriceSchedule::BySupply`
+        let mut price: u64 = 0;
+        for i in 0..quantity {
+            let supply = selling_resource
+                .supply
+                .checked_add(i)
+                .ok_or(ErrorCode::MathOverflow)?;
+            let piece_price = payment_mint_price
+                .unwrap_or_else(|| market.current_price(supply, clock.unix_timestamp as u64));
+            price = price
+                .checked_add(piece_price)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // Buy pieces
+        let is_native = currency == System::id();
+
+        if !is_native {
+            let cpi_program = token_program.to_account_info();
+            let cpi_accounts = token::Transfer {
+                from: user_token_account.to_account_info(),
+                to: treasury_holder.to_account_info(),
+                authority: user_wallet.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, price)?;
+        } else {
+            if user_token_account.key() != user_wallet.key() {
+                return Err(ErrorCode::UserWalletMustMatchUserTokenAccount.into());
+            }
+
+            invoke(
+                // for native SOL transfer user_wallet key == user_token_account key
+                &system_instruction::transfer(
+                    &user_token_account.key(),
+                    &treasury_holder.key(),
+                    price,
+                ),
+                &[
+                    user_token_account.to_account_info(),
+                    treasury_holder.to_account_info(),
+                ],
+            )?;
+        }
+
+        let funds_collected = market
+            .funds_collected_mut(&currency)
+            .ok_or(ErrorCode::PaymentMintIsNotAccepted)?;
+        *funds_collected = funds_collected
+            .checked_add(price)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Transfer pieces out of the vault
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_OWNER_PREFIX.as_bytes(),
+            selling_resource.resource.as_ref(),
+            selling_resource.store.as_ref(),
+            &[vault_owner_bump],
+        ]];
+
+        let amount = piece_size
+            .checked_mul(quantity)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let cpi_program = token_program.to_account_info();
+        let cpi_accounts = token::Transfer {
+            from: vault.to_account_info(),
+            to: destination.to_account_info(),
+            authority: owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+
+        trade_history.already_bought = will_be_bought;
+
+        trade_history.funds_spent = trade_history
+            .funds_spent
+            .checked_add(price)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        take_market_allocation(market, selling_resource, quantity)?;
+
+        selling_resource.supply = selling_resource
+            .supply
+            .checked_add(quantity)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Check, that `SellingResource::max_supply` is not overflowed by `supply`
+        if let Some(max_supply) = selling_resource.max_supply {
+            if selling_resource.supply > max_supply {
+                return Err(ErrorCode::SupplyIsGtThanMaxSupply.into());
+            } else if selling_resource.supply == max_supply {
+                selling_resource.state = SellingResourceState::Exhausted;
+                market.state = MarketState::Ended;
+
+                emit!(MarketStateEvent {
+                    market: market.key(),
+                    state: MarketState::Ended,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        // Check, that `Market::allocation` is not sold out
+        if market.allocation == Some(market.sold) && market.state != MarketState::Ended {
+            market.state = MarketState::Ended;
+
+            emit!(MarketStateEvent {
+                market: market.key(),
+                state: MarketState::Ended,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        if let Some((purchase_receipt, purchase_receipt_bump)) = purchase_receipt {
+            Buy::create_purchase_receipt(
+                purchase_receipt,
+                purchase_receipt_bump,
+                &user_wallet.to_account_info(),
+                purchase_index,
+                PurchaseReceipt {
+                    market: market.key(),
+                    buyer: user_wallet.key(),
+                    mint: selling_resource.resource,
+                    quantity,
+                    price,
+                    currency,
+                    created_at: clock.unix_timestamp,
+                },
+            )?;
+        }
+
+        emit!(PurchaseEvent {
+            market: market.key(),
+            buyer: user_wallet.key(),
+            mint: selling_resource.resource,
+            quantity,
+            price,
+            currency,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
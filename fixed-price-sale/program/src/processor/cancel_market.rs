@@ -0,0 +1,35 @@
+use crate::{error::ErrorCode, events::MarketStateEvent, state::MarketState, CancelMarket};
+use anchor_lang::prelude::*;
+
+impl<'info> CancelMarket<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let clock = &self.clock;
+
+        // Check, that `Market` is not in `Cancelled` state
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
+        // Check, that `Market` is not `Ended`, funds could be already withdrawn
+        if market.state == MarketState::Ended {
+            return Err(ErrorCode::MarketIsEnded.into());
+        }
+
+        if let Some(end_date) = market.end_date {
+            if clock.unix_timestamp as u64 > end_date {
+                return Err(ErrorCode::MarketIsEnded.into());
+            }
+        }
+
+        market.state = MarketState::Cancelled;
+
+        emit!(MarketStateEvent {
+            market: market.key(),
+            state: MarketState::Cancelled,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
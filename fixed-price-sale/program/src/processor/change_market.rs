@@ -1,4 +1,9 @@
-use crate::{error::ErrorCode, state::MarketState, utils::*, ChangeMarket};
+use crate::{
+    error::ErrorCode,
+    state::{MarketState, PriceSchedule},
+    utils::*,
+    ChangeMarket,
+};
 use anchor_lang::prelude::*;
 
 impl<'info> ChangeMarket<'info> {
@@ -9,6 +14,7 @@ impl<'info> ChangeMarket<'info> {
         mutable: Option<bool>,
         new_price: Option<u64>,
         new_pieces_in_one_wallet: Option<u64>,
+        new_price_schedule: Option<Option<PriceSchedule>>,
     ) -> Result<()> {
         let market = &mut self.market;
         let clock = &self.clock;
@@ -50,6 +56,11 @@ impl<'info> ChangeMarket<'info> {
             market.mutable = mutable;
         }
 
+        // Every raffle entry deposits the same `price`
+        if market.raffle.is_some() && (new_price.is_some() || new_price_schedule.is_some()) {
+            return Err(ErrorCode::MarketIsRaffle.into());
+        }
+
         if let Some(new_price) = new_price {
             if new_price == 0 {
                 return Err(ErrorCode::PriceIsZero.into());
@@ -63,6 +74,19 @@ impl<'info> ChangeMarket<'info> {
             market.pieces_in_one_wallet = Some(new_pieces_in_one_wallet);
         }
 
+        // `Some(None)` removes the schedule, so only `Market::price` applies
+        if let Some(new_price_schedule) = new_price_schedule {
+            if let Some(price_schedule) = &new_price_schedule {
+                assert_price_schedule(price_schedule)?;
+
+                if !market.payment_mints.is_empty() {
+                    return Err(ErrorCode::PriceScheduleWithPaymentMints.into());
+                }
+            }
+
+            market.price_schedule = new_price_schedule;
+        }
+
         Ok(())
     }
 }
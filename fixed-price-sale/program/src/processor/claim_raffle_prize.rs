@@ -0,0 +1,132 @@
+use crate::{
+    error::ErrorCode,
+    events::{MarketStateEvent, PurchaseEvent},
+    state::{MarketState, RaffleTicketState, SellingResourceState},
+    utils::*,
+    ClaimRafflePrize,
+};
+use anchor_lang::prelude::*;
+use mpl_token_metadata::utils::get_supply_off_master_edition;
+
+impl<'info> ClaimRafflePrize<'info> {
+    pub fn process(&mut self, _trade_history_bump: u8, vault_owner_bump: u8) -> Result<()> {
+        let market = &mut self.market;
+        let selling_resource = &mut self.selling_resource;
+        let user_wallet = &self.user_wallet;
+        let trade_history = &mut self.trade_history;
+        let raffle_ticket = &mut self.raffle_ticket;
+        let new_metadata = Box::new(&self.new_metadata);
+        let new_edition = Box::new(&self.new_edition);
+        let master_edition = Box::new(&self.master_edition);
+        let new_mint = &self.new_mint;
+        let edition_marker_info = &mut self.edition_marker.to_account_info();
+        let vault = &self.vault;
+        let owner = Box::new(&self.owner);
+        let new_token_account = &self.new_token_account;
+        let master_edition_metadata = Box::new(&self.master_edition_metadata);
+        let clock = &self.clock;
+        let rent = &self.rent;
+        let token_program = &self.token_program;
+        let system_program = &self.system_program;
+
+        if raffle_ticket.state != RaffleTicketState::Won {
+            return Err(ErrorCode::RaffleTicketIsNotClaimable.into());
+        }
+
+        let metadata_mint = selling_resource.resource.clone();
+        // do supply +1 to increase master edition supply
+        let edition = get_supply_off_master_edition(&master_edition.to_account_info())?
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        mpl_mint_new_edition_from_master_edition_via_token(
+            &new_metadata.to_account_info(),
+            &new_edition.to_account_info(),
+            &new_mint.to_account_info(),
+            &user_wallet.to_account_info(),
+            &user_wallet.to_account_info(),
+            &owner.to_account_info(),
+            &vault.to_account_info(),
+            &master_edition_metadata.to_account_info(),
+            &master_edition.to_account_info(),
+            &metadata_mint,
+            &edition_marker_info,
+            &token_program.to_account_info(),
+            &system_program.to_account_info(),
+            &rent.to_account_info(),
+            edition,
+            &[
+                VAULT_OWNER_PREFIX.as_bytes(),
+                selling_resource.resource.as_ref(),
+                selling_resource.store.as_ref(),
+                &[vault_owner_bump],
+            ],
+        )?;
+
+        mpl_update_primary_sale_happened_via_token(
+            &new_metadata.to_account_info(),
+            &user_wallet.to_account_info(),
+            &new_token_account.to_account_info(),
+            &[],
+        )?;
+
+        raffle_ticket.state = RaffleTicketState::PrizeClaimed;
+
+        trade_history.already_bought = trade_history
+            .already_bought
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let raffle = market.raffle.as_mut().ok_or(ErrorCode::MarketIsNotRaffle)?;
+        raffle.prizes_claimed = raffle
+            .prizes_claimed
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        take_market_allocation(market, selling_resource, 1)?;
+
+        selling_resource.supply = selling_resource
+            .supply
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Check, that `SellingResource::max_supply` is not overflowed by `supply`
+        if let Some(max_supply) = selling_resource.max_supply {
+            if selling_resource.supply > max_supply {
+                return Err(ErrorCode::SupplyIsGtThanMaxSupply.into());
+            } else if selling_resource.supply == max_supply {
+                selling_resource.state = SellingResourceState::Exhausted;
+                market.state = MarketState::Ended;
+
+                emit!(MarketStateEvent {
+                    market: market.key(),
+                    state: MarketState::Ended,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        // Check, that `Market::allocation` is not sold out
+        if market.allocation == Some(market.sold) && market.state != MarketState::Ended {
+            market.state = MarketState::Ended;
+
+            emit!(MarketStateEvent {
+                market: market.key(),
+                state: MarketState::Ended,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        emit!(PurchaseEvent {
+            market: market.key(),
+            buyer: user_wallet.key(),
+            mint: new_mint.key(),
+            quantity: 1,
+            price: raffle_ticket.deposit,
+            currency: market.treasury_mint,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
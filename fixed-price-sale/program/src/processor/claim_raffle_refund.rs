@@ -0,0 +1,89 @@
+use crate::{
+    error::ErrorCode,
+    state::{MarketState, RaffleTicketState},
+    utils::*,
+    ClaimRaffleRefund,
+};
+use anchor_lang::{prelude::*, solana_program::program_pack::Pack, System};
+use anchor_spl::token;
+
+impl<'info> ClaimRaffleRefund<'info> {
+    pub fn process(&mut self, _trade_history_bump: u8, treasury_owner_bump: u8) -> Result<()> {
+        let market = &mut self.market;
+        let user_wallet = &self.user_wallet;
+        let trade_history = &mut self.trade_history;
+        let raffle_ticket = &mut self.raffle_ticket;
+        let treasury_holder = &self.treasury_holder;
+        let treasury_owner = &self.owner;
+        let destination = &self.destination;
+        let token_program = &self.token_program;
+
+        if market.raffle.is_none() {
+            return Err(ErrorCode::MarketIsNotRaffle.into());
+        }
+
+        // Losers are refunded after the draw, every entry is refunded if `Market` is `Cancelled`
+        match raffle_ticket.state {
+            RaffleTicketState::Lost => {}
+            RaffleTicketState::Entered if market.state == MarketState::Cancelled => {
+                // Deposit was not reserved for refund by the draw
+                market.funds_collected = market
+                    .funds_collected
+                    .checked_sub(raffle_ticket.deposit)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+            _ => return Err(ErrorCode::RaffleTicketIsNotClaimable.into()),
+        }
+
+        let refund = raffle_ticket.deposit;
+
+        trade_history.funds_spent = trade_history
+            .funds_spent
+            .checked_sub(refund)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        raffle_ticket.state = RaffleTicketState::Refunded;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOLDER_PREFIX.as_bytes(),
+            market.treasury_mint.as_ref(),
+            market.selling_resource.as_ref(),
+            &[treasury_owner_bump],
+        ]];
+
+        let is_native = market.treasury_mint == System::id();
+
+        if is_native {
+            if destination.key() != user_wallet.key() {
+                return Err(ErrorCode::InvalidRefundDestination.into());
+            }
+
+            sys_transfer(
+                &treasury_holder.to_account_info(),
+                &destination.to_account_info(),
+                refund,
+                signer_seeds[0],
+            )?;
+        } else {
+            if *destination.owner != spl_token::id() {
+                return Err(ErrorCode::InvalidRefundDestination.into());
+            }
+
+            let destination_data = spl_token::state::Account::unpack(&destination.data.borrow())?;
+            if destination_data.owner != user_wallet.key() {
+                return Err(ErrorCode::InvalidRefundDestination.into());
+            }
+
+            let cpi_program = token_program.to_account_info();
+            let cpi_accounts = token::Transfer {
+                from: treasury_holder.to_account_info(),
+                to: destination.to_account_info(),
+                authority: treasury_owner.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, refund)?;
+        }
+
+        Ok(())
+    }
+}
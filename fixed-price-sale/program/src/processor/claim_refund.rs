@@ -0,0 +1,239 @@
+use crate::{
+    error::ErrorCode,
+    state::{MarketState, SellingResourceKind},
+    utils::*,
+    ClaimRefund,
+};
+use anchor_lang::{prelude::*, solana_program::program_pack::Pack, System};
+use anchor_spl::token;
+use mpl_token_metadata::state::{Edition, EDITION, PREFIX};
+
+impl<'info> ClaimRefund<'info> {
+    pub fn process(
+        &mut self,
+        _trade_history_bump: u8,
+        treasury_owner_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let market = &mut self.market;
+        let selling_resource = &self.selling_resource;
+        let user_wallet = &self.user_wallet;
+        let trade_history = &mut self.trade_history;
+        let treasury_holder = &self.treasury_holder;
+        let treasury_mint = &self.treasury_mint;
+        let treasury_owner = &self.owner;
+        let destination = &self.destination;
+        let token_program = &self.token_program;
+
+        // Check, that `Market` is in `Cancelled` state
+        if market.state != MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsNotCancelled.into());
+        }
+
+        // Raffle entries are refunded by `ClaimRaffleRefund`
+        if market.raffle.is_some() {
+            return Err(ErrorCode::MarketIsRaffle.into());
+        }
+
+        // Check, that refund is paid from the treasury of currency user paid in
+        assert_keys_equal(treasury_mint.key(), trade_history.currency)?;
+        let (treasury_holder_key, _) = market
+            .treasury(&trade_history.currency)
+            .ok_or(ErrorCode::PaymentMintIsNotAccepted)?;
+        assert_keys_equal(treasury_holder.key(), treasury_holder_key)?;
+
+        let returned = match selling_resource.kind {
+            SellingResourceKind::MasterEdition => Self::burn_editions(
+                &selling_resource.resource,
+                trade_history.already_bought,
+                &mut trade_history.editions,
+                &user_wallet.to_account_info(),
+                &token_program.to_account_info(),
+                remaining_accounts,
+            )?,
+            SellingResourceKind::Token { piece_size } => Self::return_tokens(
+                &selling_resource.vault,
+                piece_size,
+                trade_history.already_bought,
+                &user_wallet.to_account_info(),
+                &token_program.to_account_info(),
+                remaining_accounts,
+            )?,
+        };
+
+        // Pieces could be bought at different prices, so refund is a share of funds spent
+        let refund = (trade_history.funds_spent as u128)
+            .checked_mul(returned as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(trade_history.already_bought as u128)
+            .ok_or(ErrorCode::MathOverflow)? as u64;
+
+        trade_history.already_bought = trade_history
+            .already_bought
+            .checked_sub(returned)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        trade_history.funds_spent = trade_history
+            .funds_spent
+            .checked_sub(refund)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let funds_collected = market
+            .funds_collected_mut(&trade_history.currency)
+            .ok_or(ErrorCode::PaymentMintIsNotAccepted)?;
+        *funds_collected = funds_collected
+            .checked_sub(refund)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOLDER_PREFIX.as_bytes(),
+            trade_history.currency.as_ref(),
+            market.selling_resource.as_ref(),
+            &[treasury_owner_bump],
+        ]];
+
+        let is_native = trade_history.currency == System::id();
+
+        if is_native {
+            if destination.key() != user_wallet.key() {
+                return Err(ErrorCode::InvalidRefundDestination.into());
+            }
+
+            sys_transfer(
+                &treasury_holder.to_account_info(),
+                &destination.to_account_info(),
+                refund,
+                signer_seeds[0],
+            )?;
+        } else {
+            if *destination.owner != spl_token::id() {
+                return Err(ErrorCode::InvalidRefundDestination.into());
+            }
+
+            let destination_data = spl_token::state::Account::unpack(&destination.data.borrow())?;
+            if destination_data.owner != user_wallet.key() {
+                return Err(ErrorCode::InvalidRefundDestination.into());
+            }
+
+            let cpi_program = token_program.to_account_info();
+            let cpi_accounts = token::Transfer {
+                from: treasury_holder.to_account_info(),
+                to: destination.to_account_info(),
+                authority: treasury_owner.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, refund)?;
+        }
+
+        Ok(())
+    }
+
+    /// Burn editions printed from `resource` master edition to the buyer in this market,
+    /// three accounts per edition. Burned ones are removed from `editions`.
+    fn burn_editions(
+        resource: &Pubkey,
+        already_bought: u64,
+        editions: &mut Vec<u64>,
+        user_wallet: &AccountInfo<'info>,
+        token_program: &AccountInfo<'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<u64> {
+        // Check, that at least one edition is returned and user bought that much
+        let returned = (remaining_accounts.len() / 3) as u64;
+        if returned == 0 || remaining_accounts.len() % 3 != 0 || returned > already_bought {
+            return Err(ErrorCode::NothingToRefund.into());
+        }
+
+        let token_metadata_program_key = mpl_token_metadata::id();
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                token_metadata_program_key.as_ref(),
+                resource.as_ref(),
+                EDITION.as_bytes(),
+            ],
+            &token_metadata_program_key,
+        );
+
+        for accounts in remaining_accounts.chunks(3) {
+            let token_account = &accounts[0];
+            let mint = &accounts[1];
+            let edition = &accounts[2];
+
+            // Check, that edition is printed from the selling resource master edition
+            // to this wallet in this market, not bought elsewhere for less
+            assert_derivation(
+                &token_metadata_program_key,
+                edition,
+                &[
+                    PREFIX.as_bytes(),
+                    token_metadata_program_key.as_ref(),
+                    mint.key.as_ref(),
+                    EDITION.as_bytes(),
+                ],
+            )?;
+
+            if edition.owner != &token_metadata_program_key {
+                return Err(ErrorCode::WrongRefundEdition.into());
+            }
+
+            let edition_data = Edition::from_account_info(edition)?;
+            if edition_data.parent != master_edition {
+                return Err(ErrorCode::WrongRefundEdition.into());
+            }
+
+            let position = editions
+                .iter()
+                .position(|number| *number == edition_data.edition)
+                .ok_or(ErrorCode::WrongRefundEdition)?;
+            editions.swap_remove(position);
+
+            let cpi_program = token_program.clone();
+            let cpi_accounts = token::Burn {
+                mint: mint.clone(),
+                to: token_account.clone(),
+                authority: user_wallet.clone(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::burn(cpi_ctx, 1)?;
+        }
+
+        Ok(returned)
+    }
+
+    /// Return every piece bought back to the vault, user token account and vault are passed.
+    fn return_tokens(
+        vault: &Pubkey,
+        piece_size: u64,
+        already_bought: u64,
+        user_wallet: &AccountInfo<'info>,
+        token_program: &AccountInfo<'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<u64> {
+        if already_bought == 0 || remaining_accounts.len() != 2 {
+            return Err(ErrorCode::NothingToRefund.into());
+        }
+
+        let token_account = &remaining_accounts[0];
+        let vault_account = &remaining_accounts[1];
+
+        if vault_account.key != vault {
+            return Err(ErrorCode::PublicKeyMismatch.into());
+        }
+
+        let amount = piece_size
+            .checked_mul(already_bought)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let cpi_program = token_program.clone();
+        let cpi_accounts = token::Transfer {
+            from: token_account.clone(),
+            to: vault_account.clone(),
+            authority: user_wallet.clone(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        Ok(already_bought)
+    }
+}
@@ -1,11 +1,21 @@
-use crate::{error::ErrorCode, state::MarketState, utils::*, ClaimResource};
+use crate::{
+    error::ErrorCode,
+    events::ClaimResourceEvent,
+    state::{MarketState, SellingResourceKind, SellingResourceState},
+    utils::*,
+    ClaimResource,
+};
 use anchor_lang::{prelude::*, solana_program::program_pack::Pack, System};
 use anchor_spl::token;
 
 impl<'info> ClaimResource<'info> {
-    pub fn process(&mut self, vault_owner_bump: u8) -> Result<()> {
-        let market = &self.market;
-        let selling_resource = &self.selling_resource;
+    pub fn process(
+        &mut self,
+        vault_owner_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let market = &mut self.market;
+        let selling_resource = &mut self.selling_resource;
         let vault = &self.vault;
         let metadata = &self.metadata;
         let vault_owner = &self.owner;
@@ -15,34 +25,76 @@ impl<'info> ClaimResource<'info> {
         let token_program = &self.token_program;
 
         // Check, that `Market` is `Ended`
-        if let Some(end_date) = market.end_date {
-            if clock.unix_timestamp as u64 <= end_date {
-                return Err(ErrorCode::MarketInInvalidState.into());
-            }
-        } else {
-            if market.state != MarketState::Ended {
-                return Err(ErrorCode::MarketInInvalidState.into());
+        // `Cancelled` market is claimable once every buyer is refunded, see treasury check below
+        if market.state != MarketState::Cancelled {
+            if let Some(end_date) = market.end_date {
+                if clock.unix_timestamp as u64 <= end_date {
+                    return Err(ErrorCode::MarketInInvalidState.into());
+                }
+            } else {
+                if market.state != MarketState::Ended {
+                    return Err(ErrorCode::MarketInInvalidState.into());
+                }
             }
         }
 
-        let is_native = market.treasury_mint == System::id();
+        // Check, that raffle winners got their editions, vault is needed to print them
+        if market.state != MarketState::Cancelled {
+            if let Some(raffle) = &market.raffle {
+                if !raffle.is_drawn() {
+                    return Err(ErrorCode::RaffleIsNotDrawn.into());
+                }
 
-        let treasury_holder_amount = if is_native {
-            treasury_holder.lamports()
-        } else {
-            let token_account = spl_token::state::Account::unpack(&treasury_holder.data.borrow())?;
-            if token_account.owner != market.treasury_owner {
-                return Err(ErrorCode::DerivedKeyInvalid.into());
+                if raffle.prizes_claimed != raffle.winners_drawn {
+                    return Err(ErrorCode::RafflePrizesAreNotClaimed.into());
+                }
             }
+        }
 
-            token_account.amount
-        };
+        // Return unsold allocation, every other market selling the resource should be released too
+        if !market.released {
+            release_market(market, selling_resource)?;
+        }
+
+        if selling_resource.markets != 0 {
+            return Err(ErrorCode::SellingResourceIsInUse.into());
+        }
+
+        let treasury_holder_amount = Self::treasury_amount(
+            &market.treasury_mint,
+            &market.treasury_owner,
+            &treasury_holder.to_account_info(),
+        )?;
 
         // Check, that treasury balance is zero
         if treasury_holder_amount != 0 {
             return Err(ErrorCode::TreasuryIsNotEmpty.into());
         }
 
+        // Check, that treasuries of `Market::payment_mints` are empty too
+        if remaining_accounts.len() != market.payment_mints.len() {
+            return Err(ErrorCode::PaymentMintTreasuryMissing.into());
+        }
+
+        for (payment_mint, payment_treasury_holder) in
+            market.payment_mints.iter().zip(remaining_accounts)
+        {
+            assert_keys_equal(payment_treasury_holder.key(), payment_mint.treasury_holder)?;
+
+            let (payment_treasury_owner, _) =
+                find_treasury_owner_address(&payment_mint.mint, &market.selling_resource);
+
+            let payment_treasury_holder_amount = Self::treasury_amount(
+                &payment_mint.mint,
+                &payment_treasury_owner,
+                payment_treasury_holder,
+            )?;
+
+            if payment_treasury_holder_amount != 0 {
+                return Err(ErrorCode::TreasuryIsNotEmpty.into());
+            }
+        }
+
         // Check, that provided metadata is correct
         assert_derivation(
             &mpl_token_metadata::id(),
@@ -61,6 +113,20 @@ impl<'info> ClaimResource<'info> {
             &[vault_owner_bump],
         ]];
 
+        // Unsold inventory of `Token` kind is claimed, master edition otherwise
+        let amount = match selling_resource.kind {
+            SellingResourceKind::MasterEdition => 1,
+            SellingResourceKind::Token { .. } => vault.amount,
+        };
+
+        // Resource leaves the vault, new markets can't sell it
+        selling_resource.state = SellingResourceState::Stopped;
+
+        // Check, that vault is not empty, primary sale flag can't be updated without balance
+        if amount == 0 {
+            return Ok(());
+        }
+
         // Update primary sale flag
         let metadata_state = mpl_token_metadata::state::Metadata::from_account_info(&metadata)?;
         if !metadata_state.primary_sale_happened {
@@ -80,8 +146,35 @@ impl<'info> ClaimResource<'info> {
             authority: vault_owner.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, 1)?;
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(ClaimResourceEvent {
+            market: market.key(),
+            resource: selling_resource.resource,
+            destination: destination.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
 
         Ok(())
     }
+
+    fn treasury_amount(
+        treasury_mint: &Pubkey,
+        treasury_owner: &Pubkey,
+        treasury_holder: &AccountInfo<'info>,
+    ) -> Result<u64> {
+        let is_native = *treasury_mint == System::id();
+
+        if is_native {
+            Ok(treasury_holder.lamports())
+        } else {
+            let token_account = spl_token::state::Account::unpack(&treasury_holder.data.borrow())?;
+            if token_account.owner != *treasury_owner {
+                return Err(ErrorCode::DerivedKeyInvalid.into());
+            }
+
+            Ok(token_account.amount)
+        }
+    }
 }
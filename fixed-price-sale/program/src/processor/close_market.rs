@@ -1,4 +1,4 @@
-use crate::{error::ErrorCode, state::MarketState, CloseMarket};
+use crate::{error::ErrorCode, events::MarketStateEvent, state::MarketState, CloseMarket};
 use anchor_lang::prelude::*;
 
 impl<'info> CloseMarket<'info> {
@@ -6,6 +6,11 @@ impl<'info> CloseMarket<'info> {
         let market = &mut self.market;
         let clock = &self.clock;
 
+        // Check, that `Market` is not in `Cancelled` state
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
         // Check, that `Market` is with unlimited duration
         if market.end_date.is_some() {
             return Err(ErrorCode::MarketDurationIsNotUnlimited.into());
@@ -18,6 +23,12 @@ impl<'info> CloseMarket<'info> {
 
         market.state = MarketState::Ended;
 
+        emit!(MarketStateEvent {
+            market: market.key(),
+            state: MarketState::Ended,
+            timestamp: clock.unix_timestamp,
+        });
+
         Ok(())
     }
 }
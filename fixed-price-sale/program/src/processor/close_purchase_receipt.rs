@@ -0,0 +1,9 @@
+use crate::ClosePurchaseReceipt;
+use anchor_lang::prelude::*;
+
+impl<'info> ClosePurchaseReceipt<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        // `PurchaseReceipt` rent is returned to the buyer by `close` constraint
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{error::ErrorCode, state::MarketState, utils::*, CloseRaffleEntries};
+use anchor_lang::prelude::*;
+
+impl<'info> CloseRaffleEntries<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let clock = &self.clock;
+
+        // Check, that `Market` is not in `Cancelled` state, deposits are refunded instead
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
+        if let Some(end_date) = market.end_date {
+            if clock.unix_timestamp as u64 <= end_date {
+                return Err(ErrorCode::RaffleEntriesAreOpen.into());
+            }
+        }
+
+        let raffle = market.raffle.as_mut().ok_or(ErrorCode::MarketIsNotRaffle)?;
+
+        if raffle.reveal_slot.is_some() {
+            return Err(ErrorCode::RaffleEntriesAreClosed.into());
+        }
+
+        // Hash of a future slot is unknown to everyone, including the owner who knows the seed
+        raffle.reveal_slot = Some(
+            clock
+                .slot
+                .checked_add(RAFFLE_REVEAL_DELAY_SLOTS)
+                .ok_or(ErrorCode::MathOverflow)?,
+        );
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     error::ErrorCode,
-    state::{GatingConfig, MarketState, SellingResourceState},
+    events::MarketStateEvent,
+    state::{AllowlistConfig, GatingConfig, MarketState, PriceSchedule, SellingResourceState},
     utils::*,
     CreateMarket,
 };
@@ -19,6 +20,9 @@ impl<'info> CreateMarket<'info> {
         start_date: u64,
         end_date: Option<u64>,
         gating_config: Option<GatingConfig>,
+        price_schedule: Option<PriceSchedule>,
+        allowlist: Option<AllowlistConfig>,
+        allocation: Option<u64>,
         remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         let market = &mut self.market;
@@ -78,6 +82,23 @@ impl<'info> CreateMarket<'info> {
             }
         }
 
+        if let Some(allowlist) = &allowlist {
+            if let Some(gating_time) = allowlist.gating_time {
+                if gating_time < start_date {
+                    return Err(ErrorCode::WrongGatingDate.into());
+                }
+                if let Some(end_date) = end_date {
+                    if gating_time > end_date {
+                        return Err(ErrorCode::WrongGatingDate.into());
+                    }
+                }
+            }
+        }
+
+        if let Some(price_schedule) = &price_schedule {
+            assert_price_schedule(price_schedule)?;
+        }
+
         let is_native = mint.key() == System::id();
 
         if !is_native {
@@ -105,6 +126,36 @@ impl<'info> CreateMarket<'info> {
         // Check selling resource ownership
         assert_keys_equal(selling_resource.owner, selling_resource_owner.key())?;
 
+        // Several markets can sell one resource at once, until it is exhausted or stopped
+        if selling_resource.state != SellingResourceState::Created
+            && selling_resource.state != SellingResourceState::InUse
+        {
+            return Err(ErrorCode::SellingResourceInInvalidState.into());
+        }
+
+        // Check, that allocation fits into supply not sold or reserved by other markets
+        if let Some(allocation) = allocation {
+            if allocation == 0 {
+                return Err(ErrorCode::AllocationIsGtThanAvailable.into());
+            }
+
+            if let Some(unallocated) = selling_resource.unallocated() {
+                if allocation > unallocated {
+                    return Err(ErrorCode::AllocationIsGtThanAvailable.into());
+                }
+            }
+
+            selling_resource.allocated = selling_resource
+                .allocated
+                .checked_add(allocation)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        selling_resource.markets = selling_resource
+            .markets
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
         market.store = store.key();
         market.selling_resource = selling_resource.key();
         market.treasury_mint = mint.key();
@@ -120,8 +171,19 @@ impl<'info> CreateMarket<'info> {
         market.end_date = end_date;
         market.state = MarketState::Created;
         market.gatekeeper = gating_config;
+        market.price_schedule = price_schedule;
+        market.allowlist = allowlist;
+        market.allocation = allocation;
+        market.sold = 0;
+        market.released = false;
         selling_resource.state = SellingResourceState::InUse;
 
+        emit!(MarketStateEvent {
+            market: market.key(),
+            state: MarketState::Created,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
         Ok(())
     }
 }
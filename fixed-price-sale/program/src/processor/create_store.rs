@@ -1,8 +1,13 @@
-use crate::{error::ErrorCode, utils::*, CreateStore};
+use crate::{error::ErrorCode, state::PlatformFee, utils::*, CreateStore};
 use anchor_lang::prelude::*;
 
 impl<'info> CreateStore<'info> {
-    pub fn process(&mut self, name: String, description: String) -> Result<()> {
+    pub fn process(
+        &mut self,
+        name: String,
+        description: String,
+        platform_fee: Option<PlatformFee>,
+    ) -> Result<()> {
         let admin = &self.admin;
         let store = &mut self.store;
 
@@ -14,9 +19,16 @@ impl<'info> CreateStore<'info> {
             return Err(ErrorCode::DescriptionIsTooLong.into());
         }
 
+        if let Some(platform_fee) = &platform_fee {
+            if platform_fee.basis_points > MAX_PLATFORM_FEE_BASIS_POINTS {
+                return Err(ErrorCode::PlatformFeeIsTooBig.into());
+            }
+        }
+
         store.admin = admin.key();
         store.name = puffed_out_string(name, NAME_MAX_LEN);
         store.description = puffed_out_string(description, DESCRIPTION_MAX_LEN);
+        store.platform_fee = platform_fee;
 
         Ok(())
     }
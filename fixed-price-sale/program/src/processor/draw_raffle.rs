@@ -0,0 +1,69 @@
+use crate::{
+    error::ErrorCode,
+    id,
+    state::{RaffleTicket, RaffleTicketState},
+    utils::*,
+    DrawRaffle,
+};
+use anchor_lang::prelude::*;
+
+impl<'info> DrawRaffle<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let market = &mut self.market;
+        let market_key = market.key();
+
+        let raffle = market.raffle.as_mut().ok_or(ErrorCode::MarketIsNotRaffle)?;
+        let randomness = raffle.randomness.ok_or(ErrorCode::RaffleIsNotDrawn)?;
+
+        // Tickets are drawn in chunks, each chunk continues from `Raffle::drawn`
+        let mut refunds_reserved: u64 = 0;
+        for raffle_ticket_info in remaining_accounts {
+            if raffle_ticket_info.owner != &id() {
+                return Err(ErrorCode::RaffleTicketInvalid.into());
+            }
+
+            let mut raffle_ticket =
+                RaffleTicket::try_deserialize(&mut &raffle_ticket_info.data.borrow()[..])?;
+
+            if raffle_ticket.market != market_key
+                || raffle_ticket.index != raffle.drawn
+                || raffle_ticket.state != RaffleTicketState::Entered
+            {
+                return Err(ErrorCode::RaffleTicketInvalid.into());
+            }
+
+            if is_raffle_winner(
+                &randomness,
+                raffle_ticket.index,
+                raffle.entries,
+                raffle.winners,
+                raffle.winners_drawn,
+            )? {
+                raffle_ticket.state = RaffleTicketState::Won;
+                raffle.winners_drawn = raffle
+                    .winners_drawn
+                    .checked_add(1)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            } else {
+                raffle_ticket.state = RaffleTicketState::Lost;
+                refunds_reserved = refunds_reserved
+                    .checked_add(raffle_ticket.deposit)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+
+            let mut raffle_ticket_data = raffle_ticket_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut raffle_ticket_data;
+            raffle_ticket.try_serialize(&mut writer)?;
+
+            raffle.drawn = raffle.drawn.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // Deposits of losers are not withdrawable, they are kept for refunds
+        market.funds_collected = market
+            .funds_collected
+            .checked_sub(refunds_reserved)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(())
+    }
+}
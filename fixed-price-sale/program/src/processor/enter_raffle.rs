@@ -0,0 +1,161 @@
+use crate::{
+    error::ErrorCode,
+    events::MarketStateEvent,
+    id,
+    state::{MarketState, RaffleTicket, RaffleTicketState},
+    utils::*,
+    EnterRaffle,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+    System,
+};
+use anchor_spl::token;
+
+impl<'info> EnterRaffle<'info> {
+    pub fn process(&mut self, _trade_history_bump: u8) -> Result<()> {
+        let market = &mut self.market;
+        let user_token_account = &self.user_token_account;
+        let user_wallet = &self.user_wallet;
+        let trade_history = &mut self.trade_history;
+        let treasury_holder = &self.treasury_holder;
+        let raffle_ticket = &self.raffle_ticket;
+        let clock = &self.clock;
+        let token_program = &self.token_program;
+
+        // Check, that `Market` is not in `Suspended` state
+        if market.state == MarketState::Suspended {
+            return Err(ErrorCode::MarketIsSuspended.into());
+        }
+
+        // Check, that `Market` is not in `Cancelled` state
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
+        // Check, that `Market` is started
+        if market.start_date > clock.unix_timestamp as u64 {
+            return Err(ErrorCode::MarketIsNotStarted.into());
+        }
+
+        // Check, that entries are open, raffle markets always have `end_date`
+        if let Some(end_date) = market.end_date {
+            if clock.unix_timestamp as u64 > end_date {
+                return Err(ErrorCode::MarketIsEnded.into());
+            }
+        }
+
+        let market_key = market.key();
+        let currency = market.treasury_mint;
+        let deposit = market.price;
+        let raffle = market.raffle.as_mut().ok_or(ErrorCode::MarketIsNotRaffle)?;
+
+        // Check, that wallet enters once, every entry deposits non-zero `price`
+        if trade_history.funds_spent > 0 {
+            return Err(ErrorCode::RaffleEntryExists.into());
+        }
+
+        if trade_history.market != market_key {
+            trade_history.market = market_key;
+        }
+
+        if trade_history.wallet != user_wallet.key() {
+            trade_history.wallet = user_wallet.key();
+        }
+
+        trade_history.currency = currency;
+
+        // Deposit is escrowed in `treasury_holder` until the draw
+        let is_native = currency == System::id();
+
+        if !is_native {
+            let cpi_program = token_program.to_account_info();
+            let cpi_accounts = token::Transfer {
+                from: user_token_account.to_account_info(),
+                to: treasury_holder.to_account_info(),
+                authority: user_wallet.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, deposit)?;
+        } else {
+            if user_token_account.key() != user_wallet.key() {
+                return Err(ErrorCode::UserWalletMustMatchUserTokenAccount.into());
+            }
+
+            invoke(
+                // for native SOL transfer user_wallet key == user_token_account key
+                &system_instruction::transfer(
+                    &user_token_account.key(),
+                    &treasury_holder.key(),
+                    deposit,
+                ),
+                &[
+                    user_token_account.to_account_info(),
+                    treasury_holder.to_account_info(),
+                ],
+            )?;
+        }
+
+        trade_history.funds_spent = deposit;
+
+        // Ticket is indexed by entry order, so the draw can walk tickets without a list
+        let index = raffle.entries;
+        let raffle_ticket_bump = assert_derivation(
+            &id(),
+            &raffle_ticket.to_account_info(),
+            &[
+                RAFFLE_TICKET_PREFIX.as_bytes(),
+                market_key.as_ref(),
+                &index.to_le_bytes(),
+            ],
+        )?;
+
+        sys_create_account(
+            &user_wallet.to_account_info(),
+            &raffle_ticket.to_account_info(),
+            Rent::get()?.minimum_balance(RaffleTicket::LEN),
+            RaffleTicket::LEN,
+            &id(),
+            &[
+                RAFFLE_TICKET_PREFIX.as_bytes(),
+                market_key.as_ref(),
+                &index.to_le_bytes(),
+                &[raffle_ticket_bump],
+            ],
+        )?;
+
+        let mut raffle_ticket_data = raffle_ticket.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut raffle_ticket_data;
+        RaffleTicket {
+            market: market_key,
+            wallet: user_wallet.key(),
+            index,
+            deposit,
+            state: RaffleTicketState::Entered,
+        }
+        .try_serialize(&mut writer)?;
+
+        raffle.entries = raffle
+            .entries
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        market.funds_collected = market
+            .funds_collected
+            .checked_add(deposit)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        if market.state != MarketState::Active {
+            market.state = MarketState::Active;
+
+            emit!(MarketStateEvent {
+                market: market_key,
+                state: MarketState::Active,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        Ok(())
+    }
+}
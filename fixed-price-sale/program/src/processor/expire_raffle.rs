@@ -0,0 +1,42 @@
+use crate::{
+    error::ErrorCode, events::MarketStateEvent, state::MarketState, utils::*, ExpireRaffle,
+};
+use anchor_lang::prelude::*;
+
+impl<'info> ExpireRaffle<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let clock = &self.clock;
+
+        // Check, that `Market` is not in `Cancelled` state
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
+        let raffle = market.raffle.as_ref().ok_or(ErrorCode::MarketIsNotRaffle)?;
+
+        if raffle.randomness.is_some() {
+            return Err(ErrorCode::RaffleSeedIsRevealed.into());
+        }
+
+        let reveal_slot = raffle.reveal_slot.ok_or(ErrorCode::RaffleEntriesAreOpen)?;
+        let reveal_deadline = reveal_slot
+            .checked_add(RAFFLE_REVEAL_PERIOD_SLOTS)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        if clock.slot <= reveal_deadline {
+            return Err(ErrorCode::RaffleRevealIsNotExpired.into());
+        }
+
+        // Seed is not revealed in time, every entry is refunded like in a `Cancelled` market
+        market.state = MarketState::Cancelled;
+
+        emit!(MarketStateEvent {
+            market: market.key(),
+            state: MarketState::Cancelled,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
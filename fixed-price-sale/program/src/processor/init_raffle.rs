@@ -0,0 +1,57 @@
+use crate::{
+    error::ErrorCode,
+    state::{MarketState, Raffle, SellingResourceKind},
+    InitRaffle,
+};
+use anchor_lang::prelude::*;
+
+impl<'info> InitRaffle<'info> {
+    pub fn process(&mut self, seed_commitment: [u8; 32], winners: u64) -> Result<()> {
+        let market = &mut self.market;
+        let selling_resource = &self.selling_resource;
+
+        // Check, that nobody paid yet, raffle entries are deposits instead of purchases
+        if market.state != MarketState::Created {
+            return Err(ErrorCode::MarketInInvalidState.into());
+        }
+
+        if market.raffle.is_some() {
+            return Err(ErrorCode::MarketIsRaffle.into());
+        }
+
+        // Winners claim editions printed from the master edition
+        if selling_resource.kind != SellingResourceKind::MasterEdition {
+            return Err(ErrorCode::WrongSellingResourceKind.into());
+        }
+
+        // Entries are closed at `end_date`, every entry deposits the same `price` in `treasury_mint`
+        if winners == 0
+            || market.end_date.is_none()
+            || market.price == 0
+            || market.price_schedule.is_some()
+            || !market.payment_mints.is_empty()
+        {
+            return Err(ErrorCode::InvalidRaffleConfig.into());
+        }
+
+        // Check, that every winner can get an edition, from market allocation if it is set
+        if let Some(available) = market.allocation.or_else(|| selling_resource.unallocated()) {
+            if winners > available {
+                return Err(ErrorCode::SupplyIsGtThanAvailable.into());
+            }
+        }
+
+        market.raffle = Some(Raffle {
+            seed_commitment,
+            winners,
+            entries: 0,
+            randomness: None,
+            drawn: 0,
+            winners_drawn: 0,
+            prizes_claimed: 0,
+            reveal_slot: None,
+        });
+
+        Ok(())
+    }
+}
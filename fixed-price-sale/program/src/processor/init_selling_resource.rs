@@ -1,4 +1,9 @@
-use crate::{error::ErrorCode, state::SellingResourceState, utils::*, InitSellingResource};
+use crate::{
+    error::ErrorCode,
+    state::{SellingResourceKind, SellingResourceState},
+    utils::*,
+    InitSellingResource,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 
@@ -97,6 +102,9 @@ impl<'info> InitSellingResource<'info> {
         selling_resource.supply = 0;
         selling_resource.max_supply = actual_max_supply;
         selling_resource.state = SellingResourceState::Created;
+        selling_resource.kind = SellingResourceKind::MasterEdition;
+        selling_resource.allocated = 0;
+        selling_resource.markets = 0;
 
         Ok(())
     }
@@ -0,0 +1,80 @@
+use crate::{
+    error::ErrorCode,
+    state::{SellingResourceKind, SellingResourceState},
+    utils::*,
+    InitTokenSellingResource,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+impl<'info> InitTokenSellingResource<'info> {
+    pub fn process(&mut self, _vault_owner_bump: u8, piece_size: u64, pieces: u64) -> Result<()> {
+        let store = &self.store;
+        let admin = &self.admin;
+        let selling_resource = &mut self.selling_resource;
+        let selling_resource_owner = &self.selling_resource_owner;
+        let resource_mint = &self.resource_mint;
+        let metadata = &self.metadata;
+        let vault = &self.vault;
+        let owner = &self.owner;
+        let resource_token = &self.resource_token;
+        let token_program = &self.token_program;
+
+        if piece_size == 0 || pieces == 0 {
+            return Err(ErrorCode::PieceSizeIsZero.into());
+        }
+
+        // Check, that provided metadata is correct
+        assert_derivation(
+            &mpl_token_metadata::id(),
+            metadata,
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                resource_mint.key().as_ref(),
+            ],
+        )?;
+
+        let metadata =
+            mpl_token_metadata::state::Metadata::from_account_info(&metadata.to_account_info())?;
+
+        // Check, that at least one creator exists in primary sale
+        if !metadata.primary_sale_happened {
+            if let Some(creators) = metadata.data.creators {
+                if creators.len() == 0 {
+                    return Err(ErrorCode::MetadataCreatorsIsEmpty.into());
+                }
+            } else {
+                return Err(ErrorCode::MetadataCreatorsIsEmpty.into());
+            }
+        }
+
+        // Transfer whole inventory to the vault
+        let amount = piece_size
+            .checked_mul(pieces)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let cpi_program = token_program.to_account_info();
+        let cpi_accounts = token::Transfer {
+            from: resource_token.to_account_info(),
+            to: vault.to_account_info(),
+            authority: admin.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        selling_resource.store = store.key();
+        selling_resource.owner = selling_resource_owner.key();
+        selling_resource.resource = resource_mint.key();
+        selling_resource.vault = vault.key();
+        selling_resource.vault_owner = owner.key();
+        selling_resource.supply = 0;
+        selling_resource.max_supply = Some(pieces);
+        selling_resource.state = SellingResourceState::Created;
+        selling_resource.kind = SellingResourceKind::Token { piece_size };
+        selling_resource.allocated = 0;
+        selling_resource.markets = 0;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{state::Market, utils::*, MigrateMarket};
+use anchor_lang::{prelude::*, Discriminator};
+
+impl<'info> MigrateMarket<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        // Every field added since is off by default, `sold` and `released`
+        // only matter for markets with `allocation`
+        migrate_account(
+            &self.market.to_account_info(),
+            Market::discriminator(),
+            Market::LEN,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )
+    }
+}
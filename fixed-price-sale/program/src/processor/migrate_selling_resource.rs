@@ -0,0 +1,38 @@
+use crate::{
+    state::{SellingResource, SellingResourceState},
+    utils::*,
+    MigrateSellingResource,
+};
+use anchor_lang::{prelude::*, Discriminator};
+
+impl<'info> MigrateSellingResource<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let selling_resource_info = self.selling_resource.to_account_info();
+        let legacy_len = selling_resource_info.data_len();
+
+        // New `kind` decodes as `MasterEdition` and `allocated` as `0`, as they were back then
+        migrate_account(
+            &selling_resource_info,
+            SellingResource::discriminator(),
+            SellingResource::LEN,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        let mut selling_resource =
+            SellingResource::try_deserialize(&mut &selling_resource_info.data.borrow()[..])?;
+
+        // Only one market could sell the resource back then, it should be released once it's over
+        if legacy_len <= SellingResource::LEN_WITHOUT_KIND
+            && selling_resource.state == SellingResourceState::InUse
+        {
+            selling_resource.markets = 1;
+        }
+
+        let mut selling_resource_data = selling_resource_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut selling_resource_data;
+        selling_resource.try_serialize(&mut writer)?;
+
+        Ok(())
+    }
+}
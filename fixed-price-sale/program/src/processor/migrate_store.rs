@@ -0,0 +1,15 @@
+use crate::{state::Store, utils::*, MigrateStore};
+use anchor_lang::{prelude::*, Discriminator};
+
+impl<'info> MigrateStore<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        // New `platform_fee` decodes as `None`, stores created back then didn't charge it
+        migrate_account(
+            &self.store.to_account_info(),
+            Store::discriminator(),
+            Store::LEN,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{error::ErrorCode, state::TradeHistory, utils::*, MigrateTradeHistory};
+use anchor_lang::{prelude::*, Discriminator};
+
+impl<'info> MigrateTradeHistory<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let trade_history_info = self.trade_history.to_account_info();
+        let market = &self.market;
+        let legacy_len = trade_history_info.data_len();
+
+        migrate_account(
+            &trade_history_info,
+            TradeHistory::discriminator(),
+            TradeHistory::LEN,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        let mut trade_history =
+            TradeHistory::try_deserialize(&mut &trade_history_info.data.borrow()[..])?;
+        assert_keys_equal(trade_history.market, market.key())?;
+
+        // Only fixed `Market::price` was accepted back then, so it's spent for every piece
+        if legacy_len <= TradeHistory::LEN_WITHOUT_FUNDS_SPENT {
+            trade_history.funds_spent = trade_history
+                .already_bought
+                .checked_mul(market.price)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // and paid in `Market::treasury_mint`
+        if legacy_len <= TradeHistory::LEN_WITHOUT_CURRENCY {
+            trade_history.currency = market.treasury_mint;
+        }
+
+        // Editions printed back then weren't recorded and can't be burned for refund
+
+        let mut trade_history_data = trade_history_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut trade_history_data;
+        trade_history.try_serialize(&mut writer)?;
+
+        Ok(())
+    }
+}
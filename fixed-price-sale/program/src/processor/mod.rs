@@ -1,11 +1,32 @@
+pub mod add_payment_mint;
 pub mod buy;
+pub mod buy_many;
+pub mod buy_token;
+pub mod cancel_market;
 pub mod change_market;
+pub mod claim_raffle_prize;
+pub mod claim_raffle_refund;
+pub mod claim_refund;
 pub mod claim_resource;
 pub mod close_market;
+pub mod close_purchase_receipt;
+pub mod close_raffle_entries;
 pub mod create_market;
 pub mod create_store;
+pub mod draw_raffle;
+pub mod enter_raffle;
+pub mod expire_raffle;
+pub mod init_raffle;
 pub mod init_selling_resource;
+pub mod init_token_selling_resource;
+pub mod migrate_market;
+pub mod migrate_selling_resource;
+pub mod migrate_store;
+pub mod migrate_trade_history;
+pub mod release_market;
 pub mod resume_market;
+pub mod reveal_raffle_seed;
 pub mod save_primary_metadata_creators;
+pub mod set_platform_fee;
 pub mod suspend_market;
 pub mod withdraw;
@@ -0,0 +1,34 @@
+use crate::{error::ErrorCode, state::MarketState, utils::*, ReleaseMarket};
+use anchor_lang::prelude::*;
+
+impl<'info> ReleaseMarket<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let selling_resource = &mut self.selling_resource;
+        let clock = &self.clock;
+
+        // Check, that `Market` is `Ended` or `Cancelled`, like in `claim_resource`
+        if market.state != MarketState::Cancelled {
+            if let Some(end_date) = market.end_date {
+                if clock.unix_timestamp as u64 <= end_date {
+                    return Err(ErrorCode::MarketInInvalidState.into());
+                }
+            } else if market.state != MarketState::Ended {
+                return Err(ErrorCode::MarketInInvalidState.into());
+            }
+
+            // Raffle winners get editions out of the allocation
+            if let Some(raffle) = &market.raffle {
+                if !raffle.is_drawn() {
+                    return Err(ErrorCode::RaffleIsNotDrawn.into());
+                }
+
+                if raffle.prizes_claimed != raffle.winners_drawn {
+                    return Err(ErrorCode::RafflePrizesAreNotClaimed.into());
+                }
+            }
+        }
+
+        release_market(market, selling_resource)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{error::ErrorCode, state::MarketState, ResumeMarket};
+use crate::{error::ErrorCode, events::MarketStateEvent, state::MarketState, ResumeMarket};
 use anchor_lang::prelude::*;
 
 impl<'info> ResumeMarket<'info> {
@@ -24,6 +24,12 @@ impl<'info> ResumeMarket<'info> {
 
         market.state = MarketState::Active;
 
+        emit!(MarketStateEvent {
+            market: market.key(),
+            state: MarketState::Active,
+            timestamp: clock.unix_timestamp,
+        });
+
         Ok(())
     }
 }
@@ -0,0 +1,44 @@
+use crate::{error::ErrorCode, state::MarketState, utils::*, RevealRaffleSeed};
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+impl<'info> RevealRaffleSeed<'info> {
+    pub fn process(&mut self, seed: [u8; 32]) -> Result<()> {
+        let market = &mut self.market;
+        let slot_hashes = &self.slot_hashes;
+        let clock = &self.clock;
+
+        // Check, that `Market` is not in `Cancelled` state, deposits are refunded instead
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
+        let raffle = market.raffle.as_mut().ok_or(ErrorCode::MarketIsNotRaffle)?;
+
+        // Check, that entries are closed, so nobody enters knowing the seed
+        let reveal_slot = raffle.reveal_slot.ok_or(ErrorCode::RaffleEntriesAreOpen)?;
+
+        if raffle.randomness.is_some() {
+            return Err(ErrorCode::RaffleSeedIsRevealed.into());
+        }
+
+        // Entries are refunded with `expire_raffle` after the deadline
+        let reveal_deadline = reveal_slot
+            .checked_add(RAFFLE_REVEAL_PERIOD_SLOTS)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if clock.slot > reveal_deadline {
+            return Err(ErrorCode::RaffleRevealIsExpired.into());
+        }
+
+        if keccak::hash(&seed).0 != raffle.seed_commitment {
+            return Err(ErrorCode::RaffleSeedMismatch.into());
+        }
+
+        // Slot hash is fixed by `close_raffle_entries`, so the owner can't pick it by the reveal time
+        let slot_hash = find_slot_hash(&slot_hashes.try_borrow_data()?, reveal_slot)
+            .ok_or(ErrorCode::RaffleRevealIsEarly)?;
+
+        raffle.randomness = Some(keccak::hashv(&[&seed[..], &slot_hash[..]]).0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{error::ErrorCode, state::PlatformFee, utils::*, SetPlatformFee};
+use anchor_lang::prelude::*;
+
+impl<'info> SetPlatformFee<'info> {
+    pub fn process(&mut self, platform_fee: Option<PlatformFee>) -> Result<()> {
+        let store = &mut self.store;
+
+        if let Some(platform_fee) = &platform_fee {
+            if platform_fee.basis_points > MAX_PLATFORM_FEE_BASIS_POINTS {
+                return Err(ErrorCode::PlatformFeeIsTooBig.into());
+            }
+        }
+
+        // `None` stops charging the fee, withdrawals already made keep theirs
+        store.platform_fee = platform_fee;
+
+        Ok(())
+    }
+}
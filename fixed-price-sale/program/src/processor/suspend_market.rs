@@ -1,4 +1,4 @@
-use crate::{error::ErrorCode, state::MarketState, SuspendMarket};
+use crate::{error::ErrorCode, events::MarketStateEvent, state::MarketState, SuspendMarket};
 use anchor_lang::prelude::*;
 
 impl<'info> SuspendMarket<'info> {
@@ -11,6 +11,11 @@ impl<'info> SuspendMarket<'info> {
             return Err(ErrorCode::MarketIsEnded.into());
         }
 
+        // Check, that `Market` is not in `Cancelled` state
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
         if let Some(end_date) = market.end_date {
             if clock.unix_timestamp as u64 > end_date {
                 return Err(ErrorCode::MarketIsEnded.into());
@@ -34,6 +39,12 @@ impl<'info> SuspendMarket<'info> {
 
         market.state = MarketState::Suspended;
 
+        emit!(MarketStateEvent {
+            market: market.key(),
+            state: MarketState::Suspended,
+            timestamp: clock.unix_timestamp,
+        });
+
         Ok(())
     }
 }
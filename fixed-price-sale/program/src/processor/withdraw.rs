@@ -1,15 +1,20 @@
 use crate::{
     error::ErrorCode,
+    events::WithdrawEvent,
     id,
-    state::{MarketState, PrimaryMetadataCreators},
+    state::{MarketState, PayoutTicket, PrimaryMetadataCreators},
     utils::*,
     Withdraw,
 };
-use anchor_lang::{prelude::*, solana_program::borsh::try_from_slice_unchecked};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack},
+};
 use anchor_spl::{
     associated_token::{self, get_associated_token_address},
     token,
 };
+use mpl_token_metadata::utils::resolve_royalty;
 
 impl<'info> Withdraw<'info> {
     pub fn process(
@@ -19,6 +24,7 @@ impl<'info> Withdraw<'info> {
         remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         let market = &self.market;
+        let store = &self.store;
         let token_program = &self.token_program;
         let associated_token_program = &self.associated_token_program;
         let system_program = &self.system_program;
@@ -26,6 +32,7 @@ impl<'info> Withdraw<'info> {
         let treasury_mint = Box::new(&self.treasury_mint);
         let treasury_owner = &self.owner;
         let destination = &self.destination;
+        let platform_fee_destination = &self.platform_fee_destination;
         let selling_resource = &self.selling_resource;
         let funder = &self.funder;
         let payer = &self.payer;
@@ -35,9 +42,20 @@ impl<'info> Withdraw<'info> {
         let metadata = &self.metadata.to_account_info();
 
         let selling_resource_key = selling_resource.key().clone();
-        let treasury_mint_key = market.treasury_mint.clone();
+        let treasury_mint_key = treasury_mint.key();
         let funder_key = funder.key();
 
+        // Check, that treasury belongs to one of `Market` currencies, it's withdrawn separately
+        let (treasury_holder_key, funds_collected) = market
+            .treasury(&treasury_mint_key)
+            .ok_or(ErrorCode::PaymentMintIsNotAccepted)?;
+        assert_keys_equal(treasury_holder.key(), treasury_holder_key)?;
+
+        // Check, that `Market` is not `Cancelled`, funds are left for refunds
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
         // Check, that `Market` is `Ended`
         if let Some(end_date) = market.end_date {
             if clock.unix_timestamp as u64 <= end_date {
@@ -49,6 +67,13 @@ impl<'info> Withdraw<'info> {
             }
         }
 
+        // Check, that deposits of raffle losers are reserved for refunds
+        if let Some(raffle) = &market.raffle {
+            if !raffle.is_drawn() {
+                return Err(ErrorCode::RaffleIsNotDrawn.into());
+            }
+        }
+
         // Check, that provided metadata is correct
         assert_derivation(
             &mpl_token_metadata::id(),
@@ -60,20 +85,46 @@ impl<'info> Withdraw<'info> {
             ],
         )?;
 
-        // Obtain right creators according to sale type
+        // Obtain right creators according to sale type, `remaining_accounts[0]` is
+        // the `PrimaryMetadataCreators` PDA of the metadata for primary sales, and the
+        // `CollectionRoyaltyRecord` for secondary sales of items inheriting collection royalty
+        let metadata_key = metadata.key();
         let metadata = mpl_token_metadata::state::Metadata::from_account_info(&metadata)?;
+        let royalty = if metadata.primary_sale_happened && metadata.inherits_collection_royalty() {
+            if remaining_accounts.len() == 0 {
+                return Err(ErrorCode::CollectionRoyaltyRecordNotProvided.into());
+            }
+
+            resolve_royalty(&metadata, Some(&remaining_accounts[0]))?
+        } else {
+            resolve_royalty(&metadata, None)?
+        };
         let actual_creators = if !metadata.primary_sale_happened {
             if remaining_accounts.len() == 0 {
                 return Err(ErrorCode::PrimaryMetadataCreatorsNotProvided.into());
             }
 
-            let primary_metadata_creators_data = remaining_accounts[0].data.borrow()[8..].to_vec();
+            let primary_metadata_creators_info = &remaining_accounts[0];
+            if primary_metadata_creators_info.owner != &id() {
+                return Err(ProgramError::IllegalOwner.into());
+            }
+            assert_derivation(
+                &id(),
+                primary_metadata_creators_info,
+                &[
+                    PRIMARY_METADATA_CREATORS_PREFIX.as_bytes(),
+                    metadata_key.as_ref(),
+                ],
+            )?;
+
+            let primary_metadata_creators_data =
+                primary_metadata_creators_info.data.borrow()[8..].to_vec();
             let primary_metadata_creators = try_from_slice_unchecked::<PrimaryMetadataCreators>(
                 &primary_metadata_creators_data,
             )?;
             Box::new(Some(primary_metadata_creators.creators))
         } else {
-            Box::new(metadata.data.creators)
+            Box::new(royalty.creators)
         };
 
         // Check, that funder is `Creator` or `Market` owner
@@ -97,7 +148,7 @@ impl<'info> Withdraw<'info> {
             return Err(ErrorCode::PayoutTicketExists.into());
         }
 
-        let is_native = market.treasury_mint == System::id();
+        let is_native = treasury_mint_key == System::id();
 
         let amount = if metadata.primary_sale_happened {
             if funder_creator.is_some() && funder_key == market.owner {
@@ -106,14 +157,14 @@ impl<'info> Withdraw<'info> {
                 let funder_creator = funder_creator.as_ref().unwrap();
 
                 let funder_as_creator_share = calculate_secondary_shares_for_creator(
-                    market.funds_collected,
-                    metadata.data.seller_fee_basis_points as u64,
+                    funds_collected,
+                    royalty.seller_fee_basis_points as u64,
                     funder_creator.share as u64,
                 )?;
 
                 let funder_as_market_owner_share = calculate_secondary_shares_for_market_owner(
-                    market.funds_collected,
-                    metadata.data.seller_fee_basis_points as u64,
+                    funds_collected,
+                    royalty.seller_fee_basis_points as u64,
                 )?;
 
                 funder_as_creator_share
@@ -121,27 +172,35 @@ impl<'info> Withdraw<'info> {
                     .ok_or(ErrorCode::MathOverflow)?
             } else if let Some(funder_creator) = &funder_creator {
                 calculate_secondary_shares_for_creator(
-                    market.funds_collected,
-                    metadata.data.seller_fee_basis_points as u64,
+                    funds_collected,
+                    royalty.seller_fee_basis_points as u64,
                     funder_creator.share as u64,
                 )?
             } else {
                 calculate_secondary_shares_for_market_owner(
-                    market.funds_collected,
-                    metadata.data.seller_fee_basis_points as u64,
+                    funds_collected,
+                    royalty.seller_fee_basis_points as u64,
                 )?
             }
         } else {
             if let Some(funder_creator) = funder_creator {
-                calculate_primary_shares_for_creator(
-                    market.funds_collected,
-                    funder_creator.share as u64,
-                )?
+                calculate_primary_shares_for_creator(funds_collected, funder_creator.share as u64)?
             } else {
                 return Err(ErrorCode::MarketOwnerDoesntHaveShares.into());
             }
         };
 
+        // Platform fee is deducted from every withdrawal
+        let platform_fee = if let Some(platform_fee) = &store.platform_fee {
+            calculate_platform_fee(amount, platform_fee.basis_points)?
+        } else {
+            0
+        };
+
+        let funder_amount = amount
+            .checked_sub(platform_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+
         // Transfer royalties
         let signer_seeds: &[&[&[u8]]] = &[&[
             HOLDER_PREFIX.as_bytes(),
@@ -158,9 +217,23 @@ impl<'info> Withdraw<'info> {
             sys_transfer(
                 &treasury_holder.to_account_info(),
                 &destination.to_account_info(),
-                amount,
+                funder_amount,
                 signer_seeds[0],
             )?;
+
+            if platform_fee > 0 {
+                let recipient = store.platform_fee.as_ref().unwrap().recipient;
+                if platform_fee_destination.key() != recipient {
+                    return Err(ErrorCode::InvalidPlatformFeeDestination.into());
+                }
+
+                sys_transfer(
+                    &treasury_holder.to_account_info(),
+                    &platform_fee_destination.to_account_info(),
+                    platform_fee,
+                    signer_seeds[0],
+                )?;
+            }
         } else {
             if *treasury_mint.owner != spl_token::id() {
                 return Err(ProgramError::InvalidArgument.into());
@@ -171,7 +244,7 @@ impl<'info> Withdraw<'info> {
             }
 
             let associated_token_account =
-                get_associated_token_address(&funder_key, &market.treasury_mint);
+                get_associated_token_address(&funder_key, &treasury_mint_key);
 
             // Check, that provided destination is associated token account
             if associated_token_account != destination.key() {
@@ -201,24 +274,69 @@ impl<'info> Withdraw<'info> {
                 authority: treasury_owner.to_account_info(),
             };
             let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-            token::transfer(cpi_ctx, amount)?;
+            token::transfer(cpi_ctx, funder_amount)?;
+
+            if platform_fee > 0 {
+                let recipient = store.platform_fee.as_ref().unwrap().recipient;
+                if *platform_fee_destination.owner != spl_token::id() {
+                    return Err(ErrorCode::InvalidPlatformFeeDestination.into());
+                }
+
+                let platform_fee_destination_data =
+                    spl_token::state::Account::unpack(&platform_fee_destination.data.borrow())?;
+                if platform_fee_destination_data.owner != recipient
+                    || platform_fee_destination_data.mint != treasury_mint_key
+                {
+                    return Err(ErrorCode::InvalidPlatformFeeDestination.into());
+                }
+
+                let cpi_program = token_program.to_account_info();
+                let cpi_accounts = token::Transfer {
+                    from: treasury_holder.to_account_info(),
+                    to: platform_fee_destination.to_account_info(),
+                    authority: treasury_owner.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token::transfer(cpi_ctx, platform_fee)?;
+            }
         }
 
         // Create ticket account to prevent twice withdrawal
         sys_create_account(
             &payer.to_account_info(),
             &payout_ticket.to_account_info(),
-            rent.minimum_balance(FLAG_ACCOUNT_SIZE),
-            FLAG_ACCOUNT_SIZE,
+            rent.minimum_balance(PayoutTicket::LEN),
+            PayoutTicket::LEN,
             &id(),
             &[
                 PAYOUT_TICKET_PREFIX.as_bytes(),
                 market.key().as_ref(),
                 funder_key.as_ref(),
+                treasury_mint_key.as_ref(),
                 &[payout_ticket_bump],
             ],
         )?;
 
+        // Record paid amounts on the ticket
+        let mut payout_ticket_data = payout_ticket.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut payout_ticket_data;
+        PayoutTicket {
+            market: market.key(),
+            funder: funder_key,
+            amount: funder_amount,
+            platform_fee,
+        }
+        .try_serialize(&mut writer)?;
+
+        emit!(WithdrawEvent {
+            market: market.key(),
+            funder: funder_key,
+            currency: treasury_mint_key,
+            amount: funder_amount,
+            platform_fee,
+            timestamp: clock.unix_timestamp,
+        });
+
         Ok(())
     }
 }
@@ -1,6 +1,9 @@
 //! Module provide program defined state
 
-use crate::utils::{DESCRIPTION_DEFAULT_SIZE, MAX_PRIMARY_CREATORS_LEN, NAME_DEFAULT_SIZE};
+use crate::utils::{
+    DESCRIPTION_DEFAULT_SIZE, MAX_PAYMENT_MINTS_LEN, MAX_PRICE_TIERS_LEN, MAX_PRIMARY_CREATORS_LEN,
+    NAME_DEFAULT_SIZE,
+};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -8,10 +11,19 @@ pub struct Store {
     pub admin: Pubkey,
     pub name: String,
     pub description: String,
+    pub platform_fee: Option<PlatformFee>,
 }
 
 impl Store {
-    pub const LEN: usize = 8 + 32 + NAME_DEFAULT_SIZE + DESCRIPTION_DEFAULT_SIZE;
+    pub const LEN: usize = 8 + 32 + NAME_DEFAULT_SIZE + DESCRIPTION_DEFAULT_SIZE + 1 + 32 + 2;
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlatformFee {
+    /// wallet receiving the fee, for SPL treasury fee goes to any of its token accounts
+    pub recipient: Pubkey,
+    /// share of every withdrawal taken by the platform
+    pub basis_points: u16,
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
@@ -23,6 +35,17 @@ pub enum SellingResourceState {
     Stopped,
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
+pub enum SellingResourceKind {
+    /// `resource` is a master edition, new edition is printed on every buy
+    MasterEdition,
+    /// `resource` is any mint with token metadata, `piece_size` tokens are transferred out of
+    /// `vault` on every buy. One mint per selling resource: a 1/1 NFT or a fungible amount,
+    /// pre-minted collections are sold as one selling resource per NFT. Metadata creators are
+    /// paid on withdraw, so mints without token metadata can't be sold.
+    Token { piece_size: u64 },
+}
+
 #[account]
 pub struct SellingResource {
     pub store: Pubkey,
@@ -33,10 +56,26 @@ pub struct SellingResource {
     pub supply: u64,
     pub max_supply: Option<u64>,
     pub state: SellingResourceState,
+    pub kind: SellingResourceKind,
+    /// unsold pieces reserved by markets with `Market::allocation`
+    pub allocated: u64,
+    /// markets selling the resource, a market is released once it is over
+    pub markets: u64,
 }
 
 impl SellingResource {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 9 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 9 + 1 + 1 + 8 + 8 + 8;
+    /// size of accounts created before `kind` was added
+    pub const LEN_WITHOUT_KIND: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 9 + 1;
+
+    /// Pieces left for markets without `Market::allocation`, `None` if `max_supply` is unlimited.
+    pub fn unallocated(&self) -> Option<u64> {
+        self.max_supply.map(|max_supply| {
+            max_supply
+                .saturating_sub(self.supply)
+                .saturating_sub(self.allocated)
+        })
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
@@ -46,6 +85,7 @@ pub enum MarketState {
     Suspended,
     Active,
     Ended,
+    Cancelled,
 }
 
 #[account]
@@ -67,6 +107,18 @@ pub struct Market {
     // need this field to calculate royalties at withdraw
     pub funds_collected: u64,
     pub gatekeeper: Option<GatingConfig>,
+    pub price_schedule: Option<PriceSchedule>,
+    pub allowlist: Option<AllowlistConfig>,
+    /// currencies accepted besides `treasury_mint`
+    pub payment_mints: Vec<PaymentMint>,
+    /// if set, pieces are raffled among entries made until `end_date` instead of being bought
+    pub raffle: Option<Raffle>,
+    /// pieces reserved for this market, otherwise it sells the supply left by other markets
+    pub allocation: Option<u64>,
+    /// pieces sold by this market, `SellingResource::supply` is shared by every market
+    pub sold: u64,
+    /// unsold allocation was returned to `SellingResource`
+    pub released: bool,
 }
 
 impl Market {
@@ -89,7 +141,62 @@ impl Market {
         + 1
         + 32
         + 1
-        + 9;
+        + 9
+        + 1
+        + PriceSchedule::LEN
+        + 1
+        + 32
+        + 9
+        + 4
+        + PaymentMint::LEN * MAX_PAYMENT_MINTS_LEN
+        + 1
+        + Raffle::LEN
+        + 9
+        + 8
+        + 1;
+
+    /// Price of the next piece, from `price_schedule` if some tier is still open and `price` otherwise.
+    pub fn current_price(&self, supply: u64, now: u64) -> u64 {
+        if let Some(price_schedule) = &self.price_schedule {
+            let position = match price_schedule.kind {
+                PriceScheduleKind::BySupply => supply,
+                PriceScheduleKind::ByTime => now,
+            };
+
+            if let Some(tier) = price_schedule
+                .tiers
+                .iter()
+                .find(|tier| position < tier.until)
+            {
+                return tier.price;
+            }
+        }
+
+        self.price
+    }
+
+    /// Treasury holder and funds collected in `mint`, `treasury_mint` or one of `payment_mints`.
+    pub fn treasury(&self, mint: &Pubkey) -> Option<(Pubkey, u64)> {
+        if *mint == self.treasury_mint {
+            return Some((self.treasury_holder, self.funds_collected));
+        }
+
+        self.payment_mints
+            .iter()
+            .find(|payment_mint| payment_mint.mint == *mint)
+            .map(|payment_mint| (payment_mint.treasury_holder, payment_mint.funds_collected))
+    }
+
+    pub fn funds_collected_mut(&mut self, mint: &Pubkey) -> Option<&mut u64> {
+        if *mint == self.treasury_mint {
+            return Some(&mut self.funds_collected);
+        }
+
+        self.payment_mints
+            .iter_mut()
+            .find(|payment_mint| payment_mint.mint == *mint)
+            .map(|payment_mint| &mut payment_mint.funds_collected)
+    }
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
@@ -100,16 +207,168 @@ pub struct GatingConfig {
     pub gating_time: Option<u64>,
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllowlistConfig {
+    /// root of the merkle tree of `(wallet, allocation)` leaves
+    pub merkle_root: [u8; 32],
+    /// allowlist is lifted after this time, like `GatingConfig::gating_time`
+    pub gating_time: Option<u64>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllowlistProof {
+    /// total pieces the wallet is allowed to buy
+    pub allocation: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum PriceScheduleKind {
+    /// `PriceTier::until` is a count of pieces sold
+    BySupply,
+    /// `PriceTier::until` is a unix timestamp
+    ByTime,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PriceTier {
+    /// tier is open while `SellingResource::supply` or clock is lower than this value
+    pub until: u64,
+    pub price: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PriceSchedule {
+    pub kind: PriceScheduleKind,
+    /// sorted by `until`, `Market::price` applies once every tier is closed
+    pub tiers: Vec<PriceTier>,
+}
+
+impl PriceSchedule {
+    pub const LEN: usize = 1 + 4 + (8 + 8) * MAX_PRICE_TIERS_LEN;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum PaymentPrice {
+    Fixed {
+        price: u64,
+    },
+    /// Price is converted from the quote currency of a Pyth price feed on every buy
+    Oracle {
+        price_feed: Pubkey,
+        /// piece price in the feed quote currency, scaled by the feed exponent
+        quote_price: u64,
+        /// max age of the feed price in seconds
+        max_staleness: u64,
+        /// max feed confidence interval relative to the price
+        max_confidence_basis_points: u16,
+        /// feed exponent `quote_price` is scaled by, the feed should still use it on every buy
+        exponent: i32,
+    },
+}
+
+impl PaymentPrice {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 2 + 4;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PaymentMint {
+    pub mint: Pubkey,
+    pub treasury_holder: Pubkey,
+    pub decimals: u8,
+    pub price: PaymentPrice,
+    // need this field to calculate royalties at withdraw, like `Market::funds_collected`
+    pub funds_collected: u64,
+}
+
+impl PaymentMint {
+    pub const LEN: usize = 32 + 32 + 1 + PaymentPrice::LEN + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Raffle {
+    /// keccak256 of the seed revealed by `Market::owner` once entries are closed
+    pub seed_commitment: [u8; 32],
+    /// editions given away, one per winner
+    pub winners: u64,
+    pub entries: u64,
+    /// revealed seed hashed with the hash of `reveal_slot`
+    pub randomness: Option<[u8; 32]>,
+    /// entries passed through the draw, in `RaffleTicket::index` order
+    pub drawn: u64,
+    pub winners_drawn: u64,
+    pub prizes_claimed: u64,
+    /// set once entries are closed, the seed is revealed within `RAFFLE_REVEAL_PERIOD_SLOTS` after it
+    pub reveal_slot: Option<u64>,
+}
+
+impl Raffle {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 32 + 8 + 8 + 8 + 1 + 8;
+
+    /// Every entry is drawn, winners can claim prizes and losers refunds.
+    pub fn is_drawn(&self) -> bool {
+        self.randomness.is_some() && self.drawn == self.entries
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum RaffleTicketState {
+    Entered,
+    Won,
+    Lost,
+    PrizeClaimed,
+    Refunded,
+}
+
+impl Default for RaffleTicketState {
+    fn default() -> Self {
+        RaffleTicketState::Entered
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct RaffleTicket {
+    pub market: Pubkey,
+    pub wallet: Pubkey,
+    pub index: u64,
+    /// amount paid into `Market::treasury_holder` on entry
+    pub deposit: u64,
+    pub state: RaffleTicketState,
+}
+
+impl RaffleTicket {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
 #[account]
 #[derive(Default)]
 pub struct TradeHistory {
     pub market: Pubkey,
     pub wallet: Pubkey,
     pub already_bought: u64,
+    // need this field to calculate refund if `Market` is cancelled
+    pub funds_spent: u64,
+    /// mint `funds_spent` were paid in, one currency per wallet
+    pub currency: Pubkey,
+    /// numbers of editions printed to `wallet` in `market`, only these are refunded
+    pub editions: Vec<u64>,
 }
 
 impl TradeHistory {
-    pub const LEN: usize = 8 + 32 + 32 + 8;
+    /// size without recorded `editions`, account grows by 8 bytes per edition
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 32 + 4;
+    /// size of accounts created before `funds_spent` was added
+    pub const LEN_WITHOUT_FUNDS_SPENT: usize = 8 + 32 + 32 + 8;
+    /// size of accounts created before `currency` was added
+    pub const LEN_WITHOUT_CURRENCY: usize = 8 + 32 + 32 + 8 + 8;
+    /// size of accounts created before `editions` were recorded
+    pub const LEN_WITHOUT_EDITIONS: usize = 8 + 32 + 32 + 8 + 8 + 32;
+
+    /// Size of account holding every recorded edition.
+    pub fn space(&self) -> usize {
+        Self::LEN + self.editions.len() * 8
+    }
 }
 
 #[account]
@@ -120,3 +379,37 @@ pub struct PrimaryMetadataCreators {
 impl PrimaryMetadataCreators {
     pub const LEN: usize = 8 + ((32 + 1 + 1) * MAX_PRIMARY_CREATORS_LEN + 1);
 }
+
+#[account]
+#[derive(Default)]
+pub struct PayoutTicket {
+    pub market: Pubkey,
+    pub funder: Pubkey,
+    /// amount transferred to the funder
+    pub amount: u64,
+    /// amount transferred to `PlatformFee::recipient`
+    pub platform_fee: u64,
+}
+
+impl PayoutTicket {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8;
+}
+
+/// Optional record of a purchase, closed by the buyer to reclaim rent.
+#[account]
+#[derive(Default)]
+pub struct PurchaseReceipt {
+    pub market: Pubkey,
+    pub buyer: Pubkey,
+    /// new edition mint, first one for `BuyMany`, `SellingResource::resource` for `BuyToken`
+    pub mint: Pubkey,
+    pub quantity: u64,
+    /// total amount paid in `currency`
+    pub price: u64,
+    pub currency: Pubkey,
+    pub created_at: i64,
+}
+
+impl PurchaseReceipt {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 32 + 8;
+}
@@ -1,9 +1,19 @@
 //! Module provide runtime utilities
 
-use crate::{id, ErrorCode};
+use crate::{
+    id,
+    state::{
+        Market, PaymentMint, PaymentPrice, PriceSchedule, SellingResource, SellingResourceState,
+    },
+    ErrorCode,
+};
 use anchor_lang::{
     prelude::*,
-    solana_program::{program::invoke_signed, system_instruction},
+    solana_program::{
+        keccak,
+        program::{invoke, invoke_signed},
+        system_instruction,
+    },
 };
 
 pub const NAME_MAX_LEN: usize = 40; // max len of a string buffer in bytes
@@ -15,8 +25,28 @@ pub const HISTORY_PREFIX: &str = "history";
 pub const VAULT_OWNER_PREFIX: &str = "mt_vault";
 pub const PAYOUT_TICKET_PREFIX: &str = "payout_ticket";
 pub const PRIMARY_METADATA_CREATORS_PREFIX: &str = "primary_creators";
+pub const PURCHASE_RECEIPT_PREFIX: &str = "purchase_receipt";
+pub const RAFFLE_TICKET_PREFIX: &str = "raffle_ticket";
 pub const FLAG_ACCOUNT_SIZE: usize = 1; // Size for flag account to indicate something
 pub const MAX_PRIMARY_CREATORS_LEN: usize = 5; // Total allowed creators in `PrimaryMetadataCreators`
+pub const MAX_PRICE_TIERS_LEN: usize = 10; // Total allowed tiers in `PriceSchedule`
+pub const MAX_PLATFORM_FEE_BASIS_POINTS: u16 = 10000; // Platform fee can't exceed whole withdrawal
+pub const MAX_PAYMENT_MINTS_LEN: usize = 3; // Total allowed currencies besides `Market::treasury_mint`
+pub const NATIVE_DECIMALS: u8 = 9;
+pub const RAFFLE_REVEAL_DELAY_SLOTS: u64 = 150; // Slots between closing raffle entries and the slot hash mixed into the seed
+pub const RAFFLE_REVEAL_PERIOD_SLOTS: u64 = 300; // Slots to reveal the seed in, the slot hash is kept in `SlotHashes` for 512
+
+// Pyth price account layout
+pub const PRICE_FEED_MAGIC: u32 = 0xa1b2c3d4;
+pub const PRICE_FEED_TYPE: u32 = 3;
+pub const PRICE_FEED_STATUS_TRADING: u32 = 1;
+pub const PRICE_FEED_LEN: usize = 240;
+pub const PRICE_FEED_TYPE_OFFSET: usize = 8;
+pub const PRICE_FEED_EXPONENT_OFFSET: usize = 20;
+pub const PRICE_FEED_TIMESTAMP_OFFSET: usize = 96;
+pub const PRICE_FEED_PRICE_OFFSET: usize = 208;
+pub const PRICE_FEED_CONF_OFFSET: usize = 216;
+pub const PRICE_FEED_STATUS_OFFSET: usize = 224;
 
 /// Runtime derivation check
 pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u8]]) -> Result<u8> {
@@ -62,18 +92,95 @@ pub fn find_trade_history_address(wallet: &Pubkey, market: &Pubkey) -> (Pubkey,
     )
 }
 
-/// Return payout ticket `Pubkey` and bump seed.
-pub fn find_payout_ticket_address(market: &Pubkey, funder: &Pubkey) -> (Pubkey, u8) {
+/// Return payout ticket `Pubkey` and bump seed, funder has a ticket per currency.
+pub fn find_payout_ticket_address(
+    market: &Pubkey,
+    funder: &Pubkey,
+    treasury_mint: &Pubkey,
+) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
             PAYOUT_TICKET_PREFIX.as_bytes(),
             market.as_ref(),
             funder.as_ref(),
+            treasury_mint.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+/// Return `PurchaseReceipt` `Pubkey` and bump seed, indexed by `TradeHistory::already_bought` before purchase.
+pub fn find_purchase_receipt_address(
+    market: &Pubkey,
+    buyer: &Pubkey,
+    purchase_index: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PURCHASE_RECEIPT_PREFIX.as_bytes(),
+            market.as_ref(),
+            buyer.as_ref(),
+            &purchase_index.to_le_bytes(),
+        ],
+        &id(),
+    )
+}
+
+/// Return `RaffleTicket` `Pubkey` and bump seed, indexed by `Raffle::entries` on entry.
+pub fn find_raffle_ticket_address(market: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            RAFFLE_TICKET_PREFIX.as_bytes(),
+            market.as_ref(),
+            &index.to_le_bytes(),
         ],
         &id(),
     )
 }
 
+/// Decide if raffle entry at `index` wins, selection sampling picks exactly `winners` of `entries`
+/// when entries are drawn in index order.
+pub fn is_raffle_winner(
+    randomness: &[u8; 32],
+    index: u64,
+    entries: u64,
+    winners: u64,
+    winners_drawn: u64,
+) -> Result<bool> {
+    let winners_left = winners.saturating_sub(winners_drawn);
+    let entries_left = entries.checked_sub(index).ok_or(ErrorCode::MathOverflow)?;
+    if entries_left == 0 {
+        return Err(ErrorCode::RaffleTicketInvalid.into());
+    }
+
+    let hash = keccak::hashv(&[randomness, &index.to_le_bytes()]).0;
+    let mut random = [0u8; 8];
+    random.copy_from_slice(&hash[..8]);
+
+    Ok(u64::from_le_bytes(random) % entries_left < winners_left)
+}
+
+/// Return hash of the first slot starting from `slot`, skipped slots have no hash.
+/// `SlotHashes` is a vector of (slot, hash) prefixed by its length, most recent slot first.
+pub fn find_slot_hash(slot_hashes_data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let mut slot_hash = None;
+
+    for entry in slot_hashes_data.get(8..)?.chunks_exact(8 + 32) {
+        let mut entry_slot = [0u8; 8];
+        entry_slot.copy_from_slice(&entry[..8]);
+
+        if u64::from_le_bytes(entry_slot) < slot {
+            break;
+        }
+
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&entry[8..]);
+        slot_hash = Some(hash);
+    }
+
+    slot_hash
+}
+
 /// Return `PrimaryMetadataCreators` `Pubkey` and bump seed.
 pub fn find_primary_metadata_creators(metadata: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
@@ -121,6 +228,54 @@ pub fn sys_transfer<'a>(
     Ok(())
 }
 
+/// Grow `account` created by an older program version to `len`, rent is topped up by `payer`.
+///
+/// Fields are only ever appended and the bytes past the serialized data are zero,
+/// so they decode as `None`, empty or `0` until the caller fixes them up.
+pub fn migrate_account<'a>(
+    account: &AccountInfo<'a>,
+    discriminator: [u8; 8],
+    len: usize,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<()> {
+    if account.data.borrow().get(..8) != Some(&discriminator[..]) {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+
+    if account.data_len() >= len {
+        return Err(ErrorCode::AccountIsMigrated.into());
+    }
+
+    grow_account(account, len, payer, system_program)
+}
+
+/// Realloc `account` to `len` if it's shorter, `payer` tops up rent exemption.
+pub fn grow_account<'a>(
+    account: &AccountInfo<'a>,
+    len: usize,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<()> {
+    if account.data_len() >= len {
+        return Ok(());
+    }
+
+    let lamports = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, lamports),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+
+    account.realloc(len, true)?;
+
+    Ok(())
+}
+
 /// Wrapper of `mint_new_edition_from_master_edition_via_token` instruction from `mpl_token_metadata` program
 #[inline(always)]
 pub fn mpl_mint_new_edition_from_master_edition_via_token<'a>(
@@ -250,6 +405,108 @@ pub fn assert_keys_equal(key1: Pubkey, key2: Pubkey) -> Result<()> {
     }
 }
 
+/// Check `PriceSchedule` length, tiers order and prices
+pub fn assert_price_schedule(price_schedule: &PriceSchedule) -> Result<()> {
+    if price_schedule.tiers.is_empty() || price_schedule.tiers.len() > MAX_PRICE_TIERS_LEN {
+        return Err(ErrorCode::PriceScheduleInvalidLength.into());
+    }
+
+    let mut previous_until = 0;
+    for tier in &price_schedule.tiers {
+        if tier.price == 0 || tier.until <= previous_until {
+            return Err(ErrorCode::PriceScheduleInvalidTier.into());
+        }
+
+        previous_until = tier.until;
+    }
+
+    Ok(())
+}
+
+/// Count `quantity` pieces sold by `market` against its allocation or the unallocated supply
+pub fn take_market_allocation(
+    market: &mut Market,
+    selling_resource: &mut SellingResource,
+    quantity: u64,
+) -> Result<()> {
+    market.sold = market
+        .sold
+        .checked_add(quantity)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if let Some(allocation) = market.allocation {
+        if market.sold > allocation {
+            return Err(ErrorCode::AllocationIsSoldOut.into());
+        }
+
+        selling_resource.allocated = selling_resource
+            .allocated
+            .checked_sub(quantity)
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else if let Some(unallocated) = selling_resource.unallocated() {
+        // pieces reserved by other markets can't be sold
+        if quantity > unallocated {
+            return Err(ErrorCode::SupplyIsGtThanAvailable.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Return unsold allocation of `market` to `selling_resource`, which is free for new markets once every market is released
+pub fn release_market(market: &mut Market, selling_resource: &mut SellingResource) -> Result<()> {
+    if market.released {
+        return Err(ErrorCode::MarketIsReleased.into());
+    }
+
+    if let Some(allocation) = market.allocation {
+        let unsold = allocation
+            .checked_sub(market.sold)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        selling_resource.allocated = selling_resource
+            .allocated
+            .checked_sub(unsold)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    selling_resource.markets = selling_resource
+        .markets
+        .checked_sub(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if selling_resource.markets == 0 && selling_resource.state == SellingResourceState::InUse {
+        selling_resource.state = SellingResourceState::Created;
+    }
+
+    market.released = true;
+
+    Ok(())
+}
+
+/// Return merkle leaf of `wallet` allowed to buy `allocation` pieces in `AllowlistConfig`.
+pub fn allowlist_leaf(wallet: &Pubkey, allocation: u64) -> [u8; 32] {
+    keccak::hashv(&[&[0x00], wallet.as_ref(), &allocation.to_le_bytes()]).0
+}
+
+/// Return parent merkle node of two nodes, hashed in sorted order so proofs don't need positions.
+pub fn allowlist_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        keccak::hashv(&[&[0x01], a, b]).0
+    } else {
+        keccak::hashv(&[&[0x01], b, a]).0
+    }
+}
+
+/// Merkle proof check of `leaf` against `root`
+pub fn verify_allowlist_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof
+        .iter()
+        .fold(leaf, |node, sibling| allowlist_node(&node, sibling));
+
+    computed_root == *root
+}
+
 pub fn calculate_primary_shares_for_creator(total_amount: u64, shares: u64) -> Result<u64> {
     Ok(total_amount
         .checked_mul(shares)
@@ -288,3 +545,109 @@ pub fn calculate_secondary_shares_for_market_owner(
         )
         .ok_or(ErrorCode::MathOverflow)?)
 }
+
+pub fn calculate_platform_fee(amount: u64, basis_points: u16) -> Result<u64> {
+    Ok(amount
+        .checked_mul(basis_points as u64)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::MathOverflow)?)
+}
+
+/// Price of one piece in `payment_mint`, oracle priced mints expect their price feed.
+pub fn calculate_payment_mint_price(
+    payment_mint: &PaymentMint,
+    price_feed: Option<&AccountInfo>,
+    current_time: i64,
+) -> Result<u64> {
+    match payment_mint.price {
+        PaymentPrice::Fixed { price } => Ok(price),
+        PaymentPrice::Oracle {
+            price_feed: price_feed_key,
+            quote_price,
+            max_staleness,
+            max_confidence_basis_points,
+            exponent,
+        } => {
+            let price_feed = price_feed.ok_or(ErrorCode::PriceFeedMissing)?;
+            assert_keys_equal(*price_feed.key, price_feed_key)?;
+
+            convert_oracle_price(
+                &price_feed.try_borrow_data()?,
+                quote_price,
+                max_staleness,
+                max_confidence_basis_points,
+                exponent,
+                payment_mint.decimals,
+                current_time,
+            )
+        }
+    }
+}
+
+/// Amount of token with `decimals` worth `quote_price` by the price feed, rounded up.
+pub fn convert_oracle_price(
+    price_feed_data: &[u8],
+    quote_price: u64,
+    max_staleness: u64,
+    max_confidence_basis_points: u16,
+    exponent: i32,
+    decimals: u8,
+    current_time: i64,
+) -> Result<u64> {
+    let read_u32 = |offset: usize| {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&price_feed_data[offset..offset + 4]);
+        u32::from_le_bytes(bytes)
+    };
+    let read_u64 = |offset: usize| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&price_feed_data[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    };
+
+    if price_feed_data.len() < PRICE_FEED_LEN
+        || read_u32(0) != PRICE_FEED_MAGIC
+        || read_u32(PRICE_FEED_TYPE_OFFSET) != PRICE_FEED_TYPE
+        || read_u32(PRICE_FEED_STATUS_OFFSET) != PRICE_FEED_STATUS_TRADING
+    {
+        return Err(ErrorCode::PriceFeedInvalid.into());
+    }
+
+    // `quote_price` is only comparable to the feed price at the same scale
+    if read_u32(PRICE_FEED_EXPONENT_OFFSET) as i32 != exponent {
+        return Err(ErrorCode::PriceFeedExponentMismatch.into());
+    }
+
+    let price = read_u64(PRICE_FEED_PRICE_OFFSET) as i64;
+    let conf = read_u64(PRICE_FEED_CONF_OFFSET);
+    let timestamp = read_u64(PRICE_FEED_TIMESTAMP_OFFSET) as i64;
+
+    if price <= 0 {
+        return Err(ErrorCode::PriceFeedInvalid.into());
+    }
+
+    if current_time.saturating_sub(timestamp) > max_staleness as i64 {
+        return Err(ErrorCode::PriceFeedIsStale.into());
+    }
+
+    // conf / price > max_confidence_basis_points / 10000
+    if (conf as u128) * 10000 > (max_confidence_basis_points as u128) * (price as u128) {
+        return Err(ErrorCode::PriceFeedConfidenceIsTooWide.into());
+    }
+
+    let amount = (quote_price as u128)
+        .checked_mul(10u128.pow(decimals as u32))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let amount = amount
+        .checked_add(price as u128 - 1)
+        .ok_or(ErrorCode::MathOverflow)?
+        / price as u128;
+
+    if amount > u64::MAX as u128 {
+        return Err(ErrorCode::MathOverflow.into());
+    }
+
+    Ok(amount as u64)
+}
@@ -0,0 +1,504 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod add_payment_mint {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::{create_mint, create_token_account},
+            setup_functions::{setup_selling_resource, setup_store},
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, System, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{Market, PaymentMint, PaymentPrice, PriceSchedule, PriceScheduleKind, PriceTier},
+        utils::find_treasury_owner_address,
+    };
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        signature::Keypair,
+        signer::Signer,
+        system_program,
+        sysvar::clock::Clock,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _) = setup_selling_resource(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            100,
+            None,
+            true,
+            false,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // AddPaymentMint, native SOL treasury holder is PDA
+        let payment_mint = System::id();
+        let (payment_treasury_owner, payment_treasury_owner_bump) =
+            find_treasury_owner_address(&payment_mint, &selling_resource_keypair.pubkey());
+
+        let accounts = mpl_fixed_price_sale_accounts::AddPaymentMint {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            mint: payment_mint,
+            treasury_holder: payment_treasury_owner,
+            treasury_owner: payment_treasury_owner,
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::AddPaymentMint {
+            _treasury_owner_bump: payment_treasury_owner_bump,
+            price: PaymentPrice::Fixed { price: 2_000_000 },
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+        assert_eq!(
+            market_data.payment_mints,
+            vec![PaymentMint {
+                mint: payment_mint,
+                treasury_holder: payment_treasury_owner,
+                decimals: 9,
+                price: PaymentPrice::Fixed { price: 2_000_000 },
+                funds_collected: 0,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_payment_mint_is_already_accepted() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _) = setup_selling_resource(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            100,
+            None,
+            true,
+            false,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // AddPaymentMint, `Market::treasury_mint` is accepted already
+        let accounts = mpl_fixed_price_sale_accounts::AddPaymentMint {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            treasury_owner,
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::AddPaymentMint {
+            _treasury_owner_bump: treasyry_owner_bump,
+            price: PaymentPrice::Fixed { price: 2_000_000 },
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::PaymentMintIsAlreadyAccepted as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_price_schedule() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _) = setup_selling_resource(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            100,
+            None,
+            true,
+            false,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: Some(PriceSchedule {
+                kind: PriceScheduleKind::BySupply,
+                tiers: vec![PriceTier {
+                    until: 10,
+                    price: 500_000,
+                }],
+            }),
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // AddPaymentMint, market prices pieces by schedule
+        let payment_mint = System::id();
+        let (payment_treasury_owner, payment_treasury_owner_bump) =
+            find_treasury_owner_address(&payment_mint, &selling_resource_keypair.pubkey());
+
+        let accounts = mpl_fixed_price_sale_accounts::AddPaymentMint {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            mint: payment_mint,
+            treasury_holder: payment_treasury_owner,
+            treasury_owner: payment_treasury_owner,
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::AddPaymentMint {
+            _treasury_owner_bump: payment_treasury_owner_bump,
+            price: PaymentPrice::Fixed { price: 2_000_000 },
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::PriceScheduleWithPaymentMints as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
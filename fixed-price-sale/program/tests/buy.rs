@@ -6,8 +6,8 @@ mod buy {
         setup_context,
         utils::{
             helpers::{
-                airdrop, create_collection, create_master_nft, create_mint, create_token_account,
-                mint_to,
+                airdrop, create_collection, create_master_nft, create_mint, create_price_feed,
+                create_token_account, mint_to,
             },
             setup_functions::{setup_selling_resource, setup_store},
         },
@@ -19,8 +19,13 @@ mod buy {
         accounts as mpl_fixed_price_sale_accounts,
         error::ErrorCode,
         instruction as mpl_fixed_price_sale_instruction,
-        state::{GatingConfig, SellingResource, TradeHistory},
+        state::{
+            AllowlistConfig, AllowlistProof, GatingConfig, Market, MarketState, PaymentPrice,
+            PriceSchedule, PriceScheduleKind, PriceTier, PurchaseReceipt, SellingResource,
+            TradeHistory,
+        },
         utils::{
+            allowlist_leaf, allowlist_node, find_purchase_receipt_address,
             find_trade_history_address, find_treasury_owner_address, find_vault_owner_address,
         },
     };
@@ -123,6 +128,9 @@ mod buy {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -283,6 +291,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -327,6 +336,2644 @@ mod buy {
         assert_eq!(trade_history_data.already_bought, 1);
     }
 
+    #[tokio::test]
+    async fn success_allocation() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: Some(1),
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &treasury_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &treasury_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // Checks
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource_data =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        assert_eq!(selling_resource_data.supply, 1);
+        assert_eq!(selling_resource_data.allocated, 0);
+        assert_eq!(trade_history_data.already_bought, 1);
+        assert_eq!(market_data.sold, 1);
+        assert_eq!(market_data.state, MarketState::Ended);
+    }
+
+    #[tokio::test]
+    async fn success_purchase_receipt() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &treasury_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &treasury_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (purchase_receipt, _) =
+            find_purchase_receipt_address(&market_keypair.pubkey(), &payer_pubkey, 0);
+
+        // Buy
+        let mut accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new(purchase_receipt, false));
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // Checks
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource_data =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        let purchase_receipt_acc = context
+            .banks_client
+            .get_account(purchase_receipt)
+            .await
+            .unwrap()
+            .unwrap();
+        let purchase_receipt_data =
+            PurchaseReceipt::try_deserialize(&mut purchase_receipt_acc.data.as_ref()).unwrap();
+
+        assert_eq!(selling_resource_data.supply, 1);
+        assert_eq!(trade_history_data.already_bought, 1);
+        assert_eq!(purchase_receipt_data.market, market_keypair.pubkey());
+        assert_eq!(purchase_receipt_data.buyer, payer_pubkey);
+        assert_eq!(purchase_receipt_data.mint, new_mint_keypair.pubkey());
+        assert_eq!(purchase_receipt_data.quantity, 1);
+        assert_eq!(purchase_receipt_data.price, price);
+        assert_eq!(
+            purchase_receipt_data.currency,
+            treasury_mint_keypair.pubkey()
+        );
+    }
+
+    #[tokio::test]
+    async fn success_payment_mint_oracle_price() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // AddPaymentMint
+        let payment_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &payment_mint_keypair,
+            &admin_wallet.pubkey(),
+            6,
+        )
+        .await;
+
+        let (payment_treasury_owner, payment_treasury_owner_bump) = find_treasury_owner_address(
+            &payment_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let payment_treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &payment_treasury_holder_keypair,
+            &payment_mint_keypair.pubkey(),
+            &payment_treasury_owner,
+        )
+        .await;
+
+        let price_feed = Pubkey::new_unique();
+
+        let accounts = mpl_fixed_price_sale_accounts::AddPaymentMint {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            mint: payment_mint_keypair.pubkey(),
+            treasury_holder: payment_treasury_holder_keypair.pubkey(),
+            treasury_owner: payment_treasury_owner,
+        }
+        .to_account_metas(None);
+
+        // piece costs 10.0 in the feed quote currency, feed exponent is -8
+        let data = mpl_fixed_price_sale_instruction::AddPaymentMint {
+            _treasury_owner_bump: payment_treasury_owner_bump,
+            price: PaymentPrice::Oracle {
+                price_feed,
+                quote_price: 1_000_000_000,
+                max_staleness: 60,
+                max_confidence_basis_points: 100,
+                exponent: -8,
+            },
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Payment mint costs 20.0
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        create_price_feed(
+            &mut context,
+            &price_feed,
+            2_000_000_000,
+            1_000_000,
+            -8,
+            clock.unix_timestamp,
+        );
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &payment_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &payment_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let mut accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: payment_treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new_readonly(price_feed, false));
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Checks
+        let payment_treasury_holder_acc = context
+            .banks_client
+            .get_account(payment_treasury_holder_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let payment_treasury_holder_data =
+            spl_token::state::Account::unpack(&payment_treasury_holder_acc.data).unwrap();
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        // 10.0 / 20.0 of payment mint with 6 decimals
+        assert_eq!(payment_treasury_holder_data.amount, 500_000);
+        assert_eq!(market_data.payment_mints[0].funds_collected, 500_000);
+        assert_eq!(market_data.funds_collected, 0);
+        assert_eq!(trade_history_data.already_bought, 1);
+        assert_eq!(trade_history_data.funds_spent, 500_000);
+        assert_eq!(trade_history_data.currency, payment_mint_keypair.pubkey());
+    }
+
+    #[tokio::test]
+    async fn fail_price_feed_is_stale() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // AddPaymentMint
+        let payment_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &payment_mint_keypair,
+            &admin_wallet.pubkey(),
+            6,
+        )
+        .await;
+
+        let (payment_treasury_owner, payment_treasury_owner_bump) = find_treasury_owner_address(
+            &payment_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let payment_treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &payment_treasury_holder_keypair,
+            &payment_mint_keypair.pubkey(),
+            &payment_treasury_owner,
+        )
+        .await;
+
+        let price_feed = Pubkey::new_unique();
+
+        let accounts = mpl_fixed_price_sale_accounts::AddPaymentMint {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            mint: payment_mint_keypair.pubkey(),
+            treasury_holder: payment_treasury_holder_keypair.pubkey(),
+            treasury_owner: payment_treasury_owner,
+        }
+        .to_account_metas(None);
+
+        // piece costs 10.0 in the feed quote currency, feed exponent is -8
+        let data = mpl_fixed_price_sale_instruction::AddPaymentMint {
+            _treasury_owner_bump: payment_treasury_owner_bump,
+            price: PaymentPrice::Oracle {
+                price_feed,
+                quote_price: 1_000_000_000,
+                max_staleness: 60,
+                max_confidence_basis_points: 100,
+                exponent: -8,
+            },
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Payment mint costs 20.0
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        create_price_feed(
+            &mut context,
+            &price_feed,
+            2_000_000_000,
+            1_000_000,
+            -8,
+            clock.unix_timestamp - 120,
+        );
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &payment_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &payment_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let mut accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: payment_treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new_readonly(price_feed, false));
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::PriceFeedIsStale as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_price_feed_exponent_mismatch() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // AddPaymentMint
+        let payment_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &payment_mint_keypair,
+            &admin_wallet.pubkey(),
+            6,
+        )
+        .await;
+
+        let (payment_treasury_owner, payment_treasury_owner_bump) = find_treasury_owner_address(
+            &payment_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let payment_treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &payment_treasury_holder_keypair,
+            &payment_mint_keypair.pubkey(),
+            &payment_treasury_owner,
+        )
+        .await;
+
+        let price_feed = Pubkey::new_unique();
+
+        let accounts = mpl_fixed_price_sale_accounts::AddPaymentMint {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            mint: payment_mint_keypair.pubkey(),
+            treasury_holder: payment_treasury_holder_keypair.pubkey(),
+            treasury_owner: payment_treasury_owner,
+        }
+        .to_account_metas(None);
+
+        // piece costs 10.0 in the feed quote currency, feed exponent is -8
+        let data = mpl_fixed_price_sale_instruction::AddPaymentMint {
+            _treasury_owner_bump: payment_treasury_owner_bump,
+            price: PaymentPrice::Oracle {
+                price_feed,
+                quote_price: 1_000_000_000,
+                max_staleness: 60,
+                max_confidence_basis_points: 100,
+                exponent: -8,
+            },
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Feed is rescaled, 20.0 would read as 2000.0 with the exponent of quote price
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        create_price_feed(
+            &mut context,
+            &price_feed,
+            20_000_000,
+            10_000,
+            -6,
+            clock.unix_timestamp,
+        );
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &payment_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &payment_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let mut accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: payment_treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new_readonly(price_feed, false));
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::PriceFeedExponentMismatch as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn success_allowlist() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = None;
+
+        let payer_pubkey = context.payer.pubkey();
+        let other_leaf = allowlist_leaf(&Keypair::new().pubkey(), 2);
+        let merkle_root = allowlist_node(&allowlist_leaf(&payer_pubkey, 1), &other_leaf);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: Some(AllowlistConfig {
+                merkle_root,
+                gating_time: None,
+            }),
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &treasury_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &treasury_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: Some(AllowlistProof {
+                allocation: 1,
+                proof: vec![other_leaf],
+            }),
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // Checks
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource_data =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        assert_eq!(selling_resource_data.supply, 1);
+        assert_eq!(trade_history_data.already_bought, 1);
+    }
+
+    #[tokio::test]
+    async fn fail_allowlist_allocation_is_not_in_tree() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = None;
+
+        let payer_pubkey = context.payer.pubkey();
+        let other_leaf = allowlist_leaf(&Keypair::new().pubkey(), 2);
+        let merkle_root = allowlist_node(&allowlist_leaf(&payer_pubkey, 1), &other_leaf);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: Some(AllowlistConfig {
+                merkle_root,
+                gating_time: None,
+            }),
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &treasury_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &treasury_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: Some(AllowlistProof {
+                allocation: 2,
+                proof: vec![other_leaf],
+            }),
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::AllowlistProofInvalid as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn success_price_schedule_by_supply() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: Some(PriceSchedule {
+                kind: PriceScheduleKind::BySupply,
+                tiers: vec![PriceTier {
+                    until: 1,
+                    price: 400_000,
+                }],
+            }),
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &treasury_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &treasury_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // Checks
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource_data =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        let user_token_account_acc = context
+            .banks_client
+            .get_account(user_token_account.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let user_token_account_data =
+            spl_token::state::Account::unpack(&user_token_account_acc.data).unwrap();
+
+        // First piece is sold at the tier price, not at `Market::price`
+        assert_eq!(selling_resource_data.supply, 1);
+        assert_eq!(trade_history_data.already_bought, 1);
+        assert_eq!(market_data.funds_collected, 400_000);
+        assert_eq!(user_token_account_data.amount, 600_000);
+    }
+
     #[tokio::test]
     async fn success_native_sol() {
         setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
@@ -395,6 +3042,9 @@ mod buy {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -540,6 +3190,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -670,6 +3321,9 @@ mod buy {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -827,6 +3481,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -944,6 +3599,9 @@ mod buy {
             start_date: start_date as u64,
             end_date: Some(end_date as u64),
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -1104,6 +3762,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -1219,6 +3878,9 @@ mod buy {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -1404,6 +4066,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -1519,6 +4182,9 @@ mod buy {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -1704,6 +4370,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -1819,6 +4486,9 @@ mod buy {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -1979,6 +4649,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -2106,6 +4777,9 @@ mod buy {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -2266,6 +4940,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -2380,6 +5055,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -2490,6 +5166,9 @@ mod buy {
                 expire_on_use: true,
                 gating_time: None,
             }),
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -2661,6 +5340,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -2802,6 +5482,9 @@ mod buy {
                 expire_on_use: false,
                 gating_time: None,
             }),
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -2973,6 +5656,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -3114,6 +5798,9 @@ mod buy {
                 expire_on_use: false,
                 gating_time: None,
             }),
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -3285,6 +5972,7 @@ mod buy {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -0,0 +1,584 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod buy_many {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::{airdrop, create_mint, create_token_account, mint_to},
+            setup_functions::{setup_selling_resource, setup_store},
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{SellingResource, TradeHistory},
+        utils::{
+            find_trade_history_address, find_treasury_owner_address, find_vault_owner_address,
+        },
+    };
+    use solana_program::{clock::Clock, instruction::AccountMeta};
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        system_program, sysvar,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    #[tokio::test]
+    async fn success_native_sol() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(2);
+        let quantity = 2;
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = user_wallet.pubkey();
+
+        // Accounts of new editions, in `remaining_accounts` order
+        let mut new_editions_accounts = Vec::new();
+        for edition in 1..=quantity {
+            let new_mint_keypair = Keypair::new();
+            create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+            let new_mint_token_account = Keypair::new();
+            create_token_account(
+                &mut context,
+                &new_mint_token_account,
+                &new_mint_keypair.pubkey(),
+                &payer_pubkey,
+            )
+            .await;
+
+            mint_to(
+                &mut context,
+                &new_mint_keypair.pubkey(),
+                &new_mint_token_account.pubkey(),
+                &user_wallet,
+                1,
+            )
+            .await;
+
+            let (new_metadata, _) = Pubkey::find_program_address(
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    new_mint_keypair.pubkey().as_ref(),
+                ],
+                &mpl_token_metadata::id(),
+            );
+
+            let (new_edition, _) = Pubkey::find_program_address(
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    new_mint_keypair.pubkey().as_ref(),
+                    mpl_token_metadata::state::EDITION.as_bytes(),
+                ],
+                &mpl_token_metadata::id(),
+            );
+
+            let (edition_marker, _) = Pubkey::find_program_address(
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    selling_resource.resource.as_ref(),
+                    mpl_token_metadata::state::EDITION.as_bytes(),
+                    (edition / mpl_token_metadata::state::EDITION_MARKER_BIT_SIZE)
+                        .to_string()
+                        .as_bytes(),
+                ],
+                &mpl_token_metadata::id(),
+            );
+
+            new_editions_accounts.push(AccountMeta::new(new_mint_keypair.pubkey(), false));
+            new_editions_accounts.push(AccountMeta::new(new_metadata, false));
+            new_editions_accounts.push(AccountMeta::new(new_edition, false));
+            new_editions_accounts.push(AccountMeta::new(edition_marker, false));
+            new_editions_accounts.push(AccountMeta::new(new_mint_token_account.pubkey(), false));
+        }
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // BuyMany
+        let mut accounts = mpl_fixed_price_sale_accounts::BuyMany {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_wallet.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            master_edition,
+            vault: selling_resource.vault,
+            owner,
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.extend(new_editions_accounts);
+
+        let data = mpl_fixed_price_sale_instruction::BuyMany {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            quantity,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        let treasury_balance_before = context
+            .banks_client
+            .get_balance(treasury_owner)
+            .await
+            .unwrap();
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // Checks
+        let treasury_balance_after = context
+            .banks_client
+            .get_balance(treasury_owner)
+            .await
+            .unwrap();
+
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource_data =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        assert_eq!(
+            treasury_balance_after - treasury_balance_before,
+            price * quantity
+        );
+        assert_eq!(selling_resource_data.supply, quantity);
+        assert_eq!(trade_history_data.already_bought, quantity);
+        assert_eq!(trade_history_data.funds_spent, price * quantity);
+    }
+
+    #[tokio::test]
+    async fn fail_user_reach_buy_limit() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+        let quantity = 2;
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = user_wallet.pubkey();
+
+        // Accounts of new editions, in `remaining_accounts` order
+        let mut new_editions_accounts = Vec::new();
+        for edition in 1..=quantity {
+            let new_mint_keypair = Keypair::new();
+            create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+            let new_mint_token_account = Keypair::new();
+            create_token_account(
+                &mut context,
+                &new_mint_token_account,
+                &new_mint_keypair.pubkey(),
+                &payer_pubkey,
+            )
+            .await;
+
+            mint_to(
+                &mut context,
+                &new_mint_keypair.pubkey(),
+                &new_mint_token_account.pubkey(),
+                &user_wallet,
+                1,
+            )
+            .await;
+
+            let (new_metadata, _) = Pubkey::find_program_address(
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    new_mint_keypair.pubkey().as_ref(),
+                ],
+                &mpl_token_metadata::id(),
+            );
+
+            let (new_edition, _) = Pubkey::find_program_address(
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    new_mint_keypair.pubkey().as_ref(),
+                    mpl_token_metadata::state::EDITION.as_bytes(),
+                ],
+                &mpl_token_metadata::id(),
+            );
+
+            let (edition_marker, _) = Pubkey::find_program_address(
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    selling_resource.resource.as_ref(),
+                    mpl_token_metadata::state::EDITION.as_bytes(),
+                    (edition / mpl_token_metadata::state::EDITION_MARKER_BIT_SIZE)
+                        .to_string()
+                        .as_bytes(),
+                ],
+                &mpl_token_metadata::id(),
+            );
+
+            new_editions_accounts.push(AccountMeta::new(new_mint_keypair.pubkey(), false));
+            new_editions_accounts.push(AccountMeta::new(new_metadata, false));
+            new_editions_accounts.push(AccountMeta::new(new_edition, false));
+            new_editions_accounts.push(AccountMeta::new(edition_marker, false));
+            new_editions_accounts.push(AccountMeta::new(new_mint_token_account.pubkey(), false));
+        }
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // BuyMany
+        let mut accounts = mpl_fixed_price_sale_accounts::BuyMany {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_wallet.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            master_edition,
+            vault: selling_resource.vault,
+            owner,
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.extend(new_editions_accounts);
+
+        let data = mpl_fixed_price_sale_instruction::BuyMany {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            quantity,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::UserReachBuyLimit as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
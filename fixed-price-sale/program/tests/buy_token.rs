@@ -0,0 +1,576 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod buy_token {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::{airdrop, create_token_account},
+            setup_functions::{setup_selling_resource, setup_store, setup_token_selling_resource},
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{SellingResource, TradeHistory},
+        utils::{
+            find_trade_history_address, find_treasury_owner_address, find_vault_owner_address,
+        },
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        program_pack::Pack,
+        signature::Keypair,
+        signer::Signer,
+        system_program, sysvar,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    #[tokio::test]
+    async fn success_native_sol() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, vault) =
+            setup_token_selling_resource(&mut context, &admin_wallet, &store_keypair, 10, 5).await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(2);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // BuyToken setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let destination = Keypair::new();
+        create_token_account(
+            &mut context,
+            &destination,
+            &selling_resource.resource,
+            &user_wallet.pubkey(),
+        )
+        .await;
+
+        // BuyToken
+        let accounts = mpl_fixed_price_sale_accounts::BuyToken {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_wallet.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            vault: selling_resource.vault,
+            owner,
+            destination: destination.pubkey(),
+            clock: sysvar::clock::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::BuyToken {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            quantity: 2,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // Checks
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource_data =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        let destination_acc = context
+            .banks_client
+            .get_account(destination.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let destination_data = spl_token::state::Account::unpack(&destination_acc.data).unwrap();
+
+        let vault_acc = context
+            .banks_client
+            .get_account(vault.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let vault_data = spl_token::state::Account::unpack(&vault_acc.data).unwrap();
+
+        assert_eq!(selling_resource_data.supply, 2);
+        assert_eq!(trade_history_data.already_bought, 2);
+        assert_eq!(trade_history_data.funds_spent, price * 2);
+        assert_eq!(destination_data.amount, 20);
+        assert_eq!(vault_data.amount, 30);
+    }
+
+    #[tokio::test]
+    async fn fail_wrong_selling_resource_kind() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // BuyToken setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let destination = Keypair::new();
+        create_token_account(
+            &mut context,
+            &destination,
+            &selling_resource.resource,
+            &user_wallet.pubkey(),
+        )
+        .await;
+
+        // BuyToken
+        let accounts = mpl_fixed_price_sale_accounts::BuyToken {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_wallet.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            vault: selling_resource.vault,
+            owner,
+            destination: destination.pubkey(),
+            clock: sysvar::clock::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::BuyToken {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            quantity: 2,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::WrongSellingResourceKind as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_foreign_market() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, _selling_resource_owner_keypair, _vault) =
+            setup_token_selling_resource(&mut context, &admin_wallet, &store_keypair, 10, 5).await;
+
+        // Attacker's own selling resource and cheap market
+        let (attacker_selling_resource_keypair, attacker_keypair, _attacker_vault) =
+            setup_token_selling_resource(&mut context, &admin_wallet, &store_keypair, 1, 1).await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint,
+            &attacker_selling_resource_keypair.pubkey(),
+        );
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: attacker_keypair.pubkey(),
+            selling_resource: attacker_selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: "Marktname".to_string(),
+            description: "Marktbeschreibung".to_string(),
+            mutable: true,
+            price: 1,
+            pieces_in_one_wallet: None,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &market_keypair, &attacker_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // BuyToken setup, with the victim's selling resource and vault
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&attacker_keypair.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let destination = Keypair::new();
+        create_token_account(
+            &mut context,
+            &destination,
+            &selling_resource.resource,
+            &attacker_keypair.pubkey(),
+        )
+        .await;
+
+        // BuyToken
+        let accounts = mpl_fixed_price_sale_accounts::BuyToken {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: attacker_keypair.pubkey(),
+            user_wallet: attacker_keypair.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            vault: selling_resource.vault,
+            owner,
+            destination: destination.pubkey(),
+            clock: sysvar::clock::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::BuyToken {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            quantity: 5,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &attacker_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    anchor_lang::error::ErrorCode::ConstraintHasOne as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
@@ -0,0 +1,263 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod claim_raffle_prize {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::{create_mint, create_token_account, mint_to},
+            setup_functions::{
+                setup_raffle_draw, setup_raffle_entry, setup_raffle_market, setup_selling_resource,
+                setup_store,
+            },
+        },
+    };
+    use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
+        state::{Market, RaffleTicket, RaffleTicketState, SellingResource, TradeHistory},
+        utils::{find_trade_history_address, find_vault_owner_address},
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::Instruction, program_pack::Pack, pubkey::Pubkey, signature::Keypair,
+        signer::Signer, system_program, sysvar, transaction::Transaction,
+    };
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, treasury_mint_keypair, treasury_holder_keypair) = setup_raffle_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            &seed,
+            1,
+        )
+        .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // EnterRaffle, the only entry wins
+        let (user_wallet, raffle_ticket) = setup_raffle_entry(
+            &mut context,
+            &admin_wallet,
+            &market_keypair,
+            &treasury_mint_keypair,
+            &treasury_holder_keypair,
+            0,
+        )
+        .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // CloseRaffleEntries, RevealRaffleSeed and DrawRaffle
+        setup_raffle_draw(
+            &mut context,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+            &seed,
+            &[raffle_ticket],
+        )
+        .await;
+
+        // ClaimRafflePrize setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &user_wallet.pubkey(),
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &user_wallet,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // ClaimRafflePrize
+        let accounts = mpl_fixed_price_sale_accounts::ClaimRafflePrize {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            raffle_ticket,
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::ClaimRafflePrize {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Checks
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        assert_eq!(market_data.raffle.unwrap().prizes_claimed, 1);
+
+        let raffle_ticket_acc = context
+            .banks_client
+            .get_account(raffle_ticket)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let raffle_ticket_data =
+            RaffleTicket::try_deserialize(&mut raffle_ticket_acc.data.as_ref()).unwrap();
+
+        assert_eq!(raffle_ticket_data.state, RaffleTicketState::PrizeClaimed);
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        assert_eq!(trade_history_data.already_bought, 1);
+
+        let new_mint_token_account_acc = context
+            .banks_client
+            .get_account(new_mint_token_account.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let new_mint_token_account_data =
+            spl_token::state::Account::unpack(&new_mint_token_account_acc.data).unwrap();
+
+        assert_eq!(new_mint_token_account_data.amount, 1);
+    }
+}
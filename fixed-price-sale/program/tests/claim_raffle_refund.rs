@@ -0,0 +1,199 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod claim_raffle_refund {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::create_token_account,
+            setup_functions::{
+                setup_raffle_draw, setup_raffle_entry, setup_raffle_market, setup_selling_resource,
+                setup_store,
+            },
+        },
+    };
+    use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
+        state::{RaffleTicket, RaffleTicketState, TradeHistory},
+        utils::{find_trade_history_address, find_treasury_owner_address},
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::Instruction, program_pack::Pack, signature::Keypair, signer::Signer,
+        system_program, transaction::Transaction,
+    };
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, treasury_mint_keypair, treasury_holder_keypair) = setup_raffle_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            &seed,
+            1,
+        )
+        .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // EnterRaffle
+        let mut entries = Vec::new();
+        for index in 0..2 {
+            entries.push(
+                setup_raffle_entry(
+                    &mut context,
+                    &admin_wallet,
+                    &market_keypair,
+                    &treasury_mint_keypair,
+                    &treasury_holder_keypair,
+                    index,
+                )
+                .await,
+            );
+        }
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // CloseRaffleEntries, RevealRaffleSeed and DrawRaffle
+        let raffle_tickets = entries
+            .iter()
+            .map(|(_, raffle_ticket)| *raffle_ticket)
+            .collect::<Vec<_>>();
+        setup_raffle_draw(
+            &mut context,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+            &seed,
+            &raffle_tickets,
+        )
+        .await;
+
+        // Find the loser
+        let mut loser = None;
+        for (user_wallet, raffle_ticket) in entries {
+            let raffle_ticket_acc = context
+                .banks_client
+                .get_account(raffle_ticket)
+                .await
+                .expect("account not found")
+                .expect("account empty");
+
+            let raffle_ticket_data =
+                RaffleTicket::try_deserialize(&mut raffle_ticket_acc.data.as_ref()).unwrap();
+
+            if raffle_ticket_data.state == RaffleTicketState::Lost {
+                loser = Some((user_wallet, raffle_ticket));
+            }
+        }
+        let (user_wallet, raffle_ticket) = loser.unwrap();
+
+        let destination = Keypair::new();
+        create_token_account(
+            &mut context,
+            &destination,
+            &treasury_mint_keypair.pubkey(),
+            &user_wallet.pubkey(),
+        )
+        .await;
+
+        // ClaimRaffleRefund
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (treasury_owner, treasury_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let accounts = mpl_fixed_price_sale_accounts::ClaimRaffleRefund {
+            market: market_keypair.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            raffle_ticket,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            destination: destination.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::ClaimRaffleRefund {
+            _trade_history_bump: trade_history_bump,
+            treasury_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Checks
+        let destination_acc = context
+            .banks_client
+            .get_account(destination.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let destination_data = spl_token::state::Account::unpack(&destination_acc.data).unwrap();
+
+        assert_eq!(destination_data.amount, 1_000_000);
+
+        let raffle_ticket_acc = context
+            .banks_client
+            .get_account(raffle_ticket)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let raffle_ticket_data =
+            RaffleTicket::try_deserialize(&mut raffle_ticket_acc.data.as_ref()).unwrap();
+
+        assert_eq!(raffle_ticket_data.state, RaffleTicketState::Refunded);
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        assert_eq!(trade_history_data.funds_spent, 0);
+    }
+}
@@ -0,0 +1,1062 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod claim_refund {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::{airdrop, create_mint, create_token_account, mint_to},
+            setup_functions::{setup_selling_resource, setup_store},
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{SellingResource, TradeHistory},
+        utils::{
+            find_trade_history_address, find_treasury_owner_address, find_vault_owner_address,
+        },
+    };
+    use solana_program::{clock::Clock, instruction::AccountMeta};
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        system_program, sysvar,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    /// Buy an edition with `user_wallet`, return its mint, token account and edition.
+    async fn buy_edition(
+        context: &mut ProgramTestContext,
+        market: &Pubkey,
+        selling_resource_address: &Pubkey,
+        treasury_holder: &Pubkey,
+        user_wallet: &Keypair,
+    ) -> (Pubkey, Pubkey, Pubkey) {
+        let selling_resource_data = context
+            .banks_client
+            .get_account(*selling_resource_address)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), market);
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &user_wallet.pubkey(),
+        )
+        .await;
+
+        mint_to(
+            context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            user_wallet,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let edition = selling_resource.supply + 1;
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                (edition / mpl_token_metadata::state::EDITION_MARKER_BIT_SIZE)
+                    .to_string()
+                    .as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: *market,
+            selling_resource: *selling_resource_address,
+            user_token_account: user_wallet.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: *treasury_holder,
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, user_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        (
+            new_mint_keypair.pubkey(),
+            new_mint_token_account.pubkey(),
+            new_edition,
+        )
+    }
+
+    #[tokio::test]
+    async fn success_native_sol() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = user_wallet.pubkey();
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let _payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &user_wallet,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_wallet.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // CancelMarket
+        let accounts = mpl_fixed_price_sale_accounts::CancelMarket {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CancelMarket {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        let user_balance_before = context
+            .banks_client
+            .get_balance(user_wallet.pubkey())
+            .await
+            .unwrap();
+
+        // ClaimRefund
+        let mut accounts = mpl_fixed_price_sale_accounts::ClaimRefund {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            treasury_mint,
+            owner: treasury_owner,
+            destination: user_wallet.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        accounts.push(AccountMeta::new(new_mint_token_account.pubkey(), false));
+        accounts.push(AccountMeta::new(new_mint_keypair.pubkey(), false));
+        accounts.push(AccountMeta::new_readonly(new_edition, false));
+
+        let data = mpl_fixed_price_sale_instruction::ClaimRefund {
+            _trade_history_bump: trade_history_bump,
+            treasury_owner_bump: treasyry_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Checks
+        let user_balance_after = context
+            .banks_client
+            .get_balance(user_wallet.pubkey())
+            .await
+            .unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        let new_mint_token_account_acc = context
+            .banks_client
+            .get_account(new_mint_token_account.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let new_mint_token_account_data =
+            spl_token::state::Account::unpack(&new_mint_token_account_acc.data).unwrap();
+
+        assert_eq!(user_balance_after - user_balance_before, price);
+        assert_eq!(trade_history_data.already_bought, 0);
+        assert_eq!(trade_history_data.funds_spent, 0);
+        assert!(trade_history_data.editions.is_empty());
+        assert_eq!(new_mint_token_account_data.amount, 0);
+    }
+
+    #[tokio::test]
+    async fn fail_market_is_not_cancelled() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = user_wallet.pubkey();
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let _payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &user_wallet,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_wallet.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        let user_balance_before = context
+            .banks_client
+            .get_balance(user_wallet.pubkey())
+            .await
+            .unwrap();
+
+        // ClaimRefund
+        let mut accounts = mpl_fixed_price_sale_accounts::ClaimRefund {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            treasury_mint,
+            owner: treasury_owner,
+            destination: user_wallet.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        accounts.push(AccountMeta::new(new_mint_token_account.pubkey(), false));
+        accounts.push(AccountMeta::new(new_mint_keypair.pubkey(), false));
+        accounts.push(AccountMeta::new_readonly(new_edition, false));
+
+        let data = mpl_fixed_price_sale_instruction::ClaimRefund {
+            _trade_history_bump: trade_history_bump,
+            treasury_owner_bump: treasyry_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::MarketIsNotCancelled as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_edition_bought_by_other_wallet() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: "Marktname".to_string(),
+            description: "Marktbeschreibung".to_string(),
+            mutable: true,
+            price: 1_000_000,
+            pieces_in_one_wallet: None,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Both wallets buy an edition
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let other_wallet = Keypair::new();
+        airdrop(&mut context, &other_wallet.pubkey(), 1_000_000_000).await;
+
+        buy_edition(
+            &mut context,
+            &market_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+            &treasury_owner,
+            &user_wallet,
+        )
+        .await;
+
+        let (other_mint, other_token_account, other_edition) = buy_edition(
+            &mut context,
+            &market_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+            &treasury_owner,
+            &other_wallet,
+        )
+        .await;
+
+        // Other wallet's edition is passed to user
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &other_mint,
+            &user_wallet.pubkey(),
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[spl_token::instruction::transfer(
+                &spl_token::id(),
+                &other_token_account,
+                &user_token_account.pubkey(),
+                &other_wallet.pubkey(),
+                &[],
+                1,
+            )
+            .unwrap()],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &other_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // CancelMarket
+        let accounts = mpl_fixed_price_sale_accounts::CancelMarket {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CancelMarket {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // ClaimRefund with edition user didn't buy
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+
+        let mut accounts = mpl_fixed_price_sale_accounts::ClaimRefund {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            treasury_mint,
+            owner: treasury_owner,
+            destination: user_wallet.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        accounts.push(AccountMeta::new(user_token_account.pubkey(), false));
+        accounts.push(AccountMeta::new(other_mint, false));
+        accounts.push(AccountMeta::new_readonly(other_edition, false));
+
+        let data = mpl_fixed_price_sale_instruction::ClaimRefund {
+            _trade_history_bump: trade_history_bump,
+            treasury_owner_bump: treasyry_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::WrongRefundEdition as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
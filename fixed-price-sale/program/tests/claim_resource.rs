@@ -116,6 +116,9 @@ mod claim_resource {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -315,6 +318,7 @@ mod claim_resource {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -365,6 +369,7 @@ mod claim_resource {
         let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
             &market_keypair.pubkey(),
             &primary_royalties_holder.pubkey(),
+            &treasury_mint_keypair.pubkey(),
         );
 
         let destination = spl_associated_token_account::get_associated_token_address(
@@ -383,12 +388,14 @@ mod claim_resource {
 
         let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
             market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
             metadata,
             treasury_holder: treasury_holder_keypair.pubkey(),
             treasury_mint: treasury_mint_keypair.pubkey(),
             owner: treasury_owner,
             destination,
+            platform_fee_destination: destination,
             funder: primary_royalties_holder.pubkey(),
             payer: payer_pubkey,
             payout_ticket,
@@ -535,6 +542,9 @@ mod claim_resource {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -719,6 +729,7 @@ mod claim_resource {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -769,6 +780,7 @@ mod claim_resource {
         let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
             &market_keypair.pubkey(),
             &primary_royalties_receiver.pubkey(),
+            &treasury_mint,
         );
 
         let (metadata, _) = Pubkey::find_program_address(
@@ -782,12 +794,14 @@ mod claim_resource {
 
         let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
             market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
             metadata,
             treasury_holder,
             treasury_mint,
             owner: treasury_owner,
             destination: primary_royalties_receiver.pubkey(),
+            platform_fee_destination: primary_royalties_receiver.pubkey(),
             funder: primary_royalties_receiver.pubkey(),
             payer: payer_pubkey,
             payout_ticket,
@@ -951,6 +965,9 @@ mod claim_resource {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -1149,6 +1166,7 @@ mod claim_resource {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -104,6 +104,9 @@ mod close_market {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -243,6 +246,9 @@ mod close_market {
             start_date: start_date as u64,
             end_date: Some((start_date + 2) as u64),
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
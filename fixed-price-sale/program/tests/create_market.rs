@@ -4,20 +4,34 @@ mod utils;
 mod create_market {
     use crate::utils::{
         helpers::{create_mint, create_token_account},
-        setup_functions::{setup_market, setup_selling_resource, setup_store},
+        setup_functions::{
+            setup_market, setup_market_with_allocation, setup_selling_resource, setup_store,
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
     };
-    use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
     use mpl_fixed_price_sale::{
-        accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
-        state::{Market, MarketState},
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{
+            Market, MarketState, PriceSchedule, PriceScheduleKind, PriceTier, SellingResource,
+            SellingResourceState,
+        },
         utils::{
             find_treasury_owner_address, puffed_out_string, DESCRIPTION_MAX_LEN, NAME_MAX_LEN,
         },
     };
     use solana_program_test::*;
     use solana_sdk::{
-        instruction::Instruction, signature::Keypair, signer::Signer, system_program,
-        sysvar::clock::Clock, transaction::Transaction, transport::TransportError,
+        instruction::{Instruction, InstructionError},
+        signature::Keypair,
+        signer::Signer,
+        system_program,
+        sysvar::clock::Clock,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
     };
 
     use crate::setup_context;
@@ -99,6 +113,9 @@ mod create_market {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -274,6 +291,9 @@ mod create_market {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -383,6 +403,9 @@ mod create_market {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -491,6 +514,9 @@ mod create_market {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -588,6 +614,9 @@ mod create_market {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -689,6 +718,9 @@ mod create_market {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -783,6 +815,9 @@ mod create_market {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -893,6 +928,9 @@ mod create_market {
             start_date: start_date as u64,
             end_date: Some(end_date as u64),
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -921,4 +959,304 @@ mod create_market {
             _ => assert!(false),
         }
     }
+
+    #[tokio::test]
+    async fn failure_price_schedule_tiers_are_unsorted() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _) = setup_selling_resource(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            100,
+            None,
+            true,
+            false,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: Some(PriceSchedule {
+                kind: PriceScheduleKind::BySupply,
+                tiers: vec![
+                    PriceTier {
+                        until: 10,
+                        price: 500_000,
+                    },
+                    PriceTier {
+                        until: 5,
+                        price: 700_000,
+                    },
+                ],
+            }),
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        let tx_result = context.banks_client.process_transaction(tx).await;
+
+        match tx_result.unwrap_err() {
+            TransportError::Custom(_) => assert!(true),
+            TransportError::TransactionError(_) => assert!(true),
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn success_concurrent_markets() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _) = setup_selling_resource(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            100,
+            None,
+            true,
+            false,
+        )
+        .await;
+
+        let presale_keypair = setup_market_with_allocation(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            Some(1),
+        )
+        .await;
+
+        setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let presale_acc = context
+            .banks_client
+            .get_account(presale_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let presale_data = Market::try_deserialize(&mut presale_acc.data.as_ref()).unwrap();
+
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let selling_resource_data =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        assert_eq!(Some(1), presale_data.allocation);
+        assert_eq!(0, presale_data.sold);
+        assert_eq!(1, selling_resource_data.allocated);
+        assert_eq!(2, selling_resource_data.markets);
+        assert_eq!(SellingResourceState::InUse, selling_resource_data.state);
+    }
+
+    #[tokio::test]
+    async fn failure_allocation_is_gt_than_available() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _) = setup_selling_resource(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            100,
+            None,
+            true,
+            false,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: Some(2),
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        let tx_result = context.banks_client.process_transaction(tx).await;
+
+        match tx_result.unwrap_err() {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::AllocationIsGtThanAvailable as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
 }
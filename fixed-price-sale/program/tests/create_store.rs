@@ -4,17 +4,24 @@ mod utils;
 mod create_store {
     use crate::{setup_context, utils::helpers::airdrop};
     use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, system_program};
-    use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
     use mpl_fixed_price_sale::utils::puffed_out_string;
 
     use mpl_fixed_price_sale::{
-        accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
-        state::Store,
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{PlatformFee, Store},
         utils::{DESCRIPTION_MAX_LEN, NAME_MAX_LEN},
     };
-    use solana_program::instruction::Instruction;
+    use solana_program::instruction::{Instruction, InstructionError};
     use solana_program_test::*;
-    use solana_sdk::{transaction::Transaction, transport::TransportError};
+    use solana_sdk::{
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
 
     #[tokio::test]
     async fn success() {
@@ -38,6 +45,7 @@ mod create_store {
         let data = mpl_fixed_price_sale_instruction::CreateStore {
             name: name.to_owned(),
             description: description.to_owned(),
+            platform_fee: None,
         }
         .data();
 
@@ -96,6 +104,7 @@ mod create_store {
         let data = mpl_fixed_price_sale_instruction::CreateStore {
             name: name.to_owned(),
             description: description.to_owned(),
+            platform_fee: None,
         }
         .data();
 
@@ -145,6 +154,7 @@ mod create_store {
         let data = mpl_fixed_price_sale_instruction::CreateStore {
             name: name.to_owned(),
             description: description.to_owned(),
+            platform_fee: None,
         }
         .data();
 
@@ -170,6 +180,69 @@ mod create_store {
         }
     }
 
+    #[tokio::test]
+    async fn failure_platform_fee_is_too_big() {
+        setup_context!(context, mpl_fixed_price_sale);
+
+        let admin_wallet = Keypair::new();
+        let store_keypair = Keypair::new();
+
+        airdrop(&mut context, &admin_wallet.pubkey(), 10_000_000_000).await;
+
+        let name = String::from("123456789_123456789_");
+        let description = String::from("123456789_123456789_");
+
+        let accounts = mpl_fixed_price_sale_accounts::CreateStore {
+            admin: admin_wallet.pubkey(),
+            store: store_keypair.pubkey(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        // platform can't take more than the whole withdrawal
+        let data = mpl_fixed_price_sale_instruction::CreateStore {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            platform_fee: Some(PlatformFee {
+                recipient: admin_wallet.pubkey(),
+                basis_points: 10001,
+            }),
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &admin_wallet, &store_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::PlatformFeeIsTooBig as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn failure_signer_is_missed() {
@@ -193,6 +266,7 @@ mod create_store {
         let data = mpl_fixed_price_sale_instruction::CreateStore {
             name: name.to_owned(),
             description: description.to_owned(),
+            platform_fee: None,
         }
         .data();
 
@@ -0,0 +1,116 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod draw_raffle {
+    use crate::{
+        setup_context,
+        utils::setup_functions::{
+            setup_raffle_draw, setup_raffle_entry, setup_raffle_market, setup_selling_resource,
+            setup_store,
+        },
+    };
+    use anchor_lang::AccountDeserialize;
+    use mpl_fixed_price_sale::state::{Market, RaffleTicket, RaffleTicketState};
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::signer::Signer;
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, treasury_mint_keypair, treasury_holder_keypair) = setup_raffle_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            &seed,
+            1,
+        )
+        .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // EnterRaffle
+        let mut raffle_tickets = Vec::new();
+        for index in 0..3 {
+            let (_user_wallet, raffle_ticket) = setup_raffle_entry(
+                &mut context,
+                &admin_wallet,
+                &market_keypair,
+                &treasury_mint_keypair,
+                &treasury_holder_keypair,
+                index,
+            )
+            .await;
+            raffle_tickets.push(raffle_ticket);
+        }
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // CloseRaffleEntries, RevealRaffleSeed and DrawRaffle
+        setup_raffle_draw(
+            &mut context,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+            &seed,
+            &raffle_tickets,
+        )
+        .await;
+
+        // Checks
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+        let raffle = market_data.raffle.unwrap();
+
+        assert!(raffle.is_drawn());
+        assert_eq!(raffle.drawn, 3);
+        assert_eq!(raffle.winners_drawn, 1);
+        // Deposits of losers are reserved for refunds
+        assert_eq!(market_data.funds_collected, 1_000_000);
+
+        let mut winners = 0;
+        for raffle_ticket in raffle_tickets {
+            let raffle_ticket_acc = context
+                .banks_client
+                .get_account(raffle_ticket)
+                .await
+                .expect("account not found")
+                .expect("account empty");
+
+            let raffle_ticket_data =
+                RaffleTicket::try_deserialize(&mut raffle_ticket_acc.data.as_ref()).unwrap();
+
+            match raffle_ticket_data.state {
+                RaffleTicketState::Won => winners += 1,
+                RaffleTicketState::Lost => {}
+                _ => assert!(false),
+            }
+        }
+
+        assert_eq!(winners, 1);
+    }
+}
@@ -0,0 +1,121 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod enter_raffle {
+    use crate::{
+        setup_context,
+        utils::setup_functions::{
+            setup_raffle_entry, setup_raffle_market, setup_selling_resource, setup_store,
+        },
+    };
+    use anchor_lang::AccountDeserialize;
+    use mpl_fixed_price_sale::{
+        state::{Market, MarketState, RaffleTicket, RaffleTicketState, TradeHistory},
+        utils::find_trade_history_address,
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{program_pack::Pack, signer::Signer};
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, treasury_mint_keypair, treasury_holder_keypair) = setup_raffle_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            &seed,
+            1,
+        )
+        .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // EnterRaffle
+        let (user_wallet, raffle_ticket) = setup_raffle_entry(
+            &mut context,
+            &admin_wallet,
+            &market_keypair,
+            &treasury_mint_keypair,
+            &treasury_holder_keypair,
+            0,
+        )
+        .await;
+
+        // Checks
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        assert_eq!(market_data.state, MarketState::Active);
+        assert_eq!(market_data.funds_collected, 1_000_000);
+        assert_eq!(market_data.raffle.unwrap().entries, 1);
+
+        let raffle_ticket_acc = context
+            .banks_client
+            .get_account(raffle_ticket)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let raffle_ticket_data =
+            RaffleTicket::try_deserialize(&mut raffle_ticket_acc.data.as_ref()).unwrap();
+
+        assert_eq!(raffle_ticket_data.market, market_keypair.pubkey());
+        assert_eq!(raffle_ticket_data.wallet, user_wallet.pubkey());
+        assert_eq!(raffle_ticket_data.index, 0);
+        assert_eq!(raffle_ticket_data.deposit, 1_000_000);
+        assert_eq!(raffle_ticket_data.state, RaffleTicketState::Entered);
+
+        let (trade_history, _) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        assert_eq!(trade_history_data.already_bought, 0);
+        assert_eq!(trade_history_data.funds_spent, 1_000_000);
+        assert_eq!(trade_history_data.currency, treasury_mint_keypair.pubkey());
+
+        let treasury_holder_acc = context
+            .banks_client
+            .get_account(treasury_holder_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let treasury_holder_data =
+            spl_token::state::Account::unpack(&treasury_holder_acc.data).unwrap();
+
+        assert_eq!(treasury_holder_data.amount, 1_000_000);
+    }
+}
@@ -0,0 +1,284 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod expire_raffle {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::create_token_account,
+            setup_functions::{
+                setup_raffle_close, setup_raffle_entry, setup_raffle_market,
+                setup_selling_resource, setup_store,
+            },
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{Market, MarketState, RaffleTicket, RaffleTicketState},
+        utils::{
+            find_trade_history_address, find_treasury_owner_address, RAFFLE_REVEAL_DELAY_SLOTS,
+            RAFFLE_REVEAL_PERIOD_SLOTS,
+        },
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        program_pack::Pack,
+        signature::Keypair,
+        signer::Signer,
+        system_program, sysvar,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let (market_keypair, treasury_mint_keypair, treasury_holder_keypair) = setup_raffle_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            &[7; 32],
+            1,
+        )
+        .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // EnterRaffle
+        let (user_wallet, raffle_ticket) = setup_raffle_entry(
+            &mut context,
+            &admin_wallet,
+            &market_keypair,
+            &treasury_mint_keypair,
+            &treasury_holder_keypair,
+            0,
+        )
+        .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        setup_raffle_close(&mut context, &market_keypair).await;
+
+        // Owner doesn't reveal the seed in time
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context
+            .warp_to_slot(clock.slot + RAFFLE_REVEAL_DELAY_SLOTS + RAFFLE_REVEAL_PERIOD_SLOTS + 10)
+            .unwrap();
+
+        // ExpireRaffle
+        let accounts = mpl_fixed_price_sale_accounts::ExpireRaffle {
+            market: market_keypair.pubkey(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::ExpireRaffle {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        assert_eq!(market_data.state, MarketState::Cancelled);
+
+        // ClaimRaffleRefund of the entered ticket
+        let destination = Keypair::new();
+        create_token_account(
+            &mut context,
+            &destination,
+            &treasury_mint_keypair.pubkey(),
+            &user_wallet.pubkey(),
+        )
+        .await;
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let (treasury_owner, treasury_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let accounts = mpl_fixed_price_sale_accounts::ClaimRaffleRefund {
+            market: market_keypair.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            raffle_ticket,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            destination: destination.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::ClaimRaffleRefund {
+            _trade_history_bump: trade_history_bump,
+            treasury_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Checks
+        let destination_acc = context
+            .banks_client
+            .get_account(destination.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let destination_data = spl_token::state::Account::unpack(&destination_acc.data).unwrap();
+
+        assert_eq!(destination_data.amount, 1_000_000);
+
+        let raffle_ticket_acc = context
+            .banks_client
+            .get_account(raffle_ticket)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let raffle_ticket_data =
+            RaffleTicket::try_deserialize(&mut raffle_ticket_acc.data.as_ref()).unwrap();
+
+        assert_eq!(raffle_ticket_data.state, RaffleTicketState::Refunded);
+    }
+
+    #[tokio::test]
+    async fn fail_reveal_is_not_expired() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let (market_keypair, _treasury_mint_keypair, _treasury_holder_keypair) =
+            setup_raffle_market(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                &selling_resource_keypair,
+                &selling_resource_owner_keypair,
+                &[7; 32],
+                1,
+            )
+            .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3000).unwrap();
+
+        setup_raffle_close(&mut context, &market_keypair).await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context
+            .warp_to_slot(clock.slot + RAFFLE_REVEAL_DELAY_SLOTS + 10)
+            .unwrap();
+
+        // ExpireRaffle
+        let accounts = mpl_fixed_price_sale_accounts::ExpireRaffle {
+            market: market_keypair.pubkey(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::ExpireRaffle {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::RaffleRevealIsNotExpired as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod init_raffle {
+    use crate::{
+        setup_context,
+        utils::setup_functions::{
+            setup_market, setup_raffle_market, setup_selling_resource, setup_store,
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{Market, MarketState},
+    };
+    use solana_program::keccak;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        signer::Signer,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, _treasury_mint_keypair, _treasury_holder_keypair) =
+            setup_raffle_market(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                &selling_resource_keypair,
+                &selling_resource_owner_keypair,
+                &seed,
+                2,
+            )
+            .await;
+
+        // Checks
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+        let raffle = market_data.raffle.unwrap();
+
+        assert_eq!(market_data.state, MarketState::Created);
+        assert_eq!(raffle.seed_commitment, keccak::hash(&seed).0);
+        assert_eq!(raffle.winners, 2);
+        assert_eq!(raffle.entries, 0);
+        assert_eq!(raffle.randomness, None);
+    }
+
+    #[tokio::test]
+    async fn fail_market_without_end_date() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        // InitRaffle
+        let accounts = mpl_fixed_price_sale_accounts::InitRaffle {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::InitRaffle {
+            seed_commitment: keccak::hash(&[7; 32]).0,
+            winners: 2,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::InvalidRaffleConfig as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod migrate_market {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::truncate_account,
+            setup_functions::{setup_market, setup_selling_resource, setup_store},
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::Market,
+        utils::{DESCRIPTION_DEFAULT_SIZE, NAME_DEFAULT_SIZE},
+    };
+    use solana_program::rent::Rent;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signer::Signer,
+        system_program,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    // `Market::LEN` before `price_schedule` was added
+    const LEGACY_MARKET_LEN: usize = 8
+        + 32
+        + 32
+        + 32
+        + 32
+        + 32
+        + 32
+        + NAME_DEFAULT_SIZE
+        + DESCRIPTION_DEFAULT_SIZE
+        + 1
+        + 8
+        + 9
+        + 8
+        + 9
+        + 1
+        + 8
+        + 1
+        + 32
+        + 1
+        + 9;
+
+    fn migrate_market_instruction(market: &Pubkey, payer: &Pubkey) -> Instruction {
+        let accounts = mpl_fixed_price_sale_accounts::MigrateMarket {
+            market: *market,
+            payer: *payer,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::MigrateMarket {}.data();
+
+        Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        }
+    }
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        truncate_account(&mut context, &market_keypair.pubkey(), LEGACY_MARKET_LEN).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[migrate_market_instruction(
+                &market_keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let rent = context.banks_client.get_sysvar::<Rent>().await.unwrap();
+        let migrated_market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated_market_acc.data.len(), Market::LEN);
+        assert_eq!(
+            migrated_market_acc.lamports,
+            rent.minimum_balance(Market::LEN)
+        );
+
+        let migrated_market_data =
+            Market::try_deserialize(&mut migrated_market_acc.data.as_ref()).unwrap();
+        assert_eq!(migrated_market_data.name, market_data.name);
+        assert_eq!(migrated_market_data.price, market_data.price);
+        assert_eq!(migrated_market_data.state, market_data.state);
+        assert_eq!(migrated_market_data.price_schedule, None);
+        assert_eq!(migrated_market_data.allowlist, None);
+        assert!(migrated_market_data.payment_mints.is_empty());
+        assert_eq!(migrated_market_data.raffle, None);
+        assert_eq!(migrated_market_data.allocation, None);
+        assert_eq!(migrated_market_data.sold, 0);
+        assert!(!migrated_market_data.released);
+    }
+
+    #[tokio::test]
+    async fn fail_already_migrated() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[migrate_market_instruction(
+                &market_keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::AccountIsMigrated as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod migrate_selling_resource {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::truncate_account,
+            setup_functions::{setup_market, setup_selling_resource, setup_store},
+        },
+    };
+    use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
+        state::{SellingResource, SellingResourceKind, SellingResourceState},
+    };
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::Instruction, pubkey::Pubkey, signer::Signer, system_program,
+        transaction::Transaction,
+    };
+
+    async fn migrate_selling_resource(
+        context: &mut ProgramTestContext,
+        selling_resource: &Pubkey,
+    ) -> SellingResource {
+        let accounts = mpl_fixed_price_sale_accounts::MigrateSellingResource {
+            selling_resource: *selling_resource,
+            payer: context.payer.pubkey(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::MigrateSellingResource {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(*selling_resource)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(selling_resource_acc.data.len(), SellingResource::LEN);
+
+        SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, _selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        truncate_account(
+            &mut context,
+            &selling_resource_keypair.pubkey(),
+            SellingResource::LEN_WITHOUT_KIND,
+        )
+        .await;
+
+        let selling_resource_data =
+            migrate_selling_resource(&mut context, &selling_resource_keypair.pubkey()).await;
+
+        assert_eq!(selling_resource_data.state, SellingResourceState::Created);
+        assert_eq!(
+            selling_resource_data.kind,
+            SellingResourceKind::MasterEdition
+        );
+        assert_eq!(selling_resource_data.allocated, 0);
+        assert_eq!(selling_resource_data.markets, 0);
+    }
+
+    #[tokio::test]
+    async fn success_in_use() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        truncate_account(
+            &mut context,
+            &selling_resource_keypair.pubkey(),
+            SellingResource::LEN_WITHOUT_KIND,
+        )
+        .await;
+
+        let selling_resource_data =
+            migrate_selling_resource(&mut context, &selling_resource_keypair.pubkey()).await;
+
+        // Market selling the resource should release it once it's over
+        assert_eq!(selling_resource_data.state, SellingResourceState::InUse);
+        assert_eq!(selling_resource_data.markets, 1);
+    }
+}
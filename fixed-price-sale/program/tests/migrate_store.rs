@@ -0,0 +1,66 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod migrate_store {
+    use crate::{
+        setup_context,
+        utils::{helpers::truncate_account, setup_functions::setup_store},
+    };
+    use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
+        state::Store,
+        utils::{DESCRIPTION_DEFAULT_SIZE, NAME_DEFAULT_SIZE},
+    };
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::Instruction, signer::Signer, system_program, transaction::Transaction,
+    };
+
+    // `Store::LEN` before `platform_fee` was added
+    const LEGACY_STORE_LEN: usize = 8 + 32 + NAME_DEFAULT_SIZE + DESCRIPTION_DEFAULT_SIZE;
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        truncate_account(&mut context, &store_keypair.pubkey(), LEGACY_STORE_LEN).await;
+
+        let accounts = mpl_fixed_price_sale_accounts::MigrateStore {
+            store: store_keypair.pubkey(),
+            payer: context.payer.pubkey(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::MigrateStore {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let store_acc = context
+            .banks_client
+            .get_account(store_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(store_acc.data.len(), Store::LEN);
+
+        let store_data = Store::try_deserialize(&mut store_acc.data.as_ref()).unwrap();
+        assert_eq!(store_data.admin, admin_wallet.pubkey());
+        assert_eq!(store_data.platform_fee, None);
+    }
+}
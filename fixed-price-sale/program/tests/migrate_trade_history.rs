@@ -0,0 +1,381 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod migrate_trade_history {
+    use crate::{
+        setup_context,
+        utils::setup_functions::{setup_market, setup_selling_resource, setup_store},
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, AccountSerialize, InstructionData,
+        ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{Market, TradeHistory},
+        utils::find_trade_history_address,
+    };
+    use solana_program::rent::Rent;
+    use solana_program_test::*;
+    use solana_sdk::{
+        account::{Account, AccountSharedData},
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signer::Signer,
+        system_program,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    /// Create `TradeHistory` as it was when its size was `len`
+    async fn set_legacy_trade_history(
+        context: &mut ProgramTestContext,
+        address: &Pubkey,
+        trade_history: &TradeHistory,
+        len: usize,
+    ) {
+        let mut data = Vec::new();
+        trade_history.try_serialize(&mut data).unwrap();
+        data.truncate(len);
+
+        let rent = context.banks_client.get_sysvar::<Rent>().await.unwrap();
+        context.set_account(
+            address,
+            &AccountSharedData::from(Account {
+                lamports: rent.minimum_balance(data.len()),
+                data,
+                owner: mpl_fixed_price_sale::id(),
+                executable: false,
+                rent_epoch: 0,
+            }),
+        );
+    }
+
+    fn migrate_trade_history_instruction(
+        trade_history: &Pubkey,
+        market: &Pubkey,
+        payer: &Pubkey,
+    ) -> Instruction {
+        let accounts = mpl_fixed_price_sale_accounts::MigrateTradeHistory {
+            trade_history: *trade_history,
+            market: *market,
+            payer: *payer,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::MigrateTradeHistory {}.data();
+
+        Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        }
+    }
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let wallet = Pubkey::new_unique();
+        let (trade_history, _) = find_trade_history_address(&wallet, &market_keypair.pubkey());
+        set_legacy_trade_history(
+            &mut context,
+            &trade_history,
+            &TradeHistory {
+                market: market_keypair.pubkey(),
+                wallet,
+                already_bought: 2,
+                ..Default::default()
+            },
+            TradeHistory::LEN_WITHOUT_FUNDS_SPENT,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[migrate_trade_history_instruction(
+                &trade_history,
+                &market_keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade_history_acc.data.len(), TradeHistory::LEN);
+
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+        assert_eq!(trade_history_data.wallet, wallet);
+        assert_eq!(trade_history_data.already_bought, 2);
+        assert_eq!(trade_history_data.funds_spent, market_data.price * 2);
+        assert_eq!(trade_history_data.currency, market_data.treasury_mint);
+    }
+
+    #[tokio::test]
+    async fn success_without_currency() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let wallet = Pubkey::new_unique();
+        let (trade_history, _) = find_trade_history_address(&wallet, &market_keypair.pubkey());
+        set_legacy_trade_history(
+            &mut context,
+            &trade_history,
+            &TradeHistory {
+                market: market_keypair.pubkey(),
+                wallet,
+                already_bought: 2,
+                funds_spent: 1_500_000,
+                ..Default::default()
+            },
+            TradeHistory::LEN_WITHOUT_CURRENCY,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[migrate_trade_history_instruction(
+                &trade_history,
+                &market_keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade_history_acc.data.len(), TradeHistory::LEN);
+
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+        assert_eq!(trade_history_data.wallet, wallet);
+        assert_eq!(trade_history_data.already_bought, 2);
+        // Funds spent are kept, price may have changed after some sales
+        assert_eq!(trade_history_data.funds_spent, 1_500_000);
+        assert_eq!(trade_history_data.currency, market_data.treasury_mint);
+    }
+
+    #[tokio::test]
+    async fn success_without_editions() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let wallet = Pubkey::new_unique();
+        let currency = Pubkey::new_unique();
+        let (trade_history, _) = find_trade_history_address(&wallet, &market_keypair.pubkey());
+        set_legacy_trade_history(
+            &mut context,
+            &trade_history,
+            &TradeHistory {
+                market: market_keypair.pubkey(),
+                wallet,
+                already_bought: 2,
+                funds_spent: 1_500_000,
+                currency,
+                ..Default::default()
+            },
+            TradeHistory::LEN_WITHOUT_EDITIONS,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[migrate_trade_history_instruction(
+                &trade_history,
+                &market_keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade_history_acc.data.len(), TradeHistory::LEN);
+
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+        assert_eq!(trade_history_data.already_bought, 2);
+        assert_eq!(trade_history_data.funds_spent, 1_500_000);
+        assert_eq!(trade_history_data.currency, currency);
+        assert!(trade_history_data.editions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fail_market_mismatch() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let other_market = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let (trade_history, _) = find_trade_history_address(&wallet, &other_market);
+        set_legacy_trade_history(
+            &mut context,
+            &trade_history,
+            &TradeHistory {
+                market: other_market,
+                wallet,
+                already_bought: 2,
+                ..Default::default()
+            },
+            TradeHistory::LEN_WITHOUT_FUNDS_SPENT,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[migrate_trade_history_instruction(
+                &trade_history,
+                &market_keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::PublicKeyMismatch as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
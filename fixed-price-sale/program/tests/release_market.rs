@@ -0,0 +1,319 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod release_market {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::airdrop,
+            setup_functions::{
+                setup_market, setup_market_with_allocation, setup_selling_resource, setup_store,
+            },
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{Market, SellingResource, SellingResourceState},
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        sysvar,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    fn release_market_instruction(market: &Pubkey, selling_resource: &Pubkey) -> Instruction {
+        let accounts = mpl_fixed_price_sale_accounts::ReleaseMarket {
+            market: *market,
+            selling_resource: *selling_resource,
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::ReleaseMarket {}.data();
+
+        Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        }
+    }
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        // Presale reserves the only piece, public sale sells what is left
+        let presale_keypair = setup_market_with_allocation(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            Some(1),
+        )
+        .await;
+
+        let public_sale_keypair = setup_market_with_allocation(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            None,
+        )
+        .await;
+
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        assert_eq!(selling_resource.allocated, 1);
+        assert_eq!(selling_resource.markets, 2);
+        assert_eq!(selling_resource.state, SellingResourceState::InUse);
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3000).unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[release_market_instruction(
+                &presale_keypair.pubkey(),
+                &selling_resource_keypair.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let presale_acc = context
+            .banks_client
+            .get_account(presale_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let presale = Market::try_deserialize(&mut presale_acc.data.as_ref()).unwrap();
+
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        assert!(presale.released);
+        assert_eq!(selling_resource.allocated, 0);
+        assert_eq!(selling_resource.markets, 1);
+        assert_eq!(selling_resource.state, SellingResourceState::InUse);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[release_market_instruction(
+                &public_sale_keypair.pubkey(),
+                &selling_resource_keypair.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        assert_eq!(selling_resource.markets, 0);
+        assert_eq!(selling_resource.state, SellingResourceState::Created);
+
+        // Released selling resource can be sold again without `ClaimResource`
+        setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let selling_resource_acc = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+        assert_eq!(selling_resource.markets, 1);
+        assert_eq!(selling_resource.state, SellingResourceState::InUse);
+    }
+
+    #[tokio::test]
+    async fn fail_market_is_not_ended() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market_with_allocation(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            Some(1),
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[release_market_instruction(
+                &market_keypair.pubkey(),
+                &selling_resource_keypair.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::MarketInInvalidState as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_market_is_released() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let market_keypair = setup_market_with_allocation(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+            Some(1),
+        )
+        .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3000).unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[release_market_instruction(
+                &market_keypair.pubkey(),
+                &selling_resource_keypair.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Other fee payer, so transaction is not a duplicate of the first one
+        let payer = Keypair::new();
+        airdrop(&mut context, &payer.pubkey(), 10_000_000_000).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[release_market_instruction(
+                &market_keypair.pubkey(),
+                &selling_resource_keypair.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::MarketIsReleased as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
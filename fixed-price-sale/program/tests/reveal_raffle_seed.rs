@@ -0,0 +1,431 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod reveal_raffle_seed {
+    use crate::{
+        setup_context,
+        utils::setup_functions::{
+            setup_raffle_close, setup_raffle_market, setup_selling_resource, setup_store,
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::Market,
+        utils::{RAFFLE_REVEAL_DELAY_SLOTS, RAFFLE_REVEAL_PERIOD_SLOTS},
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        signer::Signer,
+        sysvar,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, _treasury_mint_keypair, _treasury_holder_keypair) =
+            setup_raffle_market(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                &selling_resource_keypair,
+                &selling_resource_owner_keypair,
+                &seed,
+                1,
+            )
+            .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3000).unwrap();
+
+        setup_raffle_close(&mut context, &market_keypair).await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context
+            .warp_to_slot(clock.slot + RAFFLE_REVEAL_DELAY_SLOTS + 10)
+            .unwrap();
+
+        // RevealRaffleSeed
+        let accounts = mpl_fixed_price_sale_accounts::RevealRaffleSeed {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            slot_hashes: sysvar::slot_hashes::id(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::RevealRaffleSeed { seed }.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Checks
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+        let raffle = market_data.raffle.unwrap();
+
+        assert!(raffle.reveal_slot.is_some());
+        assert!(raffle.randomness.is_some());
+    }
+
+    #[tokio::test]
+    async fn fail_seed_mismatch() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let (market_keypair, _treasury_mint_keypair, _treasury_holder_keypair) =
+            setup_raffle_market(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                &selling_resource_keypair,
+                &selling_resource_owner_keypair,
+                &[7; 32],
+                1,
+            )
+            .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3000).unwrap();
+
+        setup_raffle_close(&mut context, &market_keypair).await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context
+            .warp_to_slot(clock.slot + RAFFLE_REVEAL_DELAY_SLOTS + 10)
+            .unwrap();
+
+        // RevealRaffleSeed
+        let accounts = mpl_fixed_price_sale_accounts::RevealRaffleSeed {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            slot_hashes: sysvar::slot_hashes::id(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::RevealRaffleSeed { seed: [8; 32] }.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::RaffleSeedMismatch as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_entries_are_open() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, _treasury_mint_keypair, _treasury_holder_keypair) =
+            setup_raffle_market(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                &selling_resource_keypair,
+                &selling_resource_owner_keypair,
+                &seed,
+                1,
+            )
+            .await;
+
+        // RevealRaffleSeed
+        let accounts = mpl_fixed_price_sale_accounts::RevealRaffleSeed {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            slot_hashes: sysvar::slot_hashes::id(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::RevealRaffleSeed { seed }.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::RaffleEntriesAreOpen as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_reveal_is_early() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, _treasury_mint_keypair, _treasury_holder_keypair) =
+            setup_raffle_market(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                &selling_resource_keypair,
+                &selling_resource_owner_keypair,
+                &seed,
+                1,
+            )
+            .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3000).unwrap();
+
+        setup_raffle_close(&mut context, &market_keypair).await;
+
+        // RevealRaffleSeed
+        let accounts = mpl_fixed_price_sale_accounts::RevealRaffleSeed {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            slot_hashes: sysvar::slot_hashes::id(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::RevealRaffleSeed { seed }.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::RaffleRevealIsEarly as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_reveal_is_expired() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        let seed = [7; 32];
+        let (market_keypair, _treasury_mint_keypair, _treasury_holder_keypair) =
+            setup_raffle_market(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                &selling_resource_keypair,
+                &selling_resource_owner_keypair,
+                &seed,
+                1,
+            )
+            .await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3000).unwrap();
+
+        setup_raffle_close(&mut context, &market_keypair).await;
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context
+            .warp_to_slot(clock.slot + RAFFLE_REVEAL_DELAY_SLOTS + RAFFLE_REVEAL_PERIOD_SLOTS + 10)
+            .unwrap();
+
+        // RevealRaffleSeed
+        let accounts = mpl_fixed_price_sale_accounts::RevealRaffleSeed {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            slot_hashes: sysvar::slot_hashes::id(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::RevealRaffleSeed { seed }.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::RaffleRevealIsExpired as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod set_platform_fee {
+    use crate::{setup_context, utils::setup_functions::setup_store};
+    use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        state::{PlatformFee, Store},
+    };
+    use solana_program::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+    };
+    use solana_program_test::*;
+    use solana_sdk::{
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    };
+
+    fn set_platform_fee_instruction(
+        store: &Pubkey,
+        admin: &Pubkey,
+        platform_fee: Option<PlatformFee>,
+    ) -> Instruction {
+        let accounts = mpl_fixed_price_sale_accounts::SetPlatformFee {
+            store: *store,
+            admin: *admin,
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::SetPlatformFee { platform_fee }.data();
+
+        Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        }
+    }
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let platform_fee = PlatformFee {
+            recipient: Pubkey::new_unique(),
+            basis_points: 250,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_platform_fee_instruction(
+                &store_keypair.pubkey(),
+                &admin_wallet.pubkey(),
+                Some(platform_fee.clone()),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &admin_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let store_acc = context
+            .banks_client
+            .get_account(store_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let store_data = Store::try_deserialize(&mut store_acc.data.as_ref()).unwrap();
+
+        assert_eq!(store_data.platform_fee, Some(platform_fee));
+    }
+
+    #[tokio::test]
+    async fn fail_platform_fee_is_too_big() {
+        setup_context!(context, mpl_fixed_price_sale);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_platform_fee_instruction(
+                &store_keypair.pubkey(),
+                &admin_wallet.pubkey(),
+                Some(PlatformFee {
+                    recipient: Pubkey::new_unique(),
+                    basis_points: 10001,
+                }),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &admin_wallet],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    ERROR_CODE_OFFSET + ErrorCode::PlatformFeeIsTooBig as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_not_admin() {
+        setup_context!(context, mpl_fixed_price_sale);
+        let (_admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let not_admin = Keypair::new();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_platform_fee_instruction(
+                &store_keypair.pubkey(),
+                &not_admin.pubkey(),
+                Some(PlatformFee {
+                    recipient: not_admin.pubkey(),
+                    basis_points: 10000,
+                }),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &not_admin],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        match err {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    anchor_lang::error::ErrorCode::ConstraintHasOne as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
@@ -4,10 +4,19 @@ use anchor_client::solana_sdk::{
     pubkey::Pubkey,
     signer::{keypair::Keypair, Signer},
 };
+use mpl_fixed_price_sale::utils::{
+    PRICE_FEED_CONF_OFFSET, PRICE_FEED_EXPONENT_OFFSET, PRICE_FEED_LEN, PRICE_FEED_MAGIC,
+    PRICE_FEED_PRICE_OFFSET, PRICE_FEED_STATUS_OFFSET, PRICE_FEED_STATUS_TRADING,
+    PRICE_FEED_TIMESTAMP_OFFSET, PRICE_FEED_TYPE, PRICE_FEED_TYPE_OFFSET,
+};
 use mpl_token_metadata::state::Collection;
 use solana_program::{clock::Clock, system_instruction};
 use solana_program_test::*;
-use solana_sdk::{program_pack::Pack, transaction::Transaction};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    program_pack::Pack,
+    transaction::Transaction,
+};
 use std::convert::TryFrom;
 
 pub async fn mint_to(
@@ -366,3 +375,58 @@ pub async fn create_master_nft(
 
     (mint.pubkey(), token_account.pubkey(), metadata)
 }
+
+/// Mock Pyth price account, `price` and `conf` are scaled by the feed exponent
+pub fn create_price_feed(
+    context: &mut ProgramTestContext,
+    price_feed: &Pubkey,
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    timestamp: i64,
+) {
+    let mut data = vec![0; PRICE_FEED_LEN];
+    data[..4].copy_from_slice(&PRICE_FEED_MAGIC.to_le_bytes());
+    data[PRICE_FEED_TYPE_OFFSET..PRICE_FEED_TYPE_OFFSET + 4]
+        .copy_from_slice(&PRICE_FEED_TYPE.to_le_bytes());
+    data[PRICE_FEED_EXPONENT_OFFSET..PRICE_FEED_EXPONENT_OFFSET + 4]
+        .copy_from_slice(&exponent.to_le_bytes());
+    data[PRICE_FEED_TIMESTAMP_OFFSET..PRICE_FEED_TIMESTAMP_OFFSET + 8]
+        .copy_from_slice(&timestamp.to_le_bytes());
+    data[PRICE_FEED_PRICE_OFFSET..PRICE_FEED_PRICE_OFFSET + 8]
+        .copy_from_slice(&price.to_le_bytes());
+    data[PRICE_FEED_CONF_OFFSET..PRICE_FEED_CONF_OFFSET + 8].copy_from_slice(&conf.to_le_bytes());
+    data[PRICE_FEED_STATUS_OFFSET..PRICE_FEED_STATUS_OFFSET + 4]
+        .copy_from_slice(&PRICE_FEED_STATUS_TRADING.to_le_bytes());
+
+    context.set_account(
+        price_feed,
+        &AccountSharedData::from(Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+}
+
+/// Shrink account to `len` bytes with rent exempt balance, like one created by an older program version
+pub async fn truncate_account(context: &mut ProgramTestContext, address: &Pubkey, len: usize) {
+    let mut account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = context
+        .banks_client
+        .get_sysvar::<solana_program::rent::Rent>()
+        .await
+        .unwrap();
+
+    account.data.truncate(len);
+    account.lamports = rent.minimum_balance(len);
+
+    context.set_account(address, &AccountSharedData::from(account));
+}
@@ -2,16 +2,23 @@
 
 use super::helpers::{
     airdrop, create_master_edition, create_mint, create_token_account, create_token_metadata,
-    mint_to,
+    mint_to, verify_collection,
 };
 use anchor_lang::{InstructionData, ToAccountMetas};
 use mpl_fixed_price_sale::{
     accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
-    utils::{find_treasury_owner_address, find_vault_owner_address},
+    state::PlatformFee,
+    utils::{
+        find_raffle_ticket_address, find_trade_history_address, find_treasury_owner_address,
+        find_vault_owner_address, RAFFLE_REVEAL_DELAY_SLOTS,
+    },
 };
+use mpl_token_metadata::state::Collection;
+use solana_program::keccak;
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program,
@@ -33,6 +40,14 @@ macro_rules! setup_context {
 
 /// Setup Store with default options
 pub async fn setup_store(context: &mut ProgramTestContext) -> (Keypair, Keypair) {
+    setup_store_with_platform_fee(context, None).await
+}
+
+/// Setup Store charging provided platform fee
+pub async fn setup_store_with_platform_fee(
+    context: &mut ProgramTestContext,
+    platform_fee: Option<PlatformFee>,
+) -> (Keypair, Keypair) {
     let admin_wallet = Keypair::new();
     let store_keypair = Keypair::new();
 
@@ -51,6 +66,7 @@ pub async fn setup_store(context: &mut ProgramTestContext) -> (Keypair, Keypair)
     let data = mpl_fixed_price_sale_instruction::CreateStore {
         name: name.to_owned(),
         description: description.to_owned(),
+        platform_fee,
     }
     .data();
 
@@ -217,6 +233,258 @@ pub async fn setup_selling_resource(
     )
 }
 
+/// Setup selling resource of a secondary sale item without creators,
+/// inheriting the royalty of a verified collection with `admin_wallet` as authority
+pub async fn setup_collection_item_selling_resource(
+    context: &mut ProgramTestContext,
+    admin_wallet: &Keypair,
+    store_keypair: &Keypair,
+    collection_mint: &Pubkey,
+) -> (Keypair, Keypair, Keypair) {
+    let selling_resource_keypair = Keypair::new();
+    let selling_resource_owner_keypair = Keypair::new();
+
+    let resource_mint = Keypair::new();
+    create_mint(context, &resource_mint, &admin_wallet.pubkey(), 0).await;
+
+    let resource_token = Keypair::new();
+    create_token_account(
+        context,
+        &resource_token,
+        &resource_mint.pubkey(),
+        &admin_wallet.pubkey(),
+    )
+    .await;
+
+    let (vault_owner, vault_owner_bump) =
+        find_vault_owner_address(&resource_mint.pubkey(), &store_keypair.pubkey());
+
+    let vault = Keypair::new();
+    create_token_account(context, &vault, &resource_mint.pubkey(), &vault_owner).await;
+
+    mint_to(
+        context,
+        &resource_mint.pubkey(),
+        &resource_token.pubkey(),
+        &admin_wallet,
+        1,
+    )
+    .await;
+
+    // Create metadata, in the collection and already sold once
+    let metadata = create_token_metadata(
+        context,
+        &resource_mint.pubkey(),
+        &admin_wallet,
+        &admin_wallet,
+        String::from("TEST"),
+        String::from("TST"),
+        String::from("https://github.com/"),
+        None,
+        100,
+        true,
+        true,
+        Some(Collection {
+            verified: false,
+            key: *collection_mint,
+        }),
+    )
+    .await;
+
+    verify_collection(context, &metadata, &admin_wallet, collection_mint).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            mpl_token_metadata::instruction::update_primary_sale_happened_via_token(
+                mpl_token_metadata::id(),
+                metadata,
+                admin_wallet.pubkey(),
+                resource_token.pubkey(),
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &admin_wallet],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Create MasterEdition
+    let (master_edition, master_edition_bump) = create_master_edition(
+        context,
+        &resource_mint.pubkey(),
+        &admin_wallet,
+        &admin_wallet,
+        &metadata,
+        Some(1),
+    )
+    .await;
+
+    airdrop(
+        context,
+        &selling_resource_owner_keypair.pubkey(),
+        10_000_000_000,
+    )
+    .await;
+
+    let accounts = mpl_fixed_price_sale_accounts::InitSellingResource {
+        store: store_keypair.pubkey(),
+        admin: admin_wallet.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        resource_mint: resource_mint.pubkey(),
+        master_edition,
+        metadata,
+        vault: vault.pubkey(),
+        owner: vault_owner,
+        resource_token: resource_token.pubkey(),
+        rent: sysvar::rent::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale_instruction::InitSellingResource {
+        master_edition_bump: master_edition_bump,
+        vault_owner_bump: vault_owner_bump,
+        max_supply: Some(1),
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &admin_wallet, &selling_resource_keypair],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (
+        selling_resource_keypair,
+        selling_resource_owner_keypair,
+        vault,
+    )
+}
+
+/// Setup `SellingResource` of `Token` kind, `pieces` of `piece_size` fungible tokens
+pub async fn setup_token_selling_resource(
+    context: &mut ProgramTestContext,
+    admin_wallet: &Keypair,
+    store_keypair: &Keypair,
+    piece_size: u64,
+    pieces: u64,
+) -> (Keypair, Keypair, Keypair) {
+    let selling_resource_keypair = Keypair::new();
+    let selling_resource_owner_keypair = Keypair::new();
+
+    // Create `SellingResource`
+    let resource_mint = Keypair::new();
+    create_mint(context, &resource_mint, &admin_wallet.pubkey(), 0).await;
+
+    let resource_token = Keypair::new();
+    create_token_account(
+        context,
+        &resource_token,
+        &resource_mint.pubkey(),
+        &admin_wallet.pubkey(),
+    )
+    .await;
+
+    let (vault_owner, vault_owner_bump) =
+        find_vault_owner_address(&resource_mint.pubkey(), &store_keypair.pubkey());
+
+    let vault = Keypair::new();
+    create_token_account(context, &vault, &resource_mint.pubkey(), &vault_owner).await;
+
+    mint_to(
+        context,
+        &resource_mint.pubkey(),
+        &resource_token.pubkey(),
+        &admin_wallet,
+        piece_size * pieces,
+    )
+    .await;
+
+    // Create metadata
+    let metadata = create_token_metadata(
+        context,
+        &resource_mint.pubkey(),
+        &admin_wallet,
+        &selling_resource_owner_keypair,
+        String::from("TEST"),
+        String::from("TST"),
+        String::from("https://github.com/"),
+        Some(vec![mpl_token_metadata::state::Creator {
+            address: selling_resource_owner_keypair.pubkey(),
+            share: 100,
+            verified: false,
+        }]),
+        100,
+        true,
+        false,
+        None,
+    )
+    .await;
+
+    airdrop(
+        context,
+        &selling_resource_owner_keypair.pubkey(),
+        10_000_000_000,
+    )
+    .await;
+
+    let accounts = mpl_fixed_price_sale_accounts::InitTokenSellingResource {
+        store: store_keypair.pubkey(),
+        admin: admin_wallet.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        resource_mint: resource_mint.pubkey(),
+        metadata,
+        vault: vault.pubkey(),
+        owner: vault_owner,
+        resource_token: resource_token.pubkey(),
+        rent: sysvar::rent::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale_instruction::InitTokenSellingResource {
+        vault_owner_bump,
+        piece_size,
+        pieces,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &admin_wallet, &selling_resource_keypair],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (
+        selling_resource_keypair,
+        selling_resource_owner_keypair,
+        vault,
+    )
+}
+
 pub async fn setup_market(
     context: &mut ProgramTestContext,
     admin_wallet: &Keypair,
@@ -279,6 +547,9 @@ pub async fn setup_market(
         start_date: start_date as u64,
         end_date: None,
         gating_config: None,
+        price_schedule: None,
+        allowlist: None,
+        allocation: None,
     }
     .data();
 
@@ -303,3 +574,364 @@ pub async fn setup_market(
 
     market_keypair
 }
+
+/// Setup `Market` with optional `allocation`, open for a couple of seconds after start
+pub async fn setup_market_with_allocation(
+    context: &mut ProgramTestContext,
+    admin_wallet: &Keypair,
+    store_keypair: &Keypair,
+    selling_resource_keypair: &Keypair,
+    selling_resource_owner_keypair: &Keypair,
+    allocation: Option<u64>,
+) -> Keypair {
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(context, &treasury_mint_keypair, &admin_wallet.pubkey(), 0).await;
+
+    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let name = "Marktname".to_string();
+    let description = "Marktbeschreibung".to_string();
+    let mutable = true;
+    let price = 1_000_000;
+    let pieces_in_one_wallet = Some(1);
+
+    let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        owner: treasury_owner,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale_instruction::CreateMarket {
+        _treasury_owner_bump: treasyry_owner_bump,
+        name: name.to_owned(),
+        description: description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: Some(start_date as u64 + 2),
+        gating_config: None,
+        price_schedule: None,
+        allowlist: None,
+        allocation,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    market_keypair
+}
+
+/// Setup raffle `Market` with entries open for a couple of seconds after start,
+/// returns `Market`, treasury mint and treasury holder
+pub async fn setup_raffle_market(
+    context: &mut ProgramTestContext,
+    admin_wallet: &Keypair,
+    store_keypair: &Keypair,
+    selling_resource_keypair: &Keypair,
+    selling_resource_owner_keypair: &Keypair,
+    seed: &[u8; 32],
+    winners: u64,
+) -> (Keypair, Keypair, Keypair) {
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(context, &treasury_mint_keypair, &admin_wallet.pubkey(), 0).await;
+
+    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let end_date = start_date + 2;
+
+    let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        owner: treasury_owner,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale_instruction::CreateMarket {
+        _treasury_owner_bump: treasyry_owner_bump,
+        name: "Marktname".to_string(),
+        description: "Marktbeschreibung".to_string(),
+        mutable: true,
+        price: 1_000_000,
+        pieces_in_one_wallet: None,
+        start_date: start_date as u64,
+        end_date: Some(end_date as u64),
+        gating_config: None,
+        price_schedule: None,
+        allowlist: None,
+        allocation: None,
+    }
+    .data();
+
+    let create_market_instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let accounts = mpl_fixed_price_sale_accounts::InitRaffle {
+        market: market_keypair.pubkey(),
+        owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale_instruction::InitRaffle {
+        seed_commitment: keccak::hash(seed).0,
+        winners,
+    }
+    .data();
+
+    let init_raffle_instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_market_instruction, init_raffle_instruction],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (
+        market_keypair,
+        treasury_mint_keypair,
+        treasury_holder_keypair,
+    )
+}
+
+/// Enter raffle from a new wallet funded with the price, returns the wallet and its `RaffleTicket`
+pub async fn setup_raffle_entry(
+    context: &mut ProgramTestContext,
+    admin_wallet: &Keypair,
+    market_keypair: &Keypair,
+    treasury_mint_keypair: &Keypair,
+    treasury_holder_keypair: &Keypair,
+    index: u64,
+) -> (Keypair, Pubkey) {
+    let user_wallet = Keypair::new();
+    airdrop(context, &user_wallet.pubkey(), 10_000_000_000).await;
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+
+    mint_to(
+        context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &admin_wallet,
+        1_000_000,
+    )
+    .await;
+
+    let (trade_history, trade_history_bump) =
+        find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+    let (raffle_ticket, _) = find_raffle_ticket_address(&market_keypair.pubkey(), index);
+
+    let accounts = mpl_fixed_price_sale_accounts::EnterRaffle {
+        market: market_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        raffle_ticket,
+        clock: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale_instruction::EnterRaffle {
+        _trade_history_bump: trade_history_bump,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &user_wallet],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (user_wallet, raffle_ticket)
+}
+
+/// Close raffle entries, which fixes `Raffle::reveal_slot`
+pub async fn setup_raffle_close(context: &mut ProgramTestContext, market_keypair: &Keypair) {
+    let accounts = mpl_fixed_price_sale_accounts::CloseRaffleEntries {
+        market: market_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale_instruction::CloseRaffleEntries {}.data();
+
+    let instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+/// Close raffle entries, reveal raffle seed once the reveal slot hash is available
+/// and draw every ticket in one chunk
+pub async fn setup_raffle_draw(
+    context: &mut ProgramTestContext,
+    market_keypair: &Keypair,
+    selling_resource_owner_keypair: &Keypair,
+    seed: &[u8; 32],
+    raffle_tickets: &[Pubkey],
+) {
+    setup_raffle_close(context, market_keypair).await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context
+        .warp_to_slot(clock.slot + RAFFLE_REVEAL_DELAY_SLOTS + 10)
+        .unwrap();
+
+    let accounts = mpl_fixed_price_sale_accounts::RevealRaffleSeed {
+        market: market_keypair.pubkey(),
+        owner: selling_resource_owner_keypair.pubkey(),
+        slot_hashes: sysvar::slot_hashes::id(),
+        clock: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_fixed_price_sale_instruction::RevealRaffleSeed { seed: *seed }.data();
+
+    let reveal_instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let mut accounts = mpl_fixed_price_sale_accounts::DrawRaffle {
+        market: market_keypair.pubkey(),
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        raffle_tickets
+            .iter()
+            .map(|raffle_ticket| AccountMeta::new(*raffle_ticket, false)),
+    );
+
+    let data = mpl_fixed_price_sale_instruction::DrawRaffle {}.data();
+
+    let draw_instruction = Instruction {
+        program_id: mpl_fixed_price_sale::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[reveal_instruction, draw_instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
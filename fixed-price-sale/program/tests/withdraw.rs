@@ -5,23 +5,27 @@ mod withdraw {
     use crate::{
         setup_context,
         utils::{
-            helpers::{airdrop, create_mint, create_token_account, mint_to},
-            setup_functions::{setup_selling_resource, setup_store},
+            helpers::{airdrop, create_collection, create_mint, create_token_account, mint_to},
+            setup_functions::{
+                setup_collection_item_selling_resource, setup_selling_resource, setup_store,
+                setup_store_with_platform_fee,
+            },
         },
     };
     use anchor_lang::{AccountDeserialize, Id, InstructionData, System, ToAccountMetas};
     use mpl_fixed_price_sale::{
         accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
-        state::SellingResource,
+        state::{PayoutTicket, PlatformFee, SellingResource},
         utils::{
             find_payout_ticket_address, find_primary_metadata_creators, find_trade_history_address,
             find_treasury_owner_address, find_vault_owner_address,
         },
     };
+    use mpl_token_metadata::pda::find_collection_royalty_account;
     use solana_program::clock::Clock;
     use solana_program_test::*;
     use solana_sdk::{
-        account::ReadableAccount,
+        account::{AccountSharedData, ReadableAccount},
         instruction::{AccountMeta, Instruction},
         program_pack::Pack,
         pubkey::Pubkey,
@@ -118,6 +122,9 @@ mod withdraw {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -317,6 +324,7 @@ mod withdraw {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -367,6 +375,7 @@ mod withdraw {
         let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
             &market_keypair.pubkey(),
             &primary_royalties_holder.pubkey(),
+            &treasury_mint_keypair.pubkey(),
         );
 
         let destination = spl_associated_token_account::get_associated_token_address(
@@ -385,12 +394,14 @@ mod withdraw {
 
         let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
             market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
             metadata,
             treasury_holder: treasury_holder_keypair.pubkey(),
             treasury_mint: treasury_mint_keypair.pubkey(),
             owner: treasury_owner,
             destination,
+            platform_fee_destination: destination,
             funder: primary_royalties_holder.pubkey(),
             payer: payer_pubkey,
             payout_ticket,
@@ -512,6 +523,9 @@ mod withdraw {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -696,6 +710,7 @@ mod withdraw {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -746,6 +761,7 @@ mod withdraw {
         let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
             &market_keypair.pubkey(),
             &primary_royalties_holder.pubkey(),
+            &treasury_mint,
         );
 
         let destination = primary_royalties_holder.pubkey();
@@ -761,12 +777,14 @@ mod withdraw {
 
         let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
             market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
             metadata,
             treasury_holder,
             treasury_mint,
             owner: treasury_owner,
             destination,
+            platform_fee_destination: destination,
             funder: primary_royalties_holder.pubkey(),
             payer: payer_pubkey,
             payout_ticket,
@@ -819,9 +837,24 @@ mod withdraw {
     }
 
     #[tokio::test]
-    async fn fail_invalid_destination() {
+    async fn success_native_sol_with_platform_fee() {
         setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
-        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+        let platform_fee_recipient = Keypair::new();
+        airdrop(
+            &mut context,
+            &platform_fee_recipient.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let (admin_wallet, store_keypair) = setup_store_with_platform_fee(
+            &mut context,
+            Some(PlatformFee {
+                recipient: platform_fee_recipient.pubkey(),
+                basis_points: 500,
+            }),
+        )
+        .await;
 
         let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
             setup_selling_resource(
@@ -831,7 +864,7 @@ mod withdraw {
                 100,
                 None,
                 true,
-                false,
+                true,
             )
             .await;
 
@@ -843,29 +876,12 @@ mod withdraw {
         .await;
 
         let market_keypair = Keypair::new();
+        let treasury_mint = System::id();
 
-        let treasury_mint_keypair = Keypair::new();
-        create_mint(
-            &mut context,
-            &treasury_mint_keypair,
-            &admin_wallet.pubkey(),
-            0,
-        )
-        .await;
-
-        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
-            &treasury_mint_keypair.pubkey(),
-            &selling_resource_keypair.pubkey(),
-        );
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
 
-        let treasury_holder_keypair = Keypair::new();
-        create_token_account(
-            &mut context,
-            &treasury_holder_keypair,
-            &treasury_mint_keypair.pubkey(),
-            &treasury_owner,
-        )
-        .await;
+        let treasury_holder = treasury_owner.clone();
 
         let start_date = context
             .banks_client
@@ -887,8 +903,8 @@ mod withdraw {
             store: store_keypair.pubkey(),
             selling_resource_owner: selling_resource_owner_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
-            mint: treasury_mint_keypair.pubkey(),
-            treasury_holder: treasury_holder_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder,
             owner: treasury_owner,
             system_program: system_program::id(),
         }
@@ -904,6 +920,9 @@ mod withdraw {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -948,22 +967,7 @@ mod withdraw {
         let payer_pubkey = context.payer.pubkey();
 
         let user_token_account = Keypair::new();
-        create_token_account(
-            &mut context,
-            &user_token_account,
-            &treasury_mint_keypair.pubkey(),
-            &payer_pubkey,
-        )
-        .await;
-
-        mint_to(
-            &mut context,
-            &treasury_mint_keypair.pubkey(),
-            &user_token_account.pubkey(),
-            &admin_wallet,
-            1_000_000,
-        )
-        .await;
+        airdrop(&mut context, &user_token_account.pubkey(), 10_000_000_000).await;
 
         let new_mint_keypair = Keypair::new();
         create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
@@ -1036,14 +1040,53 @@ mod withdraw {
             &mpl_token_metadata::id(),
         );
 
+        // SavePrimaryMetadataCreators
+        let (primary_metadata_creators, primary_metadata_creators_bump) =
+            find_primary_metadata_creators(&master_edition_metadata);
+
+        let accounts = mpl_fixed_price_sale_accounts::SavePrimaryMetadataCreators {
+            admin: selling_resource_owner_keypair.pubkey(),
+            metadata: master_edition_metadata,
+            primary_metadata_creators,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let primary_royalties_holder = Keypair::new();
+
+        let data = mpl_fixed_price_sale_instruction::SavePrimaryMetadataCreators {
+            primary_metadata_creators_bump: primary_metadata_creators_bump,
+            creators: vec![mpl_token_metadata::state::Creator {
+                address: primary_royalties_holder.pubkey(),
+                verified: false,
+                share: 100,
+            }],
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
         // Buy
         let accounts = mpl_fixed_price_sale_accounts::Buy {
             market: market_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
-            user_token_account: user_token_account.pubkey(),
+            user_token_account: context.payer.pubkey(),
             user_wallet: context.payer.pubkey(),
             trade_history,
-            treasury_holder: treasury_holder_keypair.pubkey(),
+            treasury_holder,
             new_metadata,
             new_edition,
             master_edition,
@@ -1064,6 +1107,7 @@ mod withdraw {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -1113,10 +1157,11 @@ mod withdraw {
         // Withdraw
         let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
             &market_keypair.pubkey(),
-            &selling_resource_owner_keypair.pubkey(),
+            &primary_royalties_holder.pubkey(),
+            &treasury_mint,
         );
 
-        let destination = Pubkey::new_unique();
+        let destination = primary_royalties_holder.pubkey();
 
         let (metadata, _) = Pubkey::find_program_address(
             &[
@@ -1127,15 +1172,17 @@ mod withdraw {
             &mpl_token_metadata::id(),
         );
 
-        let accounts = mpl_fixed_price_sale_accounts::Withdraw {
+        let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
             market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
             metadata,
-            treasury_holder: treasury_holder_keypair.pubkey(),
-            treasury_mint: treasury_mint_keypair.pubkey(),
+            treasury_holder,
+            treasury_mint,
             owner: treasury_owner,
             destination,
-            funder: selling_resource_owner_keypair.pubkey(),
+            platform_fee_destination: platform_fee_recipient.pubkey(),
+            funder: primary_royalties_holder.pubkey(),
             payer: payer_pubkey,
             payout_ticket,
             rent: sysvar::rent::id(),
@@ -1145,6 +1192,7 @@ mod withdraw {
             system_program: system_program::id(),
         }
         .to_account_metas(None);
+        accounts.push(AccountMeta::new(primary_metadata_creators, false));
 
         let data = mpl_fixed_price_sale_instruction::Withdraw {
             payout_ticket_bump,
@@ -1165,20 +1213,42 @@ mod withdraw {
             context.last_blockhash,
         );
 
-        let tx_err = context
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Checks
+        let payout_ticket_acc = context
             .banks_client
-            .process_transaction(tx)
+            .get_account(payout_ticket)
             .await
-            .unwrap_err();
-        match tx_err {
-            TransportError::Custom(_) => assert!(true),
-            TransportError::TransactionError(_) => assert!(true),
-            _ => assert!(false),
-        }
+            .expect("account not found")
+            .expect("account empty");
+
+        let payout_ticket_data =
+            PayoutTicket::try_deserialize(&mut payout_ticket_acc.data.as_ref()).unwrap();
+        assert_eq!(payout_ticket_data.amount, 950000);
+        assert_eq!(payout_ticket_data.platform_fee, 50000);
+
+        let destination_acc = context
+            .banks_client
+            .get_account(destination)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(destination_acc.lamports(), 950000);
+
+        let platform_fee_recipient_acc = context
+            .banks_client
+            .get_account(platform_fee_recipient.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(platform_fee_recipient_acc.lamports(), 10_000_050_000);
     }
 
     #[tokio::test]
-    async fn fail_invalid_funder() {
+    async fn fail_invalid_destination() {
         setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
         let (admin_wallet, store_keypair) = setup_store(&mut context).await;
 
@@ -1263,6 +1333,9 @@ mod withdraw {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -1423,6 +1496,7 @@ mod withdraw {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -1470,9 +1544,11 @@ mod withdraw {
         context.banks_client.process_transaction(tx).await.unwrap();
 
         // Withdraw
-        let funder = Pubkey::new_unique();
-        let (payout_ticket, payout_ticket_bump) =
-            find_payout_ticket_address(&market_keypair.pubkey(), &funder);
+        let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
+            &market_keypair.pubkey(),
+            &selling_resource_owner_keypair.pubkey(),
+            &treasury_mint_keypair.pubkey(),
+        );
 
         let destination = Pubkey::new_unique();
 
@@ -1487,13 +1563,15 @@ mod withdraw {
 
         let accounts = mpl_fixed_price_sale_accounts::Withdraw {
             market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
             metadata,
             treasury_holder: treasury_holder_keypair.pubkey(),
             treasury_mint: treasury_mint_keypair.pubkey(),
             owner: treasury_owner,
             destination,
-            funder,
+            platform_fee_destination: destination,
+            funder: selling_resource_owner_keypair.pubkey(),
             payer: payer_pubkey,
             payout_ticket,
             rent: sysvar::rent::id(),
@@ -1536,7 +1614,7 @@ mod withdraw {
     }
 
     #[tokio::test]
-    async fn fail_withdraw_twice() {
+    async fn fail_invalid_funder() {
         setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
         let (admin_wallet, store_keypair) = setup_store(&mut context).await;
 
@@ -1548,7 +1626,7 @@ mod withdraw {
                 100,
                 None,
                 true,
-                true,
+                false,
             )
             .await;
 
@@ -1621,6 +1699,9 @@ mod withdraw {
             start_date: start_date as u64,
             end_date: None,
             gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
         }
         .data();
 
@@ -1753,45 +1834,6 @@ mod withdraw {
             &mpl_token_metadata::id(),
         );
 
-        // SavePrimaryMetadataCreators
-        let (primary_metadata_creators, primary_metadata_creators_bump) =
-            find_primary_metadata_creators(&master_edition_metadata);
-
-        let accounts = mpl_fixed_price_sale_accounts::SavePrimaryMetadataCreators {
-            admin: selling_resource_owner_keypair.pubkey(),
-            metadata: master_edition_metadata,
-            primary_metadata_creators,
-            system_program: system_program::id(),
-        }
-        .to_account_metas(None);
-
-        let primary_royalties_holder = Keypair::new();
-
-        let data = mpl_fixed_price_sale_instruction::SavePrimaryMetadataCreators {
-            primary_metadata_creators_bump: primary_metadata_creators_bump,
-            creators: vec![mpl_token_metadata::state::Creator {
-                address: primary_royalties_holder.pubkey(),
-                verified: false,
-                share: 100,
-            }],
-        }
-        .data();
-
-        let instruction = Instruction {
-            program_id: mpl_fixed_price_sale::id(),
-            data,
-            accounts,
-        };
-
-        let tx = Transaction::new_signed_with_payer(
-            &[instruction],
-            Some(&context.payer.pubkey()),
-            &[&context.payer, &selling_resource_owner_keypair],
-            context.last_blockhash,
-        );
-
-        context.banks_client.process_transaction(tx).await.unwrap();
-
         // Buy
         let accounts = mpl_fixed_price_sale_accounts::Buy {
             market: market_keypair.pubkey(),
@@ -1820,6 +1862,7 @@ mod withdraw {
         let data = mpl_fixed_price_sale_instruction::Buy {
             _trade_history_bump: trade_history_bump,
             vault_owner_bump,
+            allowlist_proof: None,
         }
         .data();
 
@@ -1867,16 +1910,15 @@ mod withdraw {
         context.banks_client.process_transaction(tx).await.unwrap();
 
         // Withdraw
+        let funder = Pubkey::new_unique();
         let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
             &market_keypair.pubkey(),
-            &primary_royalties_holder.pubkey(),
-        );
-
-        let destination = spl_associated_token_account::get_associated_token_address(
-            &primary_royalties_holder.pubkey(),
+            &funder,
             &treasury_mint_keypair.pubkey(),
         );
 
+        let destination = Pubkey::new_unique();
+
         let (metadata, _) = Pubkey::find_program_address(
             &[
                 mpl_token_metadata::state::PREFIX.as_bytes(),
@@ -1886,15 +1928,17 @@ mod withdraw {
             &mpl_token_metadata::id(),
         );
 
-        let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
+        let accounts = mpl_fixed_price_sale_accounts::Withdraw {
             market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
             selling_resource: selling_resource_keypair.pubkey(),
             metadata,
             treasury_holder: treasury_holder_keypair.pubkey(),
             treasury_mint: treasury_mint_keypair.pubkey(),
             owner: treasury_owner,
             destination,
-            funder: primary_royalties_holder.pubkey(),
+            platform_fee_destination: destination,
+            funder,
             payer: payer_pubkey,
             payout_ticket,
             rent: sysvar::rent::id(),
@@ -1904,7 +1948,6 @@ mod withdraw {
             system_program: system_program::id(),
         }
         .to_account_metas(None);
-        accounts.push(AccountMeta::new(primary_metadata_creators, false));
 
         let data = mpl_fixed_price_sale_instruction::Withdraw {
             payout_ticket_bump,
@@ -1925,39 +1968,1191 @@ mod withdraw {
             context.last_blockhash,
         );
 
-        context.banks_client.process_transaction(tx).await.unwrap();
-
-        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
-        context.warp_to_slot(clock.slot + 3).unwrap();
-
-        // Withdraw
-        let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
-            market: market_keypair.pubkey(),
-            selling_resource: selling_resource_keypair.pubkey(),
-            metadata,
-            treasury_holder: treasury_holder_keypair.pubkey(),
-            treasury_mint: treasury_mint_keypair.pubkey(),
-            owner: treasury_owner,
-            destination,
-            funder: primary_royalties_holder.pubkey(),
-            payer: payer_pubkey,
-            payout_ticket,
-            rent: sysvar::rent::id(),
-            clock: sysvar::clock::id(),
-            token_program: spl_token::id(),
-            associated_token_program: spl_associated_token_account::id(),
-            system_program: system_program::id(),
-        }
-        .to_account_metas(None);
-        accounts.push(AccountMeta::new(primary_metadata_creators, false));
-
-        let data = mpl_fixed_price_sale_instruction::Withdraw {
-            payout_ticket_bump,
-            treasury_owner_bump: treasyry_owner_bump,
+        let tx_err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        match tx_err {
+            TransportError::Custom(_) => assert!(true),
+            TransportError::TransactionError(_) => assert!(true),
+            _ => assert!(false),
         }
-        .data();
+    }
 
-        let instruction = Instruction {
+    #[tokio::test]
+    async fn fail_withdraw_twice() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                true,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &treasury_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &treasury_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // SavePrimaryMetadataCreators
+        let (primary_metadata_creators, primary_metadata_creators_bump) =
+            find_primary_metadata_creators(&master_edition_metadata);
+
+        let accounts = mpl_fixed_price_sale_accounts::SavePrimaryMetadataCreators {
+            admin: selling_resource_owner_keypair.pubkey(),
+            metadata: master_edition_metadata,
+            primary_metadata_creators,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let primary_royalties_holder = Keypair::new();
+
+        let data = mpl_fixed_price_sale_instruction::SavePrimaryMetadataCreators {
+            primary_metadata_creators_bump: primary_metadata_creators_bump,
+            creators: vec![mpl_token_metadata::state::Creator {
+                address: primary_royalties_holder.pubkey(),
+                verified: false,
+                share: 100,
+            }],
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // CloseMarket
+        let accounts = mpl_fixed_price_sale_accounts::CloseMarket {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CloseMarket {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Withdraw
+        let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
+            &market_keypair.pubkey(),
+            &primary_royalties_holder.pubkey(),
+            &treasury_mint_keypair.pubkey(),
+        );
+
+        let destination = spl_associated_token_account::get_associated_token_address(
+            &primary_royalties_holder.pubkey(),
+            &treasury_mint_keypair.pubkey(),
+        );
+
+        let (metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            metadata,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            treasury_mint: treasury_mint_keypair.pubkey(),
+            owner: treasury_owner,
+            destination,
+            platform_fee_destination: destination,
+            funder: primary_royalties_holder.pubkey(),
+            payer: payer_pubkey,
+            payout_ticket,
+            rent: sysvar::rent::id(),
+            clock: sysvar::clock::id(),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new(primary_metadata_creators, false));
+
+        let data = mpl_fixed_price_sale_instruction::Withdraw {
+            payout_ticket_bump,
+            treasury_owner_bump: treasyry_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // Withdraw
+        let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            metadata,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            treasury_mint: treasury_mint_keypair.pubkey(),
+            owner: treasury_owner,
+            destination,
+            platform_fee_destination: destination,
+            funder: primary_royalties_holder.pubkey(),
+            payer: payer_pubkey,
+            payout_ticket,
+            rent: sysvar::rent::id(),
+            clock: sysvar::clock::id(),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new(primary_metadata_creators, false));
+
+        let data = mpl_fixed_price_sale_instruction::Withdraw {
+            payout_ticket_bump,
+            treasury_owner_bump: treasyry_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let tx_err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        match tx_err {
+            TransportError::Custom(_) => assert!(true),
+            TransportError::TransactionError(_) => assert!(true),
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_spoofed_primary_metadata_creators() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                true,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &treasury_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &treasury_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (primary_metadata_creators, primary_metadata_creators_bump) =
+            find_primary_metadata_creators(&master_edition_metadata);
+
+        // SavePrimaryMetadataCreators
+        let accounts = mpl_fixed_price_sale_accounts::SavePrimaryMetadataCreators {
+            admin: selling_resource_owner_keypair.pubkey(),
+            metadata: master_edition_metadata,
+            primary_metadata_creators,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let primary_royalties_holder = Keypair::new();
+
+        let data = mpl_fixed_price_sale_instruction::SavePrimaryMetadataCreators {
+            primary_metadata_creators_bump: primary_metadata_creators_bump,
+            creators: vec![mpl_token_metadata::state::Creator {
+                address: primary_royalties_holder.pubkey(),
+                verified: false,
+                share: 100,
+            }],
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // CloseMarket
+        let accounts = mpl_fixed_price_sale_accounts::CloseMarket {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CloseMarket {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Withdraw
+        let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
+            &market_keypair.pubkey(),
+            &primary_royalties_holder.pubkey(),
+            &treasury_mint_keypair.pubkey(),
+        );
+
+        let destination = spl_associated_token_account::get_associated_token_address(
+            &primary_royalties_holder.pubkey(),
+            &treasury_mint_keypair.pubkey(),
+        );
+
+        let (metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Same data as the real `PrimaryMetadataCreators`, but not derived from the metadata
+        let spoofed_primary_metadata_creators = Pubkey::new_unique();
+        let primary_metadata_creators_acc = context
+            .banks_client
+            .get_account(primary_metadata_creators)
+            .await
+            .unwrap()
+            .unwrap();
+        context.set_account(
+            &spoofed_primary_metadata_creators,
+            &AccountSharedData::from(primary_metadata_creators_acc),
+        );
+
+        let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            metadata,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            treasury_mint: treasury_mint_keypair.pubkey(),
+            owner: treasury_owner,
+            destination,
+            platform_fee_destination: destination,
+            funder: primary_royalties_holder.pubkey(),
+            payer: payer_pubkey,
+            payout_ticket,
+            rent: sysvar::rent::id(),
+            clock: sysvar::clock::id(),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new(spoofed_primary_metadata_creators, false));
+
+        let data = mpl_fixed_price_sale_instruction::Withdraw {
+            payout_ticket_bump,
+            treasury_owner_bump: treasyry_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let tx_err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        match tx_err {
+            TransportError::Custom(_) => assert!(true),
+            TransportError::TransactionError(_) => assert!(true),
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn success_inherited_collection_royalty() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        // Collection paying 5% to a single creator
+        let (collection_mint, _) = create_collection(&mut context, &admin_wallet).await;
+        let (collection_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                collection_mint.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+        let (collection_master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                collection_mint.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+        let (collection_royalty_record, _) = find_collection_royalty_account(&collection_mint);
+        let collection_royalties_holder = Keypair::new();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mpl_token_metadata::instruction::set_collection_royalty(
+                mpl_token_metadata::id(),
+                collection_royalty_record,
+                admin_wallet.pubkey(),
+                context.payer.pubkey(),
+                collection_metadata,
+                collection_master_edition,
+                500,
+                Some(vec![mpl_token_metadata::state::Creator {
+                    address: collection_royalties_holder.pubkey(),
+                    verified: false,
+                    share: 100,
+                }]),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &admin_wallet],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_collection_item_selling_resource(
+                &mut context,
+                &admin_wallet,
+                &store_keypair,
+                &collection_mint,
+            )
+            .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint_keypair = Keypair::new();
+        create_mint(
+            &mut context,
+            &treasury_mint_keypair,
+            &admin_wallet.pubkey(),
+            0,
+        )
+        .await;
+
+        let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+            &treasury_mint_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+        );
+
+        let treasury_holder_keypair = Keypair::new();
+        create_token_account(
+            &mut context,
+            &treasury_holder_keypair,
+            &treasury_mint_keypair.pubkey(),
+            &treasury_owner,
+        )
+        .await;
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        let name = "Marktname".to_string();
+        let description = "Marktbeschreibung".to_string();
+        let mutable = true;
+        let price = 1_000_000;
+        let pieces_in_one_wallet = Some(1);
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint_keypair.pubkey(),
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: name.to_owned(),
+            description: description.to_owned(),
+            mutable,
+            price,
+            pieces_in_one_wallet,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+            price_schedule: None,
+            allowlist: None,
+            allocation: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        // Buy setup
+        let selling_resource_data = context
+            .banks_client
+            .get_account(selling_resource_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let user_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &user_token_account,
+            &treasury_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        mint_to(
+            &mut context,
+            &treasury_mint_keypair.pubkey(),
+            &user_token_account.pubkey(),
+            &admin_wallet,
+            1_000_000,
+        )
+        .await;
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(&mut context, &new_mint_keypair, &payer_pubkey, 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &payer_pubkey,
+        )
+        .await;
+
+        let payer_keypair = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        mint_to(
+            &mut context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            &payer_keypair,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        // Buy
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: market_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            user_token_account: user_token_account.pubkey(),
+            user_wallet: context.payer.pubkey(),
+            trade_history,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            allowlist_proof: None,
+        }
+        .data();
+
+        let instruction = Instruction {
             program_id: mpl_fixed_price_sale::id(),
             data,
             accounts,
@@ -1970,15 +3165,118 @@ mod withdraw {
             context.last_blockhash,
         );
 
-        let tx_err = context
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 3).unwrap();
+
+        // CloseMarket
+        let accounts = mpl_fixed_price_sale_accounts::CloseMarket {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            clock: sysvar::clock::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CloseMarket {}.data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Withdraw
+        let (payout_ticket, payout_ticket_bump) = find_payout_ticket_address(
+            &market_keypair.pubkey(),
+            &collection_royalties_holder.pubkey(),
+            &treasury_mint_keypair.pubkey(),
+        );
+
+        let destination = spl_associated_token_account::get_associated_token_address(
+            &collection_royalties_holder.pubkey(),
+            &treasury_mint_keypair.pubkey(),
+        );
+
+        let (metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let mut accounts = mpl_fixed_price_sale_accounts::Withdraw {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            metadata,
+            treasury_holder: treasury_holder_keypair.pubkey(),
+            treasury_mint: treasury_mint_keypair.pubkey(),
+            owner: treasury_owner,
+            destination,
+            platform_fee_destination: destination,
+            funder: collection_royalties_holder.pubkey(),
+            payer: payer_pubkey,
+            payout_ticket,
+            rent: sysvar::rent::id(),
+            clock: sysvar::clock::id(),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+        // Secondary sale of an item inheriting the royalty of its collection
+        accounts.push(AccountMeta::new_readonly(collection_royalty_record, false));
+
+        let data = mpl_fixed_price_sale_instruction::Withdraw {
+            payout_ticket_bump,
+            treasury_owner_bump: treasyry_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Checks
+        let payout_ticket_acc = context
             .banks_client
-            .process_transaction(tx)
+            .get_account(payout_ticket)
             .await
-            .unwrap_err();
-        match tx_err {
-            TransportError::Custom(_) => assert!(true),
-            TransportError::TransactionError(_) => assert!(true),
-            _ => assert!(false),
-        }
+            .unwrap();
+        assert!(payout_ticket_acc.is_some());
+
+        let destination_acc = context
+            .banks_client
+            .get_account(destination)
+            .await
+            .unwrap()
+            .unwrap();
+        let destination_token_acc =
+            spl_token::state::Account::unpack(&destination_acc.data).unwrap();
+        // Royalty follows the record of the collection, not the 1% of the item
+        assert_eq!(destination_token_acc.amount, 50_000);
     }
 }
@@ -0,0 +1,599 @@
+//! Manifest driven versions of the single mint commands. Rows are batched into transactions,
+//! every confirmed row is appended to a checkpoint file so an interrupted run can be resumed,
+//! and a report with the outcome of every row is written at the end.
+//!
+//! Rows are also checkpointed as pending before they are sent, with the signature and the new
+//! mint keypair, so a transaction that lands after the client gave up is found by the next run
+//! instead of minting the row again.
+
+use {
+    clap::ArgMatches,
+    mpl_token_metadata::{
+        instruction::{
+            create_metadata_accounts_v2, update_metadata_accounts_v2, verify_collection,
+        },
+        state::{Collection, DataV2, Metadata, EDITION, PREFIX},
+    },
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    solana_client::rpc_client::RpcClient,
+    solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack},
+    solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature, Signer},
+        system_instruction::create_account,
+        transaction::Transaction,
+    },
+    spl_token::{instruction::initialize_mint, state::Mint},
+    std::{
+        collections::HashMap,
+        fs::{self, File, OpenOptions},
+        io::{BufRead, BufReader, Write},
+        path::Path,
+        str::FromStr,
+    },
+};
+
+const DEFAULT_RETRIES: usize = 3;
+
+#[derive(Debug, Deserialize)]
+struct CreateRow {
+    name: String,
+    symbol: String,
+    uri: String,
+    /// Existing mint with the keypair as mint authority, a new mint is made when empty
+    mint: Option<String>,
+    seller_fee_basis_points: Option<u16>,
+    mutable: Option<bool>,
+    /// Unverified collection mint, verify it afterwards with bulk_verify_collection
+    collection: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateRow {
+    mint: String,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+    seller_fee_basis_points: Option<u16>,
+    new_update_authority: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerifyCollectionRow {
+    mint: String,
+    /// Falls back to --collection_mint when empty
+    collection_mint: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportRow {
+    row: usize,
+    mint: String,
+    metadata: String,
+    status: String,
+    detail: String,
+}
+
+/// A manifest row turned into instructions, with the keypairs it needs besides the payer and
+/// the update authority.
+struct PreparedRow {
+    row: usize,
+    mint: Pubkey,
+    instructions: Vec<Instruction>,
+    signers: Vec<Keypair>,
+}
+
+/// A row sent by an earlier run without a confirmation, it may still have landed.
+struct PendingRow {
+    mint: Pubkey,
+    signature: Signature,
+    /// Keypair of the new mint, reused so a resent row can't make a second mint
+    mint_keypair: Option<Keypair>,
+}
+
+/// Rows confirmed by earlier runs, stored as `row,mint,signature` lines, and rows sent but not
+/// confirmed, stored as `row,mint,pending,signature,mint_keypair` lines.
+struct Checkpoint {
+    file: File,
+    done: HashMap<usize, (String, String)>,
+    pending: HashMap<usize, PendingRow>,
+}
+
+impl Checkpoint {
+    fn open(path: &str) -> Checkpoint {
+        let mut done = HashMap::new();
+        let mut pending = HashMap::new();
+        if Path::new(path).exists() {
+            let reader = BufReader::new(File::open(path).unwrap());
+            for line in reader.lines() {
+                let line = line.unwrap();
+                let parts: Vec<&str> = line.split(',').collect();
+                let row = match parts[0].parse::<usize>() {
+                    Ok(row) => row,
+                    Err(_) => continue,
+                };
+                if parts.len() == 3 {
+                    done.insert(row, (parts[1].to_owned(), parts[2].to_owned()));
+                } else if parts.len() == 5 && parts[2] == "pending" {
+                    if let (Ok(mint), Ok(signature)) =
+                        (Pubkey::from_str(parts[1]), Signature::from_str(parts[3]))
+                    {
+                        let mint_keypair = Some(parts[4])
+                            .filter(|keypair| !keypair.is_empty())
+                            .map(Keypair::from_base58_string);
+                        pending.insert(
+                            row,
+                            PendingRow {
+                                mint,
+                                signature,
+                                mint_keypair,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        pending.retain(|row, _| !done.contains_key(row));
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        Checkpoint {
+            file,
+            done,
+            pending,
+        }
+    }
+
+    fn record(&mut self, row: usize, mint: &Pubkey, signature: &str) {
+        writeln!(self.file, "{},{},{}", row, mint, signature).unwrap();
+        self.file.flush().unwrap();
+        self.pending.remove(&row);
+        self.done
+            .insert(row, (mint.to_string(), signature.to_owned()));
+    }
+
+    /// Written before the transaction is sent, the last pending line of a row wins.
+    fn record_pending(
+        &mut self,
+        row: usize,
+        mint: &Pubkey,
+        signature: &Signature,
+        mint_keypair: Option<&Keypair>,
+    ) {
+        writeln!(
+            self.file,
+            "{},{},pending,{},{}",
+            row,
+            mint,
+            signature,
+            mint_keypair
+                .map(|keypair| keypair.to_base58_string())
+                .unwrap_or_default()
+        )
+        .unwrap();
+        self.file.flush().unwrap();
+    }
+}
+
+fn read_manifest<T: DeserializeOwned>(path: &str) -> Vec<T> {
+    if path.ends_with(".json") {
+        serde_json::from_str(&fs::read_to_string(path).unwrap())
+            .expect("Manifest is not a JSON array of rows")
+    } else {
+        csv::Reader::from_path(path)
+            .unwrap()
+            .deserialize()
+            .map(|row| row.expect("Manifest has an invalid CSV row"))
+            .collect()
+    }
+}
+
+fn write_report(path: &str, report: &[ReportRow]) {
+    if path.ends_with(".json") {
+        fs::write(path, serde_json::to_string_pretty(report).unwrap()).unwrap();
+    } else {
+        let mut writer = csv::Writer::from_path(path).unwrap();
+        for row in report {
+            writer.serialize(row).unwrap();
+        }
+        writer.flush().unwrap();
+    }
+}
+
+fn metadata_key(mint: &Pubkey) -> Pubkey {
+    let program_key = mpl_token_metadata::id();
+    let metadata_seeds = &[PREFIX.as_bytes(), program_key.as_ref(), mint.as_ref()];
+    Pubkey::find_program_address(metadata_seeds, &program_key).0
+}
+
+fn edition_key(mint: &Pubkey) -> Pubkey {
+    let program_key = mpl_token_metadata::id();
+    let edition_seeds = &[
+        PREFIX.as_bytes(),
+        program_key.as_ref(),
+        mint.as_ref(),
+        EDITION.as_bytes(),
+    ];
+    Pubkey::find_program_address(edition_seeds, &program_key).0
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value.trim()).map_err(|_| format!("Invalid pubkey {}", value))
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(|v| v.trim()).filter(|v| !v.is_empty())
+}
+
+fn is_confirmed(client: &RpcClient, signature: &Signature) -> bool {
+    matches!(client.get_signature_status(signature), Ok(Some(Ok(()))))
+}
+
+/// Sends the prepared rows `batch_size` at a time, retrying failed batches before giving up on
+/// them. Only confirmed rows go to the checkpoint as done, so failed rows are picked up by the
+/// next run. A retry first checks whether the previous attempt landed after all.
+fn send_batches(
+    client: &RpcClient,
+    payer: &Keypair,
+    update_authority: &Keypair,
+    prepared: Vec<PreparedRow>,
+    batch_size: usize,
+    checkpoint: &mut Checkpoint,
+    report: &mut Vec<ReportRow>,
+) {
+    let total = prepared.len();
+    let mut sent = 0;
+    for batch in prepared.chunks(batch_size.max(1)) {
+        let instructions: Vec<Instruction> = batch
+            .iter()
+            .flat_map(|row| row.instructions.clone())
+            .collect();
+        let mut signers: Vec<&Keypair> = vec![payer];
+        if update_authority.pubkey() != payer.pubkey() {
+            signers.push(update_authority);
+        }
+        for row in batch {
+            signers.extend(row.signers.iter());
+        }
+
+        let mut result = Err(String::new());
+        let mut last_signature: Option<Signature> = None;
+        for _ in 0..DEFAULT_RETRIES {
+            if let Some(signature) = &last_signature {
+                if is_confirmed(client, signature) {
+                    result = Ok(signature.to_string());
+                    break;
+                }
+            }
+            let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+            let recent_blockhash = match client.get_latest_blockhash() {
+                Ok(blockhash) => blockhash,
+                Err(err) => {
+                    result = Err(err.to_string());
+                    continue;
+                }
+            };
+            if let Err(err) = transaction.try_sign(&signers, recent_blockhash) {
+                result = Err(err.to_string());
+                break;
+            }
+            let signature = transaction.signatures[0];
+            for row in batch {
+                let mint_keypair = row
+                    .signers
+                    .iter()
+                    .find(|signer| signer.pubkey() == row.mint);
+                checkpoint.record_pending(row.row, &row.mint, &signature, mint_keypair);
+            }
+            last_signature = Some(signature);
+            result = client
+                .send_and_confirm_transaction(&transaction)
+                .map(|signature| signature.to_string())
+                .map_err(|err| err.to_string());
+            if result.is_ok() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1000));
+        }
+
+        sent += batch.len();
+        for row in batch {
+            let (status, detail) = match &result {
+                Ok(signature) => {
+                    checkpoint.record(row.row, &row.mint, signature);
+                    ("ok", signature.clone())
+                }
+                Err(err) => ("failed", err.clone()),
+            };
+            report.push(ReportRow {
+                row: row.row,
+                mint: row.mint.to_string(),
+                metadata: metadata_key(&row.mint).to_string(),
+                status: status.to_owned(),
+                detail,
+            });
+        }
+        println!("{} / {} rows sent", sent, total);
+    }
+}
+
+/// Reads the manifest, skips the rows already in the checkpoint and hands the others to
+/// `prepare`, with the mint keypair of an earlier unconfirmed attempt. Pending rows whose
+/// transaction is confirmed, or whose metadata exists when `landed_if_metadata_exists`, are
+/// moved to done instead. Rows that cannot be prepared are reported as invalid.
+fn run<T, F>(
+    app_matches: &ArgMatches,
+    payer: Keypair,
+    client: RpcClient,
+    default_batch_size: usize,
+    landed_if_metadata_exists: bool,
+    mut prepare: F,
+) where
+    T: DeserializeOwned,
+    F: FnMut(usize, T, &RpcClient, &Keypair, Option<Keypair>) -> Result<PreparedRow, String>,
+{
+    let update_authority = read_keypair_file(
+        app_matches
+            .value_of("update_authority")
+            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
+    )
+    .unwrap();
+    let manifest = app_matches.value_of("manifest").unwrap();
+    let checkpoint_path = app_matches
+        .value_of("checkpoint")
+        .map(|val| val.to_owned())
+        .unwrap_or_else(|| format!("{}.checkpoint", manifest));
+    let report_path = app_matches
+        .value_of("report")
+        .map(|val| val.to_owned())
+        .unwrap_or_else(|| format!("{}.report.csv", manifest));
+    let batch_size = app_matches
+        .value_of("batch_size")
+        .map(|val| val.parse::<usize>().unwrap())
+        .unwrap_or(default_batch_size);
+
+    let rows: Vec<T> = read_manifest(manifest);
+    let mut checkpoint = Checkpoint::open(&checkpoint_path);
+    let mut report = vec![];
+    let mut prepared = vec![];
+    for (i, row) in rows.into_iter().enumerate() {
+        if let Some((mint, signature)) = checkpoint.done.get(&i) {
+            let mint = Pubkey::from_str(mint).unwrap();
+            report.push(ReportRow {
+                row: i,
+                mint: mint.to_string(),
+                metadata: metadata_key(&mint).to_string(),
+                status: "checkpoint".to_owned(),
+                detail: signature.clone(),
+            });
+            continue;
+        }
+        let mut mint_keypair = None;
+        if let Some(pending) = checkpoint.pending.remove(&i) {
+            let landed = is_confirmed(&client, &pending.signature)
+                || (landed_if_metadata_exists
+                    && client.get_account(&metadata_key(&pending.mint)).is_ok());
+            if landed {
+                checkpoint.record(i, &pending.mint, &pending.signature.to_string());
+                report.push(ReportRow {
+                    row: i,
+                    mint: pending.mint.to_string(),
+                    metadata: metadata_key(&pending.mint).to_string(),
+                    status: "landed".to_owned(),
+                    detail: pending.signature.to_string(),
+                });
+                continue;
+            }
+            mint_keypair = pending.mint_keypair;
+        }
+        match prepare(i, row, &client, &update_authority, mint_keypair) {
+            Ok(prepared_row) => prepared.push(prepared_row),
+            Err(err) => report.push(ReportRow {
+                row: i,
+                mint: String::new(),
+                metadata: String::new(),
+                status: "invalid".to_owned(),
+                detail: err,
+            }),
+        }
+    }
+    println!(
+        "{} rows to send, {} already in checkpoint {}",
+        prepared.len(),
+        checkpoint.done.len(),
+        checkpoint_path
+    );
+
+    send_batches(
+        &client,
+        &payer,
+        &update_authority,
+        prepared,
+        batch_size,
+        &mut checkpoint,
+        &mut report,
+    );
+
+    report.sort_by_key(|row| row.row);
+    write_report(&report_path, &report);
+    let failed = report
+        .iter()
+        .filter(|row| row.status == "failed" || row.status == "invalid")
+        .count();
+    println!(
+        "Wrote report for {} rows ({} not done) to {}",
+        report.len(),
+        failed,
+        report_path
+    );
+}
+
+pub fn bulk_create(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let program_key = mpl_token_metadata::id();
+    let token_key = spl_token::id();
+    let payer_key = payer.pubkey();
+    let mint_rent = client
+        .get_minimum_balance_for_rent_exemption(Mint::LEN)
+        .unwrap();
+
+    run(
+        app_matches,
+        payer,
+        client,
+        1,
+        true,
+        |row: usize, entry: CreateRow, _client, update_authority, pending_mint| {
+            let mut instructions = vec![];
+            let mut signers = vec![];
+            let mint_key = match non_empty(&entry.mint) {
+                Some(mint) => parse_pubkey(mint)?,
+                None => {
+                    let new_mint = pending_mint.unwrap_or_else(Keypair::new);
+                    let mint_key = new_mint.pubkey();
+                    instructions.push(create_account(
+                        &payer_key,
+                        &mint_key,
+                        mint_rent,
+                        Mint::LEN as u64,
+                        &token_key,
+                    ));
+                    instructions.push(
+                        initialize_mint(&token_key, &mint_key, &payer_key, Some(&payer_key), 0)
+                            .unwrap(),
+                    );
+                    signers.push(new_mint);
+                    mint_key
+                }
+            };
+            let collection = match non_empty(&entry.collection) {
+                Some(collection) => Some(Collection {
+                    verified: false,
+                    key: parse_pubkey(collection)?,
+                }),
+                None => None,
+            };
+            instructions.push(create_metadata_accounts_v2(
+                program_key,
+                metadata_key(&mint_key),
+                mint_key,
+                payer_key,
+                payer_key,
+                update_authority.pubkey(),
+                entry.name,
+                entry.symbol,
+                entry.uri,
+                None,
+                entry.seller_fee_basis_points.unwrap_or(0),
+                update_authority.pubkey() != payer_key,
+                entry.mutable.unwrap_or(false),
+                collection,
+                None,
+            ));
+            Ok(PreparedRow {
+                row,
+                mint: mint_key,
+                instructions,
+                signers,
+            })
+        },
+    );
+}
+
+pub fn bulk_update(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let program_key = mpl_token_metadata::id();
+
+    run(
+        app_matches,
+        payer,
+        client,
+        3,
+        false,
+        |row: usize, entry: UpdateRow, client, update_authority, _| {
+            let mint_key = parse_pubkey(&entry.mint)?;
+            let metadata_key = metadata_key(&mint_key);
+            let metadata_account = client
+                .get_account(&metadata_key)
+                .map_err(|err| err.to_string())?;
+            let metadata: Metadata =
+                try_from_slice_unchecked(&metadata_account.data).map_err(|err| err.to_string())?;
+            let new_update_authority = match non_empty(&entry.new_update_authority) {
+                Some(new_update_authority) => Some(parse_pubkey(new_update_authority)?),
+                None => None,
+            };
+
+            let new_data = DataV2 {
+                name: non_empty(&entry.name)
+                    .map(|val| val.to_owned())
+                    .unwrap_or(metadata.data.name),
+                symbol: non_empty(&entry.symbol)
+                    .map(|val| val.to_owned())
+                    .unwrap_or(metadata.data.symbol),
+                uri: non_empty(&entry.uri)
+                    .map(|val| val.to_owned())
+                    .unwrap_or(metadata.data.uri),
+                seller_fee_basis_points: entry
+                    .seller_fee_basis_points
+                    .unwrap_or(metadata.data.seller_fee_basis_points),
+                creators: metadata.data.creators,
+                collection: metadata.collection,
+                uses: metadata.uses,
+            };
+            Ok(PreparedRow {
+                row,
+                mint: mint_key,
+                instructions: vec![update_metadata_accounts_v2(
+                    program_key,
+                    metadata_key,
+                    update_authority.pubkey(),
+                    new_update_authority,
+                    Some(new_data),
+                    None,
+                    None,
+                )],
+                signers: vec![],
+            })
+        },
+    );
+}
+
+pub fn bulk_verify_collection(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let program_key = mpl_token_metadata::id();
+    let payer_key = payer.pubkey();
+    let default_collection_mint = app_matches
+        .value_of("collection_mint")
+        .map(|val| Pubkey::from_str(val).unwrap());
+
+    run(
+        app_matches,
+        payer,
+        client,
+        8,
+        false,
+        |row: usize, entry: VerifyCollectionRow, _client, update_authority, _| {
+            let mint_key = parse_pubkey(&entry.mint)?;
+            let collection_mint = match non_empty(&entry.collection_mint) {
+                Some(collection_mint) => parse_pubkey(collection_mint)?,
+                None => default_collection_mint
+                    .ok_or_else(|| "No collection_mint for row".to_owned())?,
+            };
+            Ok(PreparedRow {
+                row,
+                mint: mint_key,
+                instructions: vec![verify_collection(
+                    program_key,
+                    metadata_key(&mint_key),
+                    update_authority.pubkey(),
+                    payer_key,
+                    collection_mint,
+                    metadata_key(&collection_mint),
+                    edition_key(&collection_mint),
+                    None,
+                )],
+                signers: vec![],
+            })
+        },
+    );
+}
@@ -0,0 +1,275 @@
+use {
+    crate::utils::{
+        creators_of, get_metadata, master_edition_key, mint_arg, pubkey_arg, send, signer_arg,
+        signer_of_or_payer,
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        instruction::{
+            approve_collection_authority, revoke_collection_authority, set_and_verify_collection,
+            set_collection_royalty, unverify_collection, verify_collection,
+        },
+        pda::{
+            find_collection_authority_account, find_collection_royalty_account,
+            find_metadata_account,
+        },
+    },
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{pubkey::Pubkey, signature::Signer},
+};
+
+fn collection_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(mint_arg("Mint of the NFT in the collection"))
+        .arg(
+            pubkey_arg("collection_mint", "COLLECTION_MINT", "Mint of the collection NFT")
+                .required(true),
+        )
+        .arg(signer_arg(
+            "collection_authority",
+            "COLLECTION_AUTHORITY",
+            "Filepath or URL to a keypair of the collection authority, defaults to the update authority",
+        ))
+        .arg(
+            Arg::with_name("delegated")
+                .long("delegated")
+                .takes_value(false)
+                .required(false)
+                .help("The collection authority is delegated through a Collection Authority Record"),
+        )
+}
+
+pub fn subcommands() -> Vec<App<'static, 'static>> {
+    vec![
+        collection_args(
+            SubCommand::with_name("verify_collection")
+                .about("Verify the collection of an NFT, as the collection authority"),
+        ),
+        collection_args(
+            SubCommand::with_name("unverify_collection")
+                .about("Unverify the collection of an NFT, as the collection authority"),
+        ),
+        collection_args(SubCommand::with_name("set_and_verify_collection").about(
+            "Set and verify the collection of an NFT sharing its update authority with the collection",
+        )),
+        SubCommand::with_name("approve_collection_authority")
+            .about("Delegate verifying NFTs of a collection to another authority")
+            .arg(mint_arg("Mint of the collection NFT"))
+            .arg(
+                pubkey_arg(
+                    "new_collection_authority",
+                    "NEW_COLLECTION_AUTHORITY",
+                    "Authority to delegate to",
+                )
+                .required(true),
+            ),
+        SubCommand::with_name("revoke_collection_authority")
+            .about("Revoke a delegated collection authority, as the update authority or the delegate")
+            .arg(mint_arg("Mint of the collection NFT"))
+            .arg(
+                pubkey_arg(
+                    "delegate_authority",
+                    "DELEGATE_AUTHORITY",
+                    "Delegated authority to revoke",
+                )
+                .required(true),
+            ),
+        SubCommand::with_name("set_collection_royalty")
+            .about("Set the royalty inherited by verified NFTs of a collection that have no creators of their own")
+            .arg(mint_arg("Mint of the collection NFT"))
+            .arg(
+                Arg::with_name("seller_fee_basis_points")
+                    .long("seller_fee_basis_points")
+                    .value_name("SELLER_FEE_BASIS_POINTS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Royalty basis points of secondary sales (0-10000)"),
+            )
+            .arg(
+                Arg::with_name("creator")
+                    .long("creator")
+                    .value_name("ADDRESS:SHARE")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .required(false)
+                    .help("Creator receiving a share of the royalty, may be repeated"),
+            ),
+    ]
+}
+
+fn collection_authority_record(
+    app_matches: &ArgMatches,
+    collection_mint: &Pubkey,
+    collection_authority: &dyn Signer,
+) -> Option<Pubkey> {
+    if app_matches.is_present("delegated") {
+        Some(find_collection_authority_account(collection_mint, &collection_authority.pubkey()).0)
+    } else {
+        None
+    }
+}
+
+pub fn verify_collection_call(
+    app_matches: &ArgMatches,
+    sub_command: &str,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let collection_authority = match app_matches.value_of("collection_authority") {
+        Some(_) => signer_of_or_payer(app_matches, "collection_authority"),
+        None => signer_of_or_payer(app_matches, "update_authority"),
+    };
+    let program_key = mpl_token_metadata::id();
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let collection_mint = pubkey_of(app_matches, "collection_mint").unwrap();
+    let (metadata_key, _) = find_metadata_account(&mint_key);
+    let (collection_key, _) = find_metadata_account(&collection_mint);
+    let collection_master_edition = master_edition_key(&collection_mint);
+    let record =
+        collection_authority_record(app_matches, &collection_mint, collection_authority.as_ref());
+
+    let instruction = match sub_command {
+        "verify_collection" => verify_collection(
+            program_key,
+            metadata_key,
+            collection_authority.pubkey(),
+            payer.pubkey(),
+            collection_mint,
+            collection_key,
+            collection_master_edition,
+            record,
+        ),
+        "unverify_collection" => unverify_collection(
+            program_key,
+            metadata_key,
+            collection_authority.pubkey(),
+            collection_mint,
+            collection_key,
+            collection_master_edition,
+            record,
+        ),
+        _ => set_and_verify_collection(
+            program_key,
+            metadata_key,
+            collection_authority.pubkey(),
+            payer.pubkey(),
+            get_metadata(&client, &mint_key).1.update_authority,
+            collection_mint,
+            collection_key,
+            collection_master_edition,
+            record,
+        ),
+    };
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[instruction],
+        &[collection_authority.as_ref()],
+    );
+    println!(
+        "Ran {} on metadata {:?} with collection {:?}: {}",
+        sub_command, metadata_key, collection_mint, signature
+    );
+}
+
+pub fn approve_collection_authority_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let new_collection_authority = pubkey_of(app_matches, "new_collection_authority").unwrap();
+    let (metadata_key, _) = find_metadata_account(&mint_key);
+    let (record, _) = find_collection_authority_account(&mint_key, &new_collection_authority);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[approve_collection_authority(
+            mpl_token_metadata::id(),
+            record,
+            new_collection_authority,
+            update_authority.pubkey(),
+            payer.pubkey(),
+            metadata_key,
+            mint_key,
+        )],
+        &[update_authority.as_ref()],
+    );
+    println!(
+        "Approved collection authority {:?} with record {:?}: {}",
+        new_collection_authority, record, signature
+    );
+}
+
+pub fn revoke_collection_authority_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let delegate_authority = pubkey_of(app_matches, "delegate_authority").unwrap();
+    let (metadata_key, _) = find_metadata_account(&mint_key);
+    let (record, _) = find_collection_authority_account(&mint_key, &delegate_authority);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[revoke_collection_authority(
+            mpl_token_metadata::id(),
+            record,
+            delegate_authority,
+            update_authority.pubkey(),
+            metadata_key,
+            mint_key,
+        )],
+        &[update_authority.as_ref()],
+    );
+    println!(
+        "Revoked collection authority {:?}: {}",
+        delegate_authority, signature
+    );
+}
+
+pub fn set_collection_royalty_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let seller_fee_basis_points = app_matches
+        .value_of("seller_fee_basis_points")
+        .map(|val| val.parse::<u16>().unwrap())
+        .unwrap();
+    let creators = creators_of(app_matches, &update_authority.pubkey());
+    let (metadata_key, _) = find_metadata_account(&mint_key);
+    let (record, _) = find_collection_royalty_account(&mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[set_collection_royalty(
+            mpl_token_metadata::id(),
+            record,
+            update_authority.pubkey(),
+            payer.pubkey(),
+            metadata_key,
+            master_edition_key(&mint_key),
+            seller_fee_basis_points,
+            creators,
+        )],
+        &[update_authority.as_ref()],
+    );
+    println!(
+        "Set collection royalty record {:?} to {} basis points: {}",
+        record, seller_fee_basis_points, signature
+    );
+}
@@ -0,0 +1,100 @@
+use {
+    crate::utils::{get_metadata, master_edition_key, mint_arg, send, signer_of_or_payer},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        instruction::{convert_master_edition_v1_to_v2, set_edition_uri_policy},
+        pda::find_edition_uri_policy_account,
+        state::{EditionUriPolicy, Key, MasterEditionV1},
+    },
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_program::borsh::try_from_slice_unchecked,
+    solana_sdk::signature::Signer,
+};
+
+pub fn subcommands() -> Vec<App<'static, 'static>> {
+    vec![
+        SubCommand::with_name("set_edition_uri_policy")
+            .about("Choose whether prints of a Master Edition may override its uri")
+            .arg(mint_arg("Mint of the Master Edition"))
+            .arg(
+                Arg::with_name("policy")
+                    .long("policy")
+                    .value_name("POLICY")
+                    .takes_value(true)
+                    .required(true)
+                    .possible_values(&["fixed", "overridable"])
+                    .help("Uri policy of new prints"),
+            ),
+        SubCommand::with_name("convert_master_edition_v1_to_v2")
+            .about("Convert a deprecated Master Edition V1 whose printing tokens are all burned")
+            .arg(mint_arg("Mint of the Master Edition")),
+    ]
+}
+
+pub fn set_edition_uri_policy_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let policy = match app_matches.value_of("policy").unwrap() {
+        "overridable" => EditionUriPolicy::Overridable,
+        _ => EditionUriPolicy::Fixed,
+    };
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+    let (record, _) = find_edition_uri_policy_account(&mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[set_edition_uri_policy(
+            mpl_token_metadata::id(),
+            record,
+            update_authority.pubkey(),
+            payer.pubkey(),
+            master_edition_key(&mint_key),
+            metadata_key,
+            policy,
+        )],
+        &[update_authority.as_ref()],
+    );
+    println!(
+        "Set edition uri policy {:?} in record {:?}: {}",
+        policy, record, signature
+    );
+}
+
+pub fn convert_master_edition_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let master_edition_key = master_edition_key(&mint_key);
+    let master_edition_account = client.get_account(&master_edition_key).unwrap();
+    if master_edition_account.data[0] != Key::MasterEditionV1 as u8 {
+        panic!("{:?} is not a Master Edition V1", master_edition_key);
+    }
+    let master_edition: MasterEditionV1 =
+        try_from_slice_unchecked(&master_edition_account.data).unwrap();
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[convert_master_edition_v1_to_v2(
+            mpl_token_metadata::id(),
+            master_edition_key,
+            master_edition.one_time_printing_authorization_mint,
+            master_edition.printing_mint,
+        )],
+        &[],
+    );
+    println!(
+        "Converted master edition {:?} to V2: {}",
+        master_edition_key, signature
+    );
+}
@@ -1,3 +1,14 @@
+mod bulk;
+mod collection;
+mod edition;
+mod metadata;
+mod offline;
+mod scan;
+mod show;
+mod token;
+mod uses;
+mod utils;
+
 use solana_client::rpc_request::TokenAccountsFilter;
 use solana_sdk::account::ReadableAccount;
 
@@ -5,13 +16,15 @@ use {
     clap::{crate_description, crate_name, crate_version, App, Arg, ArgMatches, SubCommand},
     mpl_token_metadata::{
         instruction::{
-            create_master_edition, create_metadata_accounts,
-            mint_new_edition_from_master_edition_via_token, puff_metadata_account,
-            update_metadata_accounts,
+            create_master_edition_v3, create_metadata_accounts_v2,
+            mint_new_edition_from_master_edition_via_token,
+            mint_new_edition_from_master_edition_via_token_with_uri, puff_metadata_account,
+            update_metadata_accounts_v2,
         },
         state::{
-            get_reservation_list, Data, Edition, Key, MasterEditionV1, MasterEditionV2, Metadata,
-            EDITION, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
+            get_reservation_list, Collection, DataV2, Edition, Key, MasterEditionV1,
+            MasterEditionV2, Metadata, EDITION, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+            PREFIX,
         },
     },
     solana_clap_utils::{
@@ -96,7 +109,7 @@ fn puff_unpuffed_metadata(_app_matches: &ArgMatches, payer: Keypair, client: Rpc
     }
 }
 
-fn mint_coins(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+fn mint_coins(app_matches: &ArgMatches, payer: Box<dyn Signer>, client: RpcClient) {
     let token_key = Pubkey::from_str(TOKEN_PROGRAM_PUBKEY).unwrap();
     let amount = app_matches
         .value_of("amount")
@@ -105,7 +118,7 @@ fn mint_coins(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
     let mint_key = pubkey_of(app_matches, "mint").unwrap();
     let mut instructions = vec![];
 
-    let mut signers = vec![&payer];
+    let mut signers: Vec<&dyn Signer> = vec![];
     let destination_key: Pubkey;
     let destination = Keypair::new();
     if app_matches.is_present("destination") {
@@ -137,11 +150,13 @@ fn mint_coins(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
         )
         .unwrap(),
     );
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-
-    transaction.sign(&signers, recent_blockhash);
-    client.send_and_confirm_transaction(&transaction).unwrap();
+    utils::send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &instructions,
+        &signers,
+    );
 
     println!("Minted {:?} tokens to {:?}.", amount, destination_key);
 }
@@ -200,6 +215,12 @@ fn show(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
     println!("Metadata key: {:?}", master_metadata_key);
     println!("Metadata: {:#?}", master_metadata);
     println!("Update authority: {:?}", update_authority);
+    if app_matches.is_present("fetch_uri") {
+        println!(
+            "Off-chain metadata: {}",
+            serde_json::to_string_pretty(&show::fetch_uri(&master_metadata.data.uri)).unwrap()
+        );
+    }
     match master_edition_account_res {
         Ok(master_edition_account) => {
             if master_edition_account.data[0] == Key::MasterEditionV1 as u8 {
@@ -224,42 +245,39 @@ fn show(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
 
 fn mint_edition_via_token_call(
     app_matches: &ArgMatches,
-    payer: Keypair,
+    payer: Box<dyn Signer>,
     client: RpcClient,
 ) -> (Edition, Pubkey, Pubkey) {
-    let account_authority = read_keypair_file(
-        app_matches
-            .value_of("account_authority")
-            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
-    )
-    .unwrap();
+    let account_authority = utils::signer_of_or_payer(app_matches, "account_authority");
 
     let program_key = mpl_token_metadata::id();
     let token_key = Pubkey::from_str(TOKEN_PROGRAM_PUBKEY).unwrap();
 
     let mint_key = pubkey_of(app_matches, "mint").unwrap();
-    let existing_token_account = Pubkey::from_str(
-        &client
-            .get_token_accounts_by_owner(
-                &account_authority.pubkey(),
-                TokenAccountsFilter::Mint(mint_key),
-            )
-            .unwrap()
-            .iter()
-            .find(|x| {
-                client
-                    .get_token_account_balance(&Pubkey::from_str(&x.pubkey).unwrap())
-                    .unwrap()
-                    .amount
-                    != "0"
-            })
-            .unwrap()
-            .pubkey,
-    )
-    .unwrap();
-
-    let new_mint_key = Keypair::new();
-    let added_token_account = Keypair::new();
+    let existing_token_account = pubkey_of(app_matches, "account").unwrap_or_else(|| {
+        Pubkey::from_str(
+            &client
+                .get_token_accounts_by_owner(
+                    &account_authority.pubkey(),
+                    TokenAccountsFilter::Mint(mint_key),
+                )
+                .unwrap()
+                .iter()
+                .find(|x| {
+                    client
+                        .get_token_account_balance(&Pubkey::from_str(&x.pubkey).unwrap())
+                        .unwrap()
+                        .amount
+                        != "0"
+                })
+                .unwrap()
+                .pubkey,
+        )
+        .unwrap()
+    });
+
+    let new_mint_key = utils::signer_of_or_new(app_matches, "new_mint");
+    let added_token_account = utils::signer_of_or_new(app_matches, "new_token_account");
     let new_mint_pub = new_mint_key.pubkey();
     let metadata_seeds = &[
         PREFIX.as_bytes(),
@@ -294,7 +312,11 @@ fn mint_edition_via_token_call(
     let master_edition_account = client.get_account(&master_edition_key).unwrap();
     let master_edition: MasterEditionV2 =
         try_from_slice_unchecked(&master_edition_account.data).unwrap();
-    let signers = vec![&account_authority, &new_mint_key, &added_token_account];
+    let signers: Vec<&dyn Signer> = vec![
+        account_authority.as_ref(),
+        new_mint_key.as_ref(),
+        added_token_account.as_ref(),
+    ];
     let mut instructions = vec![
         create_account(
             &payer.pubkey(),
@@ -340,27 +362,47 @@ fn mint_edition_via_token_call(
         .unwrap(),
     ];
 
-    instructions.push(mint_new_edition_from_master_edition_via_token(
-        program_key,
-        metadata_key,
-        edition_key,
-        master_edition_key,
-        new_mint_key.pubkey(),
-        account_authority.pubkey(),
-        payer.pubkey(),
-        account_authority.pubkey(),
-        existing_token_account,
-        account_authority.pubkey(),
-        master_metadata_key,
-        master_metadata.mint,
-        master_edition.supply + 1,
-    ));
-
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
+    instructions.push(match app_matches.value_of("uri") {
+        Some(uri) => mint_new_edition_from_master_edition_via_token_with_uri(
+            program_key,
+            metadata_key,
+            edition_key,
+            master_edition_key,
+            new_mint_key.pubkey(),
+            account_authority.pubkey(),
+            payer.pubkey(),
+            account_authority.pubkey(),
+            existing_token_account,
+            account_authority.pubkey(),
+            master_metadata_key,
+            master_metadata.mint,
+            master_edition.supply + 1,
+            uri.to_owned(),
+        ),
+        None => mint_new_edition_from_master_edition_via_token(
+            program_key,
+            metadata_key,
+            edition_key,
+            master_edition_key,
+            new_mint_key.pubkey(),
+            account_authority.pubkey(),
+            payer.pubkey(),
+            account_authority.pubkey(),
+            existing_token_account,
+            account_authority.pubkey(),
+            master_metadata_key,
+            master_metadata.mint,
+            master_edition.supply + 1,
+        ),
+    });
 
-    transaction.sign(&signers, recent_blockhash);
-    client.send_and_confirm_transaction(&transaction).unwrap();
+    utils::send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &instructions,
+        &signers,
+    );
     let account = client.get_account(&edition_key).unwrap();
     let edition: Edition = try_from_slice_unchecked(&account.data).unwrap();
     (edition, edition_key, new_mint_key.pubkey())
@@ -368,21 +410,11 @@ fn mint_edition_via_token_call(
 
 fn master_edition_call(
     app_matches: &ArgMatches,
-    payer: Keypair,
+    payer: Box<dyn Signer>,
     client: RpcClient,
 ) -> (MasterEditionV2, Pubkey) {
-    let update_authority = read_keypair_file(
-        app_matches
-            .value_of("update_authority")
-            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
-    )
-    .unwrap();
-    let mint_authority = read_keypair_file(
-        app_matches
-            .value_of("mint_authority")
-            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
-    )
-    .unwrap();
+    let update_authority = utils::signer_of_or_payer(app_matches, "update_authority");
+    let mint_authority = utils::signer_of_or_payer(app_matches, "mint_authority");
 
     let program_key = mpl_token_metadata::id();
     let token_key = Pubkey::from_str(TOKEN_PROGRAM_PUBKEY).unwrap();
@@ -409,7 +441,7 @@ fn master_edition_call(
     let added_token_account = Keypair::new();
 
     let needs_a_token = app_matches.is_present("add_one_token");
-    let mut signers = vec![&update_authority, &mint_authority];
+    let mut signers: Vec<&dyn Signer> = vec![update_authority.as_ref(), mint_authority.as_ref()];
     let mut instructions = vec![];
 
     if needs_a_token {
@@ -445,7 +477,7 @@ fn master_edition_call(
         )
     }
 
-    instructions.push(create_master_edition(
+    instructions.push(create_master_edition_v3(
         program_key,
         master_edition_key,
         mint_key,
@@ -456,11 +488,13 @@ fn master_edition_call(
         max_supply,
     ));
 
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-
-    transaction.sign(&signers, recent_blockhash);
-    client.send_and_confirm_transaction(&transaction).unwrap();
+    utils::send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &instructions,
+        &signers,
+    );
     let account = client.get_account(&master_edition_key).unwrap();
     let master_edition: MasterEditionV2 = try_from_slice_unchecked(&account.data).unwrap();
     (master_edition, master_edition_key)
@@ -468,15 +502,10 @@ fn master_edition_call(
 
 fn update_metadata_account_call(
     app_matches: &ArgMatches,
-    payer: Keypair,
+    payer: Box<dyn Signer>,
     client: RpcClient,
 ) -> (Metadata, Pubkey) {
-    let update_authority = read_keypair_file(
-        app_matches
-            .value_of("update_authority")
-            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
-    )
-    .unwrap();
+    let update_authority = utils::signer_of_or_payer(app_matches, "update_authority");
     let program_key = mpl_token_metadata::id();
     let mint_key = pubkey_of(app_matches, "mint").unwrap();
     let metadata_seeds = &[PREFIX.as_bytes(), program_key.as_ref(), mint_key.as_ref()];
@@ -486,34 +515,57 @@ fn update_metadata_account_call(
 
     let name = app_matches.value_of("name").map(|val| val.to_owned());
 
+    let symbol = app_matches.value_of("symbol").map(|val| val.to_owned());
+
+    let seller_fee_basis_points = app_matches
+        .value_of("seller_fee_basis_points")
+        .map(|val| val.parse::<u16>().unwrap());
+
     let new_update_authority = pubkey_of(app_matches, "new_update_authority");
 
+    let primary_sale_happened = if app_matches.is_present("primary_sale_happened") {
+        Some(true)
+    } else {
+        None
+    };
+
+    let is_mutable = if app_matches.is_present("immutable") {
+        Some(false)
+    } else {
+        None
+    };
+
     let metadata_account = client.get_account(&metadata_key).unwrap();
     let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
 
-    let new_data = Data {
+    let new_data = DataV2 {
         name: name.unwrap_or(metadata.data.name),
-        symbol: metadata.data.symbol,
+        symbol: symbol.unwrap_or(metadata.data.symbol),
         uri: uri.unwrap_or(metadata.data.uri),
-        seller_fee_basis_points: 0,
+        seller_fee_basis_points: seller_fee_basis_points
+            .unwrap_or(metadata.data.seller_fee_basis_points),
         creators: metadata.data.creators,
+        collection: metadata.collection,
+        uses: utils::uses_of(app_matches).or(metadata.uses),
     };
 
-    let instructions = [update_metadata_accounts(
+    let instructions = [update_metadata_accounts_v2(
         program_key,
         metadata_key,
         update_authority.pubkey(),
         new_update_authority,
         Some(new_data),
-        None,
+        primary_sale_happened,
+        is_mutable,
     )];
 
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-    let signers = vec![&update_authority];
-
-    transaction.sign(&signers, recent_blockhash);
-    client.send_and_confirm_transaction(&transaction).unwrap();
+    utils::send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &instructions,
+        &[update_authority.as_ref()],
+    );
     let metadata_account = client.get_account(&metadata_key).unwrap();
     let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
     (metadata, metadata_key)
@@ -521,15 +573,10 @@ fn update_metadata_account_call(
 
 fn create_metadata_account_call(
     app_matches: &ArgMatches,
-    payer: Keypair,
+    payer: Box<dyn Signer>,
     client: RpcClient,
 ) -> (Metadata, Pubkey) {
-    let update_authority = read_keypair_file(
-        app_matches
-            .value_of("update_authority")
-            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
-    )
-    .unwrap();
+    let update_authority = utils::signer_of_or_payer(app_matches, "update_authority");
 
     let program_key = mpl_token_metadata::id();
     let token_key = Pubkey::from_str(TOKEN_PROGRAM_PUBKEY).unwrap();
@@ -538,7 +585,17 @@ fn create_metadata_account_call(
     let uri = app_matches.value_of("uri").unwrap().to_owned();
     let create_new_mint = !app_matches.is_present("mint");
     let mutable = app_matches.is_present("mutable");
-    let new_mint = Keypair::new();
+    let seller_fee_basis_points = app_matches
+        .value_of("seller_fee_basis_points")
+        .map(|val| val.parse::<u16>().unwrap())
+        .unwrap_or(0);
+    let creators = utils::creators_of(app_matches, &update_authority.pubkey());
+    let collection = pubkey_of(app_matches, "collection").map(|key| Collection {
+        key,
+        verified: false,
+    });
+    let uses = utils::uses_of(app_matches);
+    let new_mint = utils::signer_of_or_new(app_matches, "new_mint");
     let mint_key = match app_matches.value_of("mint") {
         Some(_val) => pubkey_of(app_matches, "mint").unwrap(),
         None => new_mint.pubkey(),
@@ -580,7 +637,7 @@ fn create_metadata_account_call(
         mint.mint_authority.expect("Mint has no mint authority.")
     };
 
-    let new_metadata_instruction = create_metadata_accounts(
+    let new_metadata_instruction = create_metadata_accounts_v2(
         program_key,
         metadata_key,
         mint_key,
@@ -590,25 +647,30 @@ fn create_metadata_account_call(
         name,
         symbol,
         uri,
-        None,
-        0,
+        creators,
+        seller_fee_basis_points,
         update_authority.pubkey() != payer.pubkey(),
         mutable,
+        collection,
+        uses,
     );
 
     instructions.push(new_metadata_instruction);
 
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-    let mut signers = vec![&payer];
+    let mut signers: Vec<&dyn Signer> = vec![];
     if create_new_mint {
-        signers.push(&new_mint);
+        signers.push(new_mint.as_ref());
     }
     if update_authority.pubkey() != payer.pubkey() {
-        signers.push(&update_authority)
+        signers.push(update_authority.as_ref())
     }
-    transaction.sign(&signers, recent_blockhash);
-    client.send_and_confirm_transaction(&transaction).unwrap();
+    utils::send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &instructions,
+        &signers,
+    );
     let account = client.get_account(&metadata_key).unwrap();
     let metadata: Metadata = try_from_slice_unchecked(&account.data).unwrap();
     (metadata, metadata_key)
@@ -644,6 +706,7 @@ fn main() {
                 .global(true)
                 .help("Update authority filepath or url to keypair besides yourself, defaults to normal keypair"),
         )
+        .args(&offline::args())
         .subcommand(
      SubCommand::with_name("create_metadata_accounts")
                 .about("Create Metadata Accounts")
@@ -679,6 +742,16 @@ fn main() {
                         .required(false)
                         .help("Pubkey for an existing mint (random new mint otherwise)"),
                 )
+                .arg(
+                    Arg::with_name("new_mint")
+                        .long("new_mint")
+                        .value_name("NEW_MINT")
+                        .takes_value(true)
+                        .required(false)
+                        .conflicts_with("mint")
+                        .validator(is_valid_signer)
+                        .help("Keypair of the mint to create, or its pubkey when signing offline [default: random keypair]"),
+                )
                 .arg(
                     Arg::with_name("mutable")
                         .long("mutable")
@@ -687,6 +760,50 @@ fn main() {
                         .required(false)
                         .help("Permit future metadata updates"),
                 )
+                .arg(
+                    Arg::with_name("seller_fee_basis_points")
+                        .long("seller_fee_basis_points")
+                        .value_name("SELLER_FEE_BASIS_POINTS")
+                        .takes_value(true)
+                        .required(false)
+                        .help("Royalty basis points of secondary sales (0-10000) [default: 0]"),
+                )
+                .arg(
+                    Arg::with_name("creator")
+                        .long("creator")
+                        .value_name("ADDRESS:SHARE")
+                        .takes_value(true)
+                        .required(false)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("Creator receiving a share of the royalty, may be repeated. The update authority is verified"),
+                )
+                .arg(
+                    Arg::with_name("collection")
+                        .long("collection")
+                        .value_name("COLLECTION")
+                        .takes_value(true)
+                        .required(false)
+                        .validator(is_valid_pubkey)
+                        .help("Mint of the collection NFT, verified separately with verify_collection"),
+                )
+                .arg(
+                    Arg::with_name("use_method")
+                        .long("use_method")
+                        .value_name("USE_METHOD")
+                        .takes_value(true)
+                        .required(false)
+                        .possible_values(&["burn", "multiple", "single"])
+                        .help("How the NFT is used: burn, multiple or single"),
+                )
+                .arg(
+                    Arg::with_name("total_uses")
+                        .long("total_uses")
+                        .value_name("TOTAL_USES")
+                        .takes_value(true)
+                        .required(false)
+                        .help("Number of uses of the NFT [default: 1]"),
+                )
         ).subcommand(
             SubCommand::with_name("mint_coins")
                        .about("Mint coins to your mint to an account")
@@ -743,6 +860,53 @@ fn main() {
                         .required(false)
                         .help("new NAME for the Metadata"),
                 )
+                .arg(
+                    Arg::with_name("symbol")
+                        .long("symbol")
+                        .value_name("SYMBOL")
+                        .takes_value(true)
+                        .required(false)
+                        .help("new SYMBOL for the Metadata"),
+                )
+                .arg(
+                    Arg::with_name("seller_fee_basis_points")
+                        .long("seller_fee_basis_points")
+                        .value_name("SELLER_FEE_BASIS_POINTS")
+                        .takes_value(true)
+                        .required(false)
+                        .help("new royalty basis points of secondary sales (0-10000)"),
+                )
+                .arg(
+                    Arg::with_name("use_method")
+                        .long("use_method")
+                        .value_name("USE_METHOD")
+                        .takes_value(true)
+                        .required(false)
+                        .possible_values(&["burn", "multiple", "single"])
+                        .help("How the NFT is used: burn, multiple or single"),
+                )
+                .arg(
+                    Arg::with_name("total_uses")
+                        .long("total_uses")
+                        .value_name("TOTAL_USES")
+                        .takes_value(true)
+                        .required(false)
+                        .help("Number of uses of the NFT [default: 1]"),
+                )
+                .arg(
+                    Arg::with_name("primary_sale_happened")
+                        .long("primary_sale_happened")
+                        .takes_value(false)
+                        .required(false)
+                        .help("Mark the primary sale as happened"),
+                )
+                .arg(
+                    Arg::with_name("immutable")
+                        .long("immutable")
+                        .takes_value(false)
+                        .required(false)
+                        .help("Make the Metadata immutable, permanently"),
+                )
                 .arg(
                     Arg::with_name("new_update_authority")
                         .long("new_update_authority")
@@ -763,6 +927,33 @@ fn main() {
                         .takes_value(true)
                         .help("Metadata mint"),
                 )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .value_name("FORMAT")
+                        .takes_value(true)
+                        .required(false)
+                        .possible_values(&["text", "json"])
+                        .help("Output format, json includes editions and authority records [default: text]"),
+                )
+                .arg(
+                    Arg::with_name("fetch_uri")
+                        .long("fetch_uri")
+                        .takes_value(false)
+                        .required(false)
+                        .help("Also fetch the off-chain JSON the uri points at"),
+                )
+                .arg(
+                    Arg::with_name("authority")
+                        .long("authority")
+                        .value_name("AUTHORITY")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .required(false)
+                        .validator(is_valid_pubkey)
+                        .help("Look up use and collection authority records of this authority besides the update authority and token delegate, may be repeated"),
+                )
         ).subcommand(
             SubCommand::with_name("show_reservation_list")
                 .about("Show Reservation List")
@@ -829,6 +1020,22 @@ fn main() {
                                 .validator(is_valid_pubkey)
                                 .takes_value(true)
                                 .help("Account which contains authorization token. If not provided, one will be made."),
+                        ).arg(
+                            Arg::with_name("new_mint")
+                                .long("new_mint")
+                                .value_name("NEW_MINT")
+                                .required(false)
+                                .validator(is_valid_signer)
+                                .takes_value(true)
+                                .help("Keypair of the edition mint to create, or its pubkey when signing offline [default: random keypair]"),
+                        ).arg(
+                            Arg::with_name("new_token_account")
+                                .long("new_token_account")
+                                .value_name("NEW_TOKEN_ACCOUNT")
+                                .required(false)
+                                .validator(is_valid_signer)
+                                .takes_value(true)
+                                .help("Keypair of the token account to create for the edition, or its pubkey when signing offline [default: random keypair]"),
                         ).arg(
                             Arg::with_name("account_authority")
                                 .long("account_authority")
@@ -837,11 +1044,133 @@ fn main() {
                                 .validator(is_valid_signer)
                                 .takes_value(true)
                                 .help("Account's authority, defaults to you"),
+                        ).arg(
+                            Arg::with_name("uri")
+                                .long("uri")
+                                .value_name("URI")
+                                .required(false)
+                                .takes_value(true)
+                                .help("URI of the new edition, if the master edition uri policy is overridable"),
                         )
 
         ).subcommand(
                 SubCommand::with_name("puff_unpuffed_metadata")
-                        .about("Take metadata that still have variable length name, symbol, and uri fields and stretch them out with null symbols so they can be searched more easily by RPC.")).get_matches();
+                        .about("Take metadata that still have variable length name, symbol, and uri fields and stretch them out with null symbols so they can be searched more easily by RPC."))
+        .subcommand(
+            SubCommand::with_name("bulk_create")
+                .about("Create Metadata Accounts for every row of a manifest with name, symbol, uri and optional mint, seller_fee_basis_points, mutable and collection columns")
+                .arg(
+                    Arg::with_name("manifest")
+                        .long("manifest")
+                        .value_name("MANIFEST")
+                        .required(true)
+                        .takes_value(true)
+                        .help("CSV file with a header row, or JSON file with an array of rows"),
+                ).arg(
+                    Arg::with_name("checkpoint")
+                        .long("checkpoint")
+                        .value_name("CHECKPOINT")
+                        .required(false)
+                        .takes_value(true)
+                        .help("File of confirmed and pending rows, checked when resuming a run [default: <MANIFEST>.checkpoint]"),
+                ).arg(
+                    Arg::with_name("report")
+                        .long("report")
+                        .value_name("REPORT")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Where to write the outcome of every row, as JSON if it ends in .json [default: <MANIFEST>.report.csv]"),
+                ).arg(
+                    Arg::with_name("batch_size")
+                        .long("batch_size")
+                        .value_name("BATCH_SIZE")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Rows per transaction [default: 1]"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("bulk_update")
+                .about("Update Metadata Accounts for every row of a manifest with mint and optional name, symbol, uri, seller_fee_basis_points and new_update_authority columns")
+                .arg(
+                    Arg::with_name("manifest")
+                        .long("manifest")
+                        .value_name("MANIFEST")
+                        .required(true)
+                        .takes_value(true)
+                        .help("CSV file with a header row, or JSON file with an array of rows"),
+                ).arg(
+                    Arg::with_name("checkpoint")
+                        .long("checkpoint")
+                        .value_name("CHECKPOINT")
+                        .required(false)
+                        .takes_value(true)
+                        .help("File of confirmed and pending rows, checked when resuming a run [default: <MANIFEST>.checkpoint]"),
+                ).arg(
+                    Arg::with_name("report")
+                        .long("report")
+                        .value_name("REPORT")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Where to write the outcome of every row, as JSON if it ends in .json [default: <MANIFEST>.report.csv]"),
+                ).arg(
+                    Arg::with_name("batch_size")
+                        .long("batch_size")
+                        .value_name("BATCH_SIZE")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Rows per transaction [default: 3]"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("bulk_verify_collection")
+                .about("Verify the collection of every row of a manifest with mint and optional collection_mint columns, signed by the update authority of the collection")
+                .arg(
+                    Arg::with_name("manifest")
+                        .long("manifest")
+                        .value_name("MANIFEST")
+                        .required(true)
+                        .takes_value(true)
+                        .help("CSV file with a header row, or JSON file with an array of rows"),
+                ).arg(
+                    Arg::with_name("checkpoint")
+                        .long("checkpoint")
+                        .value_name("CHECKPOINT")
+                        .required(false)
+                        .takes_value(true)
+                        .help("File of confirmed and pending rows, checked when resuming a run [default: <MANIFEST>.checkpoint]"),
+                ).arg(
+                    Arg::with_name("report")
+                        .long("report")
+                        .value_name("REPORT")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Where to write the outcome of every row, as JSON if it ends in .json [default: <MANIFEST>.report.csv]"),
+                ).arg(
+                    Arg::with_name("batch_size")
+                        .long("batch_size")
+                        .value_name("BATCH_SIZE")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Rows per transaction [default: 8]"),
+                ).arg(
+                    Arg::with_name("collection_mint")
+                        .long("collection_mint")
+                        .value_name("COLLECTION_MINT")
+                        .required(false)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Collection mint for rows that do not name one"),
+                )
+        )
+        .subcommands(collection::subcommands())
+        .subcommands(uses::subcommands())
+        .subcommands(metadata::subcommands())
+        .subcommands(token::subcommands())
+        .subcommands(edition::subcommands())
+        .subcommand(offline::subcommand())
+        .subcommand(scan::subcommand())
+        .get_matches();
 
     let client = RpcClient::new(
         app_matches
@@ -850,11 +1179,17 @@ fn main() {
             .to_owned(),
     );
 
-    let payer = read_keypair_file(app_matches.value_of("keypair").unwrap()).unwrap();
+    // Signers of offline transactions may pass a pubkey, the commands sending many transactions
+    // need a keypair
+    let payer = utils::signer_of(&app_matches, "keypair");
+    let keypair = || read_keypair_file(app_matches.value_of("keypair").unwrap()).unwrap();
 
     let (sub_command, sub_matches) = app_matches.subcommand();
     match (sub_command, sub_matches) {
         ("create_metadata_accounts", Some(arg_matches)) => {
+            if !arg_matches.is_present("mint") {
+                offline::assert_new_accounts_given(arg_matches, sub_command, &["new_mint"]);
+            }
             let (metadata, metadata_key) = create_metadata_account_call(arg_matches, payer, client);
             println!(
                 "Create metadata account with mint {:?} and key {:?} and name of {:?} and symbol of {:?}",
@@ -877,6 +1212,11 @@ fn main() {
             );
         }
         ("mint_new_edition_from_master_edition_via_token", Some(arg_matches)) => {
+            offline::assert_new_accounts_given(
+                arg_matches,
+                sub_command,
+                &["new_mint", "new_token_account"],
+            );
             let (edition, edition_key, mint) =
                 mint_edition_via_token_call(arg_matches, payer, client);
             println!(
@@ -884,17 +1224,98 @@ fn main() {
                 edition_key, edition.parent, edition.edition, mint
             );
         }
-        ("show", Some(arg_matches)) => {
-            show(arg_matches, payer, client);
-        }
+        ("show", Some(arg_matches)) => match arg_matches.value_of("output") {
+            Some("json") => show::show_json(arg_matches, client),
+            _ => show(arg_matches, keypair(), client),
+        },
         ("show_reservation_list", Some(arg_matches)) => {
-            show_reservation_list(arg_matches, payer, client);
+            show_reservation_list(arg_matches, keypair(), client);
         }
         ("mint_coins", Some(arg_matches)) => {
             mint_coins(arg_matches, payer, client);
         }
         ("puff_unpuffed_metadata", Some(arg_matches)) => {
-            puff_unpuffed_metadata(arg_matches, payer, client);
+            offline::assert_online(arg_matches, sub_command);
+            puff_unpuffed_metadata(arg_matches, keypair(), client);
+        }
+        ("bulk_create", Some(arg_matches)) => {
+            offline::assert_online(arg_matches, sub_command);
+            bulk::bulk_create(arg_matches, keypair(), client);
+        }
+        ("bulk_update", Some(arg_matches)) => {
+            offline::assert_online(arg_matches, sub_command);
+            bulk::bulk_update(arg_matches, keypair(), client);
+        }
+        ("bulk_verify_collection", Some(arg_matches)) => {
+            offline::assert_online(arg_matches, sub_command);
+            bulk::bulk_verify_collection(arg_matches, keypair(), client);
+        }
+        ("verify_collection", Some(arg_matches))
+        | ("unverify_collection", Some(arg_matches))
+        | ("set_and_verify_collection", Some(arg_matches)) => {
+            collection::verify_collection_call(arg_matches, sub_command, payer, client);
+        }
+        ("approve_collection_authority", Some(arg_matches)) => {
+            collection::approve_collection_authority_call(arg_matches, payer, client);
+        }
+        ("revoke_collection_authority", Some(arg_matches)) => {
+            collection::revoke_collection_authority_call(arg_matches, payer, client);
+        }
+        ("set_collection_royalty", Some(arg_matches)) => {
+            collection::set_collection_royalty_call(arg_matches, payer, client);
+        }
+        ("approve_use_authority", Some(arg_matches)) => {
+            uses::approve_use_authority_call(arg_matches, payer, client);
+        }
+        ("revoke_use_authority", Some(arg_matches)) => {
+            uses::revoke_use_authority_call(arg_matches, payer, client);
+        }
+        ("utilize", Some(arg_matches)) => {
+            uses::utilize_call(arg_matches, payer, client);
+        }
+        ("reset_uses", Some(arg_matches)) => {
+            uses::reset_uses_call(arg_matches, payer, client);
+        }
+        ("sign_metadata", Some(arg_matches))
+        | ("remove_creator_verification", Some(arg_matches)) => {
+            metadata::sign_metadata_call(arg_matches, sub_command, payer, client);
+        }
+        ("update_primary_sale_happened_via_token", Some(arg_matches)) => {
+            metadata::update_primary_sale_happened_call(arg_matches, payer, client);
+        }
+        ("nominate_update_authority", Some(arg_matches)) => {
+            metadata::nominate_update_authority_call(arg_matches, payer, client);
+        }
+        ("accept_update_authority", Some(arg_matches)) => {
+            metadata::accept_update_authority_call(arg_matches, payer, client);
+        }
+        ("cancel_update_authority_nomination", Some(arg_matches)) => {
+            metadata::cancel_update_authority_nomination_call(arg_matches, payer, client);
+        }
+        ("freeze_delegated_account", Some(arg_matches))
+        | ("thaw_delegated_account", Some(arg_matches)) => {
+            token::freeze_delegated_account_call(arg_matches, sub_command, payer, client);
+        }
+        ("create_non_transferable_master_edition", Some(arg_matches)) => {
+            token::create_non_transferable_master_edition_call(arg_matches, payer, client);
+        }
+        ("burn_non_transferable", Some(arg_matches)) => {
+            token::burn_non_transferable_call(arg_matches, payer, client);
+        }
+        ("recover_non_transferable", Some(arg_matches)) => {
+            token::recover_non_transferable_call(arg_matches, payer, client);
+        }
+        ("set_edition_uri_policy", Some(arg_matches)) => {
+            edition::set_edition_uri_policy_call(arg_matches, payer, client);
+        }
+        ("convert_master_edition_v1_to_v2", Some(arg_matches)) => {
+            edition::convert_master_edition_call(arg_matches, payer, client);
+        }
+        ("submit", Some(arg_matches)) => {
+            offline::submit_call(arg_matches, client);
+        }
+        ("scan", Some(arg_matches)) => {
+            scan::scan_call(arg_matches, payer, client);
         }
         _ => unreachable!(),
     }
@@ -0,0 +1,217 @@
+use {
+    crate::utils::{
+        get_metadata, mint_arg, pubkey_arg, send, signer_arg, signer_of_or_payer,
+        token_account_arg, token_account_of,
+    },
+    clap::{App, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        instruction::{
+            accept_update_authority, cancel_update_authority_nomination, nominate_update_authority,
+            remove_creator_verification, sign_metadata, update_primary_sale_happened_via_token,
+        },
+        pda::find_pending_update_authority_account,
+    },
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::signature::Signer,
+};
+
+pub fn subcommands() -> Vec<App<'static, 'static>> {
+    vec![
+        SubCommand::with_name("sign_metadata")
+            .about("Verify yourself as a creator of an NFT")
+            .arg(mint_arg("Mint of the Metadata"))
+            .arg(signer_arg(
+                "creator",
+                "CREATOR",
+                "Filepath or URL to a keypair of the creator, defaults to you",
+            )),
+        SubCommand::with_name("remove_creator_verification")
+            .about("Unverify yourself as a creator of an NFT")
+            .arg(mint_arg("Mint of the Metadata"))
+            .arg(signer_arg(
+                "creator",
+                "CREATOR",
+                "Filepath or URL to a keypair of the creator, defaults to you",
+            )),
+        SubCommand::with_name("update_primary_sale_happened_via_token")
+            .about("Mark the primary sale of an NFT as happened, as the token owner")
+            .arg(mint_arg("Mint of the Metadata"))
+            .arg(signer_arg(
+                "owner",
+                "OWNER",
+                "Filepath or URL to a keypair of the token owner, defaults to you",
+            ))
+            .arg(token_account_arg()),
+        SubCommand::with_name("nominate_update_authority")
+            .about("Nominate a new update authority, which takes over once it accepts")
+            .arg(mint_arg("Mint of the Metadata"))
+            .arg(
+                pubkey_arg(
+                    "new_update_authority",
+                    "NEW_UPDATE_AUTHORITY",
+                    "Update authority to nominate",
+                )
+                .required(true),
+            ),
+        SubCommand::with_name("accept_update_authority")
+            .about("Accept a nomination as the update authority of an NFT")
+            .arg(mint_arg("Mint of the Metadata"))
+            .arg(signer_arg(
+                "new_update_authority",
+                "NEW_UPDATE_AUTHORITY",
+                "Filepath or URL to a keypair of the nominee, defaults to you",
+            )),
+        SubCommand::with_name("cancel_update_authority_nomination")
+            .about("Cancel a pending update authority nomination, as the update authority")
+            .arg(mint_arg("Mint of the Metadata")),
+    ]
+}
+
+pub fn sign_metadata_call(
+    app_matches: &ArgMatches,
+    sub_command: &str,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let creator = signer_of_or_payer(app_matches, "creator");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+
+    let instruction = match sub_command {
+        "sign_metadata" => sign_metadata(mpl_token_metadata::id(), metadata_key, creator.pubkey()),
+        _ => remove_creator_verification(mpl_token_metadata::id(), metadata_key, creator.pubkey()),
+    };
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[instruction],
+        &[creator.as_ref()],
+    );
+    println!(
+        "Ran {} for creator {:?} on metadata {:?}: {}",
+        sub_command,
+        creator.pubkey(),
+        metadata_key,
+        signature
+    );
+}
+
+pub fn update_primary_sale_happened_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let owner = signer_of_or_payer(app_matches, "owner");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+    let token_account = token_account_of(app_matches, &client, &owner.pubkey(), &mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[update_primary_sale_happened_via_token(
+            mpl_token_metadata::id(),
+            metadata_key,
+            owner.pubkey(),
+            token_account,
+        )],
+        &[owner.as_ref()],
+    );
+    println!(
+        "Primary sale happened on metadata {:?}: {}",
+        metadata_key, signature
+    );
+}
+
+pub fn nominate_update_authority_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let new_update_authority = pubkey_of(app_matches, "new_update_authority").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+    let (record, _) = find_pending_update_authority_account(&mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[nominate_update_authority(
+            mpl_token_metadata::id(),
+            record,
+            new_update_authority,
+            update_authority.pubkey(),
+            payer.pubkey(),
+            metadata_key,
+        )],
+        &[update_authority.as_ref()],
+    );
+    println!(
+        "Nominated {:?} as update authority of {:?}: {}",
+        new_update_authority, metadata_key, signature
+    );
+}
+
+pub fn accept_update_authority_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let new_update_authority = signer_of_or_payer(app_matches, "new_update_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let (metadata_key, metadata) = get_metadata(&client, &mint_key);
+    let (record, _) = find_pending_update_authority_account(&mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[accept_update_authority(
+            mpl_token_metadata::id(),
+            record,
+            new_update_authority.pubkey(),
+            metadata.update_authority,
+            metadata_key,
+        )],
+        &[new_update_authority.as_ref()],
+    );
+    println!(
+        "{:?} is now update authority of {:?}: {}",
+        new_update_authority.pubkey(),
+        metadata_key,
+        signature
+    );
+}
+
+pub fn cancel_update_authority_nomination_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+    let (record, _) = find_pending_update_authority_account(&mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[cancel_update_authority_nomination(
+            mpl_token_metadata::id(),
+            record,
+            update_authority.pubkey(),
+            metadata_key,
+        )],
+        &[update_authority.as_ref()],
+    );
+    println!(
+        "Cancelled the update authority nomination of {:?}: {}",
+        metadata_key, signature
+    );
+}
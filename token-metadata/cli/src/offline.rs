@@ -0,0 +1,208 @@
+use {
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::{
+        input_parsers::{pubkey_of, pubkeys_sigs_of, value_of},
+        input_validators::{is_hash, is_pubkey_sig, is_valid_pubkey, is_valid_signer},
+        nonce::{NONCE_ARG, NONCE_AUTHORITY_ARG},
+        offline::{BLOCKHASH_ARG, SIGNER_ARG, SIGN_ONLY_ARG},
+    },
+    solana_client::{nonce_utils, rpc_client::RpcClient},
+    solana_sdk::{
+        hash::Hash,
+        signature::{Signature, Signer},
+        signer::presigner::Presigner,
+        transaction::Transaction,
+    },
+};
+
+/// Global args for signing transactions offline and submitting them later.
+pub fn args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name(SIGN_ONLY_ARG.name)
+            .long(SIGN_ONLY_ARG.long)
+            .takes_value(false)
+            .global(true)
+            .requires(BLOCKHASH_ARG.name)
+            .help("Print the partially signed transaction for `submit` instead of sending it"),
+        Arg::with_name(BLOCKHASH_ARG.name)
+            .long(BLOCKHASH_ARG.long)
+            .value_name("BLOCKHASH")
+            .takes_value(true)
+            .global(true)
+            .validator(is_hash)
+            .help("Blockhash to sign with, the stored blockhash of the nonce account when using --nonce"),
+        Arg::with_name(NONCE_ARG.name)
+            .long(NONCE_ARG.long)
+            .value_name("NONCE_ACCOUNT")
+            .takes_value(true)
+            .global(true)
+            .validator(is_valid_pubkey)
+            .help("Durable nonce account to use instead of a recent blockhash, so signatures do not expire"),
+        Arg::with_name(NONCE_AUTHORITY_ARG.name)
+            .long(NONCE_AUTHORITY_ARG.long)
+            .value_name("NONCE_AUTHORITY")
+            .takes_value(true)
+            .global(true)
+            .requires(NONCE_ARG.name)
+            .validator(is_valid_signer)
+            .help("Filepath or URL to a keypair of the nonce authority, defaults to you"),
+        Arg::with_name(SIGNER_ARG.name)
+            .long(SIGNER_ARG.long)
+            .value_name("PUBKEY=SIGNATURE")
+            .takes_value(true)
+            .global(true)
+            .multiple(true)
+            .number_of_values(1)
+            .validator(is_pubkey_sig)
+            .help("Signature collected offline for a signer given by pubkey, may be repeated"),
+    ]
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("submit")
+        .about("Combine the signatures of transactions signed with --sign-only and send the result")
+        .arg(
+            Arg::with_name("transaction")
+                .long("transaction")
+                .value_name("TRANSACTION")
+                .takes_value(true)
+                .required(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Partially signed transaction printed by --sign-only, or a file holding one, may be repeated"),
+        )
+}
+
+/// Signatures passed with `--signer`, to sign in place of the matching pubkey signers.
+pub fn presigners_of(app_matches: &ArgMatches) -> Vec<Presigner> {
+    pubkeys_sigs_of(app_matches, SIGNER_ARG.name)
+        .unwrap_or_default()
+        .iter()
+        .map(|(pubkey, signature)| Presigner::new(pubkey, signature))
+        .collect()
+}
+
+/// Exits with an error when offline signing args are passed to `command`, which sends many
+/// transactions and cannot be signed offline.
+pub fn assert_online(app_matches: &ArgMatches, command: &str) {
+    let offline = [
+        SIGN_ONLY_ARG.name,
+        BLOCKHASH_ARG.name,
+        NONCE_ARG.name,
+        NONCE_AUTHORITY_ARG.name,
+        SIGNER_ARG.name,
+    ]
+    .iter()
+    .any(|name| app_matches.is_present(name));
+    if offline {
+        eprintln!(
+            "{} sends many transactions and cannot be signed offline",
+            command
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Exits with an error when `command` is signed with `--sign-only` without the accounts it
+/// creates given as `names`, every offline signer has to sign the same new accounts.
+pub fn assert_new_accounts_given(app_matches: &ArgMatches, command: &str, names: &[&str]) {
+    if !app_matches.is_present(SIGN_ONLY_ARG.name) {
+        return;
+    }
+    for name in names {
+        if !app_matches.is_present(name) {
+            eprintln!(
+                "{} needs --{} to be signed offline, pass the same keypair or pubkey to every signer",
+                command, name
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Blockhash from `--blockhash`, else the one stored in the `--nonce` account, else a recent one.
+pub fn blockhash_of(app_matches: &ArgMatches, client: &RpcClient) -> Hash {
+    if let Some(blockhash) = value_of::<Hash>(app_matches, BLOCKHASH_ARG.name) {
+        return blockhash;
+    }
+    match pubkey_of(app_matches, NONCE_ARG.name) {
+        Some(nonce_account) => {
+            let account = nonce_utils::get_account(client, &nonce_account).unwrap();
+            nonce_utils::data_from_account(&account).unwrap().blockhash
+        }
+        None => client.get_latest_blockhash().unwrap(),
+    }
+}
+
+fn encode_transaction(transaction: &Transaction) -> String {
+    bs58::encode(bincode::serialize(transaction).unwrap()).into_string()
+}
+
+fn decode_transaction(value: &str) -> Transaction {
+    let encoded = std::fs::read_to_string(value).unwrap_or_else(|_| value.to_owned());
+    let bytes = bs58::decode(encoded.trim())
+        .into_vec()
+        .expect("Transaction must be base58 encoded");
+    bincode::deserialize(&bytes).expect("Could not deserialize transaction")
+}
+
+fn print_signers(transaction: &Transaction) {
+    let signers = transaction
+        .message
+        .account_keys
+        .iter()
+        .zip(transaction.signatures.iter());
+    for (pubkey, signature) in signers {
+        if *signature == Signature::default() {
+            eprintln!("Absent signer: {}", pubkey);
+        } else {
+            eprintln!("Signer: {}={}", pubkey, signature);
+        }
+    }
+}
+
+/// Prints the transaction on stdout, so it can be redirected to a file, and its signers on stderr.
+pub fn print_sign_only(transaction: &Transaction) {
+    println!("{}", encode_transaction(transaction));
+    print_signers(transaction);
+}
+
+pub fn submit_call(app_matches: &ArgMatches, client: RpcClient) {
+    let mut transactions = app_matches
+        .values_of("transaction")
+        .unwrap()
+        .map(decode_transaction);
+    let mut transaction = transactions.next().unwrap();
+    for other in transactions {
+        if other.message != transaction.message {
+            panic!("Transactions to combine must sign the same message");
+        }
+        for (signature, other_signature) in transaction
+            .signatures
+            .iter_mut()
+            .zip(other.signatures.into_iter())
+        {
+            if *signature == Signature::default() {
+                *signature = other_signature;
+            }
+        }
+    }
+
+    let num_signers = transaction.message.header.num_required_signatures as usize;
+    for presigner in presigners_of(app_matches) {
+        let index = transaction.message.account_keys[..num_signers]
+            .iter()
+            .position(|key| *key == presigner.pubkey())
+            .expect("--signer is not a signer of the transaction");
+        transaction.signatures[index] = presigner
+            .try_sign_message(&transaction.message_data())
+            .unwrap();
+    }
+
+    print_signers(&transaction);
+    if transaction.verify().is_err() {
+        panic!("Transaction has absent or invalid signatures");
+    }
+    let signature = client.send_and_confirm_transaction(&transaction).unwrap();
+    println!("Submitted transaction: {}", signature);
+}
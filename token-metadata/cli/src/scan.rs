@@ -0,0 +1,263 @@
+use {
+    crate::utils::{master_edition_key, send},
+    borsh::{maybestd::io::Error as BorshError, BorshDeserialize},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        deser::meta_deser,
+        instruction::puff_metadata_account,
+        pda::find_metadata_account,
+        state::{
+            Collection, Data, Key, Metadata, TokenStandard, Uses, EDITION, MAX_METADATA_LEN,
+            MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
+        },
+    },
+    solana_clap_utils::{input_parsers::pubkey_of, input_validators::is_valid_pubkey},
+    solana_client::{
+        rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+    },
+    solana_sdk::{account::Account, pubkey::Pubkey, signature::Signer},
+};
+
+/// Offset of the update authority in a Metadata account.
+const UPDATE_AUTHORITY_OFFSET: usize = 1;
+
+/// Offset of the first creator address in a puffed Metadata account.
+const FIRST_CREATOR_OFFSET: usize =
+    1 + 32 + 32 + 4 + MAX_NAME_LENGTH + 4 + MAX_SYMBOL_LENGTH + 4 + MAX_URI_LENGTH + 2 + 1 + 4;
+
+/// Metadata accounts puffed per repair transaction.
+const REPAIR_BATCH_SIZE: usize = 20;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("scan")
+        .about("List Metadata accounts that are corrupted, unpuffed, the wrong size or inconsistent with their edition")
+        .arg(
+            Arg::with_name("filter_update_authority")
+                .long("filter_update_authority")
+                .value_name("UPDATE_AUTHORITY")
+                .takes_value(true)
+                .required(false)
+                .validator(is_valid_pubkey)
+                .help("Only scan Metadata with this update authority"),
+        )
+        .arg(
+            Arg::with_name("filter_creator")
+                .long("filter_creator")
+                .value_name("CREATOR")
+                .takes_value(true)
+                .required(false)
+                .validator(is_valid_pubkey)
+                .help("Only scan puffed Metadata with this first creator, such as a candy machine"),
+        )
+        .arg(
+            Arg::with_name("repair")
+                .long("repair")
+                .takes_value(false)
+                .required(false)
+                .help("Puff the accounts that can be repaired, which rewrites corrupted fields the program already reads as empty"),
+        )
+}
+
+fn memcmp(offset: usize, pubkey: &Pubkey) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp {
+        offset,
+        bytes: MemcmpEncodedBytes::Base58(pubkey.to_string()),
+        encoding: None,
+    })
+}
+
+struct Finding {
+    address: Pubkey,
+    problems: Vec<&'static str>,
+    repairable: bool,
+}
+
+/// Expected key of the edition account of a Metadata with `token_standard`, if it needs one.
+fn expected_edition_keys(token_standard: &Option<TokenStandard>) -> Option<&'static [Key]> {
+    match token_standard {
+        Some(TokenStandard::NonFungible) | Some(TokenStandard::NonTransferable) => {
+            Some(&[Key::MasterEditionV1, Key::MasterEditionV2])
+        }
+        Some(TokenStandard::NonFungibleEdition) => Some(&[Key::EditionV1]),
+        _ => None,
+    }
+}
+
+/// Reads every field of a Metadata without the fallbacks of `meta_deser`, which reads
+/// corrupted trailing fields as empty.
+fn strict_deser(buf: &mut &[u8]) -> Result<(), BorshError> {
+    Key::deserialize(buf)?;
+    Pubkey::deserialize(buf)?;
+    Pubkey::deserialize(buf)?;
+    Data::deserialize(buf)?;
+    bool::deserialize(buf)?;
+    bool::deserialize(buf)?;
+    Option::<u8>::deserialize(buf)?;
+    Option::<TokenStandard>::deserialize(buf)?;
+    Option::<Collection>::deserialize(buf)?;
+    Option::<Uses>::deserialize(buf)?;
+    Option::<i64>::deserialize(buf)?;
+    u64::deserialize(buf)?;
+    Ok(())
+}
+
+/// Checks a Metadata account against strict deserialization and the invariants the program
+/// keeps. Returns the decoding the program itself uses, if any, and whether puffing the
+/// account would fix a problem found.
+fn check(
+    address: &Pubkey,
+    account: &Account,
+    problems: &mut Vec<&'static str>,
+) -> (Option<Metadata>, bool) {
+    if account.data.len() != MAX_METADATA_LEN {
+        problems.push("wrong size");
+    }
+    let metadata = match meta_deser(&mut account.data.as_slice()) {
+        Ok(metadata) => metadata,
+        Err(_) => {
+            problems.push("unreadable");
+            return (None, false);
+        }
+    };
+    if *address != find_metadata_account(&metadata.mint).0 {
+        problems.push("not the metadata PDA of its mint");
+    }
+
+    // Puffing rewrites the account in place, so it cannot fix a resized or misplaced one
+    let unpuffable = problems.len();
+    if strict_deser(&mut account.data.as_slice()).is_err() {
+        problems.push("fails strict deserialization");
+    }
+    if metadata.data.name.len() < MAX_NAME_LENGTH
+        || metadata.data.symbol.len() < MAX_SYMBOL_LENGTH
+        || metadata.data.uri.len() < MAX_URI_LENGTH
+    {
+        problems.push("unpuffed");
+    }
+    match metadata.edition_nonce {
+        None => problems.push("missing edition nonce"),
+        Some(nonce) => {
+            let derived = Pubkey::create_program_address(
+                &[
+                    PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    metadata.mint.as_ref(),
+                    EDITION.as_bytes(),
+                    &[nonce],
+                ],
+                &mpl_token_metadata::id(),
+            );
+            if derived != Ok(master_edition_key(&metadata.mint)) {
+                problems.push("edition nonce does not derive the edition PDA");
+            }
+        }
+    }
+    let needs_puff = unpuffable == 0 && problems.len() > unpuffable;
+    (Some(metadata), needs_puff)
+}
+
+pub fn scan_call(app_matches: &ArgMatches, payer: Box<dyn Signer>, client: RpcClient) {
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp {
+        offset: 0,
+        bytes: MemcmpEncodedBytes::Base58(bs58::encode([Key::MetadataV1 as u8]).into_string()),
+        encoding: None,
+    })];
+    if let Some(update_authority) = pubkey_of(app_matches, "filter_update_authority") {
+        filters.push(memcmp(UPDATE_AUTHORITY_OFFSET, &update_authority));
+    }
+    if let Some(creator) = pubkey_of(app_matches, "filter_creator") {
+        filters.push(memcmp(FIRST_CREATOR_OFFSET, &creator));
+    }
+    let accounts = client
+        .get_program_accounts_with_config(
+            &mpl_token_metadata::id(),
+            RpcProgramAccountsConfig {
+                filters: Some(filters),
+                account_config: RpcAccountInfoConfig::default(),
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .unwrap();
+    println!("Scanning {} Metadata accounts", accounts.len());
+
+    let mut findings = vec![];
+    let mut needing_edition = vec![];
+    for (address, account) in &accounts {
+        let mut problems = vec![];
+        let (metadata, repairable) = check(address, account, &mut problems);
+        if let Some(metadata) = &metadata {
+            if let Some(keys) = expected_edition_keys(&metadata.token_standard) {
+                needing_edition.push((findings.len(), master_edition_key(&metadata.mint), keys));
+            }
+        }
+        findings.push(Finding {
+            address: *address,
+            problems,
+            repairable,
+        });
+    }
+
+    for chunk in needing_edition.chunks(100) {
+        let keys: Vec<Pubkey> = chunk.iter().map(|(_, key, _)| *key).collect();
+        let editions = client.get_multiple_accounts(&keys).unwrap();
+        for ((index, _, expected), edition) in chunk.iter().zip(editions) {
+            let matches = edition.map_or(false, |edition| {
+                edition.owner == mpl_token_metadata::id()
+                    && expected
+                        .iter()
+                        .any(|key| edition.data.first() == Some(&(*key as u8)))
+            });
+            if !matches {
+                findings[*index]
+                    .problems
+                    .push("edition PDA missing or of the wrong kind for its token standard");
+            }
+        }
+    }
+
+    let findings: Vec<Finding> = findings
+        .into_iter()
+        .filter(|finding| !finding.problems.is_empty())
+        .collect();
+    for finding in &findings {
+        println!(
+            "{}: {}{}",
+            finding.address,
+            finding.problems.join(", "),
+            if finding.repairable {
+                ""
+            } else {
+                " (not repairable)"
+            }
+        );
+    }
+    println!(
+        "Found {} problem accounts out of {}",
+        findings.len(),
+        accounts.len()
+    );
+
+    if !app_matches.is_present("repair") {
+        return;
+    }
+    let repairs: Vec<Pubkey> = findings
+        .iter()
+        .filter(|finding| finding.repairable)
+        .map(|finding| finding.address)
+        .collect();
+    for (i, batch) in repairs.chunks(REPAIR_BATCH_SIZE).enumerate() {
+        let instructions: Vec<_> = batch
+            .iter()
+            .map(|address| puff_metadata_account(mpl_token_metadata::id(), *address))
+            .collect();
+        let signature = send(app_matches, &client, payer.as_ref(), &instructions, &[]);
+        println!(
+            "Repaired {} / {}: {}",
+            i * REPAIR_BATCH_SIZE + batch.len(),
+            repairs.len(),
+            signature
+        );
+    }
+}
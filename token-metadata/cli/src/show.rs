@@ -0,0 +1,294 @@
+use {
+    crate::utils::{get_metadata, master_edition_key},
+    clap::ArgMatches,
+    mpl_token_metadata::{
+        pda::{
+            find_collection_authority_account, find_collection_royalty_account,
+            find_edition_account, find_edition_uri_policy_account,
+            find_pending_update_authority_account, find_use_authority_account,
+        },
+        state::{
+            CollectionAuthorityRecord, CollectionRoyaltyRecord, Creator, Edition, EditionMarker,
+            EditionUriPolicyRecord, Key, MasterEditionV1, MasterEditionV2, Metadata,
+            PendingUpdateAuthorityRecord, UseAuthorityRecord, EDITION_MARKER_BIT_SIZE,
+        },
+    },
+    serde_json::{json, Value},
+    solana_clap_utils::input_parsers::{pubkey_of, pubkeys_of},
+    solana_client::rpc_client::RpcClient,
+    solana_program::borsh::try_from_slice_unchecked,
+    solana_sdk::pubkey::Pubkey,
+    std::str::FromStr,
+};
+
+/// Fetches the off-chain JSON the uri of a Metadata points at.
+pub fn fetch_uri(uri: &str) -> Value {
+    match reqwest::blocking::get(trimmed(uri)).and_then(|response| response.json::<Value>()) {
+        Ok(value) => value,
+        Err(err) => json!({ "error": err.to_string() }),
+    }
+}
+
+/// Puffed name, symbol and uri fields are padded with null characters.
+fn trimmed(value: &str) -> &str {
+    value.trim_matches(char::from(0))
+}
+
+fn creators_json(creators: &Option<Vec<Creator>>) -> Value {
+    json!(creators.as_ref().map(|creators| {
+        creators
+            .iter()
+            .map(|creator| {
+                json!({
+                    "address": creator.address.to_string(),
+                    "verified": creator.verified,
+                    "share": creator.share,
+                })
+            })
+            .collect::<Vec<_>>()
+    }))
+}
+
+fn metadata_json(metadata: &Metadata) -> Value {
+    json!({
+        "key": format!("{:?}", metadata.key),
+        "update_authority": metadata.update_authority.to_string(),
+        "mint": metadata.mint.to_string(),
+        "name": trimmed(&metadata.data.name),
+        "symbol": trimmed(&metadata.data.symbol),
+        "uri": trimmed(&metadata.data.uri),
+        "seller_fee_basis_points": metadata.data.seller_fee_basis_points,
+        "creators": creators_json(&metadata.data.creators),
+        "primary_sale_happened": metadata.primary_sale_happened,
+        "is_mutable": metadata.is_mutable,
+        "edition_nonce": metadata.edition_nonce,
+        "token_standard": metadata
+            .token_standard
+            .as_ref()
+            .map(|token_standard| format!("{:?}", token_standard)),
+        "collection": metadata.collection.as_ref().map(|collection| {
+            json!({
+                "key": collection.key.to_string(),
+                "verified": collection.verified,
+            })
+        }),
+        "uses": metadata.uses.as_ref().map(|uses| {
+            json!({
+                "use_method": format!("{:?}", uses.use_method),
+                "remaining": uses.remaining,
+                "total": uses.total,
+            })
+        }),
+        "uses_expiry": metadata.uses_expiry,
+        "uses_utilized": metadata.uses_utilized,
+        "inherits_collection_royalty": metadata.inherits_collection_royalty(),
+    })
+}
+
+/// Edition numbers taken in every Edition Marker of a Master Edition, up to the marker of its
+/// supply. Max supply may be huge, so markers past the supply are not walked. Accounts that
+/// cannot be fetched or read are reported as an error in place of their marker.
+fn edition_markers_json(client: &RpcClient, mint: &Pubkey, supply: u64) -> Value {
+    let last_marker = supply / EDITION_MARKER_BIT_SIZE;
+    let markers: Vec<(u64, Pubkey)> = (0..=last_marker)
+        .map(|marker| (marker, find_edition_account(mint, marker.to_string()).0))
+        .collect();
+    let mut found = vec![];
+    for chunk in markers.chunks(100) {
+        let keys: Vec<Pubkey> = chunk.iter().map(|(_, key)| *key).collect();
+        let accounts = match client.get_multiple_accounts(&keys) {
+            Ok(accounts) => accounts,
+            Err(err) => {
+                found.push(json!({
+                    "first_edition": chunk[0].0 * EDITION_MARKER_BIT_SIZE,
+                    "error": err.to_string(),
+                }));
+                continue;
+            }
+        };
+        for ((marker, key), account) in chunk.iter().zip(accounts) {
+            let first_edition = marker * EDITION_MARKER_BIT_SIZE;
+            let edition_marker: EditionMarker = match account {
+                Some(account) => match try_from_slice_unchecked(&account.data) {
+                    Ok(edition_marker) => edition_marker,
+                    Err(err) => {
+                        found.push(json!({
+                            "key": key.to_string(),
+                            "first_edition": first_edition,
+                            "error": err.to_string(),
+                        }));
+                        continue;
+                    }
+                },
+                None => continue,
+            };
+            let taken: Vec<u64> = (first_edition..first_edition + EDITION_MARKER_BIT_SIZE)
+                .filter(|edition| edition_marker.edition_taken(*edition).unwrap_or(false))
+                .collect();
+            found.push(json!({
+                "key": key.to_string(),
+                "first_edition": first_edition,
+                "taken": taken,
+            }));
+        }
+    }
+    json!(found)
+}
+
+fn edition_json(client: &RpcClient, mint: &Pubkey) -> Value {
+    let edition_key = master_edition_key(mint);
+    let account = match client.get_account(&edition_key) {
+        Ok(account) => account,
+        Err(_) => return Value::Null,
+    };
+    if account.data[0] == Key::MasterEditionV1 as u8 {
+        let master_edition: MasterEditionV1 = try_from_slice_unchecked(&account.data).unwrap();
+        json!({
+            "type": "MasterEditionV1",
+            "key": edition_key.to_string(),
+            "supply": master_edition.supply,
+            "max_supply": master_edition.max_supply,
+            "printing_mint": master_edition.printing_mint.to_string(),
+            "one_time_printing_authorization_mint":
+                master_edition.one_time_printing_authorization_mint.to_string(),
+        })
+    } else if account.data[0] == Key::MasterEditionV2 as u8 {
+        let master_edition: MasterEditionV2 = try_from_slice_unchecked(&account.data).unwrap();
+        json!({
+            "type": "MasterEditionV2",
+            "key": edition_key.to_string(),
+            "supply": master_edition.supply,
+            "max_supply": master_edition.max_supply,
+            "edition_markers": edition_markers_json(client, mint, master_edition.supply),
+        })
+    } else {
+        let edition: Edition = try_from_slice_unchecked(&account.data).unwrap();
+        json!({
+            "type": "Edition",
+            "key": edition_key.to_string(),
+            "parent": edition.parent.to_string(),
+            "edition": edition.edition,
+        })
+    }
+}
+
+/// Largest token account of the mint holding any tokens, and its delegate.
+fn token_account_json(client: &RpcClient, mint: &Pubkey) -> (Value, Option<Pubkey>) {
+    let largest = client
+        .get_token_largest_accounts(mint)
+        .unwrap()
+        .into_iter()
+        .find(|balance| balance.amount.amount != "0");
+    let address = match largest {
+        Some(balance) => balance.address,
+        None => return (Value::Null, None),
+    };
+    match client
+        .get_token_account(&Pubkey::from_str(&address).unwrap())
+        .unwrap()
+    {
+        Some(token_account) => {
+            let delegate = token_account
+                .delegate
+                .as_ref()
+                .and_then(|delegate| Pubkey::from_str(delegate).ok());
+            (
+                json!({ "address": address, "account": token_account }),
+                delegate,
+            )
+        }
+        None => (Value::Null, None),
+    }
+}
+
+/// Records derived from the mint, and the use and collection authority records of every
+/// candidate authority.
+fn records_json(client: &RpcClient, metadata: &Metadata, candidates: &[Pubkey]) -> Value {
+    let mint = &metadata.mint;
+    let use_authorities: Vec<Value> = candidates
+        .iter()
+        .filter_map(|authority| {
+            let (record, _) = find_use_authority_account(mint, authority);
+            let account = client.get_account(&record).ok()?;
+            let use_authority: UseAuthorityRecord = try_from_slice_unchecked(&account.data).ok()?;
+            Some(json!({
+                "authority": authority.to_string(),
+                "record": record.to_string(),
+                "allowed_uses": use_authority.allowed_uses,
+            }))
+        })
+        .collect();
+    let collection_authorities: Vec<Value> = candidates
+        .iter()
+        .filter_map(|authority| {
+            let (record, _) = find_collection_authority_account(mint, authority);
+            let account = client.get_account(&record).ok()?;
+            let _: CollectionAuthorityRecord = try_from_slice_unchecked(&account.data).ok()?;
+            Some(json!({
+                "authority": authority.to_string(),
+                "record": record.to_string(),
+            }))
+        })
+        .collect();
+
+    let (record, _) = find_pending_update_authority_account(mint);
+    let pending_update_authority = client.get_account(&record).ok().map(|account| {
+        let pending: PendingUpdateAuthorityRecord =
+            try_from_slice_unchecked(&account.data).unwrap();
+        json!({
+            "record": record.to_string(),
+            "update_authority": pending.update_authority.to_string(),
+            "pending_update_authority": pending.pending_update_authority.to_string(),
+        })
+    });
+    let (record, _) = find_edition_uri_policy_account(mint);
+    let edition_uri_policy = client.get_account(&record).ok().map(|account| {
+        let policy: EditionUriPolicyRecord = try_from_slice_unchecked(&account.data).unwrap();
+        json!({
+            "record": record.to_string(),
+            "policy": format!("{:?}", policy.policy),
+        })
+    });
+    let (record, _) = find_collection_royalty_account(mint);
+    let collection_royalty = client.get_account(&record).ok().map(|account| {
+        let royalty: CollectionRoyaltyRecord = try_from_slice_unchecked(&account.data).unwrap();
+        json!({
+            "record": record.to_string(),
+            "seller_fee_basis_points": royalty.seller_fee_basis_points,
+            "creators": creators_json(&royalty.creators),
+        })
+    });
+
+    json!({
+        "use_authorities": use_authorities,
+        "collection_authorities": collection_authorities,
+        "pending_update_authority": pending_update_authority,
+        "edition_uri_policy": edition_uri_policy,
+        "collection_royalty": collection_royalty,
+    })
+}
+
+pub fn show_json(app_matches: &ArgMatches, client: RpcClient) {
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let (metadata_key, metadata) = get_metadata(&client, &mint_key);
+    let (token_account, delegate) = token_account_json(&client, &mint_key);
+
+    // Authority records are PDAs of the authority, so only known candidates can be looked up
+    let mut candidates = pubkeys_of(app_matches, "authority").unwrap_or_default();
+    candidates.push(metadata.update_authority);
+    candidates.extend(delegate);
+    candidates.sort();
+    candidates.dedup();
+
+    let mut output = json!({
+        "metadata_key": metadata_key.to_string(),
+        "metadata": metadata_json(&metadata),
+        "edition": edition_json(&client, &mint_key),
+        "token_account": token_account,
+        "records": records_json(&client, &metadata, &candidates),
+    });
+    if app_matches.is_present("fetch_uri") {
+        output["off_chain"] = fetch_uri(&metadata.data.uri);
+    }
+    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+}
@@ -0,0 +1,243 @@
+use {
+    crate::utils::{
+        get_metadata, master_edition_key, mint_arg, pubkey_arg, send, signer_arg,
+        signer_of_or_payer, token_account_arg, token_account_of,
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    mpl_token_metadata::instruction::{
+        burn_non_transferable, create_non_transferable_master_edition, freeze_delegated_account,
+        recover_non_transferable, thaw_delegated_account,
+    },
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::signature::Signer,
+};
+
+fn owner_arg<'a, 'b>() -> Arg<'a, 'b> {
+    signer_arg(
+        "owner",
+        "OWNER",
+        "Filepath or URL to a keypair of the token owner, defaults to you",
+    )
+}
+
+pub fn subcommands() -> Vec<App<'static, 'static>> {
+    vec![
+        SubCommand::with_name("freeze_delegated_account")
+            .about("Freeze a token account of an NFT, as the delegate of its tokens")
+            .arg(mint_arg("Mint of the NFT"))
+            .arg(signer_arg(
+                "delegate",
+                "DELEGATE",
+                "Filepath or URL to a keypair of the delegate, defaults to you",
+            ))
+            .arg(
+                pubkey_arg(
+                    "owner",
+                    "OWNER",
+                    "Owner of the token account, used to find it if not given",
+                )
+                .required(false),
+            )
+            .arg(token_account_arg()),
+        SubCommand::with_name("thaw_delegated_account")
+            .about("Thaw a token account of an NFT, as the delegate of its tokens")
+            .arg(mint_arg("Mint of the NFT"))
+            .arg(signer_arg(
+                "delegate",
+                "DELEGATE",
+                "Filepath or URL to a keypair of the delegate, defaults to you",
+            ))
+            .arg(
+                pubkey_arg(
+                    "owner",
+                    "OWNER",
+                    "Owner of the token account, used to find it if not given",
+                )
+                .required(false),
+            )
+            .arg(token_account_arg()),
+        SubCommand::with_name("create_non_transferable_master_edition")
+            .about("Create the master edition of a non-transferable NFT, freezing it in its owner's wallet")
+            .arg(mint_arg("Mint of the NFT, with a freeze authority"))
+            .arg(signer_arg(
+                "mint_authority",
+                "MINT_AUTHORITY",
+                "Filepath or URL to a keypair of the mint and freeze authority, defaults to you",
+            ))
+            .arg(owner_arg())
+            .arg(token_account_arg()),
+        SubCommand::with_name("burn_non_transferable")
+            .about("Burn a non-transferable NFT, as its owner")
+            .arg(mint_arg("Mint of the NFT"))
+            .arg(owner_arg())
+            .arg(token_account_arg()),
+        SubCommand::with_name("recover_non_transferable")
+            .about(
+                "Move a non-transferable NFT to a new wallet of its owner, as the update authority",
+            )
+            .arg(mint_arg("Mint of the NFT"))
+            .arg(
+                pubkey_arg(
+                    "token_account",
+                    "TOKEN_ACCOUNT",
+                    "Token account currently holding the NFT",
+                )
+                .required(true),
+            )
+            .arg(signer_arg(
+                "new_owner",
+                "NEW_OWNER",
+                "Filepath or URL to a keypair of the new owner, defaults to you",
+            ))
+            .arg(
+                pubkey_arg(
+                    "new_token_account",
+                    "NEW_TOKEN_ACCOUNT",
+                    "Initialized token account of the new owner for the mint",
+                )
+                .required(true),
+            ),
+    ]
+}
+
+pub fn freeze_delegated_account_call(
+    app_matches: &ArgMatches,
+    sub_command: &str,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let delegate = signer_of_or_payer(app_matches, "delegate");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let owner = pubkey_of(app_matches, "owner").unwrap_or_else(|| delegate.pubkey());
+    let token_account = token_account_of(app_matches, &client, &owner, &mint_key);
+    let edition = master_edition_key(&mint_key);
+
+    let instruction = match sub_command {
+        "freeze_delegated_account" => freeze_delegated_account(
+            mpl_token_metadata::id(),
+            delegate.pubkey(),
+            token_account,
+            edition,
+            mint_key,
+        ),
+        _ => thaw_delegated_account(
+            mpl_token_metadata::id(),
+            delegate.pubkey(),
+            token_account,
+            edition,
+            mint_key,
+        ),
+    };
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[instruction],
+        &[delegate.as_ref()],
+    );
+    println!(
+        "Ran {} on token account {:?}: {}",
+        sub_command, token_account, signature
+    );
+}
+
+pub fn create_non_transferable_master_edition_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let mint_authority = signer_of_or_payer(app_matches, "mint_authority");
+    let owner = signer_of_or_payer(app_matches, "owner");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+    let token_account = token_account_of(app_matches, &client, &owner.pubkey(), &mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[create_non_transferable_master_edition(
+            mpl_token_metadata::id(),
+            master_edition_key(&mint_key),
+            mint_key,
+            update_authority.pubkey(),
+            mint_authority.pubkey(),
+            metadata_key,
+            payer.pubkey(),
+            owner.pubkey(),
+            token_account,
+        )],
+        &[
+            update_authority.as_ref(),
+            mint_authority.as_ref(),
+            owner.as_ref(),
+        ],
+    );
+    println!(
+        "Created non-transferable master edition of {:?} frozen in {:?}: {}",
+        mint_key, token_account, signature
+    );
+}
+
+pub fn burn_non_transferable_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let owner = signer_of_or_payer(app_matches, "owner");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+    let token_account = token_account_of(app_matches, &client, &owner.pubkey(), &mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[burn_non_transferable(
+            mpl_token_metadata::id(),
+            metadata_key,
+            owner.pubkey(),
+            token_account,
+            mint_key,
+            master_edition_key(&mint_key),
+        )],
+        &[owner.as_ref()],
+    );
+    println!("Burned non-transferable {:?}: {}", mint_key, signature);
+}
+
+pub fn recover_non_transferable_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let new_owner = signer_of_or_payer(app_matches, "new_owner");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let token_account = pubkey_of(app_matches, "token_account").unwrap();
+    let new_token_account = pubkey_of(app_matches, "new_token_account").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[recover_non_transferable(
+            mpl_token_metadata::id(),
+            metadata_key,
+            update_authority.pubkey(),
+            token_account,
+            new_owner.pubkey(),
+            new_token_account,
+            mint_key,
+            master_edition_key(&mint_key),
+        )],
+        &[update_authority.as_ref(), new_owner.as_ref()],
+    );
+    println!(
+        "Recovered non-transferable {:?} to {:?}: {}",
+        mint_key, new_token_account, signature
+    );
+}
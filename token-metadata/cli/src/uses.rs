@@ -0,0 +1,246 @@
+use {
+    crate::utils::{
+        find_token_account, get_metadata, mint_arg, pubkey_arg, send, signer_arg,
+        signer_of_or_payer,
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        instruction::{
+            approve_use_authority, reset_uses, revoke_use_authority, utilize, utilize_with_receipt,
+        },
+        pda::{
+            find_program_as_burner_account, find_use_authority_account, find_use_receipt_account,
+        },
+    },
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::signature::Signer,
+};
+
+fn number_of_uses_arg<'a, 'b>(help: &'b str) -> Arg<'a, 'b> {
+    Arg::with_name("number_of_uses")
+        .long("number_of_uses")
+        .value_name("NUMBER_OF_USES")
+        .takes_value(true)
+        .required(false)
+        .help(help)
+}
+
+fn number_of_uses_of(app_matches: &ArgMatches) -> u64 {
+    app_matches
+        .value_of("number_of_uses")
+        .map(|val| val.parse::<u64>().unwrap())
+        .unwrap_or(1)
+}
+
+pub fn subcommands() -> Vec<App<'static, 'static>> {
+    vec![
+        SubCommand::with_name("approve_use_authority")
+            .about("Allow another account to utilize an NFT you own")
+            .arg(mint_arg("Mint of the NFT"))
+            .arg(pubkey_arg("user", "USER", "Use authority to approve").required(true))
+            .arg(number_of_uses_arg(
+                "Uses the authority may utilize [default: 1]",
+            ))
+            .arg(signer_arg(
+                "owner",
+                "OWNER",
+                "Filepath or URL to a keypair of the token owner, defaults to you",
+            )),
+        SubCommand::with_name("revoke_use_authority")
+            .about("Revoke a use authority of an NFT you own")
+            .arg(mint_arg("Mint of the NFT"))
+            .arg(pubkey_arg("user", "USER", "Use authority to revoke").required(true))
+            .arg(signer_arg(
+                "owner",
+                "OWNER",
+                "Filepath or URL to a keypair of the token owner, defaults to you",
+            )),
+        SubCommand::with_name("utilize")
+            .about("Utilize an NFT, as its owner or an approved use authority")
+            .arg(mint_arg("Mint of the NFT"))
+            .arg(number_of_uses_arg("Uses to utilize [default: 1]"))
+            .arg(signer_arg(
+                "use_authority",
+                "USE_AUTHORITY",
+                "Filepath or URL to a keypair of the owner or use authority, defaults to you",
+            ))
+            .arg(
+                pubkey_arg(
+                    "owner",
+                    "OWNER",
+                    "Owner of the token when utilizing as an approved use authority",
+                )
+                .required(false),
+            )
+            .arg(
+                Arg::with_name("receipt")
+                    .long("receipt")
+                    .takes_value(false)
+                    .required(false)
+                    .help("Record the use in a Use Receipt"),
+            ),
+        SubCommand::with_name("reset_uses")
+            .about(
+                "Restore the remaining uses of an NFT to its total uses, as the update authority",
+            )
+            .arg(mint_arg("Mint of the NFT"))
+            .arg(
+                Arg::with_name("expiry")
+                    .long("expiry")
+                    .value_name("UNIX_TIMESTAMP")
+                    .takes_value(true)
+                    .required(false)
+                    .help("Time after which the uses can no longer be utilized"),
+            ),
+    ]
+}
+
+pub fn approve_use_authority_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let owner = signer_of_or_payer(app_matches, "owner");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let user = pubkey_of(app_matches, "user").unwrap();
+    let number_of_uses = number_of_uses_of(app_matches);
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+    let (record, _) = find_use_authority_account(&mint_key, &user);
+    let (burner, _) = find_program_as_burner_account();
+    let token_account = find_token_account(&client, &owner.pubkey(), &mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[approve_use_authority(
+            mpl_token_metadata::id(),
+            record,
+            user,
+            owner.pubkey(),
+            payer.pubkey(),
+            token_account,
+            metadata_key,
+            mint_key,
+            burner,
+            number_of_uses,
+        )],
+        &[owner.as_ref()],
+    );
+    println!(
+        "Approved use authority {:?} for {} uses with record {:?}: {}",
+        user, number_of_uses, record, signature
+    );
+}
+
+pub fn revoke_use_authority_call(
+    app_matches: &ArgMatches,
+    payer: Box<dyn Signer>,
+    client: RpcClient,
+) {
+    let owner = signer_of_or_payer(app_matches, "owner");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let user = pubkey_of(app_matches, "user").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+    let (record, _) = find_use_authority_account(&mint_key, &user);
+    let token_account = find_token_account(&client, &owner.pubkey(), &mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[revoke_use_authority(
+            mpl_token_metadata::id(),
+            record,
+            user,
+            owner.pubkey(),
+            token_account,
+            metadata_key,
+            mint_key,
+        )],
+        &[owner.as_ref()],
+    );
+    println!("Revoked use authority {:?}: {}", user, signature);
+}
+
+pub fn utilize_call(app_matches: &ArgMatches, payer: Box<dyn Signer>, client: RpcClient) {
+    let use_authority = signer_of_or_payer(app_matches, "use_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let number_of_uses = number_of_uses_of(app_matches);
+    let owner = pubkey_of(app_matches, "owner").unwrap_or_else(|| use_authority.pubkey());
+    let (metadata_key, metadata) = get_metadata(&client, &mint_key);
+    let token_account = find_token_account(&client, &owner, &mint_key);
+
+    // A use authority other than the owner goes through its Use Authority Record
+    let (record, burner) = if owner != use_authority.pubkey() {
+        (
+            Some(find_use_authority_account(&mint_key, &use_authority.pubkey()).0),
+            Some(find_program_as_burner_account().0),
+        )
+    } else {
+        (None, None)
+    };
+
+    let instruction = if app_matches.is_present("receipt") {
+        let (use_receipt, _) = find_use_receipt_account(&mint_key, metadata.uses_utilized);
+        utilize_with_receipt(
+            mpl_token_metadata::id(),
+            metadata_key,
+            token_account,
+            mint_key,
+            use_receipt,
+            record,
+            use_authority.pubkey(),
+            owner,
+            burner,
+            number_of_uses,
+        )
+    } else {
+        utilize(
+            mpl_token_metadata::id(),
+            metadata_key,
+            token_account,
+            mint_key,
+            record,
+            use_authority.pubkey(),
+            owner,
+            burner,
+            number_of_uses,
+        )
+    };
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[instruction],
+        &[use_authority.as_ref()],
+    );
+    println!(
+        "Utilized {} uses of {:?}: {}",
+        number_of_uses, mint_key, signature
+    );
+}
+
+pub fn reset_uses_call(app_matches: &ArgMatches, payer: Box<dyn Signer>, client: RpcClient) {
+    let update_authority = signer_of_or_payer(app_matches, "update_authority");
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let expiry = app_matches
+        .value_of("expiry")
+        .map(|val| val.parse::<i64>().unwrap());
+    let (metadata_key, _) = get_metadata(&client, &mint_key);
+
+    let signature = send(
+        app_matches,
+        &client,
+        payer.as_ref(),
+        &[reset_uses(
+            mpl_token_metadata::id(),
+            metadata_key,
+            update_authority.pubkey(),
+            expiry,
+        )],
+        &[update_authority.as_ref()],
+    );
+    println!("Reset uses of {:?}: {}", mint_key, signature);
+}
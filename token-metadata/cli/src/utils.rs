@@ -0,0 +1,230 @@
+use {
+    crate::offline::{blockhash_of, presigners_of, print_sign_only},
+    clap::{Arg, ArgMatches},
+    mpl_token_metadata::{
+        pda::{find_master_edition_account, find_metadata_account},
+        state::{Creator, Metadata, UseMethod, Uses},
+    },
+    solana_clap_utils::{
+        input_parsers::pubkey_of,
+        input_validators::{is_valid_pubkey, is_valid_signer},
+        nonce::{NONCE_ARG, NONCE_AUTHORITY_ARG},
+        offline::SIGN_ONLY_ARG,
+    },
+    solana_client::{rpc_client::RpcClient, rpc_request::TokenAccountsFilter},
+    solana_program::borsh::try_from_slice_unchecked,
+    solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature, Signer},
+        signer::null_signer::NullSigner,
+        system_instruction::advance_nonce_account,
+        transaction::Transaction,
+    },
+    std::str::FromStr,
+};
+
+/// Reads the signer passed as `name`, either a keypair file or the pubkey of a signer whose
+/// signature is collected offline.
+pub fn signer_of(app_matches: &ArgMatches, name: &str) -> Box<dyn Signer> {
+    let value = app_matches.value_of(name).unwrap();
+    match Pubkey::from_str(value) {
+        Ok(pubkey) => Box::new(NullSigner::new(&pubkey)),
+        Err(_) => Box::new(read_keypair_file(value).unwrap()),
+    }
+}
+
+/// Reads the signer passed as `name`, defaulting to a new keypair for an account to create.
+pub fn signer_of_or_new(app_matches: &ArgMatches, name: &str) -> Box<dyn Signer> {
+    if app_matches.is_present(name) {
+        signer_of(app_matches, name)
+    } else {
+        Box::new(Keypair::new())
+    }
+}
+
+/// Reads the signer passed as `name`, defaulting to the fee payer.
+pub fn signer_of_or_payer(app_matches: &ArgMatches, name: &str) -> Box<dyn Signer> {
+    if app_matches.is_present(name) {
+        signer_of(app_matches, name)
+    } else {
+        signer_of(app_matches, "keypair")
+    }
+}
+
+pub fn mint_arg<'a, 'b>(help: &'b str) -> Arg<'a, 'b> {
+    Arg::with_name("mint")
+        .long("mint")
+        .value_name("MINT")
+        .required(true)
+        .validator(is_valid_pubkey)
+        .takes_value(true)
+        .help(help)
+}
+
+pub fn pubkey_arg<'a, 'b>(name: &'a str, value_name: &'a str, help: &'b str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name(value_name)
+        .validator(is_valid_pubkey)
+        .takes_value(true)
+        .help(help)
+}
+
+pub fn signer_arg<'a, 'b>(name: &'a str, value_name: &'a str, help: &'b str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name(value_name)
+        .validator(is_valid_signer)
+        .takes_value(true)
+        .required(false)
+        .help(help)
+}
+
+pub fn token_account_arg<'a, 'b>() -> Arg<'a, 'b> {
+    pubkey_arg(
+        "token_account",
+        "TOKEN_ACCOUNT",
+        "Token account holding the NFT, found from the owner if not given",
+    )
+    .required(false)
+}
+
+pub fn get_metadata(client: &RpcClient, mint: &Pubkey) -> (Pubkey, Metadata) {
+    let (metadata_key, _) = find_metadata_account(mint);
+    let metadata_account = client.get_account(&metadata_key).unwrap();
+    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    (metadata_key, metadata)
+}
+
+pub fn master_edition_key(mint: &Pubkey) -> Pubkey {
+    find_master_edition_account(mint).0
+}
+
+/// Finds a token account of `owner` holding some of `mint`.
+pub fn find_token_account(client: &RpcClient, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::from_str(
+        &client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint))
+            .unwrap()
+            .iter()
+            .find(|x| {
+                client
+                    .get_token_account_balance(&Pubkey::from_str(&x.pubkey).unwrap())
+                    .unwrap()
+                    .amount
+                    != "0"
+            })
+            .expect("No token account holding the mint")
+            .pubkey,
+    )
+    .unwrap()
+}
+
+/// Token account passed as `token_account`, or the one of `owner` holding `mint`.
+pub fn token_account_of(
+    app_matches: &ArgMatches,
+    client: &RpcClient,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Pubkey {
+    pubkey_of(app_matches, "token_account")
+        .unwrap_or_else(|| find_token_account(client, owner, mint))
+}
+
+pub fn parse_use_method(value: &str) -> UseMethod {
+    match value {
+        "burn" => UseMethod::Burn,
+        "multiple" => UseMethod::Multiple,
+        "single" => UseMethod::Single,
+        _ => panic!("Use method must be burn, multiple or single"),
+    }
+}
+
+/// Uses from `use_method` and `total_uses`, None when no use method is given.
+pub fn uses_of(app_matches: &ArgMatches) -> Option<Uses> {
+    app_matches.value_of("use_method").map(|use_method| {
+        let total = app_matches
+            .value_of("total_uses")
+            .map(|val| val.parse::<u64>().unwrap())
+            .unwrap_or(1);
+        Uses {
+            use_method: parse_use_method(use_method),
+            remaining: total,
+            total,
+        }
+    })
+}
+
+/// Creators from repeated `ADDRESS:SHARE` values of `creator`, None when none are given.
+pub fn creators_of(app_matches: &ArgMatches, verified: &Pubkey) -> Option<Vec<Creator>> {
+    app_matches.values_of("creator").map(|values| {
+        values
+            .map(|value| {
+                let (address, share) = value
+                    .split_once(':')
+                    .expect("Creators must be given as ADDRESS:SHARE");
+                let address = Pubkey::from_str(address).unwrap();
+                Creator {
+                    address,
+                    verified: address == *verified,
+                    share: share.parse::<u8>().unwrap(),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Signs `instructions` with the fee payer and `signers` and sends them in one transaction.
+///
+/// With `--sign-only` the partially signed transaction is printed for `submit` and the process
+/// exits. With `--nonce` the transaction first advances the durable nonce it is signed with.
+pub fn send(
+    app_matches: &ArgMatches,
+    client: &RpcClient,
+    payer: &dyn Signer,
+    instructions: &[Instruction],
+    signers: &[&dyn Signer],
+) -> Signature {
+    let nonce_account = pubkey_of(app_matches, NONCE_ARG.name);
+    let nonce_authority = signer_of_or_payer(app_matches, NONCE_AUTHORITY_ARG.name);
+    let mut all_instructions = vec![];
+    let mut all_signers = vec![payer];
+    if let Some(nonce_account) = nonce_account {
+        all_instructions.push(advance_nonce_account(
+            &nonce_account,
+            &nonce_authority.pubkey(),
+        ));
+        all_signers.push(nonce_authority.as_ref());
+    }
+    all_instructions.extend_from_slice(instructions);
+    for signer in signers {
+        if !all_signers.iter().any(|s| s.pubkey() == signer.pubkey()) {
+            all_signers.push(*signer);
+        }
+    }
+
+    // Signatures collected offline stand in for the pubkeys they were passed for
+    let presigners = presigners_of(app_matches);
+    let all_signers: Vec<&dyn Signer> = all_signers
+        .into_iter()
+        .map(|signer| {
+            presigners
+                .iter()
+                .find(|presigner| presigner.pubkey() == signer.pubkey())
+                .map_or(signer, |presigner| presigner as &dyn Signer)
+        })
+        .collect();
+
+    let mut transaction = Transaction::new_with_payer(&all_instructions, Some(&payer.pubkey()));
+    let blockhash = blockhash_of(app_matches, client);
+    if app_matches.is_present(SIGN_ONLY_ARG.name) {
+        transaction
+            .try_partial_sign(&all_signers, blockhash)
+            .unwrap();
+        print_sign_only(&transaction);
+        std::process::exit(0);
+    }
+    transaction.try_sign(&all_signers, blockhash).unwrap();
+    client.send_and_confirm_transaction(&transaction).unwrap()
+}
@@ -0,0 +1,36 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::MetadataError,
+    state::{EditionUriPolicy, EditionUriPolicyRecord, EDITION, EDITION_URI_POLICY, PREFIX},
+    utils::{assert_derivation, assert_owned_by},
+};
+
+/// Returns the uri policy of the master edition of `mint`. Master editions without a policy
+/// record only allow prints that copy the master uri.
+pub fn assert_edition_uri_policy_record(
+    program_id: &Pubkey,
+    edition_uri_policy_record_info: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<EditionUriPolicy, ProgramError> {
+    let bump = assert_derivation(
+        program_id,
+        edition_uri_policy_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+            EDITION_URI_POLICY.as_bytes(),
+        ],
+    )?;
+    if edition_uri_policy_record_info.try_data_is_empty()? {
+        return Ok(EditionUriPolicy::Fixed);
+    }
+    assert_owned_by(edition_uri_policy_record_info, program_id)?;
+    let record = EditionUriPolicyRecord::from_account_info(edition_uri_policy_record_info)?;
+    if record.bump != bump {
+        return Err(MetadataError::InvalidEditionUriPolicyRecord.into());
+    }
+    Ok(record.policy)
+}
@@ -1,2 +1,6 @@
 pub mod collection;
+pub mod edition_uri_policy;
+pub mod non_transferable;
+pub mod royalty;
+pub mod update_authority;
 pub mod uses;
@@ -0,0 +1,29 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use spl_token::state::Mint;
+
+use crate::{
+    error::MetadataError,
+    state::MasterEditionV2,
+    utils::{
+        assert_edition_valid, assert_freeze_authority_matches_mint, assert_initialized,
+        assert_owned_by,
+    },
+};
+
+/// A non-transferable token must be the only token of its mint, frozen by the master edition,
+/// so the edition must not be able to print copies that could be sold instead.
+pub fn assert_non_transferable_edition(
+    program_id: &Pubkey,
+    mint_info: &AccountInfo,
+    edition_info: &AccountInfo,
+) -> Result<(), ProgramError> {
+    assert_owned_by(edition_info, program_id)?;
+    assert_edition_valid(program_id, mint_info.key, edition_info)?;
+    let master_edition = MasterEditionV2::from_account_info(edition_info)?;
+    if master_edition.max_supply != Some(0) {
+        return Err(MetadataError::NonTransferableMustNotHavePrints.into());
+    }
+    let mint: Mint = assert_initialized(mint_info)?;
+    assert_freeze_authority_matches_mint(&mint.freeze_authority, edition_info)?;
+    Ok(())
+}
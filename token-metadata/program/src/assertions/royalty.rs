@@ -0,0 +1,74 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::MetadataError,
+    state::{CollectionRoyaltyRecord, Creator, COLLECTION_ROYALTY, MAX_CREATOR_LIMIT, PREFIX},
+    utils::{assert_derivation, assert_owned_by},
+};
+
+pub fn assert_collection_royalty_valid(
+    seller_fee_basis_points: u16,
+    creators: &Option<Vec<Creator>>,
+    update_authority: &Pubkey,
+) -> Result<(), ProgramError> {
+    if seller_fee_basis_points > 10000 {
+        return Err(MetadataError::InvalidBasisPoints.into());
+    }
+    if let Some(creators) = creators {
+        if creators.len() > MAX_CREATOR_LIMIT {
+            return Err(MetadataError::CreatorsTooLong.into());
+        }
+        if creators.is_empty() {
+            return Err(MetadataError::CreatorsMustBeAtleastOne.into());
+        }
+        let mut total: u8 = 0;
+        for (i, creator) in creators.iter().enumerate() {
+            if creators
+                .iter()
+                .skip(i + 1)
+                .any(|c| c.address == creator.address)
+            {
+                return Err(MetadataError::DuplicateCreatorAddress.into());
+            }
+            // Only the signing update authority can vouch for itself here, other creators
+            // are never marked verified by this record.
+            if creator.verified && creator.address != *update_authority {
+                return Err(MetadataError::CannotVerifyAnotherCreator.into());
+            }
+            total = total
+                .checked_add(creator.share)
+                .ok_or(MetadataError::NumericalOverflowError)?;
+        }
+        if total != 100 {
+            return Err(MetadataError::ShareTotalMustBe100.into());
+        }
+    }
+    Ok(())
+}
+
+/// Returns the royalty record of `collection_mint`, or None if it has not been created.
+pub fn assert_collection_royalty_record(
+    program_id: &Pubkey,
+    collection_royalty_record_info: &AccountInfo,
+    collection_mint: &Pubkey,
+) -> Result<Option<CollectionRoyaltyRecord>, ProgramError> {
+    let bump = assert_derivation(
+        program_id,
+        collection_royalty_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            collection_mint.as_ref(),
+            COLLECTION_ROYALTY.as_bytes(),
+        ],
+    )?;
+    if collection_royalty_record_info.try_data_is_empty()? {
+        return Ok(None);
+    }
+    assert_owned_by(collection_royalty_record_info, program_id)?;
+    let record = CollectionRoyaltyRecord::from_account_info(collection_royalty_record_info)?;
+    if record.bump != bump || record.collection_mint != *collection_mint {
+        return Err(MetadataError::InvalidCollectionRoyaltyRecord.into());
+    }
+    Ok(Some(record))
+}
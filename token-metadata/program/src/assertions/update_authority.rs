@@ -0,0 +1,34 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::MetadataError,
+    state::{Metadata, PendingUpdateAuthorityRecord, PENDING_UPDATE_AUTHORITY, PREFIX},
+    utils::{assert_derivation, assert_owned_by},
+};
+
+pub fn assert_pending_update_authority_record(
+    program_id: &Pubkey,
+    pending_update_authority_record_info: &AccountInfo,
+    metadata: &Metadata,
+) -> Result<PendingUpdateAuthorityRecord, ProgramError> {
+    let bump = assert_derivation(
+        program_id,
+        pending_update_authority_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+    )?;
+    if pending_update_authority_record_info.try_data_is_empty()? {
+        return Err(MetadataError::InvalidPendingUpdateAuthorityRecord.into());
+    }
+    assert_owned_by(pending_update_authority_record_info, program_id)?;
+    let record =
+        PendingUpdateAuthorityRecord::from_account_info(pending_update_authority_record_info)?;
+    if record.bump != bump {
+        return Err(MetadataError::InvalidPendingUpdateAuthorityRecord.into());
+    }
+    Ok(record)
+}
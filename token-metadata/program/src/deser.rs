@@ -17,6 +17,8 @@ pub fn meta_deser(buf: &mut &[u8]) -> Result<Metadata, borsh::maybestd::io::Erro
         BorshDeserialize::deserialize(buf);
     let collection_res: Result<Option<Collection>, BorshError> = BorshDeserialize::deserialize(buf);
     let uses_res: Result<Option<Uses>, BorshError> = BorshDeserialize::deserialize(buf);
+    let uses_expiry_res: Result<Option<i64>, BorshError> = BorshDeserialize::deserialize(buf);
+    let uses_utilized_res: Result<u64, BorshError> = BorshDeserialize::deserialize(buf);
 
     /* We can have accidentally valid, but corrupted data, particularly on the Collection struct,
     so to increase probability of catching errors If any of these deserializations fail, set all values to None.
@@ -31,6 +33,15 @@ pub fn meta_deser(buf: &mut &[u8]) -> Result<Metadata, borsh::maybestd::io::Erro
         }
     };
 
+    // Accounts written before these fields existed have zeroed padding here, the match on
+    // uses only guards against reading them out of corrupted data.
+    let (uses_expiry, uses_utilized) = match (&uses, uses_expiry_res, uses_utilized_res) {
+        (Some(_), Ok(uses_expiry_res), Ok(uses_utilized_res)) => {
+            (uses_expiry_res, uses_utilized_res)
+        }
+        _ => (None, 0),
+    };
+
     let metadata = Metadata {
         key,
         update_authority,
@@ -42,6 +53,8 @@ pub fn meta_deser(buf: &mut &[u8]) -> Result<Metadata, borsh::maybestd::io::Erro
         token_standard,
         collection,
         uses,
+        uses_expiry,
+        uses_utilized,
     };
 
     Ok(metadata)
@@ -128,6 +141,8 @@ mod tests {
             token_standard: None,
             collection: None,
             uses: None,
+            uses_expiry: None,
+            uses_utilized: 0,
         };
 
         puff_out_data_fields(&mut metadata);
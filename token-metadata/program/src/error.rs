@@ -386,6 +386,54 @@ pub enum MetadataError {
     /// Revoke Collection Authority signer is incorrect
     #[error("Revoke Collection Authority signer is incorrect")]
     RevokeCollectionAuthoritySignerIncorrect,
+
+    /// Pending Update Authority Record is invalid
+    #[error("Pending Update Authority Record is invalid")]
+    InvalidPendingUpdateAuthorityRecord,
+
+    /// Signer is not the nominated update authority
+    #[error("Signer is not the nominated update authority")]
+    PendingUpdateAuthorityMismatch,
+
+    /// Update authority changed since the nomination was made
+    #[error("Update authority changed since the nomination was made, cancel and nominate again")]
+    UpdateAuthorityChangedSinceNomination,
+
+    /// Edition Uri Policy Record is invalid
+    #[error("Edition Uri Policy Record is invalid")]
+    InvalidEditionUriPolicyRecord,
+
+    /// Master edition does not allow uri overrides on prints
+    #[error("Master edition does not allow uri overrides on prints")]
+    EditionUriOverrideNotAllowed,
+
+    /// Uses have expired
+    #[error("Uses have expired")]
+    UsesExpired,
+
+    /// Use Receipt already exists
+    #[error("Use Receipt already exists")]
+    UseReceiptAlreadyExists,
+
+    /// Non-transferable tokens must be a master edition with a max supply of zero
+    #[error("Non-transferable tokens must be a master edition with a max supply of zero")]
+    NonTransferableMustNotHavePrints,
+
+    /// Non-transferable tokens must have a freeze authority for the master edition
+    #[error("Non-transferable tokens must have a freeze authority for the master edition")]
+    NonTransferableMissingFreezeAuthority,
+
+    /// Token is not non-transferable
+    #[error("Token is not non-transferable")]
+    NotNonTransferable,
+
+    /// Collection royalty record is invalid
+    #[error("Collection royalty record is invalid")]
+    InvalidCollectionRoyaltyRecord,
+
+    /// Collection royalty record must be provided for inheriting items
+    #[error("Collection royalty record must be provided for inheriting items")]
+    CollectionRoyaltyRecordNotProvided,
 }
 
 impl PrintProgramError for MetadataError {
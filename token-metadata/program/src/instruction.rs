@@ -1,6 +1,9 @@
 use crate::{
     deprecated_instruction::{MintPrintingTokensViaTokenArgs, SetReservationListArgs},
-    state::{Collection, Creator, Data, DataV2, Uses, EDITION, EDITION_MARKER_BIT_SIZE, PREFIX},
+    state::{
+        Collection, Creator, Data, DataV2, EditionUriPolicy, Uses, EDITION,
+        EDITION_MARKER_BIT_SIZE, EDITION_URI_POLICY, PREFIX,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankInstruction;
@@ -62,6 +65,27 @@ pub struct MintNewEditionFromMasterEditionViaTokenArgs {
     pub edition: u64,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct MintNewEditionFromMasterEditionViaTokenWithUriArgs {
+    pub edition: u64,
+    /// Uri of the new edition, replaces the uri copied from the master edition
+    pub uri: String,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetEditionUriPolicyArgs {
+    pub policy: EditionUriPolicy,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetCollectionRoyaltyArgs {
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct ApproveUseAuthorityArgs {
@@ -74,6 +98,13 @@ pub struct UtilizeArgs {
     pub number_of_uses: u64,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ResetUsesArgs {
+    /// Unix timestamp after which the uses can no longer be utilized, None to never expire
+    pub expiry: Option<i64>,
+}
+
 /// Instructions supported by the Metadata program.
 #[derive(BorshSerialize, BorshDeserialize, Clone, ShankInstruction)]
 #[rustfmt::skip]
@@ -400,7 +431,126 @@ pub enum MetadataInstruction {
     /// Remove Creator Verificaton.
     #[account(0, writable, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
     #[account(1, signer, name="creator", desc="Creator")]
-    RemoveCreatorVerification
+    RemoveCreatorVerification,
+
+    /// Nominate a new update authority for a Metadata. The nominee only becomes the update authority
+    /// once they sign [accept_update_authority], until then the current update authority can cancel.
+    #[account(0, writable, name="pending_update_authority_record", desc="Pending Update Authority Record PDA (pda of ['metadata', program id, mint id, 'pending_update_authority'])")]
+    #[account(1, name="new_update_authority", desc="Nominated update authority")]
+    #[account(2, signer, name="update_authority", desc="Current update authority")]
+    #[account(3, signer, writable, name="payer", desc="Payer")]
+    #[account(4, name="metadata", desc="Metadata account")]
+    #[account(5, name="system_program", desc="System program")]
+    #[account(6, name="rent", desc="Rent info")]
+    NominateUpdateAuthority,
+
+    /// Accept a pending update authority nomination, making the signer the update authority of the Metadata.
+    #[account(0, writable, name="pending_update_authority_record", desc="Pending Update Authority Record PDA")]
+    #[account(1, signer, name="new_update_authority", desc="Nominated update authority")]
+    #[account(2, writable, name="update_authority", desc="Current update authority, receives the record rent")]
+    #[account(3, writable, name="metadata", desc="Metadata account")]
+    AcceptUpdateAuthority,
+
+    /// Cancel a pending update authority nomination before it is accepted.
+    #[account(0, writable, name="pending_update_authority_record", desc="Pending Update Authority Record PDA")]
+    #[account(1, signer, writable, name="update_authority", desc="Current update authority")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    CancelUpdateAuthorityNomination,
+
+    /// Set whether prints of a Master Edition V2 may override the master uri.
+    #[account(0, writable, name="edition_uri_policy_record", desc="Edition Uri Policy Record PDA (pda of ['metadata', program id, master metadata mint id, 'edition', 'uri_policy'])")]
+    #[account(1, signer, name="update_authority", desc="Update authority of the master metadata")]
+    #[account(2, signer, writable, name="payer", desc="Payer")]
+    #[account(3, name="master_edition", desc="Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])")]
+    #[account(4, name="metadata", desc="Master record metadata account")]
+    #[account(5, name="system_program", desc="System program")]
+    #[account(6, name="rent", desc="Rent info")]
+    SetEditionUriPolicy(SetEditionUriPolicyArgs),
+
+    /// Same as MintNewEditionFromMasterEditionViaToken, but the new edition gets its own uri.
+    /// Only allowed if the Edition Uri Policy Record of the master edition is Overridable.
+    #[account(0, writable, name="new_metadata", desc="New Metadata key (pda of ['metadata', program id, mint id])")]
+    #[account(1, writable, name="new_edition", desc="New Edition (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(2, writable, name="master_edition", desc="Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])")]
+    #[account(3, writable, name="new_mint", desc="Mint of new token - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY")]
+    #[account(4, writable, name="edition_mark_pda", desc="Edition pda to mark creation - will be checked for pre-existence. (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number]) where edition_number is NOT the edition number you pass in args but actually edition_number = floor(edition/EDITION_MARKER_BIT_SIZE).")]
+    #[account(5, signer, name="new_mint_authority", desc="Mint authority of new mint")]
+    #[account(6, signer, writable, name="payer", desc="payer")]
+    #[account(7, signer, name="token_account_owner", desc="owner of token account containing master token (#8)")]
+    #[account(8, name="token_account", desc="token account containing token from master metadata mint")]
+    #[account(9, name="new_metadata_update_authority", desc="Update authority info for new metadata")]
+    #[account(10, name="metadata", desc="Master record metadata account")]
+    #[account(11, name="token_program", desc="Token program")]
+    #[account(12, name="system_program", desc="System program")]
+    #[account(13, name="rent", desc="Rent info")]
+    #[account(14, name="edition_uri_policy_record", desc="Edition Uri Policy Record PDA (pda of ['metadata', program id, master metadata mint id, 'edition', 'uri_policy'])")]
+    MintNewEditionFromMasterEditionViaTokenWithUri(MintNewEditionFromMasterEditionViaTokenWithUriArgs),
+
+    /// Reset the remaining uses of a Metadata to its total and set when they expire, for renewable passes.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="update_authority", desc="Update authority key")]
+    ResetUses(ResetUsesArgs),
+
+    /// Same as Utilize, but also records the use in a Use Receipt PDA paid for by the use authority.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, writable, name="token_account", desc="Token Account Of NFT")]
+    #[account(2, writable, name="mint", desc="Mint of the Metadata")]
+    #[account(3, signer, writable, name="use_authority", desc="A Use Authority / Can be the current Owner of the NFT")]
+    #[account(4, name="owner", desc="Owner")]
+    #[account(5, name="token_program", desc="Token program")]
+    #[account(6, name="ata_program", desc="Associated Token program")]
+    #[account(7, name="system_program", desc="System program")]
+    #[account(8, name="rent", desc="Rent info")]
+    #[account(9, writable, name="use_receipt", desc="Use Receipt PDA (pda of ['metadata', program id, mint id, 'use_receipt', uses_utilized])")]
+    #[account(10, optional, writable, name="use_authority_record", desc="Use Authority Record PDA If present the program Assumes a delegated use authority")]
+    #[account(11, optional, name="burner", desc="Program As Signer (Burner)")]
+    UtilizeWithReceipt(UtilizeArgs),
+
+    /// Register a Metadata as a non-transferable Master Edition with a max supply of zero, like CreateMasterEditionV3,
+    /// and freeze the token account of its owner with the new edition in the same instruction.
+    /// The master edition becomes the delegate of the token so it can be recovered with RecoverNonTransferable.
+    #[account(0, writable, name="edition", desc="Unallocated edition V2 account with address as pda of ['metadata', program id, mint, 'edition']")]
+    #[account(1, writable, name="mint", desc="Metadata mint, with a freeze authority")]
+    #[account(2, signer, name="update_authority", desc="Update authority")]
+    #[account(3, signer, name="mint_authority", desc="Mint authority and freeze authority on the metadata's mint - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY")]
+    #[account(4, signer, writable, name="payer", desc="payer")]
+    #[account(5, writable, name="metadata", desc="Metadata account")]
+    #[account(6, name="token_program", desc="Token program")]
+    #[account(7, name="system_program", desc="System program")]
+    #[account(8, name="rent", desc="Rent info")]
+    #[account(9, signer, name="owner", desc="Owner of the token account")]
+    #[account(10, writable, name="token_account", desc="Token account holding the token")]
+    CreateNonTransferableMasterEdition,
+
+    /// Burn a non-transferable token, thawing it first.
+    #[account(0, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="owner", desc="Owner of the token account")]
+    #[account(2, writable, name="token_account", desc="Token account holding the token")]
+    #[account(3, writable, name="mint", desc="Mint of the Metadata")]
+    #[account(4, name="edition", desc="Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(5, name="token_program", desc="Token program")]
+    BurnNonTransferable,
+
+    /// Move a non-transferable token to another wallet of its owner, approved by the update authority.
+    #[account(0, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="update_authority", desc="Update authority key")]
+    #[account(2, writable, name="token_account", desc="Token account holding the token")]
+    #[account(3, signer, name="new_owner", desc="Owner of the new token account")]
+    #[account(4, writable, name="new_token_account", desc="Token account receiving the token")]
+    #[account(5, name="mint", desc="Mint of the Metadata")]
+    #[account(6, name="edition", desc="Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(7, name="token_program", desc="Token program")]
+    RecoverNonTransferable,
+
+    /// Set the royalty config inherited by verified items of a collection that declare no creators of their own.
+    #[account(0, writable, name="collection_royalty_record", desc="Collection Royalty Record PDA (pda of ['metadata', program id, collection mint id, 'collection_royalty'])")]
+    #[account(1, signer, name="update_authority", desc="Update authority of the collection metadata")]
+    #[account(2, signer, writable, name="payer", desc="Payer")]
+    #[account(3, name="collection_metadata", desc="Metadata account of the collection")]
+    #[account(4, name="collection_master_edition", desc="Master Edition V2 of the collection")]
+    #[account(5, name="system_program", desc="System program")]
+    #[account(6, name="rent", desc="Rent info")]
+    SetCollectionRoyalty(SetCollectionRoyaltyArgs),
 }
 
 /// Creates an CreateMetadataAccounts instruction
@@ -1263,3 +1413,471 @@ pub fn thaw_delegated_account(
             .unwrap(),
     }
 }
+
+///# Nominate Update Authority
+///
+///Nominate a new update authority for a Metadata, it has to be accepted by the nominee with [accept_update_authority]
+///
+///### Accounts:
+///
+///   0. `[writable]` Pending Update Authority Record PDA
+///   1. `[]` Nominated update authority
+///   2. `[signer]` Current update authority
+///   3. `[signer]` Payer
+///   4. `[]` Metadata account
+///   5. `[]` System program
+///   6. `[]` Rent info
+#[allow(clippy::too_many_arguments)]
+pub fn nominate_update_authority(
+    program_id: Pubkey,
+    pending_update_authority_record: Pubkey,
+    new_update_authority: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    metadata: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(pending_update_authority_record, false),
+            AccountMeta::new_readonly(new_update_authority, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::NominateUpdateAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Accept Update Authority
+///
+///Accept a pending update authority nomination, the record rent is returned to the previous update authority
+///
+///### Accounts:
+///
+///   0. `[writable]` Pending Update Authority Record PDA
+///   1. `[signer]` Nominated update authority
+///   2. `[writable]` Current update authority
+///   3. `[writable]` Metadata account
+pub fn accept_update_authority(
+    program_id: Pubkey,
+    pending_update_authority_record: Pubkey,
+    new_update_authority: Pubkey,
+    update_authority: Pubkey,
+    metadata: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(pending_update_authority_record, false),
+            AccountMeta::new_readonly(new_update_authority, true),
+            AccountMeta::new(update_authority, false),
+            AccountMeta::new(metadata, false),
+        ],
+        data: MetadataInstruction::AcceptUpdateAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Cancel Update Authority Nomination
+///
+///Cancel a pending update authority nomination, the record rent is returned to the update authority
+///
+///### Accounts:
+///
+///   0. `[writable]` Pending Update Authority Record PDA
+///   1. `[signer]` Current update authority
+///   2. `[]` Metadata account
+pub fn cancel_update_authority_nomination(
+    program_id: Pubkey,
+    pending_update_authority_record: Pubkey,
+    update_authority: Pubkey,
+    metadata: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(pending_update_authority_record, false),
+            AccountMeta::new(update_authority, true),
+            AccountMeta::new_readonly(metadata, false),
+        ],
+        data: MetadataInstruction::CancelUpdateAuthorityNomination
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Set Edition Uri Policy
+///
+///Set whether prints of a Master Edition V2 may override the master uri
+///
+///### Accounts:
+///
+///   0. `[writable]` Edition Uri Policy Record PDA
+///   1. `[signer]` Update authority of the master metadata
+///   2. `[signer]` Payer
+///   3. `[]` Master Edition V2
+///   4. `[]` Master metadata account
+///   5. `[]` System program
+///   6. `[]` Rent info
+pub fn set_edition_uri_policy(
+    program_id: Pubkey,
+    edition_uri_policy_record: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    master_edition: Pubkey,
+    metadata: Pubkey,
+    policy: EditionUriPolicy,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(edition_uri_policy_record, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(master_edition, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::SetEditionUriPolicy(SetEditionUriPolicyArgs { policy })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a mint_new_edition_from_master_edition_via_token_with_uri instruction
+#[allow(clippy::too_many_arguments)]
+pub fn mint_new_edition_from_master_edition_via_token_with_uri(
+    program_id: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    master_edition: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    token_account_owner: Pubkey,
+    token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    edition: u64,
+    uri: String,
+) -> Instruction {
+    let edition_number = edition.checked_div(EDITION_MARKER_BIT_SIZE).unwrap();
+    let as_string = edition_number.to_string();
+    let (edition_mark_pda, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_mint.as_ref(),
+            EDITION.as_bytes(),
+            as_string.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (edition_uri_policy_record, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_mint.as_ref(),
+            EDITION.as_bytes(),
+            EDITION_URI_POLICY.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    let accounts = vec![
+        AccountMeta::new(new_metadata, false),
+        AccountMeta::new(new_edition, false),
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new(new_mint, false),
+        AccountMeta::new(edition_mark_pda, false),
+        AccountMeta::new_readonly(new_mint_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(token_account_owner, true),
+        AccountMeta::new_readonly(token_account, false),
+        AccountMeta::new_readonly(new_metadata_update_authority, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(edition_uri_policy_record, false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::MintNewEditionFromMasterEditionViaTokenWithUri(
+            MintNewEditionFromMasterEditionViaTokenWithUriArgs { edition, uri },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+///# Reset Uses
+///
+///Reset the remaining uses of a Metadata to its total and set their expiry
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Update authority
+pub fn reset_uses(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    expiry: Option<i64>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+        ],
+        data: MetadataInstruction::ResetUses(ResetUsesArgs { expiry })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Utilize With Receipt
+///
+///Utilize an NFT and record the use in a Use Receipt PDA,
+///the receipt is derived from the uses_utilized value of the Metadata before the use
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[writable]` Token Account Of NFT
+///   2. `[writable]` Mint of the Metadata
+///   3. `[signer]` A Use Authority / Can be the current Owner of the NFT
+///   4. `[]` Owner
+///   5. `[]` Token program
+///   6. `[]` Associated Token program
+///   7. `[]` System program
+///   8. `[]` Rent info
+///   9. `[writable]` Use Receipt PDA
+///   10. Optional `[writable]` Use Authority Record PDA
+///   11. Optional `[]` Program As Signer (Burner)
+#[allow(clippy::too_many_arguments)]
+pub fn utilize_with_receipt(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    token_account: Pubkey,
+    mint: Pubkey,
+    use_receipt: Pubkey,
+    use_authority_record_pda: Option<Pubkey>,
+    use_authority: Pubkey,
+    owner: Pubkey,
+    burner: Option<Pubkey>,
+    number_of_uses: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(mint, false),
+        AccountMeta::new(use_authority, true),
+        AccountMeta::new_readonly(owner, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(use_receipt, false),
+    ];
+    if let Some(use_authority_record_pda) = use_authority_record_pda {
+        accounts.push(AccountMeta::new(use_authority_record_pda, false));
+    }
+    if let Some(burner) = burner {
+        accounts.push(AccountMeta::new_readonly(burner, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::UtilizeWithReceipt(UtilizeArgs { number_of_uses })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Create Non Transferable Master Edition
+///
+///Create a Master Edition with a max supply of zero and freeze the owner's token account with it
+///
+///### Accounts:
+///
+///   0. `[writable]` Master Edition V2
+///   1. `[writable]` Mint of the Metadata, with a freeze authority
+///   2. `[signer]` Update authority
+///   3. `[signer]` Mint authority and freeze authority
+///   4. `[signer, writable]` Payer
+///   5. `[writable]` Metadata account
+///   6. `[]` Token program
+///   7. `[]` System program
+///   8. `[]` Rent info
+///   9. `[signer]` Owner of the token account
+///   10. `[writable]` Token account holding the token
+#[allow(clippy::too_many_arguments)]
+pub fn create_non_transferable_master_edition(
+    program_id: Pubkey,
+    edition: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    mint_authority: Pubkey,
+    metadata: Pubkey,
+    payer: Pubkey,
+    owner: Pubkey,
+    token_account: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(edition, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(mint_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(token_account, false),
+        ],
+        data: MetadataInstruction::CreateNonTransferableMasterEdition
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Burn Non Transferable
+///
+///Thaw and burn a non-transferable token
+///
+///### Accounts:
+///
+///   0. `[]` Metadata account
+///   1. `[signer]` Owner of the token account
+///   2. `[writable]` Token account holding the token
+///   3. `[writable]` Mint of the Metadata
+///   4. `[]` Master Edition V2
+///   5. `[]` Token program
+pub fn burn_non_transferable(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    owner: Pubkey,
+    token_account: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::BurnNonTransferable
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Recover Non Transferable
+///
+///Move a non-transferable token to a new token account, approved by the update authority.
+///The new token account is frozen again with the master edition as its delegate.
+///
+///### Accounts:
+///
+///   0. `[]` Metadata account
+///   1. `[signer]` Update authority
+///   2. `[writable]` Token account holding the token
+///   3. `[signer]` Owner of the new token account
+///   4. `[writable]` Token account receiving the token
+///   5. `[]` Mint of the Metadata
+///   6. `[]` Master Edition V2
+///   7. `[]` Token program
+#[allow(clippy::too_many_arguments)]
+pub fn recover_non_transferable(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    token_account: Pubkey,
+    new_owner: Pubkey,
+    new_token_account: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(new_owner, true),
+            AccountMeta::new(new_token_account, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::RecoverNonTransferable
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Set Collection Royalty
+///
+///Set the royalty config inherited by verified items of a collection that declare no creators
+///of their own.
+///
+///### Accounts:
+///
+///   0. `[writable]` Collection Royalty Record PDA
+///   1. `[signer]` Update authority of the collection metadata
+///   2. `[signer]` Payer
+///   3. `[]` Collection metadata account
+///   4. `[]` Collection Master Edition V2
+///   5. `[]` System program
+///   6. `[]` Rent info
+#[allow(clippy::too_many_arguments)]
+pub fn set_collection_royalty(
+    program_id: Pubkey,
+    collection_royalty_record: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    collection_metadata: Pubkey,
+    collection_master_edition: Pubkey,
+    seller_fee_basis_points: u16,
+    creators: Option<Vec<Creator>>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(collection_royalty_record, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(collection_metadata, false),
+            AccountMeta::new_readonly(collection_master_edition, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::SetCollectionRoyalty(SetCollectionRoyaltyArgs {
+            seller_fee_basis_points,
+            creators,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
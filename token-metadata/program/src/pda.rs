@@ -1,6 +1,9 @@
 use solana_program::pubkey::Pubkey;
 
-use crate::state::{BURN, COLLECTION_AUTHORITY, EDITION, PREFIX, USER};
+use crate::state::{
+    BURN, COLLECTION_AUTHORITY, COLLECTION_ROYALTY, EDITION, EDITION_URI_POLICY,
+    PENDING_UPDATE_AUTHORITY, PREFIX, USER, USE_RECEIPT,
+};
 
 pub fn find_edition_account(mint: &Pubkey, edition_number: String) -> (Pubkey, u8) {
     Pubkey::find_program_address(
@@ -59,6 +62,56 @@ pub fn find_collection_authority_account(mint: &Pubkey, authority: &Pubkey) -> (
     )
 }
 
+pub fn find_pending_update_authority_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_edition_uri_policy_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+            EDITION_URI_POLICY.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_use_receipt_account(mint: &Pubkey, use_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            USE_RECEIPT.as_bytes(),
+            use_index.to_string().as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_collection_royalty_account(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            collection_mint.as_ref(),
+            COLLECTION_ROYALTY.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
 pub fn find_program_as_burner_account() -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[PREFIX.as_bytes(), crate::id().as_ref(), BURN.as_bytes()],
@@ -2,8 +2,12 @@ use crate::{
     assertions::{
         collection::{
             assert_collection_update_is_valid, assert_collection_verify_is_valid,
-            assert_has_collection_authority,
+            assert_has_collection_authority, assert_master_edition,
         },
+        edition_uri_policy::assert_edition_uri_policy_record,
+        non_transferable::assert_non_transferable_edition,
+        royalty::assert_collection_royalty_valid,
+        update_authority::assert_pending_update_authority_record,
         uses::{assert_valid_use, process_use_authority_validation},
     },
     deprecated_processor::{
@@ -13,14 +17,18 @@ use crate::{
     instruction::MetadataInstruction,
     solana_program::program_memory::sol_memset,
     state::{
-        Collection, CollectionAuthorityRecord, DataV2, Key, MasterEditionV1, MasterEditionV2,
-        Metadata, TokenStandard, UseAuthorityRecord, UseMethod, Uses, BURN, COLLECTION_AUTHORITY,
-        COLLECTION_AUTHORITY_RECORD_SIZE, EDITION, MAX_MASTER_EDITION_LEN, PREFIX, USER,
-        USE_AUTHORITY_RECORD_SIZE,
+        Collection, CollectionAuthorityRecord, CollectionRoyaltyRecord, Creator, DataV2,
+        EditionUriPolicy, EditionUriPolicyRecord, Key, MasterEditionV1, MasterEditionV2, Metadata,
+        PendingUpdateAuthorityRecord, TokenStandard, UseAuthorityRecord, UseMethod, UseReceipt,
+        Uses, BURN, COLLECTION_AUTHORITY, COLLECTION_AUTHORITY_RECORD_SIZE, COLLECTION_ROYALTY,
+        COLLECTION_ROYALTY_RECORD_SIZE, EDITION, EDITION_URI_POLICY,
+        EDITION_URI_POLICY_RECORD_SIZE, MAX_MASTER_EDITION_LEN, PENDING_UPDATE_AUTHORITY,
+        PENDING_UPDATE_AUTHORITY_RECORD_SIZE, PREFIX, USER, USE_AUTHORITY_RECORD_SIZE, USE_RECEIPT,
+        USE_RECEIPT_SIZE,
     },
     utils::{
         assert_currently_holding, assert_data_valid, assert_delegated_tokens, assert_derivation,
-        assert_freeze_authority_matches_mint, assert_initialized,
+        assert_edition_valid, assert_freeze_authority_matches_mint, assert_initialized,
         assert_mint_authority_matches_mint, assert_owned_by, assert_signer,
         assert_token_program_matches_package, assert_update_authority_is_correct,
         create_or_allocate_account_raw, get_owner_from_token_account,
@@ -35,14 +43,17 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_token_vault::{error::VaultError, state::VaultState};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
+    instruction::Instruction,
     msg,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 use spl_token::{
-    instruction::{approve, freeze_account, revoke, thaw_account},
+    instruction::{approve, freeze_account, revoke, thaw_account, transfer},
     state::{Account, Mint},
 };
 
@@ -147,6 +158,7 @@ pub fn process_instruction<'a>(
                 accounts,
                 args.edition,
                 false,
+                None,
             )
         }
         MetadataInstruction::ConvertMasterEditionV1ToV2 => {
@@ -179,7 +191,7 @@ pub fn process_instruction<'a>(
         }
         MetadataInstruction::Utilize(args) => {
             msg!("Instruction: Use/Utilize Token");
-            process_utilize(program_id, accounts, args.number_of_uses)
+            process_utilize(program_id, accounts, args.number_of_uses, false)
         }
         MetadataInstruction::ApproveUseAuthority(args) => {
             msg!("Instruction: Approve Use Authority");
@@ -205,6 +217,61 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Thaw Delegated Account");
             process_thaw_delegated_account(program_id, accounts)
         }
+        MetadataInstruction::NominateUpdateAuthority => {
+            msg!("Instruction: Nominate Update Authority");
+            process_nominate_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::AcceptUpdateAuthority => {
+            msg!("Instruction: Accept Update Authority");
+            process_accept_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::CancelUpdateAuthorityNomination => {
+            msg!("Instruction: Cancel Update Authority Nomination");
+            process_cancel_update_authority_nomination(program_id, accounts)
+        }
+        MetadataInstruction::SetEditionUriPolicy(args) => {
+            msg!("Instruction: Set Edition Uri Policy");
+            process_set_edition_uri_policy(program_id, accounts, args.policy)
+        }
+        MetadataInstruction::MintNewEditionFromMasterEditionViaTokenWithUri(args) => {
+            msg!("Instruction: Mint New Edition from Master Edition Via Token With Uri");
+            process_mint_new_edition_from_master_edition_via_token(
+                program_id,
+                accounts,
+                args.edition,
+                false,
+                Some(args.uri),
+            )
+        }
+        MetadataInstruction::ResetUses(args) => {
+            msg!("Instruction: Reset Uses");
+            process_reset_uses(program_id, accounts, args.expiry)
+        }
+        MetadataInstruction::UtilizeWithReceipt(args) => {
+            msg!("Instruction: Use/Utilize Token With Receipt");
+            process_utilize(program_id, accounts, args.number_of_uses, true)
+        }
+        MetadataInstruction::CreateNonTransferableMasterEdition => {
+            msg!("Instruction: Create Non Transferable Master Edition");
+            process_create_non_transferable_master_edition(program_id, accounts)
+        }
+        MetadataInstruction::BurnNonTransferable => {
+            msg!("Instruction: Burn Non Transferable");
+            process_burn_non_transferable(program_id, accounts)
+        }
+        MetadataInstruction::RecoverNonTransferable => {
+            msg!("Instruction: Recover Non Transferable");
+            process_recover_non_transferable(program_id, accounts)
+        }
+        MetadataInstruction::SetCollectionRoyalty(args) => {
+            msg!("Instruction: Set Collection Royalty");
+            process_set_collection_royalty(
+                program_id,
+                accounts,
+                args.seller_fee_basis_points,
+                args.creators,
+            )
+        }
     }
 }
 
@@ -516,6 +583,7 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
     accounts: &'a [AccountInfo<'a>],
     edition: u64,
     ignore_owner_signer: bool,
+    uri_override: Option<String>,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -534,6 +602,20 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
     let system_account_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
 
+    if uri_override.is_some() {
+        let edition_uri_policy_record_info = next_account_info(account_info_iter)?;
+        assert_owned_by(master_metadata_account_info, program_id)?;
+        let master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
+        let policy = assert_edition_uri_policy_record(
+            program_id,
+            edition_uri_policy_record_info,
+            &master_metadata.mint,
+        )?;
+        if policy != EditionUriPolicy::Overridable {
+            return Err(MetadataError::EditionUriOverrideNotAllowed.into());
+        }
+    }
+
     process_mint_new_edition_from_master_edition_via_token_logic(
         program_id,
         MintNewEditionFromMasterEditionViaTokenLogicArgs {
@@ -554,6 +636,7 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
         },
         edition,
         ignore_owner_signer,
+        uri_override,
     )
 }
 
@@ -711,7 +794,9 @@ pub fn process_mint_new_edition_from_master_edition_via_vault_proxy<'a>(
         rent_info,
     };
 
-    process_mint_new_edition_from_master_edition_via_token_logic(program_id, args, edition, true)
+    process_mint_new_edition_from_master_edition_via_token_logic(
+        program_id, args, edition, true, None,
+    )
 }
 
 /// Puff out the variable length fields to a fixed length on a metadata
@@ -1001,6 +1086,7 @@ pub fn process_utilize(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     number_of_uses: u64,
+    with_receipt: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let metadata_info = next_account_info(account_info_iter)?;
@@ -1010,13 +1096,24 @@ pub fn process_utilize(
     let owner_info = next_account_info(account_info_iter)?;
     let token_program_account_info = next_account_info(account_info_iter)?;
     let _ata_program_account_info = next_account_info(account_info_iter)?;
-    let _system_account_info = next_account_info(account_info_iter)?;
-    let _rent_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let use_receipt_info = if with_receipt {
+        Some(next_account_info(account_info_iter)?)
+    } else {
+        None
+    };
     let metadata = Metadata::from_account_info(metadata_info)?;
-    let approved_authority_is_using = accounts.len() == 11;
+    let approved_authority_is_using = accounts.len() == if with_receipt { 12 } else { 11 };
     if metadata.uses.is_none() {
         return Err(MetadataError::Unusable.into());
     }
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(expiry) = metadata.uses_expiry {
+        if now > expiry {
+            return Err(MetadataError::UsesExpired.into());
+        }
+    }
     if *token_program_account_info.key != spl_token::id() {
         return Err(MetadataError::InvalidTokenProgram.into());
     }
@@ -1044,6 +1141,10 @@ pub fn process_utilize(
         total: metadata_uses.total,
         remaining: remaining_uses,
     });
+    let use_index = metadata.uses_utilized;
+    metadata.uses_utilized = use_index
+        .checked_add(number_of_uses)
+        .ok_or(MetadataError::NumericalOverflowError)?;
     if approved_authority_is_using {
         let use_authority_record_info = next_account_info(account_info_iter)?;
         let data = &mut *use_authority_record_info.try_borrow_mut_data()?;
@@ -1070,6 +1171,25 @@ pub fn process_utilize(
         return Err(MetadataError::InvalidUser.into());
     }
     metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
+    if let Some(use_receipt_info) = use_receipt_info {
+        create_use_receipt(
+            program_id,
+            use_receipt_info,
+            UseReceipt {
+                key: Key::UseReceipt,
+                mint: metadata.mint,
+                user: *owner_info.key,
+                use_authority: *user_info.key,
+                timestamp: now,
+                number_of_uses,
+                use_index,
+                bump: 0,
+            },
+            user_info,
+            system_account_info,
+            rent_info,
+        )?;
+    }
     if remaining_uses == 0 && must_burn {
         if approved_authority_is_using {
             let burn_authority_info = next_account_info(account_info_iter)?;
@@ -1375,3 +1495,605 @@ pub fn process_thaw_delegated_account(
     )?;
     Ok(())
 }
+
+pub fn process_nominate_update_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pending_update_authority_record_info = next_account_info(account_info_iter)?;
+    let new_update_authority_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_signer(payer)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    let pending_update_authority_path = Vec::from([
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        metadata.mint.as_ref(),
+        PENDING_UPDATE_AUTHORITY.as_bytes(),
+    ]);
+    let pending_update_authority_bump_seed = &[assert_derivation(
+        program_id,
+        pending_update_authority_record_info,
+        &pending_update_authority_path,
+    )?];
+    if pending_update_authority_record_info.try_data_is_empty()? {
+        let mut pending_update_authority_seeds = pending_update_authority_path.clone();
+        pending_update_authority_seeds.push(pending_update_authority_bump_seed);
+        create_or_allocate_account_raw(
+            *program_id,
+            pending_update_authority_record_info,
+            rent_info,
+            system_account_info,
+            payer,
+            PENDING_UPDATE_AUTHORITY_RECORD_SIZE,
+            &pending_update_authority_seeds,
+        )?;
+    } else {
+        // Nominating again replaces the previous nominee.
+        assert_owned_by(pending_update_authority_record_info, program_id)?;
+    }
+
+    let mut record =
+        PendingUpdateAuthorityRecord::from_account_info(pending_update_authority_record_info)?;
+    record.key = Key::PendingUpdateAuthorityRecord;
+    record.update_authority = metadata.update_authority;
+    record.pending_update_authority = *new_update_authority_info.key;
+    record.bump = pending_update_authority_bump_seed[0];
+    record.serialize(&mut *pending_update_authority_record_info.try_borrow_mut_data()?)?;
+    Ok(())
+}
+
+pub fn process_accept_update_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pending_update_authority_record_info = next_account_info(account_info_iter)?;
+    let new_update_authority_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+
+    assert_signer(new_update_authority_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    let record = assert_pending_update_authority_record(
+        program_id,
+        pending_update_authority_record_info,
+        &metadata,
+    )?;
+    if record.pending_update_authority != *new_update_authority_info.key {
+        return Err(MetadataError::PendingUpdateAuthorityMismatch.into());
+    }
+    // The update authority may have been changed directly after the nomination was made.
+    if record.update_authority != metadata.update_authority {
+        return Err(MetadataError::UpdateAuthorityChangedSinceNomination.into());
+    }
+    if metadata.update_authority != *update_authority_info.key {
+        return Err(MetadataError::UpdateAuthorityIncorrect.into());
+    }
+
+    metadata.update_authority = *new_update_authority_info.key;
+    metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
+
+    close_pending_update_authority_record(
+        pending_update_authority_record_info,
+        update_authority_info,
+    )
+}
+
+pub fn process_cancel_update_authority_nomination(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pending_update_authority_record_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_pending_update_authority_record(
+        program_id,
+        pending_update_authority_record_info,
+        &metadata,
+    )?;
+
+    close_pending_update_authority_record(
+        pending_update_authority_record_info,
+        update_authority_info,
+    )
+}
+
+fn close_pending_update_authority_record(
+    pending_update_authority_record_info: &AccountInfo,
+    receiver_info: &AccountInfo,
+) -> ProgramResult {
+    let lamports = pending_update_authority_record_info.lamports();
+    **pending_update_authority_record_info.try_borrow_mut_lamports()? = 0;
+    **receiver_info.try_borrow_mut_lamports()? = receiver_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    sol_memset(
+        *pending_update_authority_record_info.try_borrow_mut_data()?,
+        0,
+        PENDING_UPDATE_AUTHORITY_RECORD_SIZE,
+    );
+    Ok(())
+}
+
+pub fn process_set_edition_uri_policy(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    policy: EditionUriPolicy,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let edition_uri_policy_record_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(master_edition_info, program_id)?;
+    assert_signer(payer)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_edition_valid(program_id, &metadata.mint, master_edition_info)?;
+    // Uri overrides are only supported on prints of Master Edition V2.
+    MasterEditionV2::from_account_info(master_edition_info)?;
+
+    let edition_uri_policy_path = Vec::from([
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        metadata.mint.as_ref(),
+        EDITION.as_bytes(),
+        EDITION_URI_POLICY.as_bytes(),
+    ]);
+    let edition_uri_policy_bump_seed = &[assert_derivation(
+        program_id,
+        edition_uri_policy_record_info,
+        &edition_uri_policy_path,
+    )?];
+    if edition_uri_policy_record_info.try_data_is_empty()? {
+        let mut edition_uri_policy_seeds = edition_uri_policy_path.clone();
+        edition_uri_policy_seeds.push(edition_uri_policy_bump_seed);
+        create_or_allocate_account_raw(
+            *program_id,
+            edition_uri_policy_record_info,
+            rent_info,
+            system_account_info,
+            payer,
+            EDITION_URI_POLICY_RECORD_SIZE,
+            &edition_uri_policy_seeds,
+        )?;
+    } else {
+        assert_owned_by(edition_uri_policy_record_info, program_id)?;
+    }
+
+    let mut record = EditionUriPolicyRecord::from_account_info(edition_uri_policy_record_info)?;
+    record.key = Key::EditionUriPolicyRecord;
+    record.policy = policy;
+    record.bump = edition_uri_policy_bump_seed[0];
+    record.serialize(&mut *edition_uri_policy_record_info.try_borrow_mut_data()?)?;
+    Ok(())
+}
+
+pub fn process_set_collection_royalty(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    seller_fee_basis_points: u16,
+    creators: Option<Vec<Creator>>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let collection_royalty_record_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+    let collection_master_edition_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(collection_metadata_info, program_id)?;
+    assert_owned_by(collection_master_edition_info, program_id)?;
+    assert_signer(payer)?;
+    let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+    assert_update_authority_is_correct(&collection_metadata, update_authority_info)?;
+    assert_edition_valid(
+        program_id,
+        &collection_metadata.mint,
+        collection_master_edition_info,
+    )?;
+    assert_master_edition(&collection_metadata, collection_master_edition_info)?;
+    assert_collection_royalty_valid(
+        seller_fee_basis_points,
+        &creators,
+        update_authority_info.key,
+    )?;
+
+    let collection_royalty_path = Vec::from([
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        collection_metadata.mint.as_ref(),
+        COLLECTION_ROYALTY.as_bytes(),
+    ]);
+    let collection_royalty_bump_seed = &[assert_derivation(
+        program_id,
+        collection_royalty_record_info,
+        &collection_royalty_path,
+    )?];
+    if collection_royalty_record_info.try_data_is_empty()? {
+        let mut collection_royalty_seeds = collection_royalty_path.clone();
+        collection_royalty_seeds.push(collection_royalty_bump_seed);
+        create_or_allocate_account_raw(
+            *program_id,
+            collection_royalty_record_info,
+            rent_info,
+            system_account_info,
+            payer,
+            COLLECTION_ROYALTY_RECORD_SIZE,
+            &collection_royalty_seeds,
+        )?;
+    } else {
+        assert_owned_by(collection_royalty_record_info, program_id)?;
+    }
+
+    let mut record = CollectionRoyaltyRecord::from_account_info(collection_royalty_record_info)?;
+    record.key = Key::CollectionRoyaltyRecord;
+    record.collection_mint = collection_metadata.mint;
+    record.seller_fee_basis_points = seller_fee_basis_points;
+    record.creators = creators;
+    record.bump = collection_royalty_bump_seed[0];
+    record.serialize(&mut *collection_royalty_record_info.try_borrow_mut_data()?)?;
+    Ok(())
+}
+
+fn create_use_receipt<'a>(
+    program_id: &Pubkey,
+    use_receipt_info: &AccountInfo<'a>,
+    mut receipt: UseReceipt,
+    payer: &AccountInfo<'a>,
+    system_account_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let use_index = receipt.use_index.to_string();
+    let use_receipt_path = Vec::from([
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        receipt.mint.as_ref(),
+        USE_RECEIPT.as_bytes(),
+        use_index.as_bytes(),
+    ]);
+    let use_receipt_bump_seed = &[assert_derivation(
+        program_id,
+        use_receipt_info,
+        &use_receipt_path,
+    )?];
+    if !use_receipt_info.try_data_is_empty()? {
+        return Err(MetadataError::UseReceiptAlreadyExists.into());
+    }
+    let mut use_receipt_seeds = use_receipt_path.clone();
+    use_receipt_seeds.push(use_receipt_bump_seed);
+    create_or_allocate_account_raw(
+        *program_id,
+        use_receipt_info,
+        rent_info,
+        system_account_info,
+        payer,
+        USE_RECEIPT_SIZE,
+        &use_receipt_seeds,
+    )?;
+    receipt.bump = use_receipt_bump_seed[0];
+    receipt.serialize(&mut *use_receipt_info.try_borrow_mut_data()?)?;
+    Ok(())
+}
+
+pub fn process_reset_uses(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    expiry: Option<i64>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    let uses = metadata.uses.as_mut().ok_or(MetadataError::Unusable)?;
+    if uses.use_method == UseMethod::Burn && uses.remaining == 0 {
+        // The token has been burned on its last use.
+        return Err(MetadataError::Unusable.into());
+    }
+    uses.remaining = uses.total;
+    metadata.uses_expiry = expiry;
+    metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
+    Ok(())
+}
+
+pub fn process_create_non_transferable_master_edition(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let edition_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let _update_authority_info = next_account_info(account_info_iter)?;
+    let _mint_authority_info = next_account_info(account_info_iter)?;
+    let _payer_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let _system_account_info = next_account_info(account_info_iter)?;
+    let _rent_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+
+    // The freeze authority moves to the edition with the mint authority, so it must exist up front.
+    let mint: Mint = assert_initialized(mint_info)?;
+    if mint.freeze_authority.is_none() {
+        return Err(MetadataError::NonTransferableMissingFreezeAuthority.into());
+    }
+
+    process_create_master_edition(program_id, accounts, Some(0))?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_signer(owner_info)?;
+    assert_currently_holding(
+        program_id,
+        owner_info,
+        metadata_info,
+        &metadata,
+        mint_info,
+        token_account_info,
+    )?;
+    assert_non_transferable_edition(program_id, mint_info, edition_info)?;
+
+    // The edition becomes the delegate so the token can still be moved by RecoverNonTransferable.
+    invoke(
+        &approve(
+            token_program_account_info.key,
+            token_account_info.key,
+            edition_info.key,
+            owner_info.key,
+            &[],
+            1,
+        )
+        .unwrap(),
+        &[
+            token_program_account_info.clone(),
+            token_account_info.clone(),
+            edition_info.clone(),
+            owner_info.clone(),
+        ],
+    )?;
+    invoke_signed_by_edition(
+        program_id,
+        &freeze_account(
+            token_program_account_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )
+        .unwrap(),
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+        ],
+        edition_info,
+        mint_info,
+    )?;
+
+    metadata.token_standard = Some(TokenStandard::NonTransferable);
+    metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
+    Ok(())
+}
+
+pub fn process_burn_non_transferable(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+
+    if *token_program_account_info.key != spl_token::id() {
+        return Err(MetadataError::InvalidTokenProgram.into());
+    }
+    assert_owned_by(metadata_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    if metadata.token_standard != Some(TokenStandard::NonTransferable) {
+        return Err(MetadataError::NotNonTransferable.into());
+    }
+    assert_signer(owner_info)?;
+    assert_currently_holding(
+        program_id,
+        owner_info,
+        metadata_info,
+        &metadata,
+        mint_info,
+        token_account_info,
+    )?;
+    assert_owned_by(edition_info, program_id)?;
+    assert_edition_valid(program_id, mint_info.key, edition_info)?;
+
+    invoke_signed_by_edition(
+        program_id,
+        &thaw_account(
+            token_program_account_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )
+        .unwrap(),
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+        ],
+        edition_info,
+        mint_info,
+    )?;
+    spl_token_burn(TokenBurnParams {
+        mint: mint_info.clone(),
+        amount: 1,
+        authority: owner_info.clone(),
+        token_program: token_program_account_info.clone(),
+        source: token_account_info.clone(),
+        authority_signer_seeds: None,
+    })
+}
+
+pub fn process_recover_non_transferable(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let new_owner_info = next_account_info(account_info_iter)?;
+    let new_token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+
+    if *token_program_account_info.key != spl_token::id() {
+        return Err(MetadataError::InvalidTokenProgram.into());
+    }
+    assert_owned_by(metadata_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    if metadata.token_standard != Some(TokenStandard::NonTransferable) {
+        return Err(MetadataError::NotNonTransferable.into());
+    }
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    assert_owned_by(edition_info, program_id)?;
+    assert_edition_valid(program_id, mint_info.key, edition_info)?;
+    assert_delegated_tokens(edition_info, mint_info, token_account_info)?;
+    assert_signer(new_owner_info)?;
+    assert_owned_by(new_token_account_info, &spl_token::id())?;
+    let new_token_account: Account = assert_initialized(new_token_account_info)?;
+    if new_token_account.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    if new_token_account.owner != *new_owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    invoke_signed_by_edition(
+        program_id,
+        &thaw_account(
+            token_program_account_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )
+        .unwrap(),
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+        ],
+        edition_info,
+        mint_info,
+    )?;
+    invoke_signed_by_edition(
+        program_id,
+        &transfer(
+            token_program_account_info.key,
+            token_account_info.key,
+            new_token_account_info.key,
+            edition_info.key,
+            &[],
+            1,
+        )
+        .unwrap(),
+        &[
+            token_account_info.clone(),
+            new_token_account_info.clone(),
+            edition_info.clone(),
+        ],
+        edition_info,
+        mint_info,
+    )?;
+    invoke(
+        &approve(
+            token_program_account_info.key,
+            new_token_account_info.key,
+            edition_info.key,
+            new_owner_info.key,
+            &[],
+            1,
+        )
+        .unwrap(),
+        &[
+            token_program_account_info.clone(),
+            new_token_account_info.clone(),
+            edition_info.clone(),
+            new_owner_info.clone(),
+        ],
+    )?;
+    invoke_signed_by_edition(
+        program_id,
+        &freeze_account(
+            token_program_account_info.key,
+            new_token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )
+        .unwrap(),
+        &[
+            new_token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+        ],
+        edition_info,
+        mint_info,
+    )
+}
+
+fn invoke_signed_by_edition(
+    program_id: &Pubkey,
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    edition_info: &AccountInfo,
+    mint_info: &AccountInfo,
+) -> ProgramResult {
+    let edition_info_path = Vec::from([
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+    ]);
+    let edition_info_path_bump_seed = &[assert_derivation(
+        program_id,
+        edition_info,
+        &edition_info_path,
+    )?];
+    let mut edition_info_seeds = edition_info_path.clone();
+    edition_info_seeds.push(edition_info_path_bump_seed);
+    invoke_signed(instruction, account_infos, &[&edition_info_seeds])
+}
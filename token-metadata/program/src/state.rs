@@ -19,6 +19,14 @@ pub const BURN: &str = "burn";
 
 pub const COLLECTION_AUTHORITY: &str = "collection_authority";
 
+pub const PENDING_UPDATE_AUTHORITY: &str = "pending_update_authority";
+
+pub const EDITION_URI_POLICY: &str = "uri_policy";
+
+pub const USE_RECEIPT: &str = "use_receipt";
+
+pub const COLLECTION_ROYALTY: &str = "collection_royalty";
+
 pub const MAX_NAME_LENGTH: usize = 32;
 
 pub const MAX_SYMBOL_LENGTH: usize = 10;
@@ -35,7 +43,9 @@ pub const MAX_METADATA_LEN: usize = 1 //key
 + 2 // token standard
 + 34 // collection
 + 18 // uses
-+ 118; // Padding
++ 9 // uses expiry
++ 8 // uses utilized
++ 101; // Padding
 
 pub const MAX_DATA_SIZE: usize = 4
     + MAX_NAME_LENGTH
@@ -75,6 +85,15 @@ pub const USE_AUTHORITY_RECORD_SIZE: usize = 18; //8 byte padding
 
 pub const COLLECTION_AUTHORITY_RECORD_SIZE: usize = 11; //10 byte padding
 
+pub const PENDING_UPDATE_AUTHORITY_RECORD_SIZE: usize = 1 + 32 + 32 + 1;
+
+pub const EDITION_URI_POLICY_RECORD_SIZE: usize = 1 + 1 + 1;
+
+pub const USE_RECEIPT_SIZE: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8 + 1;
+
+pub const COLLECTION_ROYALTY_RECORD_SIZE: usize =
+    1 + 32 + 2 + 1 + 4 + MAX_CREATOR_LIMIT * MAX_CREATOR_LEN + 1;
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
 pub enum Key {
@@ -88,6 +107,10 @@ pub enum Key {
     EditionMarker,
     UseAuthorityRecord,
     CollectionAuthorityRecord,
+    PendingUpdateAuthorityRecord,
+    EditionUriPolicyRecord,
+    UseReceipt,
+    CollectionRoyaltyRecord,
 }
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
@@ -160,6 +183,7 @@ pub enum TokenStandard {
     FungibleAsset,      // A token with metadata that can also have attrributes
     Fungible,           // A token with simple metadata
     NonFungibleEdition, // This is a limited edition
+    NonTransferable,    // A master edition frozen in its owner's wallet
 }
 
 #[repr(C)]
@@ -219,6 +243,114 @@ impl CollectionAuthorityRecord {
     }
 }
 
+/// Nomination of a new update authority for a Metadata account. The nomination only takes
+/// effect once the nominee accepts it, so a mistyped key can be cancelled instead of
+/// permanently locking the metadata.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, ShankAccount)]
+pub struct PendingUpdateAuthorityRecord {
+    pub key: Key, //1
+    /// Update authority at the time of the nomination
+    pub update_authority: Pubkey, //32
+    /// Nominated update authority, must sign to accept
+    pub pending_update_authority: Pubkey, //32
+    pub bump: u8, //1
+}
+
+impl PendingUpdateAuthorityRecord {
+    pub fn from_account_info(
+        a: &AccountInfo,
+    ) -> Result<PendingUpdateAuthorityRecord, ProgramError> {
+        let record: PendingUpdateAuthorityRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::PendingUpdateAuthorityRecord,
+            PENDING_UPDATE_AUTHORITY_RECORD_SIZE,
+        )?;
+
+        Ok(record)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum EditionUriPolicy {
+    /// Prints always copy the uri of the master edition
+    Fixed,
+    /// The master edition owner may supply a uri for each print
+    Overridable,
+}
+
+/// Policy for per-edition uri overrides of a Master Edition V2. Kept in its own PDA since
+/// converted V1 master editions still carry stale bytes after the V2 fields.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, ShankAccount)]
+pub struct EditionUriPolicyRecord {
+    pub key: Key,                 //1
+    pub policy: EditionUriPolicy, //1
+    pub bump: u8,                 //1
+}
+
+impl EditionUriPolicyRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<EditionUriPolicyRecord, ProgramError> {
+        let record: EditionUriPolicyRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::EditionUriPolicyRecord,
+            EDITION_URI_POLICY_RECORD_SIZE,
+        )?;
+
+        Ok(record)
+    }
+}
+
+/// Record of a single use of a Metadata, created by UtilizeWithReceipt.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, ShankAccount)]
+pub struct UseReceipt {
+    pub key: Key,     //1
+    pub mint: Pubkey, //32
+    /// Owner of the token at the time of the use
+    pub user: Pubkey, //32
+    /// Signer of the use, the owner or a delegated use authority
+    pub use_authority: Pubkey, //32
+    pub timestamp: i64, //8
+    pub number_of_uses: u64, //8
+    /// Value of uses_utilized on the Metadata before this use, part of the PDA seeds
+    pub use_index: u64, //8
+    pub bump: u8,     //1
+}
+
+impl UseReceipt {
+    pub fn from_account_info(a: &AccountInfo) -> Result<UseReceipt, ProgramError> {
+        let receipt: UseReceipt =
+            try_from_slice_checked(&a.data.borrow_mut(), Key::UseReceipt, USE_RECEIPT_SIZE)?;
+
+        Ok(receipt)
+    }
+}
+
+/// Royalty config of a collection, inherited by verified items that declare no creators of their own.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, ShankAccount)]
+pub struct CollectionRoyaltyRecord {
+    pub key: Key,                       //1
+    pub collection_mint: Pubkey,        //32
+    pub seller_fee_basis_points: u16,   //2
+    pub creators: Option<Vec<Creator>>, //1 + 4 + MAX_CREATOR_LIMIT * MAX_CREATOR_LEN
+    pub bump: u8,                       //1
+}
+
+impl CollectionRoyaltyRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<CollectionRoyaltyRecord, ProgramError> {
+        let record: CollectionRoyaltyRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::CollectionRoyaltyRecord,
+            COLLECTION_ROYALTY_RECORD_SIZE,
+        )?;
+
+        Ok(record)
+    }
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct Collection {
@@ -245,6 +377,11 @@ pub struct Metadata {
     pub collection: Option<Collection>,
     /// Uses
     pub uses: Option<Uses>,
+    /// Unix timestamp after which the uses can no longer be utilized, set through ResetUses.
+    /// Kept outside of Uses so DataV2 keeps its layout for existing clients.
+    pub uses_expiry: Option<i64>,
+    /// Number of uses utilized over the lifetime of the token, survives ResetUses
+    pub uses_utilized: u64,
 }
 
 impl Metadata {
@@ -253,6 +390,16 @@ impl Metadata {
 
         Ok(md)
     }
+
+    /// Whether royalties resolve to the Collection Royalty Record of the collection, see
+    /// `utils::resolve_royalty`.
+    pub fn inherits_collection_royalty(&self) -> bool {
+        self.data.creators.is_none()
+            && self
+                .collection
+                .as_ref()
+                .map_or(false, |collection| collection.verified)
+    }
 }
 
 impl borsh::de::BorshDeserialize for Metadata {
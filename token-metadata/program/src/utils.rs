@@ -1,8 +1,11 @@
 use crate::{
-    assertions::{collection::assert_collection_update_is_valid, uses::assert_valid_use},
+    assertions::{
+        collection::assert_collection_update_is_valid, royalty::assert_collection_royalty_record,
+        uses::assert_valid_use,
+    },
     error::MetadataError,
     state::{
-        get_reservation_list, Data, DataV2, EditionMarker, Key, MasterEditionV1, Metadata,
+        get_reservation_list, Creator, Data, DataV2, EditionMarker, Key, MasterEditionV1, Metadata,
         TokenStandard, Uses, EDITION, EDITION_MARKER_BIT_SIZE, MAX_CREATOR_LIMIT, MAX_EDITION_LEN,
         MAX_EDITION_MARKER_SIZE, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN, MAX_NAME_LENGTH,
         MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
@@ -544,6 +547,8 @@ pub fn mint_limited_edition<'a>(
     // Only present with MasterEditionV2 calls, if present, means
     // directing to a specific version, otherwise just pull off the top
     edition_override: Option<u64>,
+    // Only present when the master edition uri policy allows it, replaces the master uri
+    uri_override: Option<String>,
 ) -> ProgramResult {
     let me_supply = get_supply_off_master_edition(master_edition_account_info)?;
     let mint_authority = get_mint_authority(mint_info)?;
@@ -586,7 +591,7 @@ pub fn mint_limited_edition<'a>(
     let data_v2 = DataV2 {
         name: master_data.name,
         symbol: master_data.symbol,
-        uri: master_data.uri,
+        uri: uri_override.unwrap_or(master_data.uri),
         seller_fee_basis_points: master_data.seller_fee_basis_points,
         creators: master_data.creators,
         collection: master_metadata.collection,
@@ -615,6 +620,12 @@ pub fn mint_limited_edition<'a>(
         true,
         true,
     )?;
+    // Prints share the expiry of the master, uses_expiry is not part of DataV2.
+    if master_metadata.uses_expiry.is_some() {
+        let mut new_metadata = Metadata::from_account_info(new_metadata_account_info)?;
+        new_metadata.uses_expiry = master_metadata.uses_expiry;
+        new_metadata.serialize(&mut *new_metadata_account_info.try_borrow_mut_data()?)?;
+    }
     let edition_authority_seeds = &[
         PREFIX.as_bytes(),
         program_id.as_ref(),
@@ -993,6 +1004,7 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
     accounts: MintNewEditionFromMasterEditionViaTokenLogicArgs<'a>,
     edition: u64,
     ignore_owner_signer: bool,
+    uri_override: Option<String>,
 ) -> ProgramResult {
     let MintNewEditionFromMasterEditionViaTokenLogicArgs {
         new_metadata_account_info,
@@ -1104,6 +1116,7 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
         rent_info,
         None,
         Some(edition),
+        uri_override,
     )?;
     Ok(())
 }
@@ -1184,3 +1197,37 @@ pub fn assert_delegated_tokens(
     }
     Ok(())
 }
+
+/// Royalty a marketplace should pay out for a Metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedRoyalty {
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+}
+
+/// Resolves the royalty of `metadata`. Items of a verified collection that declare no creators of
+/// their own inherit the royalty record of the collection, so for those items the record PDA of the
+/// collection mint must be passed in as `collection_royalty_info`, created or not.
+pub fn resolve_royalty(
+    metadata: &Metadata,
+    collection_royalty_info: Option<&AccountInfo>,
+) -> Result<ResolvedRoyalty, ProgramError> {
+    let own_royalty = ResolvedRoyalty {
+        seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+        creators: metadata.data.creators.clone(),
+    };
+    let collection = match &metadata.collection {
+        Some(collection) if metadata.inherits_collection_royalty() => collection,
+        _ => return Ok(own_royalty),
+    };
+    let collection_royalty_info =
+        collection_royalty_info.ok_or(MetadataError::CollectionRoyaltyRecordNotProvided)?;
+    match assert_collection_royalty_record(&crate::id(), collection_royalty_info, &collection.key)?
+    {
+        Some(record) => Ok(ResolvedRoyalty {
+            seller_fee_basis_points: record.seller_fee_basis_points,
+            creators: record.creators,
+        }),
+        None => Ok(own_royalty),
+    }
+}
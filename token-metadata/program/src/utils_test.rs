@@ -45,6 +45,8 @@ mod puff_out_test {
             collection: None,
             uses: None,
             token_standard: None,
+            uses_expiry: None,
+            uses_utilized: 0,
         };
 
         puff_out_data_fields(&mut metadata);
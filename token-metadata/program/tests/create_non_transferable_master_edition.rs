@@ -0,0 +1,225 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        burn_non_transferable, create_non_transferable_master_edition, recover_non_transferable,
+    },
+    state::TokenStandard,
+};
+use num_traits::FromPrimitive;
+use solana_program::program_pack::Pack;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token::state::{Account, AccountState};
+use utils::*;
+
+mod create_non_transferable_master_edition {
+    use super::*;
+
+    async fn setup(
+        context: &mut ProgramTestContext,
+        freeze_authority: Option<&Pubkey>,
+    ) -> (Metadata, MasterEditionV2) {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                freeze_authority,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let test_master_edition = MasterEditionV2::new(&test_metadata);
+        (test_metadata, test_master_edition)
+    }
+
+    async fn create(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_master_edition: &MasterEditionV2,
+    ) -> Result<(), TransportError> {
+        let create_ix = create_non_transferable_master_edition(
+            mpl_token_metadata::id(),
+            test_master_edition.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            test_metadata.token.pubkey(),
+        );
+        let create_tx = Transaction::new_signed_with_payer(
+            &[create_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(create_tx).await
+    }
+
+    async fn get_token_account(context: &mut ProgramTestContext, token: &Keypair) -> Account {
+        let account = get_account(context, &token.pubkey()).await;
+        Account::unpack_from_slice(account.data.as_slice()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn success_frozen_at_creation() {
+        let mut context = program_test().start_with_context().await;
+        let freeze_authority = context.payer.pubkey();
+        let (test_metadata, test_master_edition) =
+            setup(&mut context, Some(&freeze_authority)).await;
+
+        create(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(
+            metadata.token_standard,
+            Some(TokenStandard::NonTransferable)
+        );
+        let token_account = get_token_account(&mut context, &test_metadata.token).await;
+        assert_eq!(token_account.state, AccountState::Frozen);
+
+        let destination = Keypair::new();
+        create_token_account(
+            &mut context,
+            &destination,
+            &test_metadata.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await
+        .unwrap();
+        let transfer_ix = spl_token::instruction::transfer(
+            &spl_token::id(),
+            &test_metadata.token.pubkey(),
+            &destination.pubkey(),
+            &context.payer.pubkey(),
+            &[],
+            1,
+        )
+        .unwrap();
+        let transfer_tx = Transaction::new_signed_with_payer(
+            &[transfer_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        assert!(context
+            .banks_client
+            .process_transaction(transfer_tx)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn success_burn() {
+        let mut context = program_test().start_with_context().await;
+        let freeze_authority = context.payer.pubkey();
+        let (test_metadata, test_master_edition) =
+            setup(&mut context, Some(&freeze_authority)).await;
+        create(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        let burn_ix = burn_non_transferable(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.token.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+        );
+        let burn_tx = Transaction::new_signed_with_payer(
+            &[burn_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(burn_tx)
+            .await
+            .unwrap();
+
+        let token_account = get_token_account(&mut context, &test_metadata.token).await;
+        assert_eq!(token_account.amount, 0);
+    }
+
+    #[tokio::test]
+    async fn success_recover() {
+        let mut context = program_test().start_with_context().await;
+        let freeze_authority = context.payer.pubkey();
+        let (test_metadata, test_master_edition) =
+            setup(&mut context, Some(&freeze_authority)).await;
+        create(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        let new_owner = Keypair::new();
+        let new_token = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_token,
+            &test_metadata.mint.pubkey(),
+            &new_owner.pubkey(),
+        )
+        .await
+        .unwrap();
+
+        let recover_ix = recover_non_transferable(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.token.pubkey(),
+            new_owner.pubkey(),
+            new_token.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+        );
+        let recover_tx = Transaction::new_signed_with_payer(
+            &[recover_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &new_owner],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(recover_tx)
+            .await
+            .unwrap();
+
+        let old_account = get_token_account(&mut context, &test_metadata.token).await;
+        assert_eq!(old_account.amount, 0);
+        let new_account = get_token_account(&mut context, &new_token).await;
+        assert_eq!(new_account.amount, 1);
+        assert_eq!(new_account.state, AccountState::Frozen);
+    }
+
+    #[tokio::test]
+    async fn fail_without_freeze_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context, None).await;
+
+        let err = create(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::NonTransferableMissingFreezeAuthority);
+    }
+}
@@ -0,0 +1,268 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        accept_update_authority, cancel_update_authority_nomination, nominate_update_authority,
+        update_metadata_accounts_v2,
+    },
+    pda::find_pending_update_authority_account,
+    state::{Key, PendingUpdateAuthorityRecord},
+};
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+mod nominate_update_authority {
+    use super::*;
+
+    async fn setup(context: &mut ProgramTestContext) -> Metadata {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        test_metadata
+    }
+
+    async fn nominate(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        new_update_authority: &Pubkey,
+    ) -> Pubkey {
+        let (record, _) = find_pending_update_authority_account(&test_metadata.mint.pubkey());
+        let nominate_ix = nominate_update_authority(
+            mpl_token_metadata::id(),
+            record,
+            *new_update_authority,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            test_metadata.pubkey,
+        );
+        let nominate_tx = Transaction::new_signed_with_payer(
+            &[nominate_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(nominate_tx)
+            .await
+            .unwrap();
+        record
+    }
+
+    #[tokio::test]
+    async fn success_nominate_and_accept() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = setup(&mut context).await;
+        let new_update_authority = Keypair::new();
+
+        let record = nominate(&mut context, &test_metadata, &new_update_authority.pubkey()).await;
+
+        let account = get_account(&mut context, &record).await;
+        let record_acct: PendingUpdateAuthorityRecord =
+            try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(record_acct.key, Key::PendingUpdateAuthorityRecord);
+        assert_eq!(record_acct.update_authority, context.payer.pubkey());
+        assert_eq!(
+            record_acct.pending_update_authority,
+            new_update_authority.pubkey()
+        );
+
+        // Nothing changes until the nominee accepts.
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.update_authority, context.payer.pubkey());
+
+        let accept_ix = accept_update_authority(
+            mpl_token_metadata::id(),
+            record,
+            new_update_authority.pubkey(),
+            context.payer.pubkey(),
+            test_metadata.pubkey,
+        );
+        let accept_tx = Transaction::new_signed_with_payer(
+            &[accept_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &new_update_authority],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(accept_tx)
+            .await
+            .unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+
+        let record_after = context
+            .banks_client
+            .get_account(record)
+            .await
+            .expect("account not found");
+        assert!(record_after.is_none());
+    }
+
+    #[tokio::test]
+    async fn success_cancel() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = setup(&mut context).await;
+        let new_update_authority = Keypair::new();
+
+        let record = nominate(&mut context, &test_metadata, &new_update_authority.pubkey()).await;
+
+        let cancel_ix = cancel_update_authority_nomination(
+            mpl_token_metadata::id(),
+            record,
+            context.payer.pubkey(),
+            test_metadata.pubkey,
+        );
+        let cancel_tx = Transaction::new_signed_with_payer(
+            &[cancel_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(cancel_tx)
+            .await
+            .unwrap();
+
+        let record_after = context
+            .banks_client
+            .get_account(record)
+            .await
+            .expect("account not found");
+        assert!(record_after.is_none());
+
+        let accept_ix = accept_update_authority(
+            mpl_token_metadata::id(),
+            record,
+            new_update_authority.pubkey(),
+            context.payer.pubkey(),
+            test_metadata.pubkey,
+        );
+        let accept_tx = Transaction::new_signed_with_payer(
+            &[accept_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &new_update_authority],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(accept_tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidPendingUpdateAuthorityRecord);
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.update_authority, context.payer.pubkey());
+    }
+
+    #[tokio::test]
+    async fn fail_accept_by_other_signer() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = setup(&mut context).await;
+        let new_update_authority = Keypair::new();
+        let other = Keypair::new();
+
+        let record = nominate(&mut context, &test_metadata, &new_update_authority.pubkey()).await;
+
+        let accept_ix = accept_update_authority(
+            mpl_token_metadata::id(),
+            record,
+            other.pubkey(),
+            context.payer.pubkey(),
+            test_metadata.pubkey,
+        );
+        let accept_tx = Transaction::new_signed_with_payer(
+            &[accept_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &other],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(accept_tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::PendingUpdateAuthorityMismatch);
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.update_authority, context.payer.pubkey());
+    }
+
+    #[tokio::test]
+    async fn fail_accept_after_update_authority_changed() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = setup(&mut context).await;
+        let new_update_authority = Keypair::new();
+        let direct_update_authority = Keypair::new();
+
+        let record = nominate(&mut context, &test_metadata, &new_update_authority.pubkey()).await;
+
+        let update_ix = update_metadata_accounts_v2(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            Some(direct_update_authority.pubkey()),
+            None,
+            None,
+            None,
+        );
+        let update_tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(update_tx)
+            .await
+            .unwrap();
+
+        let accept_ix = accept_update_authority(
+            mpl_token_metadata::id(),
+            record,
+            new_update_authority.pubkey(),
+            direct_update_authority.pubkey(),
+            test_metadata.pubkey,
+        );
+        let accept_tx = Transaction::new_signed_with_payer(
+            &[accept_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &new_update_authority],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(accept_tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::UpdateAuthorityChangedSinceNomination);
+    }
+}
@@ -0,0 +1,210 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::set_collection_royalty,
+    pda::find_collection_royalty_account,
+    state::{Collection, CollectionRoyaltyRecord, Creator, Key},
+};
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+mod set_collection_royalty {
+    use super::*;
+
+    async fn setup_collection(context: &mut ProgramTestContext) -> (Metadata, MasterEditionV2) {
+        let test_collection = Metadata::new();
+        test_collection
+            .create_v2(
+                context,
+                "Collection".to_string(),
+                "COL".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let collection_master_edition = MasterEditionV2::new(&test_collection);
+        collection_master_edition
+            .create_v3(context, Some(0))
+            .await
+            .unwrap();
+        (test_collection, collection_master_edition)
+    }
+
+    async fn set_royalty(
+        context: &mut ProgramTestContext,
+        update_authority: &Keypair,
+        test_collection: &Metadata,
+        collection_master_edition: &MasterEditionV2,
+        seller_fee_basis_points: u16,
+        creators: Option<Vec<Creator>>,
+    ) -> Result<Pubkey, TransportError> {
+        let (record, _) = find_collection_royalty_account(&test_collection.mint.pubkey());
+        let ix = set_collection_royalty(
+            mpl_token_metadata::id(),
+            record,
+            update_authority.pubkey(),
+            context.payer.pubkey(),
+            test_collection.pubkey,
+            collection_master_edition.pubkey,
+            seller_fee_basis_points,
+            creators,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, update_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await?;
+        Ok(record)
+    }
+
+    #[tokio::test]
+    async fn success_set_and_inherit() {
+        let mut context = program_test().start_with_context().await;
+        let (test_collection, collection_master_edition) = setup_collection(&mut context).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let artist = Pubkey::new_unique();
+
+        let creators = Some(vec![
+            Creator {
+                address: payer.pubkey(),
+                verified: true,
+                share: 20,
+            },
+            Creator {
+                address: artist,
+                verified: false,
+                share: 80,
+            },
+        ]);
+        let record = set_royalty(
+            &mut context,
+            &payer,
+            &test_collection,
+            &collection_master_edition,
+            500,
+            creators.clone(),
+        )
+        .await
+        .unwrap();
+
+        let account = get_account(&mut context, &record).await;
+        let record_acct: CollectionRoyaltyRecord = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(record_acct.key, Key::CollectionRoyaltyRecord);
+        assert_eq!(record_acct.collection_mint, test_collection.mint.pubkey());
+        assert_eq!(record_acct.seller_fee_basis_points, 500);
+        assert_eq!(record_acct.creators, creators);
+
+        // Updating the config rewrites the existing record.
+        set_royalty(
+            &mut context,
+            &payer,
+            &test_collection,
+            &collection_master_edition,
+            250,
+            None,
+        )
+        .await
+        .unwrap();
+        let account = get_account(&mut context, &record).await;
+        let record_acct: CollectionRoyaltyRecord = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(record_acct.seller_fee_basis_points, 250);
+        assert_eq!(record_acct.creators, None);
+
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                0,
+                false,
+                None,
+                Some(Collection {
+                    key: test_collection.mint.pubkey(),
+                    verified: false,
+                }),
+                None,
+            )
+            .await
+            .unwrap();
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert!(!metadata.inherits_collection_royalty());
+
+        test_metadata
+            .verify_collection(
+                &mut context,
+                test_collection.pubkey,
+                &payer,
+                test_collection.mint.pubkey(),
+                collection_master_edition.pubkey,
+                None,
+            )
+            .await
+            .unwrap();
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert!(metadata.inherits_collection_royalty());
+    }
+
+    #[tokio::test]
+    async fn fail_invalid_update_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_collection, collection_master_edition) = setup_collection(&mut context).await;
+        let fake_update_authority = Keypair::new();
+
+        let err = set_royalty(
+            &mut context,
+            &fake_update_authority,
+            &test_collection,
+            &collection_master_edition,
+            500,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+
+    #[tokio::test]
+    async fn fail_shares_not_100() {
+        let mut context = program_test().start_with_context().await;
+        let (test_collection, collection_master_edition) = setup_collection(&mut context).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let err = set_royalty(
+            &mut context,
+            &payer,
+            &test_collection,
+            &collection_master_edition,
+            500,
+            Some(vec![Creator {
+                address: Pubkey::new_unique(),
+                verified: false,
+                share: 50,
+            }]),
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::ShareTotalMustBe100);
+    }
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    id,
+    instruction::set_edition_uri_policy,
+    pda::find_edition_uri_policy_account,
+    state::{EditionUriPolicy, EditionUriPolicyRecord, Key, MAX_URI_LENGTH},
+    utils::puffed_out_string,
+};
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+mod set_edition_uri_policy {
+    use super::*;
+
+    async fn setup(context: &mut ProgramTestContext) -> (Metadata, MasterEditionV2) {
+        let test_metadata = Metadata::new();
+        let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+        test_metadata
+            .create(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+            )
+            .await
+            .unwrap();
+
+        test_master_edition.create(context, Some(10)).await.unwrap();
+
+        (test_metadata, test_master_edition)
+    }
+
+    async fn set_policy(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_master_edition: &MasterEditionV2,
+        policy: EditionUriPolicy,
+    ) {
+        let (record, _) = find_edition_uri_policy_account(&test_metadata.mint.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[set_edition_uri_policy(
+                id(),
+                record,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                test_master_edition.pubkey,
+                test_metadata.pubkey,
+                policy,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn success_print_with_uri() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+
+        set_policy(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            EditionUriPolicy::Overridable,
+        )
+        .await;
+
+        let (record, _) = find_edition_uri_policy_account(&test_metadata.mint.pubkey());
+        let account = get_account(&mut context, &record).await;
+        let record_data: EditionUriPolicyRecord = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(record_data.key, Key::EditionUriPolicyRecord);
+        assert_eq!(record_data.policy, EditionUriPolicy::Overridable);
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        test_edition_marker
+            .create_with_uri(&mut context, "signed-print-1".to_string())
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &test_edition_marker.new_metadata_pubkey).await;
+        let new_metadata: mpl_token_metadata::state::Metadata =
+            try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(
+            new_metadata.data.uri,
+            puffed_out_string("signed-print-1", MAX_URI_LENGTH)
+        );
+
+        // Prints without an override still copy the master uri.
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 2);
+        test_edition_marker.create(&mut context).await.unwrap();
+
+        let account = get_account(&mut context, &test_edition_marker.new_metadata_pubkey).await;
+        let new_metadata: mpl_token_metadata::state::Metadata =
+            try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(
+            new_metadata.data.uri,
+            puffed_out_string("uri", MAX_URI_LENGTH)
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_print_with_uri_without_policy() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        let err = test_edition_marker
+            .create_with_uri(&mut context, "signed-print-1".to_string())
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::EditionUriOverrideNotAllowed);
+    }
+
+    #[tokio::test]
+    async fn fail_print_with_uri_after_policy_reset() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+
+        set_policy(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            EditionUriPolicy::Overridable,
+        )
+        .await;
+        set_policy(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            EditionUriPolicy::Fixed,
+        )
+        .await;
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        let err = test_edition_marker
+            .create_with_uri(&mut context, "signed-print-1".to_string())
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::EditionUriOverrideNotAllowed);
+    }
+}
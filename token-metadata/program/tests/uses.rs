@@ -14,8 +14,11 @@ use utils::*;
 mod uses {
     use mpl_token_metadata::{
         error::MetadataError,
-        pda::{find_program_as_burner_account, find_use_authority_account},
-        state::{Key, UseAuthorityRecord},
+        instruction::{reset_uses, utilize, utilize_with_receipt},
+        pda::{
+            find_program_as_burner_account, find_use_authority_account, find_use_receipt_account,
+        },
+        state::{Key, UseAuthorityRecord, UseReceipt},
     };
     use solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack};
     use solana_sdk::signature::Keypair;
@@ -566,4 +569,191 @@ mod uses {
             Account::unpack_from_slice(token_account_after_burn.data.as_slice()).unwrap();
         assert_eq!(token_account_after_burn_data.amount, 0);
     }
+
+    async fn create_multiple_use(context: &mut ProgramTestContext) -> Metadata {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+                Some(Uses {
+                    use_method: UseMethod::Multiple,
+                    total: 3,
+                    remaining: 3,
+                }),
+            )
+            .await
+            .unwrap();
+        test_metadata
+    }
+
+    #[tokio::test]
+    async fn multi_use_with_receipt_success() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_multiple_use(&mut context).await;
+
+        for use_index in 0..2 {
+            let (use_receipt, _) =
+                find_use_receipt_account(&test_metadata.mint.pubkey(), use_index);
+            let ix = utilize_with_receipt(
+                mpl_token_metadata::id(),
+                test_metadata.pubkey,
+                test_metadata.token.pubkey(),
+                test_metadata.mint.pubkey(),
+                use_receipt,
+                None,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                None,
+                1,
+            );
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&context.payer.pubkey()),
+                &[&context.payer],
+                context.last_blockhash,
+            );
+            context.banks_client.process_transaction(tx).await.unwrap();
+
+            let account = get_account(&mut context, &use_receipt).await;
+            let receipt: UseReceipt = try_from_slice_unchecked(&account.data).unwrap();
+            assert_eq!(receipt.key, Key::UseReceipt);
+            assert_eq!(receipt.mint, test_metadata.mint.pubkey());
+            assert_eq!(receipt.user, context.payer.pubkey());
+            assert_eq!(receipt.use_authority, context.payer.pubkey());
+            assert_eq!(receipt.number_of_uses, 1);
+            assert_eq!(receipt.use_index, use_index);
+        }
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 1);
+        assert_eq!(metadata.uses_utilized, 2);
+    }
+
+    #[tokio::test]
+    async fn reset_uses_success() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_multiple_use(&mut context).await;
+
+        let ix = utilize(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            test_metadata.token.pubkey(),
+            test_metadata.mint.pubkey(),
+            None,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            None,
+            3,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let ix = reset_uses(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            Some(i64::MAX),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        let metadata_uses = metadata.uses.unwrap();
+        assert_eq!(metadata_uses.remaining, 3);
+        assert_eq!(metadata_uses.total, 3);
+        assert_eq!(metadata.uses_expiry, Some(i64::MAX));
+        // The lifetime counter keeps receipts of earlier seasons from being overwritten.
+        assert_eq!(metadata.uses_utilized, 3);
+    }
+
+    #[tokio::test]
+    async fn reset_uses_wrong_update_authority_fail() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_multiple_use(&mut context).await;
+        let fake_authority = Keypair::new();
+
+        let ix = reset_uses(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            fake_authority.pubkey(),
+            None,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &fake_authority],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+
+    #[tokio::test]
+    async fn expired_uses_fail() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_multiple_use(&mut context).await;
+
+        let ix = reset_uses(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            Some(0),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let ix = utilize(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            test_metadata.token.pubkey(),
+            test_metadata.mint.pubkey(),
+            None,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            None,
+            1,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::UsesExpired);
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 3);
+    }
 }
@@ -192,6 +192,56 @@ impl EditionMarker {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn create_with_uri(
+        &self,
+        context: &mut ProgramTestContext,
+        uri: String,
+    ) -> transport::Result<()> {
+        create_mint(context, &self.mint, &context.payer.pubkey(), None).await?;
+        create_token_account(
+            context,
+            &self.token,
+            &self.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await?;
+        mint_tokens(
+            context,
+            &self.mint.pubkey(),
+            &self.token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                instruction::mint_new_edition_from_master_edition_via_token_with_uri(
+                    id(),
+                    self.new_metadata_pubkey,
+                    self.new_edition_pubkey,
+                    self.master_edition_pubkey,
+                    self.mint.pubkey(),
+                    context.payer.pubkey(),
+                    context.payer.pubkey(),
+                    context.payer.pubkey(),
+                    self.metadata_token_pubkey,
+                    context.payer.pubkey(),
+                    self.metadata_pubkey,
+                    self.metadata_mint_pubkey,
+                    self.edition,
+                    uri,
+                ),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn create_with_invalid_token_program(
         &self,
         context: &mut ProgramTestContext,